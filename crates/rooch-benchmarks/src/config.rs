@@ -87,6 +87,7 @@ pub struct BenchTxConfig {
     pub btc_rpc_username: Option<String>,
     pub btc_rpc_password: Option<String>,
     pub pprof_output: Option<PProfOutput>, // flamegraph(default)/proto
+    pub parallel_exec: Option<bool>, // execute the txs of a btc block in parallel, only for btc-tx
 }
 
 impl Default for BenchTxConfig {
@@ -99,6 +100,7 @@ impl Default for BenchTxConfig {
             btc_rpc_username: None,
             btc_rpc_password: None,
             pprof_output: Some(PProfOutput::Flamegraph),
+            parallel_exec: Some(false),
         }
     }
 }
@@ -126,6 +128,9 @@ impl BenchTxConfig {
         if config.pprof_output.is_some() {
             self.pprof_output = config.pprof_output;
         }
+        if config.parallel_exec.is_some() {
+            self.parallel_exec = config.parallel_exec;
+        }
     }
 
     pub fn load() -> Self {
@@ -164,6 +169,9 @@ impl BenchTxConfig {
         if let Ok(pprof_output) = std::env::var("ROOCH_BENCH_PPROF_OUTPUT") {
             config.pprof_output = Some(pprof_output.parse().unwrap());
         }
+        if let Ok(parallel_exec) = std::env::var("ROOCH_BENCH_PARALLEL_EXEC") {
+            config.parallel_exec = Some(parallel_exec.parse().unwrap());
+        }
         config
     }
 }
@@ -25,14 +25,18 @@ pub fn tx_exec_benchmark(c: &mut Criterion) {
     let mut test_transaction_builder = TestTransactionBuilder::new(kp);
 
     let tx_type = config.tx_type.clone().unwrap();
+    // Only the l1 txs of a btc block can be executed in a batch
+    let parallel = tx_type == BtcTx && config.parallel_exec.unwrap_or(false);
     let (bench_id, tx_cnt) = match tx_type {
         BtcBlock => ("btc_block", 100),
+        BtcTx if parallel => ("btc_tx_parallel", 5),
         BtcTx => ("btc_tx", 5), // The tx_cnt is the block count
         Transfer => ("l2_tx_transfer", 800),
         Empty => ("l2_tx_empty", 1000),
     };
     let mut blocks = HashMap::new();
     let mut transactions: Vec<_> = Vec::with_capacity(tx_cnt);
+    let mut l1_tx_batches = Vec::with_capacity(tx_cnt);
     match tx_type {
         BtcBlock | BtcTx => {
             let btc_blk_dir = PathBuf::from(config.btc_block_dir.clone().unwrap());
@@ -81,8 +85,12 @@ pub fn tx_exec_benchmark(c: &mut Criterion) {
                     blocks.insert(l1_block.block.block_height, l1_block);
                 } else {
                     let l1_txs = binding_test.execute_l1_block(l1_block).unwrap();
-                    for tx in l1_txs {
-                        transactions.push(LedgerTxData::L1Tx(tx));
+                    if parallel {
+                        l1_tx_batches.push(l1_txs);
+                    } else {
+                        for tx in l1_txs {
+                            transactions.push(LedgerTxData::L1Tx(tx));
+                        }
                     }
                 }
             }
@@ -95,6 +103,25 @@ pub fn tx_exec_benchmark(c: &mut Criterion) {
             }
         }
     }
+    if parallel {
+        // criterion requires at least 10 samples
+        let sample_size = l1_tx_batches.len().max(10);
+        let mut batches_iter = l1_tx_batches.into_iter();
+
+        let mut group = c.benchmark_group("bench_tx_exec");
+        group.sample_size(sample_size);
+        group.sampling_mode(SamplingMode::Flat);
+        group.bench_function(bench_id, |b| {
+            b.iter(|| {
+                if let Some(l1_txs) = batches_iter.next() {
+                    binding_test.execute_l1_txs_in_parallel(l1_txs).unwrap();
+                }
+            });
+        });
+        group.finish();
+        return;
+    }
+
     let sample_size = transactions.len();
     let mut transactions_iter = transactions.into_iter();
 
@@ -119,6 +146,9 @@ pub fn tx_exec_benchmark(c: &mut Criterion) {
                 }
                 LedgerTxData::L1Tx(tx) => binding_test.execute_l1_tx(tx).unwrap(),
                 LedgerTxData::L2Tx(tx) => binding_test.execute(tx).unwrap(),
+                LedgerTxData::BlockCommitment(_) => {
+                    unreachable!("The benchmark does not generate block commitment tx")
+                }
             }
         });
     });
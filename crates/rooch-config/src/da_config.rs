@@ -16,6 +16,9 @@ use std::sync::Arc;
 
 static R_DEFAULT_OPENDA_FS_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("openda_fs"));
 
+pub const DEFAULT_MAX_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+pub const DEFAULT_MAX_CHUNK_INTERVAL_SECONDS: u64 = 60;
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DAServerSubmitStrategy {
@@ -41,10 +44,11 @@ pub enum OpenDAScheme {
     // s3 config:
     // root
     // bucket
-    // region
-    // endpoint
+    // region (default: us-east-1)
+    // endpoint (e.g. http://127.0.0.1:9000 for a local MinIO)
     // access_key_id
     // secret_access_key
+    // a missing key is read from the environment variable OPENDA_S3_{KEY}, e.g. OPENDA_S3_BUCKET
     S3,
 }
 
@@ -62,6 +66,22 @@ pub struct DAConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(name = "internal-da-server", long, help = "internal da server config")]
     pub internal_da_server: Option<InternalDAServerConfig>,
+    // batches are buffered and compressed into one chunk before submitting to DA servers,
+    // a chunk is submitted when the buffered batch data reaches max-chunk-size or it has waited for max-chunk-interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "max-chunk-size",
+        long,
+        help = "max size of the buffered batch data(before compression) in a chunk, in bytes."
+    )]
+    pub max_chunk_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "max-chunk-interval",
+        long,
+        help = "max seconds to wait for batches before submitting a chunk, 0 means submitting each batch immediately."
+    )]
+    pub max_chunk_interval: Option<u64>,
     #[serde(skip)]
     #[clap(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -130,6 +150,15 @@ impl DAConfig {
     pub fn get_openda_fs_dir(&self) -> PathBuf {
         self.data_dir().join(R_DEFAULT_OPENDA_FS_DIR.as_path())
     }
+
+    pub fn max_chunk_size(&self) -> u64 {
+        self.max_chunk_size.unwrap_or(DEFAULT_MAX_CHUNK_SIZE)
+    }
+
+    pub fn max_chunk_interval(&self) -> u64 {
+        self.max_chunk_interval
+            .unwrap_or(DEFAULT_MAX_CHUNK_INTERVAL_SECONDS)
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, Parser)]
@@ -16,10 +16,14 @@ use std::sync::Arc;
 use std::{fmt::Debug, path::Path, path::PathBuf};
 
 use crate::da_config::DAConfig;
+use crate::mempool_config::MempoolConfig;
+use crate::proposer_config::ProposerConfig;
 use crate::store_config::StoreConfig;
 
 pub mod config;
 pub mod da_config;
+pub mod mempool_config;
+pub mod proposer_config;
 pub mod server_config;
 pub mod store_config;
 
@@ -27,6 +31,8 @@ pub const ROOCH_DIR: &str = ".rooch";
 pub const ROOCH_CONFIR_DIR: &str = "rooch_config";
 pub const ROOCH_CLIENT_CONFIG: &str = "rooch.yaml";
 pub const ROOCH_KEYSTORE_FILENAME: &str = "rooch.keystore";
+/// The default max fields of an object loaded from the remote node in the fork mode
+pub const DEFAULT_FORK_MAX_OBJECT_FIELDS: u64 = 100_000;
 
 pub static R_DEFAULT_BASE_DATA_DIR: Lazy<PathBuf> = Lazy::new(|| {
     dirs_next::home_dir()
@@ -125,16 +131,49 @@ pub struct RoochOpt {
     /// The address of the sequencer account
     #[clap(long)]
     pub sequencer_account: Option<String>,
-    /// The address of the proposer account
+    /// The address of the proposer account, the proposer signs the block commitments submitted to the on-chain SCC.
+    /// It is required except on the local and dev chains, which use the sequencer account by default.
+    /// The on-chain SCC accepts the sequencer as the proposer by default,
+    /// call `state_commitment_chain::update_proposer` with the sequencer account to change it.
     #[clap(long)]
     pub proposer_account: Option<String>,
 
+    #[clap(flatten)]
+    pub proposer: ProposerConfig,
+
     #[clap(long, default_value_t)]
     pub da: DAConfig,
 
     #[clap(long, default_value_t, value_enum)]
     pub service_status: ServiceStatus,
 
+    #[clap(flatten)]
+    pub mempool: MempoolConfig,
+
+    #[serde(default)]
+    #[clap(long)]
+    /// Execute the L1 transactions of a L1 block with the parallel executor.
+    /// The L2 transactions are always executed one by one.
+    pub parallel_execution: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    /// The RPC URL of the remote node to fork from.
+    /// The fields of an object are loaded from the remote node at once when the object is first read,
+    /// and the new transactions are executed locally.
+    pub fork_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, requires = "fork_url")]
+    /// The tx order of the remote node to fork at, default is the latest tx order of the remote node.
+    pub fork_tx_order: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, requires = "fork_url")]
+    /// The max fields of an object loaded from the remote node, default is 100000.
+    /// Reading an object with more fields fails in the fork mode.
+    pub fork_max_object_fields: Option<u64>,
+
     #[serde(skip)]
     #[clap(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -166,8 +205,14 @@ impl RoochOpt {
             btc_sync_block_interval: None,
             sequencer_account: None,
             proposer_account: None,
+            proposer: ProposerConfig::default(),
             da: DAConfig::default(),
             service_status: ServiceStatus::default(),
+            mempool: MempoolConfig::default(),
+            parallel_execution: false,
+            fork_url: None,
+            fork_tx_order: None,
+            fork_max_object_fields: None,
             base: None,
         };
         opt.init()?;
@@ -189,6 +234,15 @@ impl RoochOpt {
         Ok(opt)
     }
 
+    pub fn is_fork_mode(&self) -> bool {
+        self.fork_url.is_some()
+    }
+
+    pub fn fork_max_object_fields(&self) -> u64 {
+        self.fork_max_object_fields
+            .unwrap_or(DEFAULT_FORK_MAX_OBJECT_FIELDS)
+    }
+
     pub fn init(&mut self) -> Result<()> {
         if self.base.is_none() {
             let base = BaseConfig::load_with_opt(self)?;
@@ -265,6 +319,14 @@ impl RoochOpt {
     pub fn da_config(&self) -> &DAConfig {
         &self.da
     }
+
+    pub fn mempool_config(&self) -> &MempoolConfig {
+        &self.mempool
+    }
+
+    pub fn proposer_config(&self) -> &ProposerConfig {
+        &self.proposer
+    }
 }
 
 #[derive(Debug, Clone)]
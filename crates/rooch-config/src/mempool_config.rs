@@ -0,0 +1,52 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_MEMPOOL_MAX_SIZE: usize = 10_000;
+pub const DEFAULT_MEMPOOL_MAX_SIZE_PER_SENDER: usize = 100;
+pub const DEFAULT_MEMPOOL_TTL_SECONDS: u64 = 600;
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, Parser)]
+#[serde(deny_unknown_fields)]
+pub struct MempoolConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "mempool-max-size",
+        long,
+        help = "The max number of pending transactions in the mempool"
+    )]
+    pub max_size: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "mempool-max-size-per-sender",
+        long,
+        help = "The max number of pending transactions of a sender in the mempool"
+    )]
+    pub max_size_per_sender: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "mempool-ttl-seconds",
+        long,
+        help = "How long a transaction can wait in the mempool before it is dropped"
+    )]
+    pub ttl_seconds: Option<u64>,
+}
+
+impl MempoolConfig {
+    pub fn max_size(&self) -> usize {
+        self.max_size.unwrap_or(DEFAULT_MEMPOOL_MAX_SIZE)
+    }
+
+    pub fn max_size_per_sender(&self) -> usize {
+        self.max_size_per_sender
+            .unwrap_or(DEFAULT_MEMPOOL_MAX_SIZE_PER_SENDER)
+    }
+
+    pub fn ttl_seconds(&self) -> u64 {
+        self.ttl_seconds.unwrap_or(DEFAULT_MEMPOOL_TTL_SECONDS)
+    }
+}
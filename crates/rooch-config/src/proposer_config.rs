@@ -0,0 +1,27 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BLOCK_COMMIT_INTERVAL_SECONDS: u64 = 5;
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, Parser)]
+#[serde(deny_unknown_fields)]
+pub struct ProposerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "block-commit-interval",
+        long,
+        help = "The interval in seconds to submit the commitments of the proposed blocks to the on-chain SCC"
+    )]
+    pub block_commit_interval: Option<u64>,
+}
+
+impl ProposerConfig {
+    pub fn block_commit_interval(&self) -> u64 {
+        self.block_commit_interval
+            .filter(|interval| *interval > 0)
+            .unwrap_or(DEFAULT_BLOCK_COMMIT_INTERVAL_SECONDS)
+    }
+}
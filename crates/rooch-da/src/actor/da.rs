@@ -1,7 +1,9 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
@@ -11,33 +13,105 @@ use coerce::actor::system::ActorSystem;
 use coerce::actor::{Actor, IntoActor};
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
+use moveos_types::h256::H256;
 
 use rooch_config::da_config::{DAConfig, InternalDAServerConfigType};
+use rooch_store::da_store::DAMetaStore;
+use rooch_store::RoochStore;
+use rooch_types::da::{DAChunkSubmission, DAServerStatus, DAStatus};
 
-use crate::messages::{Batch, PutBatchInternalDAMessage};
+use crate::chunk::{ChunkBuilder, ChunkV1};
+use crate::messages::{
+    Batch, FlushChunkTick, GetBatchByHashInternalDAMessage, GetBatchByHashMessage,
+    GetBatchInternalDAMessage, GetBatchMessage, GetDAStatusMessage, PutChunkInternalDAMessage,
+};
 use crate::server::celestia::actor::server::DAServerCelestiaActor;
 use crate::server::celestia::proxy::DAServerCelestiaProxy;
 use crate::server::openda::actor::server::DAServerOpenDAActor;
 use crate::server::openda::proxy::DAServerOpenDAProxy;
 use crate::server::serverproxy::DAServerProxy;
 
-// TODO tx buffer for building batch
+/// The first retry delay after a submission failure, doubled on each failure in a row
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(300);
+/// The max number of chunks submitted to a server in one round
+const MAX_CHUNKS_PER_ROUND: usize = 10;
+
+/// DAActor buffers the batches into chunks and submits them to DA servers.
+/// The batches are persisted in the outbox until all servers acknowledge the chunk including them,
+/// the failed servers are retried with exponential backoff.
 pub struct DAActor {
     internal_servers: InternalServers,
+    chunk_builder: ChunkBuilder,
+    rooch_store: RoochStore,
+    // sealed chunks not acknowledged by all servers, sorted by chunk id
+    submissions: Vec<DAChunkSubmission>,
+    backoffs: HashMap<String, ServerBackoff>,
 }
 
 struct InternalServers {
-    servers: Arc<RwLock<Vec<Arc<dyn DAServerProxy + Send + Sync>>>>,
+    servers: Arc<RwLock<Vec<DAServer>>>,
     submit_threshold: usize,
 }
 
+#[derive(Clone)]
+struct DAServer {
+    name: String,
+    proxy: Arc<dyn DAServerProxy + Send + Sync>,
+}
+
+#[derive(Default)]
+struct ServerBackoff {
+    consecutive_failures: u64,
+    next_retry_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl ServerBackoff {
+    fn is_ready(&self) -> bool {
+        match self.next_retry_at {
+            Some(next_retry_at) => Instant::now() >= next_retry_at,
+            None => true,
+        }
+    }
+
+    fn on_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.next_retry_at = None;
+    }
+
+    fn on_failure(&mut self, error: String) {
+        self.consecutive_failures += 1;
+        let exp = (self.consecutive_failures - 1).min(16) as u32;
+        let delay = RETRY_BASE_DELAY
+            .saturating_mul(2u32.saturating_pow(exp))
+            .min(RETRY_MAX_DELAY);
+        self.next_retry_at = Some(Instant::now() + delay);
+        self.last_error = Some(error);
+    }
+
+    fn next_retry_in_seconds(&self) -> u64 {
+        self.next_retry_at
+            .map(|next_retry_at| {
+                next_retry_at
+                    .saturating_duration_since(Instant::now())
+                    .as_secs()
+            })
+            .unwrap_or(0)
+    }
+}
+
 impl Actor for DAActor {}
 
 impl DAActor {
-    pub async fn new(da_config: DAConfig, actor_system: &ActorSystem) -> Result<Self> {
+    pub async fn new(
+        da_config: DAConfig,
+        rooch_store: RoochStore,
+        actor_system: &ActorSystem,
+    ) -> Result<Self> {
         // internal servers
 
-        let mut servers: Vec<Arc<dyn DAServerProxy + Send + Sync>> = Vec::new();
+        let mut servers: Vec<DAServer> = Vec::new();
         let mut submit_threshold = 1;
         let mut success_count = 0;
 
@@ -48,13 +122,15 @@ impl DAActor {
 
             for server_config_type in &server_config.servers {
                 if let InternalDAServerConfigType::Celestia(celestia_config) = server_config_type {
-                    let da_server = DAServerCelestiaActor::new(celestia_config)
-                        .await
-                        .into_actor(Some("DAServerCelestia"), actor_system)
-                        .await?;
-                    servers.push(Arc::new(DAServerCelestiaProxy::new(
-                        da_server.clone().into(),
-                    )));
+                    let da_server =
+                        DAServerCelestiaActor::new(celestia_config, rooch_store.clone())
+                            .await
+                            .into_actor(Some("DAServerCelestia"), actor_system)
+                            .await?;
+                    servers.push(DAServer {
+                        name: server_name(&servers, "celestia".to_string()),
+                        proxy: Arc::new(DAServerCelestiaProxy::new(da_server.clone().into())),
+                    });
                     success_count += 1;
                 }
                 if let InternalDAServerConfigType::OpenDa(openda_config) = server_config_type {
@@ -65,12 +141,18 @@ impl DAActor {
                             actor_system,
                         )
                         .await?;
-                    servers.push(Arc::new(DAServerOpenDAProxy::new(da_server.clone().into())));
+                    servers.push(DAServer {
+                        name: server_name(&servers, format!("open-da-{}", openda_config.scheme)),
+                        proxy: Arc::new(DAServerOpenDAProxy::new(da_server.clone().into())),
+                    });
                     success_count += 1;
                 }
             }
         } else {
-            servers.push(Arc::new(crate::server::serverproxy::DAServerNopProxy {}));
+            servers.push(DAServer {
+                name: "nop".to_string(),
+                proxy: Arc::new(crate::server::serverproxy::DAServerNopProxy {}),
+            });
             success_count += 1;
         }
 
@@ -82,59 +164,348 @@ impl DAActor {
             ));
         }
 
-        Ok(Self {
+        let chunk_builder = ChunkBuilder::new(
+            da_config.max_chunk_size() as usize,
+            Duration::from_secs(da_config.max_chunk_interval()),
+        );
+        Self::new_with_servers(servers, submit_threshold, chunk_builder, rooch_store)
+    }
+
+    fn new_with_servers(
+        servers: Vec<DAServer>,
+        submit_threshold: usize,
+        chunk_builder: ChunkBuilder,
+        rooch_store: RoochStore,
+    ) -> Result<Self> {
+        let backoffs = servers
+            .iter()
+            .map(|server| (server.name.clone(), ServerBackoff::default()))
+            .collect();
+
+        let mut actor = Self {
             internal_servers: InternalServers {
                 servers: Arc::new(RwLock::new(servers)),
                 submit_threshold,
             },
-        })
+            chunk_builder,
+            rooch_store,
+            submissions: vec![],
+            backoffs,
+        };
+        actor.load_outbox()?;
+        Ok(actor)
+    }
+
+    /// Recover the sealed chunks and the buffered batches from the outbox
+    fn load_outbox(&mut self) -> Result<()> {
+        self.submissions = self.rooch_store.get_chunk_submissions()?;
+        for batch in self.rooch_store.get_pending_batches()? {
+            let sealed = self.submissions.iter().any(|submission| {
+                submission.chunk_id <= batch.block_number
+                    && batch.block_number <= submission.end_block_number
+            });
+            if sealed {
+                continue;
+            }
+            let batch = Batch::from(batch);
+            if !self.chunk_builder.is_next(&batch) {
+                self.seal_chunk()?;
+            }
+            self.chunk_builder.append(batch)?;
+        }
+        if !self.submissions.is_empty() || !self.chunk_builder.is_empty() {
+            log::info!(
+                "Load DA outbox, pending chunks: {}, buffered batches: {}",
+                self.submissions.len(),
+                self.chunk_builder.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Persist the batch into the outbox and append it to the chunk builder,
+    /// the chunk is submitted to servers when it's ready.
+    /// Return ok once the batch is persisted, the failed submissions are retried on the next flush tick.
+    pub async fn submit_batch(&mut self, batch: Batch) -> Result<()> {
+        self.rooch_store.save_pending_batch(batch.clone().into())?;
+        // a chunk only includes consecutive blocks, seal the buffered batches first if the batch is not the next one
+        if !self.chunk_builder.is_next(&batch) {
+            self.seal_chunk()?;
+        }
+        self.chunk_builder.append(batch)?;
+        if self.chunk_builder.is_ready() {
+            self.seal_chunk()?;
+            self.submit_pending_chunks().await?;
+        }
+        Ok(())
+    }
+
+    /// Seal the buffered batches into a chunk submission
+    fn seal_chunk(&mut self) -> Result<()> {
+        if let Some((chunk_id, end_block_number)) = self.chunk_builder.block_range() {
+            let submission = DAChunkSubmission::new(chunk_id, end_block_number);
+            self.rooch_store.save_chunk_submission(submission.clone())?;
+            self.submissions.push(submission);
+            self.chunk_builder.clear();
+        }
+        Ok(())
     }
 
-    pub async fn submit_batch(&self, batch: Batch) -> Result<()> {
+    /// Seal the buffered batches if the chunk is ready, then submit the pending chunks
+    pub async fn flush_chunk(&mut self) -> Result<()> {
+        if self.chunk_builder.is_ready() {
+            self.seal_chunk()?;
+        }
+        self.submit_pending_chunks().await
+    }
+
+    /// Rebuild the chunk from the batches in the outbox
+    fn load_chunk(&self, submission: &DAChunkSubmission) -> Result<ChunkV1> {
+        let batches = (submission.chunk_id..=submission.end_block_number)
+            .map(|block_number| {
+                self.rooch_store
+                    .get_pending_batch(block_number)?
+                    .map(Batch::from)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "batch {} of chunk {} not found in outbox",
+                            block_number,
+                            submission.chunk_id
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ChunkV1::new(batches)
+    }
+
+    /// Submit the pending chunks to the servers not in backoff, in chunk id order.
+    /// A server stops at the first failed chunk and waits for the backoff.
+    async fn submit_pending_chunks(&mut self) -> Result<()> {
         // TODO calc checksum
-        // TODO richer policy for multi servers
         // TODO verify checksum
-        // TODO retry policy & log
 
+        if self.submissions.is_empty() {
+            return Ok(());
+        }
         let servers = self.internal_servers.servers.read().unwrap().to_vec();
-        let submit_threshold = self.internal_servers.submit_threshold;
 
-        let mut futures_unordered = FuturesUnordered::new();
+        let mut chunks: HashMap<u128, ChunkV1> = HashMap::new();
+        let mut server_chunks = Vec::new();
         for server in servers {
-            let server = Arc::clone(&server);
-            let batch = batch.clone();
+            if !self
+                .backoffs
+                .get(&server.name)
+                .map(|backoff| backoff.is_ready())
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            let chunk_ids = self
+                .submissions
+                .iter()
+                .filter(|submission| !submission.is_acked_by(&server.name))
+                .take(MAX_CHUNKS_PER_ROUND)
+                .map(|submission| submission.chunk_id)
+                .collect::<Vec<_>>();
+            if chunk_ids.is_empty() {
+                continue;
+            }
+            for submission in &self.submissions {
+                if chunk_ids.contains(&submission.chunk_id)
+                    && !chunks.contains_key(&submission.chunk_id)
+                {
+                    chunks.insert(submission.chunk_id, self.load_chunk(submission)?);
+                }
+            }
+            let server_chunk = chunk_ids
+                .iter()
+                .map(|chunk_id| chunks[chunk_id].clone())
+                .collect::<Vec<_>>();
+            server_chunks.push((server, server_chunk));
+        }
+
+        let mut futures_unordered = FuturesUnordered::new();
+        for (server, chunks) in server_chunks {
             futures_unordered.push(async move {
-                server
-                    .public_batch(PutBatchInternalDAMessage {
-                        batch: batch.clone(),
-                    })
-                    .await
+                let mut acked_chunk_ids = Vec::new();
+                for chunk in chunks {
+                    let chunk_id = chunk.chunk_id;
+                    if let Err(e) = server
+                        .proxy
+                        .public_chunk(PutChunkInternalDAMessage { chunk })
+                        .await
+                    {
+                        return (server.name, acked_chunk_ids, Some((chunk_id, e)));
+                    }
+                    acked_chunk_ids.push(chunk_id);
+                }
+                (server.name, acked_chunk_ids, None)
             });
         }
 
-        let mut success_count = 0;
-        while let Some(result) = futures_unordered.next().await {
-            match result {
-                Ok(_) => {
-                    success_count += 1;
-                    if success_count >= submit_threshold {
-                        return Ok(());
-                    }
+        while let Some((server_name, acked_chunk_ids, error)) = futures_unordered.next().await {
+            for submission in self.submissions.iter_mut() {
+                if acked_chunk_ids.contains(&submission.chunk_id) {
+                    submission.acked_servers.push(server_name.clone());
+                    self.rooch_store.save_chunk_submission(submission.clone())?;
+                }
+            }
+            let backoff = self.backoffs.entry(server_name.clone()).or_default();
+            match error {
+                Some((chunk_id, e)) => {
+                    log::warn!(
+                        "{:?}, fail to submit chunk {} to da server {}, failures in a row: {}",
+                        e,
+                        chunk_id,
+                        server_name,
+                        backoff.consecutive_failures + 1
+                    );
+                    backoff.on_failure(e.to_string());
                 }
+                None => backoff.on_success(),
+            }
+        }
+
+        // the chunks acknowledged by all servers are removed from the outbox
+        let server_names = self.server_names();
+        let (done, pending): (Vec<_>, Vec<_>) =
+            self.submissions.drain(..).partition(|submission| {
+                server_names
+                    .iter()
+                    .all(|server_name| submission.is_acked_by(server_name))
+            });
+        self.submissions = pending;
+        for submission in done {
+            self.rooch_store.remove_chunk_submission(submission)?;
+        }
+        Ok(())
+    }
+
+    fn server_names(&self) -> Vec<String> {
+        self.internal_servers
+            .servers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|server| server.name.clone())
+            .collect()
+    }
+
+    pub fn get_status(&self) -> DAStatus {
+        let submit_threshold = self.internal_servers.submit_threshold;
+        let server_names = self.server_names();
+        let unavailable_chunks = self
+            .submissions
+            .iter()
+            .filter(|submission| {
+                server_names
+                    .iter()
+                    .filter(|server_name| submission.is_acked_by(server_name))
+                    .count()
+                    < submit_threshold
+            })
+            .count();
+        let servers = server_names
+            .into_iter()
+            .map(|name| {
+                let pending = self
+                    .submissions
+                    .iter()
+                    .filter(|submission| !submission.is_acked_by(&name))
+                    .collect::<Vec<_>>();
+                let backoff = self.backoffs.get(&name);
+                DAServerStatus {
+                    pending_chunks: pending.len() as u64,
+                    first_pending_chunk_id: pending.first().map(|submission| submission.chunk_id),
+                    consecutive_failures: backoff
+                        .map(|backoff| backoff.consecutive_failures)
+                        .unwrap_or(0),
+                    last_error: backoff.and_then(|backoff| backoff.last_error.clone()),
+                    next_retry_in_seconds: backoff
+                        .map(|backoff| backoff.next_retry_in_seconds())
+                        .unwrap_or(0),
+                    name,
+                }
+            })
+            .collect();
+        DAStatus {
+            submit_threshold: submit_threshold as u64,
+            buffered_batches: self.chunk_builder.len() as u64,
+            pending_chunks: self.submissions.len() as u64,
+            unavailable_chunks: unavailable_chunks as u64,
+            servers,
+        }
+    }
+
+    /// Get batch by block number from the outbox or servers in order, return the first verified batch
+    pub async fn get_batch(&self, block_number: u128) -> Result<Option<Batch>> {
+        if let Some(batch) = self.rooch_store.get_pending_batch(block_number)? {
+            return Ok(Some(batch.into()));
+        }
+        let servers = self.internal_servers.servers.read().unwrap().to_vec();
+        for server in servers {
+            match server
+                .proxy
+                .get_batch(GetBatchInternalDAMessage { block_number })
+                .await
+            {
+                Ok(Some(batch)) => return Ok(Some(batch)),
+                Ok(None) => {}
                 Err(e) => {
-                    log::warn!("{:?}, fail to submit batch to da server.", e); // TODO add da server name
+                    log::warn!(
+                        "{:?}, fail to get batch from da server {}, block_number: {}",
+                        e,
+                        server.name,
+                        block_number
+                    );
                 }
             }
         }
+        Ok(None)
+    }
 
-        if success_count < submit_threshold {
-            Err(anyhow::Error::msg(format!(
-                "not enough successful submissions. exp>= {} act: {}",
-                submit_threshold, success_count
-            )))
-        } else {
-            Ok(())
+    /// Get batch by batch hash from the buffer, servers or outbox in order, return the first verified batch
+    pub async fn get_batch_by_hash(&self, batch_hash: H256) -> Result<Option<Batch>> {
+        if let Some(batch) = self.chunk_builder.get_batch_by_hash(batch_hash) {
+            return Ok(Some(batch));
+        }
+        let servers = self.internal_servers.servers.read().unwrap().to_vec();
+        for server in servers {
+            match server
+                .proxy
+                .get_batch_by_hash(GetBatchByHashInternalDAMessage { batch_hash })
+                .await
+            {
+                Ok(Some(batch)) => return Ok(Some(batch)),
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!(
+                        "{:?}, fail to get batch from da server {}, batch_hash: {:?}",
+                        e,
+                        server.name,
+                        batch_hash
+                    );
+                }
+            }
+        }
+        // the chunk might not be acknowledged by any server yet
+        if !self.submissions.is_empty() {
+            for batch in self.rooch_store.get_pending_batches()? {
+                if batch.batch_hash == batch_hash {
+                    return Ok(Some(batch.into()));
+                }
+            }
         }
+        Ok(None)
+    }
+}
+
+/// The server name is used to track the acknowledgements, append the index if the name is duplicated
+fn server_name(servers: &[DAServer], name: String) -> String {
+    if servers.iter().any(|server| server.name == name) {
+        format!("{}-{}", name, servers.len())
+    } else {
+        name
     }
 }
 
@@ -144,3 +515,164 @@ impl Handler<Batch> for DAActor {
         self.submit_batch(msg).await
     }
 }
+
+#[async_trait]
+impl Handler<FlushChunkTick> for DAActor {
+    async fn handle(&mut self, _msg: FlushChunkTick, _ctx: &mut ActorContext) {
+        if let Err(e) = self.flush_chunk().await {
+            log::error!("{:?}, fail to flush da chunks.", e);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchMessage> for DAActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Batch>> {
+        self.get_batch(msg.block_number).await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchByHashMessage> for DAActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchByHashMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Batch>> {
+        self.get_batch_by_hash(msg.batch_hash).await
+    }
+}
+
+#[async_trait]
+impl Handler<GetDAStatusMessage> for DAActor {
+    async fn handle(
+        &mut self,
+        _msg: GetDAStatusMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<DAStatus> {
+        Ok(self.get_status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::PutChunkInternalDAMessage;
+    use moveos_types::h256;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockDAServer {
+        fail: AtomicBool,
+        chunks: Mutex<Vec<u128>>,
+    }
+
+    #[async_trait]
+    impl DAServerProxy for MockDAServer {
+        async fn public_chunk(&self, request: PutChunkInternalDAMessage) -> Result<()> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(anyhow!("mock server failure"));
+            }
+            self.chunks.lock().unwrap().push(request.chunk.chunk_id);
+            Ok(())
+        }
+
+        async fn get_batch(&self, _request: GetBatchInternalDAMessage) -> Result<Option<Batch>> {
+            Ok(None)
+        }
+
+        async fn get_batch_by_hash(
+            &self,
+            _request: GetBatchByHashInternalDAMessage,
+        ) -> Result<Option<Batch>> {
+            Ok(None)
+        }
+    }
+
+    fn new_actor(
+        good: Arc<MockDAServer>,
+        bad: Arc<MockDAServer>,
+        rooch_store: RoochStore,
+    ) -> DAActor {
+        let servers = vec![
+            DAServer {
+                name: "good".to_string(),
+                proxy: good,
+            },
+            DAServer {
+                name: "bad".to_string(),
+                proxy: bad,
+            },
+        ];
+        DAActor::new_with_servers(
+            servers,
+            1,
+            ChunkBuilder::new(1024, Duration::ZERO),
+            rooch_store,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_outbox_retry() {
+        let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+        let good = Arc::new(MockDAServer::default());
+        let bad = Arc::new(MockDAServer::default());
+        bad.fail.store(true, Ordering::SeqCst);
+
+        let mut actor = new_actor(good.clone(), bad.clone(), rooch_store.clone());
+        let data = vec![1u8; 100];
+        let batch = Batch {
+            block_number: 0,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        };
+        actor.submit_batch(batch.clone()).await.unwrap();
+        assert_eq!(good.chunks.lock().unwrap().clone(), vec![0]);
+
+        let status = actor.get_status();
+        assert_eq!(status.pending_chunks, 1);
+        assert_eq!(status.unavailable_chunks, 0);
+        let bad_status = &status.servers[1];
+        assert_eq!(bad_status.name, "bad");
+        assert_eq!(bad_status.pending_chunks, 1);
+        assert_eq!(bad_status.first_pending_chunk_id, Some(0));
+        assert_eq!(bad_status.consecutive_failures, 1);
+        assert!(bad_status.last_error.is_some());
+
+        // the batch is kept in the outbox until all servers acknowledge it
+        assert_eq!(actor.get_batch(0).await.unwrap(), Some(batch));
+
+        // restart with the repaired server, the pending chunk is recovered and submitted again
+        bad.fail.store(false, Ordering::SeqCst);
+        let mut actor = new_actor(good.clone(), bad.clone(), rooch_store.clone());
+        assert_eq!(actor.get_status().pending_chunks, 1);
+        actor.flush_chunk().await.unwrap();
+        assert_eq!(bad.chunks.lock().unwrap().clone(), vec![0]);
+        assert_eq!(good.chunks.lock().unwrap().clone(), vec![0]);
+        assert_eq!(actor.get_status().pending_chunks, 0);
+        assert!(rooch_store.get_pending_batches().unwrap().is_empty());
+        assert!(rooch_store.get_chunk_submissions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_server_backoff() {
+        let mut backoff = ServerBackoff::default();
+        assert!(backoff.is_ready());
+        backoff.on_failure("error".to_string());
+        assert!(!backoff.is_ready());
+        assert_eq!(backoff.consecutive_failures, 1);
+        for _ in 0..30 {
+            backoff.on_failure("error".to_string());
+        }
+        assert!(backoff.next_retry_in_seconds() <= RETRY_MAX_DELAY.as_secs());
+        backoff.on_success();
+        assert!(backoff.is_ready());
+        assert_eq!(backoff.consecutive_failures, 0);
+    }
+}
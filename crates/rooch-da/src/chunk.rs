@@ -1,11 +1,90 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::messages::Batch;
+use crate::segment::{Segment, SegmentID, SegmentV0};
+use moveos_types::h256;
 use moveos_types::h256::H256;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
 
-// DABatchV0 is the first version of the batch inside a chunk, each batch is a chunk
-#[derive(Serialize, Debug, PartialEq, Clone)]
+/// The max number of batches in a chunk, the chunk including a block could be found within it
+pub const MAX_BATCHES_PER_CHUNK: usize = 64;
+
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_LG_WINDOW_SIZE: u32 = 22;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChunkVersion {
+    V0,
+    V1,
+    Unknown(u8),
+}
+
+impl From<u8> for ChunkVersion {
+    fn from(num: u8) -> Self {
+        match num {
+            0 => ChunkVersion::V0,
+            1 => ChunkVersion::V1,
+            // ...
+            _ => Self::Unknown(num),
+        }
+    }
+}
+
+impl From<ChunkVersion> for u8 {
+    fn from(version: ChunkVersion) -> Self {
+        match version {
+            ChunkVersion::V0 => 0,
+            ChunkVersion::V1 => 1,
+            ChunkVersion::Unknown(num) => num,
+        }
+    }
+}
+
+// `Chunk` is the unit of submission to DA server, it's split into segments for storage.
+// The first byte of the encoded chunk is the chunk version.
+pub trait Chunk: Send {
+    fn to_bytes(&self) -> Vec<u8>;
+    fn get_version(&self) -> ChunkVersion;
+    fn get_chunk_id(&self) -> u128;
+    fn get_batches(&self) -> Vec<Batch>;
+
+    /// Split the chunk into segments
+    fn to_segments(&self, max_segment_size: usize) -> Vec<SegmentV0> {
+        let chunk_bytes = self.to_bytes();
+        let segs = chunk_bytes.chunks(max_segment_size);
+        let total = segs.len();
+
+        let chunk_id = self.get_chunk_id();
+        segs.enumerate()
+            .map(|(i, data)| {
+                SegmentV0 {
+                    id: SegmentID {
+                        chunk_id,
+                        segment_number: i as u64,
+                    },
+                    is_last: i == total - 1, // extra info overhead is much smaller than max_block_size - max_segment_size
+                    data_checksum: 0,
+                    checksum: 0,
+                    data: data.to_vec(),
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// Get the batch by block number in the chunk
+    fn get_batch(&self, block_number: u128) -> Option<Batch> {
+        self.get_batches()
+            .into_iter()
+            .find(|batch| batch.block_number == block_number)
+    }
+}
+
+// DABatchV0 is the first version of the batch inside a chunk, each batch is a chunk without compression
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct DABatchV0 {
     pub version: u8,
     // each batch maps to a L2 block
@@ -15,3 +94,464 @@ pub struct DABatchV0 {
     // encoded tx list
     pub data: Vec<u8>,
 }
+
+impl From<Batch> for DABatchV0 {
+    fn from(batch: Batch) -> Self {
+        Self {
+            version: 0,
+            block_number: batch.block_number,
+            batch_hash: batch.batch_hash,
+            data: batch.data,
+        }
+    }
+}
+
+impl From<DABatchV0> for Batch {
+    fn from(chunk: DABatchV0) -> Self {
+        Self {
+            block_number: chunk.block_number,
+            batch_hash: chunk.batch_hash,
+            data: chunk.data,
+        }
+    }
+}
+
+impl DABatchV0 {
+    /// Check the batch data against the batch hash recorded by the sequencer
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let exp_batch_hash = h256::sha3_256_of(&self.data);
+        if exp_batch_hash != self.batch_hash {
+            return Err(anyhow::anyhow!(
+                "chunk_v0: batch hash mismatch, block_number: {}, exp: {:?}, act: {:?}",
+                self.block_number,
+                exp_batch_hash,
+                self.batch_hash
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Chunk for DABatchV0 {
+    fn to_bytes(&self) -> Vec<u8> {
+        // the first field is version, so the first byte of the encoded chunk is always 0
+        bcs::to_bytes(self).expect("encode chunk should success")
+    }
+
+    fn get_version(&self) -> ChunkVersion {
+        ChunkVersion::V0
+    }
+
+    // block number is used as chunk id: it's easy to get next block number for segments,
+    // then we could request chunk by block number
+    fn get_chunk_id(&self) -> u128 {
+        self.block_number
+    }
+
+    fn get_batches(&self) -> Vec<Batch> {
+        vec![self.clone().into()]
+    }
+}
+
+/// ChunkV1 packs the batches of consecutive blocks into one chunk, the batches are compressed by brotli.
+/// The chunk id is the block number of the first batch.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ChunkV1 {
+    pub chunk_id: u128,
+    pub batches: Vec<Batch>,
+}
+
+impl ChunkV1 {
+    pub fn new(batches: Vec<Batch>) -> anyhow::Result<Self> {
+        let chunk_id = match batches.first() {
+            Some(batch) => batch.block_number,
+            None => return Err(anyhow::anyhow!("chunk_v1: no batches")),
+        };
+        let chunk = Self { chunk_id, batches };
+        chunk.verify()?;
+        Ok(chunk)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        if bytes.is_empty() || ChunkVersion::from(bytes[0]) != ChunkVersion::V1 {
+            return Err(anyhow::anyhow!("chunk_v1: invalid version"));
+        }
+        let mut decompressed = Vec::new();
+        brotli::Decompressor::new(&bytes[1..], BROTLI_BUFFER_SIZE)
+            .read_to_end(&mut decompressed)?;
+        let chunk: ChunkV1 = bcs::from_bytes(&decompressed)?;
+        Ok(chunk)
+    }
+
+    /// Check the batches are consecutive blocks starting from chunk id and match their batch hashes
+    pub fn verify(&self) -> anyhow::Result<()> {
+        if self.batches.is_empty() {
+            return Err(anyhow::anyhow!(
+                "chunk_v1: no batches in chunk {}",
+                self.chunk_id
+            ));
+        }
+        for (i, batch) in self.batches.iter().enumerate() {
+            let exp_block_number = self.chunk_id + i as u128;
+            if batch.block_number != exp_block_number {
+                return Err(anyhow::anyhow!(
+                    "chunk_v1: block number mismatch in chunk {}, exp: {}, act: {}",
+                    self.chunk_id,
+                    exp_block_number,
+                    batch.block_number
+                ));
+            }
+            verify_batch(batch)?;
+        }
+        Ok(())
+    }
+}
+
+impl Chunk for ChunkV1 {
+    fn to_bytes(&self) -> Vec<u8> {
+        let encoded = bcs::to_bytes(self).expect("encode chunk should success");
+        let mut bytes = vec![ChunkVersion::V1.into()];
+        {
+            let mut writer = brotli::CompressorWriter::new(
+                &mut bytes,
+                BROTLI_BUFFER_SIZE,
+                BROTLI_QUALITY,
+                BROTLI_LG_WINDOW_SIZE,
+            );
+            writer
+                .write_all(&encoded)
+                .expect("compress chunk should success");
+        }
+        bytes
+    }
+
+    fn get_version(&self) -> ChunkVersion {
+        ChunkVersion::V1
+    }
+
+    fn get_chunk_id(&self) -> u128 {
+        self.chunk_id
+    }
+
+    fn get_batches(&self) -> Vec<Batch> {
+        self.batches.clone()
+    }
+}
+
+fn verify_batch(batch: &Batch) -> anyhow::Result<()> {
+    let exp_batch_hash = h256::sha3_256_of(&batch.data);
+    if exp_batch_hash != batch.batch_hash {
+        return Err(anyhow::anyhow!(
+            "chunk_v1: batch hash mismatch, block_number: {}, exp: {:?}, act: {:?}",
+            batch.block_number,
+            exp_batch_hash,
+            batch.batch_hash
+        ));
+    }
+    Ok(())
+}
+
+// falling back to Result here to cater for corrupted data etc
+pub fn chunk_from_bytes(bytes: &[u8]) -> anyhow::Result<Box<dyn Chunk>> {
+    if bytes.is_empty() {
+        return Err(anyhow::anyhow!("chunk: empty bytes"));
+    }
+
+    match ChunkVersion::from(bytes[0]) {
+        ChunkVersion::V0 => {
+            let chunk: DABatchV0 = bcs::from_bytes(bytes)?;
+            chunk.verify()?;
+            Ok(Box::new(chunk))
+        }
+        ChunkVersion::V1 => {
+            let chunk = ChunkV1::from_bytes(bytes)?;
+            chunk.verify()?;
+            Ok(Box::new(chunk))
+        }
+        ChunkVersion::Unknown(version) => Err(anyhow::anyhow!(
+            "chunk: unsupported chunk version {}",
+            version
+        )),
+    }
+}
+
+/// Rebuild a chunk from its segments and verify the batch hashes.
+/// Segments must belong to the same chunk and be sorted by segment number, the last one must be marked as last.
+pub fn chunk_from_segments(segments: Vec<Box<dyn Segment>>) -> anyhow::Result<Box<dyn Chunk>> {
+    let chunk_id = match segments.first() {
+        Some(segment) => segment.get_id().chunk_id,
+        None => return Err(anyhow::anyhow!("chunk: no segments")),
+    };
+
+    let total = segments.len();
+    let mut chunk_bytes = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let id = segment.get_id();
+        if id.chunk_id != chunk_id || id.segment_number != i as u64 {
+            return Err(anyhow::anyhow!(
+                "chunk: unexpected segment {}, exp: {}_{}",
+                id,
+                chunk_id,
+                i
+            ));
+        }
+        if segment.is_last() != (i == total - 1) {
+            return Err(anyhow::anyhow!(
+                "chunk: incomplete segments for chunk {}, last segment mismatch at {}",
+                chunk_id,
+                id
+            ));
+        }
+        chunk_bytes.extend_from_slice(&segment.get_data());
+    }
+
+    let chunk = chunk_from_bytes(&chunk_bytes)?;
+    if chunk.get_chunk_id() != chunk_id {
+        return Err(anyhow::anyhow!(
+            "chunk: chunk id mismatch, exp: {}, act: {}",
+            chunk_id,
+            chunk.get_chunk_id()
+        ));
+    }
+    Ok(chunk)
+}
+
+/// ChunkBuilder buffers the batches of consecutive blocks,
+/// a chunk is ready to submit when the buffered batches reach the size or time threshold.
+pub struct ChunkBuilder {
+    max_chunk_size: usize,
+    max_chunk_interval: Duration,
+    batches: Vec<Batch>,
+    // total size of the buffered batch data, before compression
+    size: usize,
+    // the time when the first batch appended
+    start_time: Option<Instant>,
+}
+
+impl ChunkBuilder {
+    pub fn new(max_chunk_size: usize, max_chunk_interval: Duration) -> Self {
+        Self {
+            max_chunk_size,
+            max_chunk_interval,
+            batches: Vec::new(),
+            size: 0,
+            start_time: None,
+        }
+    }
+
+    /// Whether the batch could be appended to the buffered batches:
+    /// the buffer is empty or the batch is the next block of the last buffered batch.
+    pub fn is_next(&self, batch: &Batch) -> bool {
+        match self.batches.last() {
+            Some(last) => batch.block_number == last.block_number + 1,
+            None => true,
+        }
+    }
+
+    pub fn append(&mut self, batch: Batch) -> anyhow::Result<()> {
+        if !self.is_next(&batch) {
+            return Err(anyhow::anyhow!(
+                "chunk builder: batch {} is not the next block of the buffered batches",
+                batch.block_number
+            ));
+        }
+        if self.start_time.is_none() {
+            self.start_time = Some(Instant::now());
+        }
+        self.size += batch.data.len();
+        self.batches.push(batch);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// The first and the last block number of the buffered batches
+    pub fn block_range(&self) -> Option<(u128, u128)> {
+        match (self.batches.first(), self.batches.last()) {
+            (Some(first), Some(last)) => Some((first.block_number, last.block_number)),
+            _ => None,
+        }
+    }
+
+    /// The buffered batches reach the size threshold, the batch count limit or the time threshold
+    pub fn is_ready(&self) -> bool {
+        match self.start_time {
+            Some(start_time) => {
+                self.size >= self.max_chunk_size
+                    || self.batches.len() >= MAX_BATCHES_PER_CHUNK
+                    || start_time.elapsed() >= self.max_chunk_interval
+            }
+            None => false,
+        }
+    }
+
+    /// Build a chunk from the buffered batches, the buffer is kept until `clear`
+    pub fn build(&self) -> anyhow::Result<Option<ChunkV1>> {
+        if self.batches.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(ChunkV1::new(self.batches.clone())?))
+    }
+
+    pub fn clear(&mut self) {
+        self.batches.clear();
+        self.size = 0;
+        self.start_time = None;
+    }
+
+    /// Get the buffered batch by block number
+    pub fn get_batch(&self, block_number: u128) -> Option<Batch> {
+        self.batches
+            .iter()
+            .find(|batch| batch.block_number == block_number)
+            .cloned()
+    }
+
+    /// Get the buffered batch by batch hash
+    pub fn get_batch_by_hash(&self, batch_hash: H256) -> Option<Batch> {
+        self.batches
+            .iter()
+            .find(|batch| batch.batch_hash == batch_hash)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::segment_from_bytes;
+
+    #[test]
+    fn test_chunk_segments_roundtrip() {
+        let data = (0..1000).map(|i| (i % 256) as u8).collect::<Vec<_>>();
+        let chunk = DABatchV0::from(Batch {
+            block_number: 7,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        });
+
+        let segments = chunk
+            .to_segments(100)
+            .into_iter()
+            .map(|segment| segment_from_bytes(&segment.to_bytes()).unwrap())
+            .collect::<Vec<_>>();
+        assert!(segments.len() > 1);
+
+        let recovered = chunk_from_segments(segments).unwrap();
+        assert_eq!(recovered.get_version(), ChunkVersion::V0);
+        assert_eq!(recovered.get_batches(), chunk.get_batches());
+    }
+
+    #[test]
+    fn test_chunk_from_incomplete_segments() {
+        let data = vec![1u8; 1000];
+        let chunk = DABatchV0::from(Batch {
+            block_number: 1,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        });
+
+        let mut segments = chunk
+            .to_segments(100)
+            .into_iter()
+            .map(|segment| Box::new(segment) as Box<dyn Segment>)
+            .collect::<Vec<_>>();
+        segments.pop();
+        assert!(chunk_from_segments(segments).is_err());
+    }
+
+    #[test]
+    fn test_chunk_batch_hash_mismatch() {
+        let chunk = DABatchV0::from(Batch {
+            block_number: 1,
+            batch_hash: H256::zero(),
+            data: vec![1, 2, 3],
+        });
+        let segments = chunk
+            .to_segments(100)
+            .into_iter()
+            .map(|segment| Box::new(segment) as Box<dyn Segment>)
+            .collect::<Vec<_>>();
+        assert!(chunk_from_segments(segments).is_err());
+    }
+
+    fn new_batch(block_number: u128, data: Vec<u8>) -> Batch {
+        Batch {
+            block_number,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_chunk_v1_segments_roundtrip() {
+        let batches = (5..10)
+            .map(|block_number| new_batch(block_number, vec![block_number as u8; 1000]))
+            .collect::<Vec<_>>();
+        let chunk = ChunkV1::new(batches.clone()).unwrap();
+        assert_eq!(chunk.get_chunk_id(), 5);
+
+        let bytes = chunk.to_bytes();
+        assert_eq!(ChunkVersion::from(bytes[0]), ChunkVersion::V1);
+        // repetitive data should be compressed
+        assert!(bytes.len() < 1000);
+
+        let segments = chunk
+            .to_segments(16)
+            .into_iter()
+            .map(|segment| segment_from_bytes(&segment.to_bytes()).unwrap())
+            .collect::<Vec<_>>();
+        assert!(segments.len() > 1);
+
+        let recovered = chunk_from_segments(segments).unwrap();
+        assert_eq!(recovered.get_version(), ChunkVersion::V1);
+        assert_eq!(recovered.get_chunk_id(), 5);
+        assert_eq!(recovered.get_batches(), batches);
+        assert_eq!(recovered.get_batch(7), Some(batches[2].clone()));
+        assert!(recovered.get_batch(10).is_none());
+    }
+
+    #[test]
+    fn test_chunk_v1_verify() {
+        assert!(ChunkV1::new(vec![]).is_err());
+        // not consecutive
+        assert!(ChunkV1::new(vec![new_batch(1, vec![1]), new_batch(3, vec![3])]).is_err());
+        // batch hash mismatch
+        let mut batch = new_batch(1, vec![1]);
+        batch.batch_hash = H256::zero();
+        assert!(ChunkV1::new(vec![batch]).is_err());
+    }
+
+    #[test]
+    fn test_chunk_builder() {
+        let mut builder = ChunkBuilder::new(100, Duration::from_secs(3600));
+        assert!(!builder.is_ready());
+        assert!(builder.build().unwrap().is_none());
+
+        builder.append(new_batch(1, vec![1; 40])).unwrap();
+        builder.append(new_batch(2, vec![2; 40])).unwrap();
+        assert!(!builder.is_ready());
+        assert!(builder.append(new_batch(4, vec![4; 40])).is_err());
+        assert_eq!(builder.get_batch(2).unwrap().data, vec![2; 40]);
+
+        builder.append(new_batch(3, vec![3; 40])).unwrap();
+        assert!(builder.is_ready());
+        let chunk = builder.build().unwrap().unwrap();
+        assert_eq!(chunk.get_chunk_id(), 1);
+        assert_eq!(chunk.get_batches().len(), 3);
+
+        builder.clear();
+        assert!(builder.is_empty());
+        assert!(!builder.is_ready());
+
+        let mut builder = ChunkBuilder::new(100, Duration::ZERO);
+        builder.append(new_batch(1, vec![1])).unwrap();
+        assert!(builder.is_ready());
+    }
+}
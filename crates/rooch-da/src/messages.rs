@@ -3,11 +3,14 @@
 
 use anyhow::Result;
 use coerce::actor::message::Message;
+use coerce::actor::scheduler::timer::TimerTick;
 use serde::{Deserialize, Serialize};
 
+use crate::chunk::ChunkV1;
 use moveos_types::h256::H256;
+use rooch_types::da::{DABatch, DAStatus};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Batch {
     // each batch maps to a L2 block
     pub block_number: u128,
@@ -21,12 +24,88 @@ impl Message for Batch {
     type Result = Result<()>;
 }
 
+impl From<Batch> for DABatch {
+    fn from(batch: Batch) -> Self {
+        Self {
+            block_number: batch.block_number,
+            batch_hash: batch.batch_hash,
+            data: batch.data,
+        }
+    }
+}
+
+impl From<DABatch> for Batch {
+    fn from(batch: DABatch) -> Self {
+        Self {
+            block_number: batch.block_number,
+            batch_hash: batch.batch_hash,
+            data: batch.data,
+        }
+    }
+}
+
+/// Seal the buffered batches into a chunk if it's ready, and submit the pending chunks to DA servers
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlushChunkTick {}
+
+impl Message for FlushChunkTick {
+    type Result = ();
+}
+
+impl TimerTick for FlushChunkTick {}
+
 #[derive(Debug, Serialize, Deserialize)]
-pub struct PutBatchInternalDAMessage {
-    pub batch: Batch,
+pub struct PutChunkInternalDAMessage {
+    pub chunk: ChunkV1,
     // TODO add put policy
 }
 
-impl Message for PutBatchInternalDAMessage {
+impl Message for PutChunkInternalDAMessage {
     type Result = Result<()>;
 }
+
+/// Get the batch by block number, the batch is verified by batch hash
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBatchMessage {
+    pub block_number: u128,
+}
+
+impl Message for GetBatchMessage {
+    type Result = Result<Option<Batch>>;
+}
+
+/// Get the batch by batch hash, the batch is verified by batch hash
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBatchByHashMessage {
+    pub batch_hash: H256,
+}
+
+impl Message for GetBatchByHashMessage {
+    type Result = Result<Option<Batch>>;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBatchInternalDAMessage {
+    pub block_number: u128,
+}
+
+impl Message for GetBatchInternalDAMessage {
+    type Result = Result<Option<Batch>>;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBatchByHashInternalDAMessage {
+    pub batch_hash: H256,
+}
+
+impl Message for GetBatchByHashInternalDAMessage {
+    type Result = Result<Option<Batch>>;
+}
+
+/// Get the status of the DA outbox and servers
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDAStatusMessage {}
+
+impl Message for GetDAStatusMessage {
+    type Result = Result<DAStatus>;
+}
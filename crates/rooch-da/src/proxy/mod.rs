@@ -4,7 +4,9 @@
 use coerce::actor::ActorRef;
 
 use crate::actor::da::DAActor;
-use crate::messages::Batch;
+use crate::messages::{Batch, GetBatchByHashMessage, GetBatchMessage, GetDAStatusMessage};
+use moveos_types::h256::H256;
+use rooch_types::da::DAStatus;
 
 #[derive(Clone)]
 pub struct DAProxy {
@@ -19,4 +21,18 @@ impl DAProxy {
     pub async fn submit_batch(&self, batch: Batch) -> anyhow::Result<()> {
         self.actor.send(batch).await?
     }
+
+    pub async fn get_batch(&self, block_number: u128) -> anyhow::Result<Option<Batch>> {
+        self.actor.send(GetBatchMessage { block_number }).await?
+    }
+
+    pub async fn get_batch_by_hash(&self, batch_hash: H256) -> anyhow::Result<Option<Batch>> {
+        self.actor
+            .send(GetBatchByHashMessage { batch_hash })
+            .await?
+    }
+
+    pub async fn get_status(&self) -> anyhow::Result<DAStatus> {
+        self.actor.send(GetDAStatusMessage {}).await?
+    }
 }
@@ -37,6 +37,8 @@ pub trait Segment: Send {
     fn to_bytes(&self) -> Vec<u8>;
     fn get_version(&self) -> SegmentVersion;
     fn get_id(&self) -> SegmentID;
+    fn get_data(&self) -> Vec<u8>;
+    fn is_last(&self) -> bool;
 }
 
 pub const SEGMENT_V0_DATA_OFFSET: usize = 42;
@@ -116,6 +118,14 @@ impl Segment for SegmentV0 {
     fn get_id(&self) -> SegmentID {
         self.id.clone()
     }
+
+    fn get_data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last
+    }
 }
 
 pub fn get_data_offset(version: SegmentVersion) -> usize {
@@ -127,6 +137,9 @@ pub fn get_data_offset(version: SegmentVersion) -> usize {
 
 // falling back to Result here to cater for corrupted data etc
 pub fn segment_from_bytes(bytes: &[u8]) -> anyhow::Result<Box<dyn Segment>> {
+    if bytes.is_empty() {
+        return Err(anyhow::anyhow!("segment: empty bytes"));
+    }
     let version = bytes[0];
 
     match SegmentVersion::from(version) {
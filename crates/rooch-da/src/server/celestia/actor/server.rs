@@ -3,98 +3,130 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
-use celestia_types::nmt::Namespace;
 use coerce::actor::context::ActorContext;
 use coerce::actor::message::Handler;
 use coerce::actor::Actor;
 
-use crate::chunk::DABatchV0;
+use crate::chunk::{chunk_from_segments, Chunk};
+use moveos_types::h256::H256;
 use rooch_config::da_config::DAServerCelestiaConfig;
+use rooch_store::da_store::DAMetaStore;
+use rooch_store::RoochStore;
 
-use crate::messages::PutBatchInternalDAMessage;
-use crate::segment::{SegmentID, SegmentV0};
+use crate::messages::{
+    Batch, GetBatchByHashInternalDAMessage, GetBatchInternalDAMessage, PutChunkInternalDAMessage,
+};
+use crate::segment::Segment;
 use crate::server::celestia::backend::Backend;
 
 pub struct DAServerCelestiaActor {
     max_segment_size: usize,
     backend: Backend,
+    // celestia blobs could only be fetched by height,
+    // the heights of submitted segments are persisted in the rooch store for reading back
+    rooch_store: RoochStore,
 }
 
-// TODO get request and response
-// 1. get by block number
-// 2. get by batch hash
-// 3. pull by stream
-//
+// TODO pull by stream
 
 impl Actor for DAServerCelestiaActor {}
 
 // TODO add FEC get for SDC protection (wrong response attacks)
 impl DAServerCelestiaActor {
-    pub async fn new(cfg: &DAServerCelestiaConfig) -> Self {
-        let namespace_str = cfg.namespace.as_ref().unwrap().clone();
-        let namespace: Namespace = serde_yaml::from_str(&namespace_str).unwrap();
-        let conn_str = cfg.conn.as_ref().unwrap().clone();
-        let token = cfg.auth_token.as_ref().unwrap().clone();
-
+    pub async fn new(cfg: &DAServerCelestiaConfig, rooch_store: RoochStore) -> Self {
         Self {
             max_segment_size: cfg.max_segment_size.unwrap() as usize,
-            backend: Backend::new(namespace, &conn_str, &token).await,
+            backend: Backend::new_with_config(cfg).await.unwrap(),
+            rooch_store,
         }
     }
 
-    // TODO reuse public_batch logic in openda
-    pub async fn public_batch(&self, batch: PutBatchInternalDAMessage) -> Result<()> {
-        // TODO using chunk builder to make segments:
-        // 1. persist batch into buffer then return ok
-        // 2. collect batch for better compression ratio
-        // 3. split chunk into segments
-        // 4. submit segments to celestia node
-        // 5. record segment id in order
-        // 6. clean up batch buffer
-        // TODO more chunk version supports
-        let chunk = DABatchV0 {
-            version: 0,
-            block_number: batch.batch.block_number,
-            batch_hash: batch.batch.batch_hash,
-            data: batch.batch.data,
-        };
-        let chunk_bytes = bcs::to_bytes(&chunk).unwrap();
-        let segs = chunk_bytes.chunks(self.max_segment_size);
-        let total = segs.len();
-
-        let chunk_id = batch.batch.block_number;
-        let segments = segs
-            .enumerate()
-            .map(|(i, data)| {
-                SegmentV0 {
-                    id: SegmentID {
-                        chunk_id,
-                        segment_number: i as u64,
-                    },
-                    is_last: i == total - 1, // extra info overhead is much smaller than max_block_size - max_segment_size
-                    data_checksum: 0,
-                    checksum: 0,
-                    data: data.to_vec(),
-                }
-            })
-            .collect::<Vec<_>>();
+    // TODO reuse public_chunk logic in openda
+    pub async fn public_chunk(&mut self, msg: PutChunkInternalDAMessage) -> Result<()> {
+        let chunk = msg.chunk;
+        let segments = chunk.to_segments(self.max_segment_size);
 
+        let mut heights = Vec::with_capacity(segments.len());
         for segment in segments {
             // TODO record ok segment in order
             // TODO segment indexer trait (local file, db, etc)
-            self.backend.submit(Box::new(segment)).await?;
+            let result = self.backend.submit(Box::new(segment)).await?;
+            heights.push(result.height);
+        }
+        heights.dedup();
+        let batches = chunk
+            .batches
+            .iter()
+            .map(|batch| (batch.block_number, batch.batch_hash))
+            .collect();
+        self.rooch_store
+            .save_celestia_chunk_index(chunk.chunk_id, heights, batches)
+    }
+
+    pub async fn get_batch(&self, block_number: u128) -> Result<Option<Batch>> {
+        let chunk_id = match self.rooch_store.get_celestia_block_chunk(block_number)? {
+            Some(chunk_id) => chunk_id,
+            None => return Ok(None),
+        };
+        let heights = match self.rooch_store.get_celestia_chunk_heights(chunk_id)? {
+            Some(heights) => heights,
+            None => return Ok(None),
+        };
+
+        let mut segments: Vec<Box<dyn Segment>> = Vec::new();
+        for height in heights {
+            for segment in self.backend.get_segments(height).await? {
+                if segment.get_id().chunk_id == chunk_id {
+                    segments.push(segment);
+                }
+            }
+        }
+        segments.sort_by_key(|segment| segment.get_id().segment_number);
+
+        let chunk = chunk_from_segments(segments)?;
+        Ok(chunk.get_batch(block_number))
+    }
+
+    pub async fn get_batch_by_hash(&self, batch_hash: H256) -> Result<Option<Batch>> {
+        match self
+            .rooch_store
+            .get_celestia_batch_block_number(batch_hash)?
+        {
+            Some(block_number) => self.get_batch(block_number).await,
+            None => Ok(None),
         }
-        Ok(())
     }
 }
 
 #[async_trait]
-impl Handler<PutBatchInternalDAMessage> for DAServerCelestiaActor {
+impl Handler<PutChunkInternalDAMessage> for DAServerCelestiaActor {
     async fn handle(
         &mut self,
-        msg: PutBatchInternalDAMessage,
+        msg: PutChunkInternalDAMessage,
         _ctx: &mut ActorContext,
     ) -> Result<()> {
-        self.public_batch(msg).await
+        self.public_chunk(msg).await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchInternalDAMessage> for DAServerCelestiaActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Batch>> {
+        self.get_batch(msg.block_number).await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchByHashInternalDAMessage> for DAServerCelestiaActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchByHashInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Batch>> {
+        self.get_batch_by_hash(msg.batch_hash).await
     }
 }
@@ -1,13 +1,16 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use celestia_rpc::{BlobClient, Client};
 use celestia_types::blob::SubmitOptions;
 use celestia_types::nmt::Namespace;
 use celestia_types::{Blob, Commitment};
+use rooch_config::da_config::DAServerCelestiaConfig;
 
-use crate::segment::{Segment, SegmentID};
+use crate::segment::{segment_from_bytes, Segment, SegmentID};
+
+pub(crate) const BLOB_NOT_FOUND: &str = "blob: not found";
 
 pub struct Backend {
     namespace: Namespace,
@@ -22,14 +25,30 @@ pub struct SubmitBackendResult {
 }
 
 impl Backend {
-    pub async fn new(namespace: Namespace, conn_str: &str, auth_token: &str) -> Self {
-        let celestia_client = Client::new(conn_str, Option::from(auth_token))
-            .await
-            .unwrap();
-        Self {
+    pub async fn new(namespace: Namespace, conn_str: &str, auth_token: &str) -> Result<Self> {
+        let celestia_client = Client::new(conn_str, Option::from(auth_token)).await?;
+        Ok(Self {
             namespace,
             client: celestia_client,
-        }
+        })
+    }
+
+    pub async fn new_with_config(cfg: &DAServerCelestiaConfig) -> Result<Self> {
+        let namespace_str = cfg
+            .namespace
+            .as_ref()
+            .ok_or_else(|| anyhow!("celestia namespace must be set"))?;
+        let namespace: Namespace = serde_yaml::from_str(namespace_str)
+            .map_err(|e| anyhow!("invalid celestia namespace {}: {}", namespace_str, e))?;
+        let conn_str = cfg
+            .conn
+            .as_ref()
+            .ok_or_else(|| anyhow!("celestia conn must be set"))?;
+        let token = cfg
+            .auth_token
+            .as_ref()
+            .ok_or_else(|| anyhow!("celestia auth token must be set"))?;
+        Self::new(namespace, conn_str, token).await
     }
 
     // TODO return segment id, height, commitment
@@ -63,4 +82,36 @@ impl Backend {
             }
         }
     }
+
+    /// Get all blobs in the namespace at the given height
+    pub async fn get_blobs(&self, height: u64) -> Result<Vec<Blob>> {
+        match self.client.blob_get_all(height, &[self.namespace]).await {
+            Ok(blobs) => Ok(blobs),
+            // celestia node returns an error rather than an empty list if no blob in the namespace
+            Err(e) if e.to_string().contains(BLOB_NOT_FOUND) => Ok(vec![]),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get all segments in the namespace at the given height,
+    /// blobs which are not valid segments are skipped
+    pub async fn get_segments(&self, height: u64) -> Result<Vec<Box<dyn Segment>>> {
+        let blobs = self.get_blobs(height).await?;
+        let segments = blobs
+            .into_iter()
+            .filter_map(|blob| match segment_from_bytes(&blob.data) {
+                Ok(segment) => Some(segment),
+                Err(e) => {
+                    log::warn!(
+                        "skip invalid segment in celestia blob, height: {}, commitment: {:?}, error: {:?}",
+                        height,
+                        blob.commitment,
+                        e,
+                    );
+                    None
+                }
+            })
+            .collect();
+        Ok(segments)
+    }
 }
@@ -4,3 +4,4 @@
 pub mod actor;
 mod backend;
 pub mod proxy;
+pub mod scanner;
@@ -4,7 +4,9 @@
 use async_trait::async_trait;
 use coerce::actor::ActorRef;
 
-use crate::messages::PutBatchInternalDAMessage;
+use crate::messages::{
+    Batch, GetBatchByHashInternalDAMessage, GetBatchInternalDAMessage, PutChunkInternalDAMessage,
+};
 use crate::server::celestia::actor::server::DAServerCelestiaActor;
 use crate::server::serverproxy::DAServerProxy;
 
@@ -18,14 +20,25 @@ impl DAServerCelestiaProxy {
         Self { actor }
     }
 
-    pub async fn submit_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
+    pub async fn submit_chunk(&self, msg: PutChunkInternalDAMessage) -> anyhow::Result<()> {
         self.actor.send(msg).await?
     }
 }
 
 #[async_trait]
 impl DAServerProxy for DAServerCelestiaProxy {
-    async fn public_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
-        self.submit_batch(msg).await
+    async fn public_chunk(&self, msg: PutChunkInternalDAMessage) -> anyhow::Result<()> {
+        self.submit_chunk(msg).await
+    }
+
+    async fn get_batch(&self, msg: GetBatchInternalDAMessage) -> anyhow::Result<Option<Batch>> {
+        self.actor.send(msg).await?
+    }
+
+    async fn get_batch_by_hash(
+        &self,
+        msg: GetBatchByHashInternalDAMessage,
+    ) -> anyhow::Result<Option<Batch>> {
+        self.actor.send(msg).await?
     }
 }
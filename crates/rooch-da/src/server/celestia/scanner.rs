@@ -0,0 +1,332 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure, Result};
+use rooch_config::da_config::DAServerCelestiaConfig;
+use rooch_types::block::Block;
+use rooch_types::transaction::LedgerTransaction;
+use std::collections::BTreeMap;
+
+use crate::chunk::{chunk_from_segments, Chunk, MAX_BATCHES_PER_CHUNK};
+use crate::messages::Batch;
+use crate::segment::{segment_from_bytes, Segment};
+use crate::server::celestia::backend::Backend;
+
+/// A chunk rebuilt from the segments found on Celestia
+pub struct ScannedChunk {
+    pub chunk: Box<dyn Chunk>,
+    /// The heights of the blobs of the chunk segments
+    pub heights: Vec<u64>,
+}
+
+/// The chunks found in the namespace of a height range
+#[derive(Default)]
+pub struct ScanResult {
+    /// The complete and checksum-valid chunks, by chunk id
+    pub chunks: BTreeMap<u128, ScannedChunk>,
+    /// The chunks missing some segments
+    pub incomplete_chunks: Vec<u128>,
+    /// The chunks with all segments but failed to be rebuilt, e.g. batch hash mismatch
+    pub invalid_chunks: Vec<(u128, String)>,
+    /// How many blobs in the namespace are not valid segments
+    pub invalid_blobs: u64,
+}
+
+impl ScanResult {
+    /// Get the batch of the block from the scanned chunks
+    pub fn get_batch(&self, block_number: u128) -> Option<Batch> {
+        // the chunk id is the first block number of the chunk
+        self.chunks
+            .range(..=block_number)
+            .rev()
+            .take(MAX_BATCHES_PER_CHUNK)
+            .find_map(|(_, scanned)| scanned.chunk.get_batch(block_number))
+    }
+}
+
+/// ChunkScanner reads the blobs in the namespace height by height,
+/// and rebuilds the chunks from their segments.
+pub struct ChunkScanner {
+    backend: Backend,
+    // chunk id -> segment number -> (height, segment)
+    segments: BTreeMap<u128, BTreeMap<u64, (u64, Box<dyn Segment>)>>,
+    invalid_blobs: u64,
+}
+
+impl ChunkScanner {
+    pub async fn new(cfg: &DAServerCelestiaConfig) -> Result<Self> {
+        Ok(Self {
+            backend: Backend::new_with_config(cfg).await?,
+            segments: BTreeMap::new(),
+            invalid_blobs: 0,
+        })
+    }
+
+    /// Read the segments at the heights from `start_height` to `end_height`(inclusive)
+    pub async fn scan(&mut self, start_height: u64, end_height: u64) -> Result<()> {
+        for height in start_height..=end_height {
+            for blob in self.backend.get_blobs(height).await? {
+                match segment_from_bytes(&blob.data) {
+                    Ok(segment) => self.add_segment(height, segment),
+                    Err(e) => {
+                        log::warn!(
+                            "invalid segment in celestia blob, height: {}, commitment: {:?}, error: {:?}",
+                            height,
+                            blob.commitment,
+                            e,
+                        );
+                        self.invalid_blobs += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_segment(&mut self, height: u64, segment: Box<dyn Segment>) {
+        let id = segment.get_id();
+        // a chunk could be submitted more than once if the submission is retried,
+        // keep the segment at the lowest height.
+        self.segments
+            .entry(id.chunk_id)
+            .or_default()
+            .entry(id.segment_number)
+            .or_insert((height, segment));
+    }
+
+    /// Rebuild the chunks from the scanned segments
+    pub fn finish(self) -> ScanResult {
+        let mut result = ScanResult {
+            invalid_blobs: self.invalid_blobs,
+            ..Default::default()
+        };
+        for (chunk_id, segments) in self.segments {
+            let last_segment_number = segments
+                .values()
+                .find(|(_, segment)| segment.is_last())
+                .map(|(_, segment)| segment.get_id().segment_number);
+            let is_complete = match last_segment_number {
+                // the segment numbers are sorted and unique
+                Some(last) => segments.keys().copied().eq(0..=last),
+                None => false,
+            };
+            if !is_complete {
+                result.incomplete_chunks.push(chunk_id);
+                continue;
+            }
+
+            let mut heights = segments
+                .values()
+                .map(|(height, _)| *height)
+                .collect::<Vec<_>>();
+            heights.sort();
+            heights.dedup();
+            let segments = segments
+                .into_values()
+                .map(|(_, segment)| segment)
+                .collect::<Vec<_>>();
+            match chunk_from_segments(segments) {
+                Ok(chunk) => {
+                    result
+                        .chunks
+                        .insert(chunk_id, ScannedChunk { chunk, heights });
+                }
+                Err(e) => result.invalid_chunks.push((chunk_id, e.to_string())),
+            }
+        }
+        result
+    }
+}
+
+/// Check the batch contains exactly the transactions of the proposed block
+pub fn verify_block_batch(block: &Block, batch: &Batch) -> Result<()> {
+    let txs = LedgerTransaction::decode_batch(&batch.data)?;
+    ensure!(
+        txs.len() as u64 == block.batch_size,
+        "transactions count mismatch, exp: {}, act: {}",
+        block.batch_size,
+        txs.len()
+    );
+    let last_tx = txs
+        .last()
+        .ok_or_else(|| anyhow!("no transactions in the batch"))?;
+    ensure!(
+        last_tx.sequence_info.tx_accumulator_root == block.tx_accumulator_root,
+        "tx accumulator root mismatch, exp: {:?}, act: {:?}",
+        block.tx_accumulator_root,
+        last_tx.sequence_info.tx_accumulator_root
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::ChunkV1;
+    use crate::server::celestia::backend::BLOB_NOT_FOUND;
+    use celestia_types::nmt::Namespace;
+    use jsonrpsee::server::{Server, ServerHandle};
+    use jsonrpsee::types::ErrorObjectOwned;
+    use jsonrpsee::RpcModule;
+    use moveos_types::h256;
+    use rooch_types::test_utils::random_ledger_transaction;
+    use serde_json::Value;
+    use std::sync::Mutex;
+
+    /// A stand-in of the celestia node blob API, blob i is included at height i + 1
+    #[derive(Default)]
+    struct MockCelestia {
+        blobs: Mutex<Vec<Vec<Value>>>,
+    }
+
+    async fn start_mock_celestia() -> (DAServerCelestiaConfig, ServerHandle) {
+        let mut module = RpcModule::new(MockCelestia::default());
+        module
+            .register_method("blob.Submit", |params, ctx, _| {
+                let (blobs, _options) = params.parse::<(Vec<Value>, Value)>()?;
+                let mut included = ctx.blobs.lock().unwrap();
+                included.push(blobs);
+                Ok::<u64, ErrorObjectOwned>(included.len() as u64)
+            })
+            .unwrap();
+        module
+            .register_method("blob.GetAll", |params, ctx, _| {
+                let (height, namespaces) = params.parse::<(u64, Vec<Value>)>()?;
+                let included = ctx.blobs.lock().unwrap();
+                let blobs = included
+                    .get((height as usize).wrapping_sub(1))
+                    .map(|blobs| {
+                        blobs
+                            .iter()
+                            .filter(|blob| namespaces.contains(&blob["namespace"]))
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                if blobs.is_empty() {
+                    return Err(ErrorObjectOwned::owned(1, BLOB_NOT_FOUND, None::<()>));
+                }
+                Ok(blobs)
+            })
+            .unwrap();
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = server.start(module);
+
+        let namespace = Namespace::new_v0(b"rooch").unwrap();
+        let cfg = DAServerCelestiaConfig {
+            namespace: Some(serde_yaml::to_string(&namespace).unwrap()),
+            conn: Some(format!("http://{}", addr)),
+            auth_token: Some("token".to_string()),
+            max_segment_size: Some(64),
+        };
+        (cfg, handle)
+    }
+
+    /// Build the blocks and their batches, each block has `txs_per_block` transactions
+    fn new_blocks(start: u128, count: u128, txs_per_block: usize) -> (Vec<Block>, Vec<Batch>) {
+        (start..start + count)
+            .map(|block_number| {
+                let txs = (0..txs_per_block)
+                    .map(|_| random_ledger_transaction())
+                    .collect::<Vec<_>>();
+                let block = Block::new(
+                    block_number,
+                    txs.len() as u64,
+                    h256::H256::random(),
+                    txs.last().unwrap().sequence_info.tx_accumulator_root,
+                    vec![],
+                );
+                let data = LedgerTransaction::encode_batch(&txs);
+                let batch = Batch {
+                    block_number,
+                    batch_hash: h256::sha3_256_of(&data),
+                    data,
+                };
+                (block, batch)
+            })
+            .unzip()
+    }
+
+    async fn submit_chunk(backend: &Backend, chunk: &dyn Chunk, max_segment_size: usize) -> usize {
+        let segments = chunk.to_segments(max_segment_size);
+        let count = segments.len();
+        for segment in segments {
+            backend.submit(Box::new(segment)).await.unwrap();
+        }
+        count
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_verify() {
+        let (cfg, _handle) = start_mock_celestia().await;
+        let backend = Backend::new_with_config(&cfg).await.unwrap();
+
+        let (blocks, batches) = new_blocks(0, 4, 3);
+        let chunk = ChunkV1::new(batches).unwrap();
+        let segment_count = submit_chunk(&backend, &chunk, 64).await;
+        assert!(segment_count > 1);
+        // the chunk is submitted again by a retry
+        submit_chunk(&backend, &chunk, 64).await;
+
+        let mut scanner = ChunkScanner::new(&cfg).await.unwrap();
+        // the heights after the last blob are empty
+        scanner.scan(1, segment_count as u64 * 2 + 2).await.unwrap();
+        let result = scanner.finish();
+
+        assert_eq!(result.chunks.len(), 1);
+        assert!(result.incomplete_chunks.is_empty());
+        assert!(result.invalid_chunks.is_empty());
+        assert_eq!(
+            result.chunks[&0].heights,
+            (1..=segment_count as u64).collect::<Vec<_>>()
+        );
+        for block in &blocks {
+            let batch = result.get_batch(block.block_number).unwrap();
+            verify_block_batch(block, &batch).unwrap();
+        }
+        assert!(result.get_batch(4).is_none());
+
+        // the batch of another block does not match
+        let batch = result.get_batch(1).unwrap();
+        assert!(verify_block_batch(&blocks[0], &batch).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_broken_chunks() {
+        let (cfg, _handle) = start_mock_celestia().await;
+        let backend = Backend::new_with_config(&cfg).await.unwrap();
+
+        // a chunk missing the last segment
+        let (_, batches) = new_blocks(0, 2, 3);
+        let chunk = ChunkV1::new(batches).unwrap();
+        let mut segments = chunk.to_segments(64);
+        segments.pop();
+        let incomplete_count = segments.len() as u64;
+        for segment in segments {
+            backend.submit(Box::new(segment)).await.unwrap();
+        }
+
+        // a chunk with a tampered batch hash
+        let (_, mut batches) = new_blocks(2, 1, 1);
+        batches[0].batch_hash = h256::H256::random();
+        let chunk = ChunkV1 {
+            chunk_id: 2,
+            batches,
+        };
+        let invalid_count = submit_chunk(&backend, &chunk, 64).await as u64;
+
+        let mut scanner = ChunkScanner::new(&cfg).await.unwrap();
+        scanner
+            .scan(1, incomplete_count + invalid_count)
+            .await
+            .unwrap();
+        let result = scanner.finish();
+
+        assert!(result.chunks.is_empty());
+        assert_eq!(result.incomplete_chunks, vec![0]);
+        assert_eq!(result.invalid_chunks.len(), 1);
+        assert_eq!(result.invalid_chunks[0].0, 2);
+        assert!(result.get_batch(0).is_none());
+    }
+}
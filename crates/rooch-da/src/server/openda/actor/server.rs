@@ -7,27 +7,26 @@ use coerce::actor::context::ActorContext;
 use coerce::actor::message::Handler;
 use coerce::actor::Actor;
 use opendal::layers::{LoggingLayer, RetryLayer};
-use opendal::{Operator, Scheme};
+use opendal::{ErrorKind, Operator, Scheme};
 use rooch_config::config::retrieve_map_config_value;
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::chunk::DABatchV0;
+use crate::chunk::{chunk_from_segments, Chunk};
+use moveos_types::h256::H256;
 use rooch_config::da_config::{DAServerOpenDAConfig, OpenDAScheme};
 
-use crate::messages::PutBatchInternalDAMessage;
-use crate::segment::{Segment, SegmentID, SegmentV0};
+use crate::messages::{
+    Batch, GetBatchByHashInternalDAMessage, GetBatchInternalDAMessage, PutChunkInternalDAMessage,
+};
+use crate::segment::{segment_from_bytes, Segment, SegmentID};
 
 pub struct DAServerOpenDAActor {
     max_segment_size: usize,
     operator: Operator,
 }
 
-// TODO get request and response
-// 1. get by block number
-// 2. get by batch hash
-// 3. pull by stream
-//
+// TODO pull by stream
 
 impl Actor for DAServerOpenDAActor {}
 
@@ -110,7 +109,42 @@ impl DAServerOpenDAActor {
                 // After setting defaults, proceed with creating Operator
                 new_retry_operator(Scheme::Gcs, config.config, None).await?
             }
-            _ => Err(anyhow!("unsupported open-da scheme: {:?}", config.scheme))?,
+            OpenDAScheme::S3 => {
+                // If certain keys don't exist in the map, set them from environment
+                for (key, env_var) in [
+                    ("bucket", "OPENDA_S3_BUCKET"),
+                    ("root", "OPENDA_S3_ROOT"),
+                    ("endpoint", "OPENDA_S3_ENDPOINT"),
+                    ("access_key_id", "OPENDA_S3_ACCESS_KEY_ID"),
+                    ("secret_access_key", "OPENDA_S3_SECRET_ACCESS_KEY"),
+                ] {
+                    if !config.config.contains_key(key) {
+                        if let Ok(value) = std::env::var(env_var) {
+                            config.config.insert(key.to_string(), value);
+                        }
+                    }
+                }
+                // most S3-compatible services(e.g. MinIO) accept any region, us-east-1 is the AWS default
+                retrieve_map_config_value(
+                    &mut config.config,
+                    "region",
+                    Some("OPENDA_S3_REGION"),
+                    "us-east-1",
+                );
+
+                check_config_exist(OpenDAScheme::S3, &config.config, "bucket")?;
+                // the credential could be loaded from the AWS environment/profile by opendal,
+                // but the key pair must be complete if it is given in config.
+                match (
+                    check_config_exist(OpenDAScheme::S3, &config.config, "access_key_id"),
+                    check_config_exist(OpenDAScheme::S3, &config.config, "secret_access_key"),
+                ) {
+                    (Ok(_), Err(e)) | (Err(e), Ok(_)) => return Err(e),
+                    _ => (),
+                }
+
+                new_retry_operator(Scheme::S3, config.config, None).await?
+            }
         };
 
         Ok(Self {
@@ -119,43 +153,9 @@ impl DAServerOpenDAActor {
         })
     }
 
-    pub async fn pub_batch(&self, batch: PutBatchInternalDAMessage) -> Result<()> {
-        // TODO using chunk builder to make segments:
-        // 1. persist batch into buffer then return ok
-        // 2. collect batch for better compression ratio
-        // 3. split chunk into segments
-        // 4. submit segments to celestia node
-        // 5. record segment id in order
-        // 6. clean up batch buffer
-
-        // TODO more chunk version supports
-        let chunk = DABatchV0 {
-            version: 0,
-            block_number: batch.batch.block_number,
-            batch_hash: batch.batch.batch_hash,
-            data: batch.batch.data,
-        };
-        let chunk_bytes = bcs::to_bytes(&chunk).unwrap();
-        let segs = chunk_bytes.chunks(self.max_segment_size);
-        let total = segs.len();
-
-        // TODO explain why block number is a good idea: easy to get next block number for segments, then we could request chunk by block number
-        let chunk_id = batch.batch.block_number;
-        let segments = segs
-            .enumerate()
-            .map(|(i, data)| {
-                SegmentV0 {
-                    id: SegmentID {
-                        chunk_id,
-                        segment_number: i as u64,
-                    },
-                    is_last: i == total - 1, // extra info overhead is much smaller than max_block_size - max_segment_size
-                    data_checksum: 0,
-                    checksum: 0,
-                    data: data.to_vec(),
-                }
-            })
-            .collect::<Vec<_>>();
+    pub async fn pub_chunk(&self, msg: PutChunkInternalDAMessage) -> Result<()> {
+        let chunk = msg.chunk;
+        let segments = chunk.to_segments(self.max_segment_size);
 
         for segment in segments {
             let bytes = segment.to_bytes();
@@ -165,8 +165,116 @@ impl DAServerOpenDAActor {
             self.operator.write(&segment.id.to_string(), bytes).await?; // TODO retry logic
         }
 
+        // index block number -> chunk id and batch hash -> block number after all segments written,
+        // so that a found index always points to a complete chunk
+        let chunk_id = chunk.get_chunk_id();
+        for batch in chunk.get_batches() {
+            self.operator
+                .write(
+                    &block_index_path(batch.block_number),
+                    chunk_id.to_le_bytes().to_vec(),
+                )
+                .await?;
+            self.operator
+                .write(
+                    &batch_hash_index_path(&batch.batch_hash),
+                    batch.block_number.to_le_bytes().to_vec(),
+                )
+                .await?;
+        }
+
         Ok(())
     }
+
+    /// Get the chunk by chunk id, return None if the chunk is not found
+    async fn get_chunk(&self, chunk_id: u128) -> Result<Option<Box<dyn Chunk>>> {
+        let mut segments: Vec<Box<dyn Segment>> = Vec::new();
+        loop {
+            let segment_id = SegmentID {
+                chunk_id,
+                segment_number: segments.len() as u64,
+            };
+            let bytes = match self.operator.read(&segment_id.to_string()).await {
+                Ok(bytes) => bytes.to_vec(),
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    if segments.is_empty() {
+                        return Ok(None);
+                    }
+                    return Err(anyhow!(
+                        "incomplete chunk {}: segment {} not found",
+                        chunk_id,
+                        segment_id
+                    ));
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let segment = segment_from_bytes(&bytes)?;
+            let is_last = segment.is_last();
+            segments.push(segment);
+            if is_last {
+                break;
+            }
+        }
+
+        Ok(Some(chunk_from_segments(segments)?))
+    }
+
+    pub async fn get_batch(&self, block_number: u128) -> Result<Option<Batch>> {
+        let chunk_id = match self.operator.read(&block_index_path(block_number)).await {
+            Ok(bytes) => u128::from_le_bytes(
+                bytes
+                    .to_vec()
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| anyhow!("invalid block index for {}", block_number))?,
+            ),
+            // the chunks submitted before the block index include one batch only,
+            // the chunk id is the block number.
+            Err(e) if e.kind() == ErrorKind::NotFound => block_number,
+            Err(e) => return Err(e.into()),
+        };
+        match self.get_chunk(chunk_id).await? {
+            Some(chunk) => Ok(chunk.get_batch(block_number)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_batch_by_hash(&self, batch_hash: H256) -> Result<Option<Batch>> {
+        let bytes = match self
+            .operator
+            .read(&batch_hash_index_path(&batch_hash))
+            .await
+        {
+            Ok(bytes) => bytes.to_vec(),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let block_number = u128::from_le_bytes(
+            bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow!("invalid batch hash index for {:?}", batch_hash))?,
+        );
+
+        let batch = self.get_batch(block_number).await?;
+        match batch {
+            Some(batch) if batch.batch_hash != batch_hash => Err(anyhow!(
+                "batch hash mismatch for block {}, exp: {:?}, act: {:?}",
+                block_number,
+                batch_hash,
+                batch.batch_hash
+            )),
+            _ => Ok(batch),
+        }
+    }
+}
+
+fn block_index_path(block_number: u128) -> String {
+    format!("block/{}", block_number)
+}
+
+fn batch_hash_index_path(batch_hash: &H256) -> String {
+    format!("batch_hash/{:x}", batch_hash)
 }
 
 fn check_config_exist(
@@ -200,12 +308,170 @@ async fn new_retry_operator(
 }
 
 #[async_trait]
-impl Handler<PutBatchInternalDAMessage> for DAServerOpenDAActor {
+impl Handler<PutChunkInternalDAMessage> for DAServerOpenDAActor {
     async fn handle(
         &mut self,
-        msg: PutBatchInternalDAMessage,
+        msg: PutChunkInternalDAMessage,
         _ctx: &mut ActorContext,
     ) -> Result<()> {
-        self.pub_batch(msg).await
+        self.pub_chunk(msg).await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchInternalDAMessage> for DAServerOpenDAActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Batch>> {
+        self.get_batch(msg.block_number).await
+    }
+}
+
+#[async_trait]
+impl Handler<GetBatchByHashInternalDAMessage> for DAServerOpenDAActor {
+    async fn handle(
+        &mut self,
+        msg: GetBatchByHashInternalDAMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Batch>> {
+        self.get_batch_by_hash(msg.batch_hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::{ChunkV1, DABatchV0};
+    use moveos_types::h256;
+
+    async fn new_fs_actor(root: &Path, max_segment_size: u64) -> DAServerOpenDAActor {
+        let mut config = HashMap::new();
+        config.insert("root".to_string(), root.to_str().unwrap().to_string());
+        DAServerOpenDAActor::new(&DAServerOpenDAConfig {
+            scheme: OpenDAScheme::Fs,
+            config,
+            max_segment_size: Some(max_segment_size),
+        })
+        .await
+        .unwrap()
+    }
+
+    fn new_batch(block_number: u128, data: Vec<u8>) -> Batch {
+        Batch {
+            block_number,
+            batch_hash: h256::sha3_256_of(&data),
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pub_and_get_batch() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let actor = new_fs_actor(tmp_dir.path(), 64).await;
+
+        let batches = (3..6)
+            .map(|block_number| {
+                new_batch(
+                    block_number,
+                    (0..1024)
+                        .map(|i| ((i + block_number) % 256) as u8)
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        actor
+            .pub_chunk(PutChunkInternalDAMessage {
+                chunk: ChunkV1::new(batches.clone()).unwrap(),
+            })
+            .await
+            .unwrap();
+
+        for batch in &batches {
+            let got = actor.get_batch(batch.block_number).await.unwrap().unwrap();
+            assert_eq!(&got, batch);
+
+            let got = actor
+                .get_batch_by_hash(batch.batch_hash)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(got.block_number, batch.block_number);
+        }
+
+        assert!(actor.get_batch(2).await.unwrap().is_none());
+        assert!(actor.get_batch(6).await.unwrap().is_none());
+
+        // the block in the middle of the chunk is found by the block index only
+        std::fs::remove_file(tmp_dir.path().join(block_index_path(4))).unwrap();
+        assert!(actor.get_batch(4).await.unwrap().is_none());
+        assert!(actor
+            .get_batch_by_hash(H256::random())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_v0_batch() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let actor = new_fs_actor(tmp_dir.path(), 64).await;
+
+        // the chunks submitted before chunk v1 should still be readable
+        let batch = new_batch(2, vec![2u8; 1024]);
+        for segment in DABatchV0::from(batch.clone()).to_segments(64) {
+            actor
+                .operator
+                .write(&segment.id.to_string(), segment.to_bytes())
+                .await
+                .unwrap();
+        }
+
+        let got = actor.get_batch(2).await.unwrap().unwrap();
+        assert_eq!(got, batch);
+    }
+
+    #[tokio::test]
+    async fn test_get_incomplete_batch() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        // the compressed chunk is small, use a tiny segment size to split it
+        let actor = new_fs_actor(tmp_dir.path(), 8).await;
+
+        actor
+            .pub_chunk(PutChunkInternalDAMessage {
+                chunk: ChunkV1::new(vec![new_batch(0, vec![7u8; 1024])]).unwrap(),
+            })
+            .await
+            .unwrap();
+        std::fs::remove_file(tmp_dir.path().join("0_1")).unwrap();
+
+        assert!(actor.get_batch(0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_s3_config_check() {
+        let new_s3_actor = |config: Vec<(&str, &str)>| {
+            DAServerOpenDAActor::new(&DAServerOpenDAConfig {
+                scheme: OpenDAScheme::S3,
+                config: config
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                max_segment_size: None,
+            })
+        };
+
+        let err = new_s3_actor(vec![("access_key_id", "key")])
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("bucket"));
+
+        let err = new_s3_actor(vec![("bucket", "rooch"), ("access_key_id", "key")])
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("secret_access_key"));
     }
 }
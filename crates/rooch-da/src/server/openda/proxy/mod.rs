@@ -1,7 +1,9 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::messages::PutBatchInternalDAMessage;
+use crate::messages::{
+    Batch, GetBatchByHashInternalDAMessage, GetBatchInternalDAMessage, PutChunkInternalDAMessage,
+};
 use crate::server::openda::actor::server::DAServerOpenDAActor;
 use crate::server::serverproxy::DAServerProxy;
 use async_trait::async_trait;
@@ -17,14 +19,25 @@ impl DAServerOpenDAProxy {
         Self { actor }
     }
 
-    pub async fn submit_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
+    pub async fn submit_chunk(&self, msg: PutChunkInternalDAMessage) -> anyhow::Result<()> {
         self.actor.send(msg).await?
     }
 }
 
 #[async_trait]
 impl DAServerProxy for DAServerOpenDAProxy {
-    async fn public_batch(&self, msg: PutBatchInternalDAMessage) -> anyhow::Result<()> {
-        self.submit_batch(msg).await
+    async fn public_chunk(&self, msg: PutChunkInternalDAMessage) -> anyhow::Result<()> {
+        self.submit_chunk(msg).await
+    }
+
+    async fn get_batch(&self, msg: GetBatchInternalDAMessage) -> anyhow::Result<Option<Batch>> {
+        self.actor.send(msg).await?
+    }
+
+    async fn get_batch_by_hash(
+        &self,
+        msg: GetBatchByHashInternalDAMessage,
+    ) -> anyhow::Result<Option<Batch>> {
+        self.actor.send(msg).await?
     }
 }
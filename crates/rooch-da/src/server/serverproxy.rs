@@ -4,11 +4,22 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::messages::PutBatchInternalDAMessage;
+use crate::messages::{
+    Batch, GetBatchByHashInternalDAMessage, GetBatchInternalDAMessage, PutChunkInternalDAMessage,
+};
 
 #[async_trait]
 pub trait DAServerProxy: Sync + Send {
-    async fn public_batch(&self, request: PutBatchInternalDAMessage) -> Result<()>;
+    async fn public_chunk(&self, request: PutChunkInternalDAMessage) -> Result<()>;
+
+    /// Get the verified batch by block number, return None if the batch is not found
+    async fn get_batch(&self, request: GetBatchInternalDAMessage) -> Result<Option<Batch>>;
+
+    /// Get the verified batch by batch hash, return None if the batch is not found
+    async fn get_batch_by_hash(
+        &self,
+        request: GetBatchByHashInternalDAMessage,
+    ) -> Result<Option<Batch>>;
 }
 
 // DAServerNopProxy is a no-op implementation of DAServerProxy
@@ -16,7 +27,18 @@ pub struct DAServerNopProxy;
 
 #[async_trait]
 impl DAServerProxy for DAServerNopProxy {
-    async fn public_batch(&self, _request: PutBatchInternalDAMessage) -> Result<()> {
+    async fn public_chunk(&self, _request: PutChunkInternalDAMessage) -> Result<()> {
         Ok(())
     }
+
+    async fn get_batch(&self, _request: GetBatchInternalDAMessage) -> Result<Option<Batch>> {
+        Ok(None)
+    }
+
+    async fn get_batch_by_hash(
+        &self,
+        _request: GetBatchByHashInternalDAMessage,
+    ) -> Result<Option<Batch>> {
+        Ok(None)
+    }
 }
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashSet;
+use std::path::Path;
 
 use anyhow::Result;
 use moveos_store::MoveOSStore;
 use moveos_types::moveos_std::object::ObjectMeta;
 use prometheus::Registry;
 use raw_store::metrics::DBMetrics;
-use raw_store::{rocks::RocksDB, StoreInstance};
+use raw_store::{rocks::RocksDB, ColumnFamilyName, StoreInstance};
 use rooch_config::store_config::StoreConfig;
 use rooch_indexer::{indexer_reader::IndexerReader, IndexerStore};
 use rooch_store::RoochStore;
@@ -35,8 +36,16 @@ impl RoochDB {
         let indexer_dir = config.get_indexer_dir();
         let moveos_store = MoveOSStore::new_with_instance(instance.clone(), registry)?;
         let rooch_store = RoochStore::new_with_instance(instance, registry)?;
-        let indexer_store = IndexerStore::new(indexer_dir.clone(), registry)?;
-        let indexer_reader = IndexerReader::new(indexer_dir, registry)?;
+        let (indexer_store, indexer_reader) = match config.indexer_db_url() {
+            Some(indexer_db_url) => (
+                IndexerStore::new_pg(indexer_db_url, registry)?,
+                IndexerReader::new_pg(indexer_db_url, registry)?,
+            ),
+            None => (
+                IndexerStore::new(indexer_dir.clone(), registry)?,
+                IndexerReader::new(indexer_dir, registry)?,
+            ),
+        };
 
         Ok(Self {
             moveos_store,
@@ -51,6 +60,37 @@ impl RoochDB {
         registry: &Registry,
     ) -> Result<StoreInstance> {
         let store_dir = config.get_store_dir();
+        let db_metrics = DBMetrics::get_or_init(registry).clone();
+        let instance = StoreInstance::new_db_instance(
+            RocksDB::new(store_dir, Self::column_families(), config.rocksdb_config())?,
+            db_metrics,
+        );
+
+        Ok(instance)
+    }
+
+    /// Open the store at `store_dir` in read only mode, it is used to inspect a store
+    /// which is not used by the node, such as a snapshot.
+    pub fn generate_readonly_store_instance(
+        store_dir: &Path,
+        config: &StoreConfig,
+        registry: &Registry,
+    ) -> Result<StoreInstance> {
+        let db_metrics = DBMetrics::get_or_init(registry).clone();
+        let instance = StoreInstance::new_db_instance(
+            RocksDB::open_with_cfs(
+                store_dir,
+                Self::column_families(),
+                true,
+                config.rocksdb_config(),
+            )?,
+            db_metrics,
+        );
+
+        Ok(instance)
+    }
+
+    fn column_families() -> Vec<ColumnFamilyName> {
         let mut column_families = moveos_store::StoreMeta::get_column_family_names().to_vec();
         column_families.append(&mut rooch_store::StoreMeta::get_column_family_names().to_vec());
         //ensure no duplicate column families
@@ -62,14 +102,7 @@ impl RoochDB {
                 }
             });
         }
-
-        let db_metrics = DBMetrics::get_or_init(registry).clone();
-        let instance = StoreInstance::new_db_instance(
-            RocksDB::new(store_dir, column_families, config.rocksdb_config())?,
-            db_metrics,
-        );
-
-        Ok(instance)
+        column_families
     }
 
     pub fn init_with_mock_metrics_for_test(config: &StoreConfig) -> Result<Self> {
@@ -3,8 +3,10 @@
 
 use super::messages::{
     ConvertL2TransactionData, DryRunTransactionMessage, DryRunTransactionResult,
-    ExecuteTransactionMessage, ExecuteTransactionResult, GetRootMessage, ValidateL1BlockMessage,
-    ValidateL1TxMessage, ValidateL2TxMessage,
+    ExecuteTransactionMessage, ExecuteTransactionResult, ExecuteTransactionsMessage,
+    GetRootMessage, ReexecuteTransactionMessage, ReexecuteTransactionResult,
+    ValidateBlockCommitmentMessage, ValidateL1BlockMessage, ValidateL1TxMessage,
+    ValidateL2TxMessage,
 };
 use crate::metrics::ExecutorMetrics;
 use anyhow::Result;
@@ -12,7 +14,9 @@ use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use function_name::named;
 use move_core_types::vm_status::VMStatus;
-use moveos::moveos::{MoveOS, MoveOSConfig};
+use move_resource_viewer::MoveValueAnnotator;
+use moveos::moveos::{ExecuteOptions, MoveOS, MoveOSConfig};
+use moveos::parallel::ParallelExecutor;
 use moveos::vm::vm_status_explainer::explain_vm_status;
 use moveos_store::MoveOSStore;
 use moveos_types::function_return_value::FunctionResult;
@@ -29,16 +33,18 @@ use rooch_genesis::FrameworksGasParameters;
 use rooch_store::RoochStore;
 use rooch_types::address::{BitcoinAddress, MultiChainAddress};
 use rooch_types::bitcoin::BitcoinModule;
+use rooch_types::block::SignedBlockCommitment;
 use rooch_types::framework::auth_validator::{
     AuthValidatorCaller, BuiltinAuthValidator, TxValidateResult,
 };
 use rooch_types::framework::ethereum::EthereumModule;
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
 use rooch_types::framework::transaction_validator::TransactionValidator;
 use rooch_types::framework::{system_post_execute_functions, system_pre_execute_functions};
 use rooch_types::multichain_id::RoochMultiChainID;
 use rooch_types::transaction::{
-    AuthenticatorInfo, L1Block, L1BlockWithBody, L1Transaction, RoochTransaction,
-    RoochTransactionData,
+    AuthenticatorInfo, L1Block, L1BlockWithBody, L1Transaction, LedgerTransaction, LedgerTxData,
+    RoochTransaction, RoochTransactionData, TransactionSequenceInfoV1,
 };
 use std::str::FromStr;
 use std::sync::Arc;
@@ -94,17 +100,34 @@ impl ExecutorActor {
     }
 
     #[named]
-    pub fn dry_run(&mut self, tx: VerifiedMoveOSTransaction) -> Result<DryRunTransactionResult> {
+    pub fn dry_run(
+        &mut self,
+        tx: VerifiedMoveOSTransaction,
+        options: ExecuteOptions,
+    ) -> Result<DryRunTransactionResult> {
         let fn_name = function_name!();
         let _timer = self
             .metrics
             .executor_execute_tx_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
-        let (raw_output, vm_error_info) = self.moveos.execute_only(tx)?;
+        let root = tx.root.clone();
+        let (raw_output, vm_error_info, diagnostics) =
+            self.moveos.execute_with_options(tx, options)?;
+        let call_traces = diagnostics.call_traces.map(|call_traces| {
+            // The values are decoded via the states before the transaction
+            let resolver = RootObjectResolver::new(root, &self.moveos_store);
+            let annotator = MoveValueAnnotator::new(&resolver);
+            call_traces
+                .into_iter()
+                .map(|call_trace| call_trace.into_annotated(&annotator))
+                .collect()
+        });
         Ok(DryRunTransactionResult {
             raw_output,
             vm_error_info,
+            call_traces,
+            gas_profile: diagnostics.gas_profile,
         })
     }
 
@@ -127,16 +150,7 @@ impl ExecutorActor {
 
         // The cost table has been upgraded, we need to reload the native functions.
         if self.moveos.cost_table.read().is_none() {
-            let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-            let gas_parameters = FrameworksGasParameters::load_from_chain(&resolver)?;
-
-            self.moveos = MoveOS::new(
-                self.moveos_store.clone(),
-                gas_parameters.all_natives(),
-                MoveOSConfig::default(),
-                system_pre_execute_functions(),
-                system_post_execute_functions(),
-            )?;
+            self.reload_moveos()?;
         }
 
         self.root = execution_info.root_metadata();
@@ -150,6 +164,134 @@ impl ExecutorActor {
         })
     }
 
+    /// Execute a batch of sequenced transactions in parallel,
+    /// the results are the same as executing them one by one.
+    #[named]
+    pub fn execute_batch(
+        &mut self,
+        txs: Vec<VerifiedMoveOSTransaction>,
+    ) -> Result<Vec<ExecuteTransactionResult>> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .executor_execute_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let size: u64 = txs.iter().map(|tx| tx.ctx.tx_size).sum();
+        let mut results = Vec::with_capacity(txs.len());
+        let mut remaining = txs;
+        while !remaining.is_empty() {
+            let tx_hashes = remaining
+                .iter()
+                .map(|tx| tx.ctx.tx_hash())
+                .collect::<Vec<_>>();
+            let block_output = ParallelExecutor::new(&self.moveos)
+                .execute_block(self.root.clone(), remaining.clone())?;
+            let executed = block_output.outputs.len();
+            for (tx_hash, (output, _)) in tx_hashes.into_iter().zip(block_output.outputs) {
+                let execution_info = self
+                    .moveos_store
+                    .handle_tx_output(tx_hash, output.clone())?;
+                self.root = execution_info.root_metadata();
+                results.push(ExecuteTransactionResult {
+                    output,
+                    transaction_info: execution_info,
+                });
+            }
+            // The execution stops at the gas schedule upgrade, reload the native functions
+            // and continue with the remaining transactions.
+            if block_output.gas_schedule_updated {
+                self.reload_moveos()?;
+            }
+            remaining.drain(..executed);
+        }
+        self.metrics
+            .executor_execute_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
+        Ok(results)
+    }
+
+    /// Re-execute a sequenced transaction on the states of `pre_root` to re-derive its output.
+    /// The output is not applied to the store, only the state roots of the change set are calculated.
+    ///
+    /// The module cache and the gas schedule of the executor are loaded at its root, so the executor
+    /// moves its root to `pre_root` and reloads them if `pre_root` is not the root it is at,
+    /// then moves to the root after the transaction like `execute`. The consecutive transactions are
+    /// re-executed without reloading. It should be called on an executor dedicated to the re-execution,
+    /// the executor executing the new transactions would be moved back to the old states.
+    pub fn reexecute(
+        &mut self,
+        ledger_tx: LedgerTransaction,
+        pre_root: ObjectMeta,
+        l1_block_body: Option<Vec<u8>>,
+    ) -> Result<ReexecuteTransactionResult> {
+        if self.root != pre_root {
+            self.root = pre_root;
+            self.reload_moveos()?;
+        }
+        let mut moveos_tx = self.validate_ledger_tx(&ledger_tx, l1_block_body)?;
+
+        // Same as `PipelineProcessorActor::execute_tx`, add sequence info to tx context
+        moveos_tx.ctx.add(ledger_tx.sequence_info.clone())?;
+        let tx_sequence_info_v1 = TransactionSequenceInfoV1::from(ledger_tx.sequence_info);
+        moveos_tx.ctx.add(tx_sequence_info_v1)?;
+
+        let (mut output, _) = self.moveos.execute_only(moveos_tx.clone())?;
+        self.moveos_store
+            .get_state_store()
+            .calculate_change_set_state_root(&mut output.changeset)?;
+
+        self.root = output.changeset.root_metadata();
+        // Same as the reader executor, the modules upgraded by the transaction are reloaded
+        self.moveos.flush_module_cache(output.is_upgrade)?;
+        // The cost table has been upgraded, we need to reload the native functions.
+        if self.moveos.cost_table.read().is_none() {
+            self.reload_moveos()?;
+        }
+        Ok(ReexecuteTransactionResult { moveos_tx, output })
+    }
+
+    fn validate_ledger_tx(
+        &self,
+        ledger_tx: &LedgerTransaction,
+        l1_block_body: Option<Vec<u8>>,
+    ) -> Result<VerifiedMoveOSTransaction> {
+        match &ledger_tx.data {
+            LedgerTxData::L1Block(block) => {
+                let block_body = l1_block_body.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Re-execute the L1 block {} tx requires the block body",
+                        block.block_height
+                    )
+                })?;
+                self.validate_l1_block(L1BlockWithBody {
+                    block: block.clone(),
+                    block_body,
+                })
+            }
+            LedgerTxData::L1Tx(l1_tx) => self.validate_l1_tx(l1_tx.clone()),
+            LedgerTxData::L2Tx(l2_tx) => self.validate_l2_tx(l2_tx.clone()),
+            LedgerTxData::BlockCommitment(signed_commitment) => {
+                self.validate_block_commitment(signed_commitment.clone())
+            }
+        }
+    }
+
+    fn reload_moveos(&mut self) -> Result<()> {
+        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let gas_parameters = FrameworksGasParameters::load_from_chain(&resolver)?;
+
+        self.moveos = MoveOS::new(
+            self.moveos_store.clone(),
+            gas_parameters.all_natives(),
+            MoveOSConfig::default(),
+            system_pre_execute_functions(),
+            system_post_execute_functions(),
+        )?;
+        Ok(())
+    }
+
     #[named]
     pub fn validate_l1_block(
         &self,
@@ -246,6 +388,38 @@ impl ExecutorActor {
         result
     }
 
+    #[named]
+    pub fn validate_block_commitment(
+        &self,
+        signed_commitment: SignedBlockCommitment,
+    ) -> Result<VerifiedMoveOSTransaction> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .executor_validate_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let tx_hash = signed_commitment.tx_hash();
+        let tx_size = signed_commitment.tx_size();
+        // The signature and the proposer are checked again by the on-chain SCC
+        signed_commitment.verify()?;
+        let ctx = TxContext::new_system_call_ctx(tx_hash, tx_size);
+        let action = VerifiedMoveAction::Function {
+            call: StateCommitmentChainModule::create_commit_block_call(signed_commitment),
+            bypass_visibility: true,
+        };
+
+        self.metrics
+            .executor_validate_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(tx_size as f64);
+        Ok(VerifiedMoveOSTransaction::new(
+            self.root.clone(),
+            ctx,
+            action,
+        ))
+    }
+
     #[named]
     pub fn validate_l2_tx(&self, mut tx: RoochTransaction) -> Result<VerifiedMoveOSTransaction> {
         let fn_name = function_name!();
@@ -356,50 +530,57 @@ impl ExecutorActor {
         &self,
         tx_data: RoochTransactionData,
     ) -> Result<VerifiedMoveOSTransaction> {
-        let root = self.root.clone();
-
-        let mut tx_ctx = TxContext::new(
-            tx_data.sender.into(),
-            tx_data.sequence_number,
-            tx_data.max_gas_amount,
-            tx_data.tx_hash(),
-            tx_data.tx_size(),
-        );
-
-        let mut bitcoin_address = BitcoinAddress::from_str("18cBEMRxXHqzWWCxZNtU91F5sbUNKhL5PX")?;
-
-        let user_multi_chain_address: MultiChainAddress = tx_data.sender.into();
-        if user_multi_chain_address.is_bitcoin_address() {
-            bitcoin_address = user_multi_chain_address.try_into()?;
-        }
-
-        let dummy_result = TxValidateResult {
-            auth_validator_id: BuiltinAuthValidator::Bitcoin.flag().into(),
-            auth_validator: MoveOption::none(),
-            session_key: MoveOption::none(),
-            bitcoin_address,
-        };
-
-        tx_ctx.add(dummy_result)?;
-
-        let verified_action = match tx_data.action {
-            MoveAction::Script(script_call) => VerifiedMoveAction::Script { call: script_call },
-            MoveAction::Function(function_call) => VerifiedMoveAction::Function {
-                call: function_call,
-                bypass_visibility: false,
-            },
-            MoveAction::ModuleBundle(module_bundle) => VerifiedMoveAction::ModuleBundle {
-                module_bundle,
-                init_function_modules: vec![],
-            },
-        };
+        convert_to_verified_tx(self.root.clone(), tx_data)
+    }
+}
 
-        Ok(VerifiedMoveOSTransaction::new(
-            root,
-            tx_ctx,
-            verified_action,
-        ))
+/// Convert the transaction data to a verified transaction at the root without the signature,
+/// the transaction is regarded as sent by a Bitcoin address for the dry run and the gas estimation.
+pub fn convert_to_verified_tx(
+    root: ObjectMeta,
+    tx_data: RoochTransactionData,
+) -> Result<VerifiedMoveOSTransaction> {
+    let mut tx_ctx = TxContext::new(
+        tx_data.sender.into(),
+        tx_data.sequence_number,
+        tx_data.max_gas_amount,
+        tx_data.tx_hash(),
+        tx_data.tx_size(),
+    );
+
+    let mut bitcoin_address = BitcoinAddress::from_str("18cBEMRxXHqzWWCxZNtU91F5sbUNKhL5PX")?;
+
+    let user_multi_chain_address: MultiChainAddress = tx_data.sender.into();
+    if user_multi_chain_address.is_bitcoin_address() {
+        bitcoin_address = user_multi_chain_address.try_into()?;
     }
+
+    let dummy_result = TxValidateResult {
+        auth_validator_id: BuiltinAuthValidator::Bitcoin.flag().into(),
+        auth_validator: MoveOption::none(),
+        session_key: MoveOption::none(),
+        bitcoin_address,
+    };
+
+    tx_ctx.add(dummy_result)?;
+
+    let verified_action = match tx_data.action {
+        MoveAction::Script(script_call) => VerifiedMoveAction::Script { call: script_call },
+        MoveAction::Function(function_call) => VerifiedMoveAction::Function {
+            call: function_call,
+            bypass_visibility: false,
+        },
+        MoveAction::ModuleBundle(module_bundle) => VerifiedMoveAction::ModuleBundle {
+            module_bundle,
+            init_function_modules: vec![],
+        },
+    };
+
+    Ok(VerifiedMoveOSTransaction::new(
+        root,
+        tx_ctx,
+        verified_action,
+    ))
 }
 
 impl Actor for ExecutorActor {}
@@ -448,6 +629,17 @@ impl Handler<ValidateL1TxMessage> for ExecutorActor {
     }
 }
 
+#[async_trait]
+impl Handler<ValidateBlockCommitmentMessage> for ExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ValidateBlockCommitmentMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<VerifiedMoveOSTransaction> {
+        self.validate_block_commitment(msg.signed_commitment)
+    }
+}
+
 #[async_trait]
 impl Handler<ExecuteTransactionMessage> for ExecutorActor {
     async fn handle(
@@ -459,6 +651,17 @@ impl Handler<ExecuteTransactionMessage> for ExecutorActor {
     }
 }
 
+#[async_trait]
+impl Handler<ExecuteTransactionsMessage> for ExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ExecuteTransactionsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<ExecuteTransactionResult>> {
+        self.execute_batch(msg.txs)
+    }
+}
+
 #[async_trait]
 impl Handler<DryRunTransactionMessage> for ExecutorActor {
     async fn handle(
@@ -466,7 +669,18 @@ impl Handler<DryRunTransactionMessage> for ExecutorActor {
         msg: DryRunTransactionMessage,
         _ctx: &mut ActorContext,
     ) -> Result<DryRunTransactionResult> {
-        self.dry_run(msg.tx)
+        self.dry_run(msg.tx, msg.options)
+    }
+}
+
+#[async_trait]
+impl Handler<ReexecuteTransactionMessage> for ExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ReexecuteTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<ReexecuteTransactionResult> {
+        self.reexecute(msg.ledger_tx, msg.pre_root, msg.l1_block_body)
     }
 }
 
@@ -3,22 +3,28 @@
 
 use anyhow::Result;
 use coerce::actor::message::Message;
+use coerce::actor::scheduler::timer::TimerTick;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::StructTag;
+use moveos::moveos::ExecuteOptions;
 use moveos_types::access_path::AccessPath;
+use moveos_types::call_trace::AnnotatedCallTrace;
 use moveos_types::function_return_value::AnnotatedFunctionResult;
+use moveos_types::gas_profile::GasProfile;
 use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState};
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::TransactionExecutionInfo;
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::transaction::VerifiedMoveOSTransaction;
 use moveos_types::transaction::{FunctionCall, RawTransactionOutput, VMErrorInfo};
 use rooch_types::address::MultiChainAddress;
+use rooch_types::block::SignedBlockCommitment;
 use rooch_types::transaction::{
-    L1BlockWithBody, L1Transaction, RoochTransaction, RoochTransactionData,
+    L1BlockWithBody, L1Transaction, LedgerTransaction, RoochTransaction, RoochTransactionData,
 };
 use serde::{Deserialize, Serialize};
 
@@ -58,6 +64,15 @@ impl Message for ValidateL1TxMessage {
     type Result = Result<VerifiedMoveOSTransaction>;
 }
 
+#[derive(Debug)]
+pub struct ValidateBlockCommitmentMessage {
+    pub signed_commitment: SignedBlockCommitment,
+}
+
+impl Message for ValidateBlockCommitmentMessage {
+    type Result = Result<VerifiedMoveOSTransaction>;
+}
+
 #[derive(Debug)]
 pub struct ExecuteTransactionMessage {
     pub tx: VerifiedMoveOSTransaction,
@@ -73,9 +88,42 @@ impl Message for ExecuteTransactionMessage {
     type Result = Result<ExecuteTransactionResult>;
 }
 
+/// Execute a batch of sequenced transactions in parallel
+#[derive(Debug)]
+pub struct ExecuteTransactionsMessage {
+    pub txs: Vec<VerifiedMoveOSTransaction>,
+}
+
+impl Message for ExecuteTransactionsMessage {
+    type Result = Result<Vec<ExecuteTransactionResult>>;
+}
+
+/// Re-execute a sequenced transaction on the states before it, the output is not applied
+#[derive(Debug)]
+pub struct ReexecuteTransactionMessage {
+    pub ledger_tx: LedgerTransaction,
+    /// The root after the previous transaction executed
+    pub pre_root: ObjectMeta,
+    /// The block body of the L1 block transaction, the ledger transaction only includes the block header
+    pub l1_block_body: Option<Vec<u8>>,
+}
+
+impl Message for ReexecuteTransactionMessage {
+    type Result = Result<ReexecuteTransactionResult>;
+}
+
+#[derive(Debug)]
+pub struct ReexecuteTransactionResult {
+    pub moveos_tx: VerifiedMoveOSTransaction,
+    /// The state roots in the change set are calculated but not applied
+    pub output: RawTransactionOutput,
+}
+
 #[derive(Debug)]
 pub struct DryRunTransactionMessage {
     pub tx: VerifiedMoveOSTransaction,
+    /// The diagnostics to collect, such as the call traces and the gas profile
+    pub options: ExecuteOptions,
 }
 
 impl Message for DryRunTransactionMessage {
@@ -86,11 +134,36 @@ impl Message for DryRunTransactionMessage {
 pub struct DryRunTransactionResult {
     pub raw_output: RawTransactionOutput,
     pub vm_error_info: Option<VMErrorInfo>,
+    /// The traces of the top-level calls, only returned if the trace is enabled
+    pub call_traces: Option<Vec<AnnotatedCallTrace>>,
+    /// The gas profile of the transaction, only returned if the gas profiling is enabled
+    pub gas_profile: Option<GasProfile>,
+}
+
+#[derive(Debug)]
+pub struct EstimateGasMessage {
+    pub tx_data: RoochTransactionData,
+}
+
+impl Message for EstimateGasMessage {
+    type Result = Result<GasEstimateResult>;
+}
+
+#[derive(Debug, Clone)]
+pub struct GasEstimateResult {
+    /// The minimal max gas amount to execute the transaction
+    pub gas_used: u64,
+    /// The gas used with a safety margin
+    pub recommended_max_gas_amount: u64,
+    /// The gas factor to convert the gas amount to the amount of GasCoin
+    pub gas_factor: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteViewFunctionMessage {
     pub call: FunctionCall,
+    /// The root to read the states, the current root if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for ExecuteViewFunctionMessage {
@@ -100,12 +173,26 @@ impl Message for ExecuteViewFunctionMessage {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatesMessage {
     pub access_path: AccessPath,
+    /// The root to read the states, the current root if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for StatesMessage {
     type Result = Result<Vec<Option<ObjectState>>>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatesWithProofMessage {
+    pub access_path: AccessPath,
+    /// The state root to prove the states, the current state root if None
+    pub state_root: Option<H256>,
+}
+
+impl Message for StatesWithProofMessage {
+    /// The state root and the states with proof
+    type Result = Result<(H256, Vec<StateWithProof>)>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResolveMessage {
     pub address: MultiChainAddress,
@@ -118,6 +205,8 @@ impl Message for ResolveMessage {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnnotatedStatesMessage {
     pub access_path: AccessPath,
+    /// The root to read the states, the current root if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for AnnotatedStatesMessage {
@@ -129,6 +218,8 @@ pub struct ListStatesMessage {
     pub access_path: AccessPath,
     pub cursor: Option<FieldKey>,
     pub limit: usize,
+    /// The root to read the states, the current root if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for ListStatesMessage {
@@ -140,6 +231,8 @@ pub struct ListAnnotatedStatesMessage {
     pub access_path: AccessPath,
     pub cursor: Option<FieldKey>,
     pub limit: usize,
+    /// The root to read the states, the current root if None
+    pub root: Option<ObjectMeta>,
 }
 
 impl Message for ListAnnotatedStatesMessage {
@@ -204,3 +297,13 @@ pub struct GetRootMessage {}
 impl Message for GetRootMessage {
     type Result = Result<ObjectState>;
 }
+
+/// Prune the stale state nodes in the background
+#[derive(Clone)]
+pub struct PruneStateTick {}
+
+impl Message for PruneStateTick {
+    type Result = ();
+}
+
+impl TimerTick for PruneStateTick {}
@@ -3,4 +3,5 @@
 
 pub mod executor;
 pub mod messages;
+pub mod pruner;
 pub mod reader_executor;
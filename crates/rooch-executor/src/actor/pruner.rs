@@ -0,0 +1,50 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::messages::PruneStateTick;
+use async_trait::async_trait;
+use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use moveos_store::MoveOSStore;
+
+/// The max number of the state versions pruned in one tick, so the executor is not blocked for long
+pub const STATE_PRUNE_BATCH_SIZE: u64 = 1000;
+
+/// StatePrunerActor removes the stale state nodes of the state roots
+/// older than the latest `retain_roots` state roots.
+pub struct StatePrunerActor {
+    moveos_store: MoveOSStore,
+    retain_roots: u64,
+}
+
+impl StatePrunerActor {
+    pub fn new(moveos_store: MoveOSStore, retain_roots: u64) -> Self {
+        Self {
+            moveos_store,
+            retain_roots,
+        }
+    }
+}
+
+impl Actor for StatePrunerActor {}
+
+#[async_trait]
+impl Handler<PruneStateTick> for StatePrunerActor {
+    async fn handle(&mut self, _message: PruneStateTick, _ctx: &mut ActorContext) {
+        let prune_store = self.moveos_store.get_state_store().get_prune_store();
+        match prune_store.prune(self.retain_roots, Some(STATE_PRUNE_BATCH_SIZE)) {
+            Ok(result) if result.pruned_versions > 0 => {
+                log::info!(
+                    "[PruneState] pruned versions: {}, removed nodes: {}",
+                    result.pruned_versions,
+                    result.removed_nodes
+                );
+            }
+            Ok(_) => {
+                log::debug!("[PruneState] no state to prune");
+            }
+            Err(e) => {
+                log::error!("[PruneState] prune state error: {:?}", e);
+            }
+        }
+    }
+}
@@ -3,15 +3,19 @@
 
 use super::messages::{
     AnnotatedStatesMessage, ExecuteViewFunctionMessage, GetAnnotatedEventsByEventHandleMessage,
-    GetEventsByEventHandleMessage, RefreshStateMessage, StatesMessage,
+    GetEventsByEventHandleMessage, RefreshStateMessage, StatesMessage, StatesWithProofMessage,
 };
+use crate::actor::executor::convert_to_verified_tx;
 use crate::actor::messages::{
-    GetEventsByEventIDsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
-    ListStatesMessage,
+    EstimateGasMessage, GasEstimateResult, GetEventsByEventIDsMessage,
+    GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage, ListStatesMessage,
 };
-use anyhow::Result;
+use anyhow::{bail, ensure, Result};
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::u256::U256;
+use move_core_types::vm_status::KeptVMStatus;
 use move_resource_viewer::MoveValueAnnotator;
 use moveos::moveos::MoveOS;
 use moveos::moveos::MoveOSConfig;
@@ -19,16 +23,35 @@ use moveos_store::transaction_store::TransactionStore;
 use moveos_store::MoveOSStore;
 use moveos_types::function_return_value::AnnotatedFunctionResult;
 use moveos_types::function_return_value::AnnotatedFunctionReturnValue;
+use moveos_types::function_return_value::FunctionResult;
+use moveos_types::h256::H256;
+use moveos_types::module_binding::MoveFunctionCaller;
 use moveos_types::moveos_std::event::EventHandle;
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event};
+use moveos_types::moveos_std::gas_schedule::GasSchedule;
 use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::{AnnotatedState, ObjectState};
+use moveos_types::state_proof::{StateProofReader, StateWithProof};
 use moveos_types::state_resolver::RootObjectResolver;
-use moveos_types::state_resolver::{AnnotatedStateKV, AnnotatedStateReader, StateKV, StateReader};
+use moveos_types::state_resolver::{
+    AnnotatedStateKV, AnnotatedStateReader, StateKV, StateReader, StateResolver,
+};
 use moveos_types::transaction::TransactionExecutionInfo;
+use moveos_types::transaction::{FunctionCall, RawTransactionOutput, VMErrorInfo};
 use rooch_genesis::FrameworksGasParameters;
 use rooch_store::RoochStore;
+use rooch_types::framework::gas_coin::GasCoinModule;
+use rooch_types::framework::transaction_fee::TransactionFeeModule;
 use rooch_types::framework::{system_post_execute_functions, system_pre_execute_functions};
+use rooch_types::rooch_network::BuiltinChainID;
+use rooch_types::transaction::RoochTransactionData;
+
+/// The safety margin added to the estimated gas in percent,
+/// the states may be changed between the estimation and the execution.
+const GAS_ESTIMATE_SAFETY_MARGIN_PERCENT: u64 = 20;
+/// The max steps to search the minimal max gas amount, each step executes the transaction once.
+const GAS_ESTIMATE_MAX_SEARCH_STEPS: usize = 8;
 
 pub struct ReaderExecutorActor {
     root: ObjectMeta,
@@ -73,6 +96,117 @@ impl ReaderExecutorActor {
         self.root = root;
         self.moveos.flush_module_cache(is_upgrade)
     }
+
+    /// Estimate the max gas amount to execute the transaction at the current root.
+    /// The transaction is executed with the max gas amount of the gas schedule first to measure the gas used,
+    /// then the minimal max gas amount which makes the transaction executed is searched in limited steps.
+    pub fn estimate_gas(&self, tx_data: RoochTransactionData) -> Result<GasEstimateResult> {
+        let gas_factor = self
+            .as_module_binding::<TransactionFeeModule>()
+            .get_gas_factor()?;
+        let max_gas_amount = self.gas_schedule_max_gas_amount()?;
+
+        // The gas for the max gas amount is deposited before the execution,
+        // so the max gas amount is limited by the gas balance of the sender.
+        // The new account on the local or dev chain gets the gas from the faucet before the deposit.
+        let sender: AccountAddress = tx_data.sender.into();
+        let balance = self.as_module_binding::<GasCoinModule>().balance(sender)?;
+        let affordable_gas_amount =
+            u64::try_from(balance / U256::from(gas_factor.max(1))).unwrap_or(u64::MAX);
+        let is_local_or_dev = BuiltinChainID::try_from(tx_data.chain_id)
+            .map_or(false, |chain_id| chain_id.is_local() || chain_id.is_dev());
+        let gas_limit = if affordable_gas_amount == 0 && is_local_or_dev {
+            max_gas_amount
+        } else {
+            max_gas_amount.min(affordable_gas_amount)
+        };
+        ensure!(
+            gas_limit > 0,
+            "The sender {} does not have enough gas balance to execute the transaction",
+            tx_data.sender
+        );
+
+        let (raw_output, vm_error_info) = self.execute_with_max_gas_amount(&tx_data, gas_limit)?;
+        if raw_output.status != KeptVMStatus::Executed {
+            bail!(
+                "The transaction can not be executed with the max gas amount {}, status: {:?}, error: {}",
+                gas_limit,
+                raw_output.status,
+                vm_error_info
+                    .map(|info| info.error_message)
+                    .unwrap_or_default()
+            );
+        }
+
+        // The gas used usually does not depend on the max gas amount,
+        // search the minimal max gas amount in case it does.
+        // The search is stopped after the max steps, the upper bound is always executable.
+        let gas_used = raw_output.gas_used;
+        let required_gas_amount = if self.is_executed_with_max_gas_amount(&tx_data, gas_used)? {
+            gas_used
+        } else {
+            let (mut low, mut high) = (gas_used + 1, gas_limit);
+            let mut steps = 0;
+            while low < high && steps < GAS_ESTIMATE_MAX_SEARCH_STEPS {
+                let mid = low + (high - low) / 2;
+                if self.is_executed_with_max_gas_amount(&tx_data, mid)? {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+                steps += 1;
+            }
+            high
+        };
+        let recommended_max_gas_amount = required_gas_amount
+            .saturating_add(required_gas_amount * GAS_ESTIMATE_SAFETY_MARGIN_PERCENT / 100)
+            .min(gas_limit);
+        Ok(GasEstimateResult {
+            gas_used: required_gas_amount,
+            recommended_max_gas_amount,
+            gas_factor,
+        })
+    }
+
+    fn gas_schedule_max_gas_amount(&self) -> Result<u64> {
+        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let gas_schedule = resolver
+            .get_object(&GasSchedule::gas_schedule_object_id())?
+            .ok_or_else(|| anyhow::anyhow!("The gas schedule does not exist"))?
+            .into_object::<GasSchedule>()?;
+        Ok(gas_schedule.value.max_gas_amount)
+    }
+
+    fn execute_with_max_gas_amount(
+        &self,
+        tx_data: &RoochTransactionData,
+        max_gas_amount: u64,
+    ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
+        let mut tx_data = tx_data.clone();
+        tx_data.max_gas_amount = max_gas_amount;
+        let tx = convert_to_verified_tx(self.root.clone(), tx_data)?;
+        let (raw_output, vm_error_info) = self.moveos.execute_only(tx)?;
+        // The modules published by the transaction are not applied, reload the modules of the current root
+        self.moveos.flush_module_cache(raw_output.is_upgrade)?;
+        Ok((raw_output, vm_error_info))
+    }
+
+    fn is_executed_with_max_gas_amount(
+        &self,
+        tx_data: &RoochTransactionData,
+        max_gas_amount: u64,
+    ) -> Result<bool> {
+        let (raw_output, _) = self.execute_with_max_gas_amount(tx_data, max_gas_amount)?;
+        Ok(raw_output.status == KeptVMStatus::Executed)
+    }
+}
+
+impl MoveFunctionCaller for ReaderExecutorActor {
+    fn call_function(&self, ctx: &TxContext, call: FunctionCall) -> Result<FunctionResult> {
+        Ok(self
+            .moveos
+            .execute_readonly_function(self.root.clone(), ctx, call))
+    }
 }
 
 impl Actor for ReaderExecutorActor {}
@@ -84,10 +218,20 @@ impl Handler<ExecuteViewFunctionMessage> for ReaderExecutorActor {
         msg: ExecuteViewFunctionMessage,
         _ctx: &mut ActorContext,
     ) -> Result<AnnotatedFunctionResult, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        let function_result = self
-            .moveos()
-            .execute_view_function(self.root.clone(), msg.call);
+        let (root, historical) = match msg.root {
+            Some(root) => (root, true),
+            None => (self.root.clone(), false),
+        };
+        // The module cache holds the modules of the current root,
+        // flush it so the modules are loaded from the historical root.
+        if historical {
+            self.moveos.flush_module_cache(true)?;
+        }
+        let resolver = RootObjectResolver::new(root.clone(), &self.moveos_store);
+        let function_result = self.moveos().execute_view_function(root, msg.call);
+        if historical {
+            self.moveos.flush_module_cache(true)?;
+        }
         Ok(AnnotatedFunctionResult {
             vm_status: function_result.vm_status,
             return_values: match function_result.return_values {
@@ -116,11 +260,27 @@ impl Handler<StatesMessage> for ReaderExecutorActor {
         msg: StatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<Option<ObjectState>>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let root = msg.root.unwrap_or_else(|| self.root.clone());
+        let resolver = RootObjectResolver::new(root, &self.moveos_store);
         resolver.get_states(msg.access_path)
     }
 }
 
+#[async_trait]
+impl Handler<StatesWithProofMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: StatesWithProofMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<(H256, Vec<StateWithProof>), anyhow::Error> {
+        let state_root = msg.state_root.unwrap_or_else(|| self.root.state_root());
+        let states = self
+            .moveos_store
+            .get_states_with_proof(state_root, msg.access_path)?;
+        Ok((state_root, states))
+    }
+}
+
 #[async_trait]
 impl Handler<AnnotatedStatesMessage> for ReaderExecutorActor {
     async fn handle(
@@ -128,7 +288,8 @@ impl Handler<AnnotatedStatesMessage> for ReaderExecutorActor {
         msg: AnnotatedStatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<Option<AnnotatedState>>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let root = msg.root.unwrap_or_else(|| self.root.clone());
+        let resolver = RootObjectResolver::new(root, &self.moveos_store);
         resolver.get_annotated_states(msg.access_path)
     }
 }
@@ -140,7 +301,8 @@ impl Handler<ListStatesMessage> for ReaderExecutorActor {
         msg: ListStatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<StateKV>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let root = msg.root.unwrap_or_else(|| self.root.clone());
+        let resolver = RootObjectResolver::new(root, &self.moveos_store);
         resolver.list_states(msg.access_path, msg.cursor, msg.limit)
     }
 }
@@ -152,7 +314,8 @@ impl Handler<ListAnnotatedStatesMessage> for ReaderExecutorActor {
         msg: ListAnnotatedStatesMessage,
         _ctx: &mut ActorContext,
     ) -> Result<Vec<AnnotatedStateKV>, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let root = msg.root.unwrap_or_else(|| self.root.clone());
+        let resolver = RootObjectResolver::new(root, &self.moveos_store);
         resolver.list_annotated_states(msg.access_path, msg.cursor, msg.limit)
     }
 }
@@ -252,6 +415,17 @@ impl Handler<GetTxExecutionInfosByHashMessage> for ReaderExecutorActor {
     }
 }
 
+#[async_trait]
+impl Handler<EstimateGasMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: EstimateGasMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<GasEstimateResult> {
+        self.estimate_gas(msg.tx_data)
+    }
+}
+
 #[async_trait]
 impl Handler<RefreshStateMessage> for ReaderExecutorActor {
     async fn handle(&mut self, msg: RefreshStateMessage, _ctx: &mut ActorContext) -> Result<()> {
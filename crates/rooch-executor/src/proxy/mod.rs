@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::messages::{
-    ConvertL2TransactionData, DryRunTransactionResult, GetEventsByEventHandleMessage,
+    ConvertL2TransactionData, DryRunTransactionResult, EstimateGasMessage,
+    ExecuteTransactionsMessage, GasEstimateResult, GetEventsByEventHandleMessage,
     GetEventsByEventIDsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
-    ListStatesMessage, RefreshStateMessage, ValidateL1BlockMessage, ValidateL1TxMessage,
+    ListStatesMessage, ReexecuteTransactionMessage, ReexecuteTransactionResult,
+    RefreshStateMessage, StatesWithProofMessage, ValidateBlockCommitmentMessage,
+    ValidateL1BlockMessage, ValidateL1TxMessage,
 };
 use crate::actor::reader_executor::ReaderExecutorActor;
 use crate::actor::{
@@ -18,6 +21,7 @@ use anyhow::Result;
 use coerce::actor::ActorRef;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::StructTag;
+use moveos::moveos::ExecuteOptions;
 use moveos_types::function_return_value::{AnnotatedFunctionResult, FunctionResult};
 use moveos_types::h256::H256;
 use moveos_types::module_binding::MoveFunctionCaller;
@@ -26,6 +30,7 @@ use moveos_types::moveos_std::event::{Event, EventID};
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::FieldKey;
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::FunctionCall;
 use moveos_types::transaction::TransactionExecutionInfo;
@@ -36,9 +41,10 @@ use moveos_types::{
     state::{AnnotatedState, ObjectState},
 };
 use rooch_types::bitcoin::network::BitcoinNetwork;
+use rooch_types::block::SignedBlockCommitment;
 use rooch_types::framework::chain_id::ChainID;
 use rooch_types::transaction::{
-    L1BlockWithBody, L1Transaction, RoochTransaction, RoochTransactionData,
+    L1BlockWithBody, L1Transaction, LedgerTransaction, RoochTransaction, RoochTransactionData,
 };
 use tokio::runtime::Handle;
 
@@ -83,6 +89,15 @@ impl ExecutorProxy {
         self.actor.send(ValidateL1TxMessage { l1_tx }).await?
     }
 
+    pub async fn validate_block_commitment(
+        &self,
+        signed_commitment: SignedBlockCommitment,
+    ) -> Result<VerifiedMoveOSTransaction> {
+        self.actor
+            .send(ValidateBlockCommitmentMessage { signed_commitment })
+            .await?
+    }
+
     //TODO ensure the execute result
     pub async fn execute_transaction(
         &self,
@@ -95,38 +110,99 @@ impl ExecutorProxy {
         Ok((result.output, result.transaction_info))
     }
 
+    pub async fn execute_transactions(
+        &self,
+        txs: Vec<VerifiedMoveOSTransaction>,
+    ) -> Result<Vec<(TransactionOutput, TransactionExecutionInfo)>> {
+        let results = self
+            .actor
+            .send(ExecuteTransactionsMessage { txs })
+            .await??;
+        Ok(results
+            .into_iter()
+            .map(|result| (result.output, result.transaction_info))
+            .collect())
+    }
+
+    /// Re-execute a sequenced transaction on the states of `pre_root` to re-derive its output.
+    /// The executor of the proxy should be dedicated to the re-execution, see `ExecutorActor::reexecute`.
+    pub async fn reexecute_transaction(
+        &self,
+        ledger_tx: LedgerTransaction,
+        pre_root: ObjectMeta,
+        l1_block_body: Option<Vec<u8>>,
+    ) -> Result<ReexecuteTransactionResult> {
+        self.actor
+            .send(ReexecuteTransactionMessage {
+                ledger_tx,
+                pre_root,
+                l1_block_body,
+            })
+            .await?
+    }
+
     pub async fn dry_run_transaction(
         &self,
         tx: VerifiedMoveOSTransaction,
+        options: ExecuteOptions,
     ) -> Result<DryRunTransactionResult> {
         let result = self
             .actor
-            .send(crate::actor::messages::DryRunTransactionMessage { tx })
+            .send(crate::actor::messages::DryRunTransactionMessage { tx, options })
             .await??;
         Ok(result)
     }
 
+    /// Estimate the gas on the reader executor, so the block production is not blocked
+    pub async fn estimate_gas(&self, tx_data: RoochTransactionData) -> Result<GasEstimateResult> {
+        self.reader_actor
+            .send(EstimateGasMessage { tx_data })
+            .await?
+    }
+
+    /// Execute the view function at the root, the current root if None
     pub async fn execute_view_function(
         &self,
         call: FunctionCall,
+        root: Option<ObjectMeta>,
     ) -> Result<AnnotatedFunctionResult> {
         self.reader_actor
-            .send(ExecuteViewFunctionMessage { call })
+            .send(ExecuteViewFunctionMessage { call, root })
+            .await?
+    }
+
+    /// Get the states at the root, the current root if None
+    pub async fn get_states(
+        &self,
+        access_path: AccessPath,
+        root: Option<ObjectMeta>,
+    ) -> Result<Vec<Option<ObjectState>>> {
+        self.reader_actor
+            .send(StatesMessage { access_path, root })
             .await?
     }
 
-    pub async fn get_states(&self, access_path: AccessPath) -> Result<Vec<Option<ObjectState>>> {
+    /// Get the states with the merkle proofs at the state root, the current state root if None
+    pub async fn get_states_with_proof(
+        &self,
+        access_path: AccessPath,
+        state_root: Option<H256>,
+    ) -> Result<(H256, Vec<StateWithProof>)> {
         self.reader_actor
-            .send(StatesMessage { access_path })
+            .send(StatesWithProofMessage {
+                access_path,
+                state_root,
+            })
             .await?
     }
 
     pub async fn get_annotated_states(
         &self,
         access_path: AccessPath,
+        root: Option<ObjectMeta>,
     ) -> Result<Vec<Option<AnnotatedState>>> {
         self.reader_actor
-            .send(AnnotatedStatesMessage { access_path })
+            .send(AnnotatedStatesMessage { access_path, root })
             .await?
     }
 
@@ -135,12 +211,14 @@ impl ExecutorProxy {
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
+        root: Option<ObjectMeta>,
     ) -> Result<Vec<StateKV>> {
         self.reader_actor
             .send(ListStatesMessage {
                 access_path,
                 cursor,
                 limit,
+                root,
             })
             .await?
     }
@@ -150,12 +228,14 @@ impl ExecutorProxy {
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
+        root: Option<ObjectMeta>,
     ) -> Result<Vec<AnnotatedStateKV>> {
         self.reader_actor
             .send(ListAnnotatedStatesMessage {
                 access_path,
                 cursor,
                 limit,
+                root,
             })
             .await?
     }
@@ -228,7 +308,7 @@ impl ExecutorProxy {
     }
 
     pub async fn chain_id(&self) -> Result<ChainID> {
-        self.get_states(AccessPath::object(ChainID::chain_id_object_id()))
+        self.get_states(AccessPath::object(ChainID::chain_id_object_id()), None)
             .await?
             .into_iter()
             .next()
@@ -238,7 +318,7 @@ impl ExecutorProxy {
     }
 
     pub async fn bitcoin_network(&self) -> Result<BitcoinNetwork> {
-        self.get_states(AccessPath::object(BitcoinNetwork::object_id()))
+        self.get_states(AccessPath::object(BitcoinNetwork::object_id()), None)
             .await?
             .into_iter()
             .next()
@@ -250,7 +330,10 @@ impl ExecutorProxy {
     //TODO provide a trait to abstract the async state reader, elemiate the duplicated code bwteen RpcService and Client
     pub async fn get_sequence_number(&self, address: AccountAddress) -> Result<u64> {
         Ok(self
-            .get_states(AccessPath::object(Account::account_object_id(address)))
+            .get_states(
+                AccessPath::object(Account::account_object_id(address)),
+                None,
+            )
             .await?
             .pop()
             .flatten()
@@ -269,7 +352,7 @@ impl MoveFunctionCaller for ExecutorProxy {
         let executor = self.clone();
         let function_result = tokio::task::block_in_place(|| {
             Handle::current()
-                .block_on(async move { executor.execute_view_function(function_call).await })
+                .block_on(async move { executor.execute_view_function(function_call, None).await })
         })?;
         function_result.try_into()
     }
@@ -181,6 +181,29 @@ impl RustBindingTest {
         self.execute_verified_tx(verified_tx)
     }
 
+    /// Execute the l1 txs of a block in parallel
+    pub fn execute_l1_txs_in_parallel(&mut self, l1_txs: Vec<L1Transaction>) -> Result<()> {
+        let verified_txs = l1_txs
+            .into_iter()
+            .map(|l1_tx| self.executor.validate_l1_tx(l1_tx))
+            .collect::<Result<Vec<_>>>()?;
+        let results = self.executor.execute_batch(verified_txs)?;
+        for result in &results {
+            if result.transaction_info.status != KeptVMStatus::Executed {
+                bail!(
+                    "tx should success, error: {:?}",
+                    result.transaction_info.status
+                );
+            }
+        }
+        if let Some(result) = results.last() {
+            self.root = result.transaction_info.root_metadata();
+            self.reader_executor
+                .refresh_state(self.root.clone(), false)?;
+        }
+        Ok(())
+    }
+
     pub fn execute_as_result(&mut self, tx: RoochTransaction) -> Result<ExecuteTransactionResult> {
         let verified_tx = self.executor.validate_l2_tx(tx)?;
         self.execute_verified_tx_as_result(verified_tx)
@@ -0,0 +1,73 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::u256::U256;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
+use moveos_types::state::MoveStructType;
+use moveos_types::transaction::MoveAction;
+use rooch_types::crypto::RoochKeyPair;
+use rooch_types::framework::empty::Empty;
+use rooch_types::framework::gas_coin::GasCoin;
+use rooch_types::framework::transfer::TransferModule;
+use rooch_types::transaction::rooch::RoochTransactionData;
+
+#[tokio::test]
+async fn test_estimate_gas_recommended_max_gas_amount() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = RoochKeyPair::generate_secp256k1();
+    let sender = kp.public().rooch_address().unwrap();
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let mut tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+
+    let estimate = binding_test
+        .reader_executor
+        .estimate_gas(tx_data.clone())
+        .unwrap();
+    assert!(estimate.gas_used > 0);
+    assert!(estimate.recommended_max_gas_amount >= estimate.gas_used);
+    assert!(estimate.recommended_max_gas_amount < tx_data.max_gas_amount);
+
+    // The transaction is executed with the recommended max gas amount
+    tx_data.max_gas_amount = estimate.recommended_max_gas_amount;
+    let result = binding_test.execute_as_result(tx_data.sign(&kp)).unwrap();
+    assert_eq!(result.transaction_info.status, KeptVMStatus::Executed);
+    assert!(result.transaction_info.gas_used <= estimate.recommended_max_gas_amount);
+}
+
+#[tokio::test]
+async fn test_estimate_gas_fallback_on_failure() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = RoochKeyPair::generate_secp256k1();
+    let sender = kp.public().rooch_address().unwrap();
+    let action = TransferModule::create_transfer_coin_action(
+        GasCoin::struct_tag(),
+        AccountAddress::random(),
+        U256::from(u128::MAX),
+    );
+    let mut tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+
+    // The transfer aborts, so the estimation fails
+    assert!(binding_test
+        .reader_executor
+        .estimate_gas(tx_data.clone())
+        .is_err());
+
+    // The client falls back to the default max gas amount, the execution reports the real error
+    tx_data.max_gas_amount = GasScheduleConfig::CLI_DEFAULT_MAX_GAS_AMOUNT;
+    let result = binding_test.execute_as_result(tx_data.sign(&kp)).unwrap();
+    assert!(
+        matches!(
+            result.transaction_info.status,
+            KeptVMStatus::MoveAbort(_, _)
+        ),
+        "unexpected status: {:?}",
+        result.transaction_info.status
+    );
+}
@@ -7,7 +7,11 @@ mod brc20_test;
 mod chain_id_test;
 mod empty_tests;
 mod ethereum_test;
+mod gas_estimate_test;
 mod multisign_account_tests;
 mod ord_test;
+mod parallel_execution_test;
+mod reexecute_test;
 mod session_validator_tests;
+mod state_commitment_chain_test;
 mod view_function_gas;
@@ -0,0 +1,62 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::u256::U256;
+use moveos_types::state::MoveStructType;
+use moveos_types::transaction::MoveAction;
+use rooch_executor::actor::executor::ExecutorActor;
+use rooch_types::crypto::RoochKeyPair;
+use rooch_types::framework::empty::Empty;
+use rooch_types::framework::gas_coin::GasCoin;
+use rooch_types::framework::transfer::TransferModule;
+use rooch_types::transaction::rooch::RoochTransactionData;
+
+#[tokio::test]
+async fn test_parallel_execution_same_as_serial() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let root = binding_test.root().clone();
+
+    // The transfers to the same recipient conflict with each other, the empty calls do not.
+    let recipient = AccountAddress::random();
+    let txs = (0..16)
+        .map(|i| {
+            let kp = RoochKeyPair::generate_secp256k1();
+            let sender = kp.public().rooch_address().unwrap();
+            let action = if i % 2 == 0 {
+                TransferModule::create_transfer_coin_action(
+                    GasCoin::struct_tag(),
+                    recipient,
+                    U256::from(100u128),
+                )
+            } else {
+                MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![])
+            };
+            let tx = RoochTransactionData::new_for_test(sender, 0, action).sign(&kp);
+            binding_test.executor.validate_l2_tx(tx).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let parallel_results = binding_test.executor.execute_batch(txs.clone()).unwrap();
+    assert_eq!(parallel_results.len(), txs.len());
+
+    // Execute the same transactions one by one from the same root
+    let registry_service = metrics::RegistryService::default();
+    let mut serial_executor = ExecutorActor::new(
+        root,
+        binding_test.rooch_db().moveos_store.clone(),
+        binding_test.rooch_db().rooch_store.clone(),
+        &registry_service.default_registry(),
+    )
+    .unwrap();
+    for (tx, parallel_result) in txs.into_iter().zip(parallel_results) {
+        let serial_result = serial_executor.execute(tx).unwrap();
+        assert_eq!(
+            serial_result.transaction_info,
+            parallel_result.transaction_info
+        );
+        assert_eq!(serial_result.output.events, parallel_result.output.events);
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use moveos_types::transaction::MoveAction;
+use rooch_executor::actor::executor::ExecutorActor;
+use rooch_types::crypto::RoochKeyPair;
+use rooch_types::framework::empty::Empty;
+use rooch_types::transaction::rooch::RoochTransactionData;
+use rooch_types::transaction::{
+    LedgerTransaction, TransactionSequenceInfo, TransactionSequenceInfoV1,
+};
+
+#[tokio::test]
+async fn test_reexecute_same_as_execute() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let genesis_root = binding_test.root().clone();
+
+    let kp = RoochKeyPair::generate_secp256k1();
+    let sender = kp.public().rooch_address().unwrap();
+    let mut ledger_txs = vec![];
+    let mut tx_infos = vec![];
+    for sequence_number in 0..3 {
+        let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+        let tx = RoochTransactionData::new_for_test(sender, sequence_number, action).sign(&kp);
+        let ledger_tx = LedgerTransaction::new_l2_tx(tx.clone(), TransactionSequenceInfo::random());
+
+        let mut moveos_tx = binding_test.executor.validate_l2_tx(tx).unwrap();
+        moveos_tx.ctx.add(ledger_tx.sequence_info.clone()).unwrap();
+        moveos_tx
+            .ctx
+            .add(TransactionSequenceInfoV1::from(
+                ledger_tx.sequence_info.clone(),
+            ))
+            .unwrap();
+        let result = binding_test
+            .execute_verified_tx_as_result(moveos_tx)
+            .unwrap();
+        ledger_txs.push(ledger_tx);
+        tx_infos.push(result.transaction_info);
+    }
+
+    // The executor is at the latest root, it moves back to the genesis root for the first tx
+    let registry_service = metrics::RegistryService::default();
+    let mut reexecutor = ExecutorActor::new(
+        binding_test.root().clone(),
+        binding_test.rooch_db().moveos_store.clone(),
+        binding_test.rooch_db().rooch_store.clone(),
+        &registry_service.default_registry(),
+    )
+    .unwrap();
+    let mut pre_root = genesis_root;
+    for (ledger_tx, tx_info) in ledger_txs.into_iter().zip(tx_infos) {
+        let result = reexecutor.reexecute(ledger_tx, pre_root, None).unwrap();
+        assert_eq!(result.output.status, tx_info.status);
+        assert_eq!(result.output.gas_used, tx_info.gas_used);
+        assert_eq!(result.output.changeset.state_root, tx_info.state_root);
+        pre_root = result.output.changeset.root_metadata();
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::h256::H256;
+use moveos_types::module_binding::MoveFunctionCaller;
+use rooch_types::block::Block;
+use rooch_types::crypto::RoochKeyPair;
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
+
+#[tokio::test]
+async fn test_commit_block_signed_in_rust() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let proposer_key = binding_test.sequencer_kp().copy();
+
+    let block0 = Block::new(0, 1, H256::random(), H256::random(), vec![H256::random()]);
+    let block1 = Block::new(
+        1,
+        2,
+        block0.tx_accumulator_root,
+        H256::random(),
+        vec![H256::random(), H256::random()],
+    );
+    for block in [block0, block1] {
+        let signed_commitment = block.commitment().sign(&proposer_key).unwrap();
+        let tx = binding_test
+            .executor
+            .validate_block_commitment(signed_commitment)
+            .unwrap();
+        binding_test.execute_verified_tx(tx).unwrap();
+    }
+    let scc = binding_test.as_module_binding::<StateCommitmentChainModule>();
+    assert_eq!(scc.last_block_number().unwrap(), Some(1));
+
+    // The commitment signed by other key is rejected by the SCC
+    let block2 = Block::new(2, 1, H256::random(), H256::random(), vec![H256::random()]);
+    let signed_commitment = block2
+        .commitment()
+        .sign(&RoochKeyPair::generate_secp256k1())
+        .unwrap();
+    let tx = binding_test
+        .executor
+        .validate_block_commitment(signed_commitment)
+        .unwrap();
+    let result = binding_test.execute_verified_tx_as_result(tx).unwrap();
+    assert!(
+        matches!(
+            result.transaction_info.status,
+            KeptVMStatus::MoveAbort(_, 4)
+        ),
+        "unexpected status: {:?}",
+        result.transaction_info.status
+    );
+}
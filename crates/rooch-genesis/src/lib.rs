@@ -318,6 +318,14 @@ impl RoochGenesis {
         }
     }
 
+    /// Load the released genesis from binary if the chain_id is builtin, otherwise build the genesis
+    pub fn load_or_build(network: RoochNetwork) -> Result<Self> {
+        match network.chain_id.to_builtin() {
+            Some(builtin_id) => Self::load(builtin_id),
+            None => Self::build(network),
+        }
+    }
+
     /// Load the genesis from the rooch db, if not exist, build and init the genesis
     pub fn load_or_init(network: RoochNetwork, rooch_db: &RoochDB) -> Result<Self> {
         let genesis_info = rooch_db.moveos_store.get_config_store().get_genesis()?;
@@ -338,12 +346,7 @@ impl RoochGenesis {
                 Self::decode(&genesis_info_from_store.genesis_bin)
             }
             None => {
-                //if the chain_id is builtin, we should load the released genesis from binary
-                let genesis = if let Some(builtin_id) = network.chain_id.to_builtin() {
-                    Self::load(builtin_id)?
-                } else {
-                    Self::build(network)?
-                };
+                let genesis = Self::load_or_build(network)?;
                 genesis.init_genesis(rooch_db)?;
                 Ok(genesis)
             }
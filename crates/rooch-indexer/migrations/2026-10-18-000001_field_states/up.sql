@@ -0,0 +1,16 @@
+CREATE TABLE field_states
+(
+    id                 VARCHAR        NOT NULL       PRIMARY KEY,
+    parent_id          VARCHAR        NOT NULL,
+    field_key          VARCHAR        NOT NULL,
+    name_type          VARCHAR        NOT NULL,
+    value_type         VARCHAR        NOT NULL,
+    tx_order           BIGINT         NOT NULL,
+    state_index        BIGINT         NOT NULL,
+    created_at         BIGINT         NOT NULL,
+    updated_at         BIGINT         NOT NULL,
+    UNIQUE (tx_order, state_index)
+);
+
+CREATE INDEX idx_field_states_parent_id_and_value_type ON field_states (parent_id, value_type);
+CREATE INDEX idx_field_states_value_type ON field_states (value_type);
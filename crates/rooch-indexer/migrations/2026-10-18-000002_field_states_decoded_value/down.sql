@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_field_states_parent_id_and_decoded_value;
+DROP INDEX IF EXISTS idx_field_states_parent_id_and_field_key;
+ALTER TABLE field_states DROP COLUMN decoded_value;
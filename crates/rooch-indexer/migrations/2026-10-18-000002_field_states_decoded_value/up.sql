@@ -0,0 +1,4 @@
+ALTER TABLE field_states ADD COLUMN decoded_value VARCHAR;
+
+CREATE INDEX idx_field_states_parent_id_and_field_key ON field_states (parent_id, field_key);
+CREATE INDEX idx_field_states_parent_id_and_decoded_value ON field_states (parent_id, decoded_value);
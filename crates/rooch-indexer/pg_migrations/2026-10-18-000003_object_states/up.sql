@@ -0,0 +1,19 @@
+CREATE TABLE object_states
+(
+    id                 VARCHAR        NOT NULL       PRIMARY KEY,
+    owner              VARCHAR        NOT NULL,
+    flag               SMALLINT       NOT NULL,
+    state_root         VARCHAR        NOT NULL,
+    size               BIGINT         NOT NULL,
+    -- The object type prefix query compares the strings byte by byte as SQLite does
+    object_type        VARCHAR        COLLATE "C"    NOT NULL,
+    tx_order           BIGINT         NOT NULL,
+    state_index        BIGINT         NOT NULL,
+    created_at         BIGINT         NOT NULL,
+    updated_at         BIGINT         NOT NULL,
+    UNIQUE (tx_order, state_index)
+);
+
+CREATE INDEX idx_object_states_object_type ON object_states (object_type);
+CREATE INDEX idx_object_states_owner_and_object_type ON object_states (owner, object_type);
+CREATE INDEX idx_object_states_created_at ON object_states (created_at);
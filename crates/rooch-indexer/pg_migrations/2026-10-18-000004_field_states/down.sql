@@ -0,0 +1 @@
+DROP TABLE IF EXISTS field_states;
\ No newline at end of file
@@ -0,0 +1,5 @@
+ALTER TABLE field_states ADD COLUMN decoded_value VARCHAR;
+
+CREATE INDEX idx_field_states_parent_id_and_field_key ON field_states (parent_id, field_key);
+-- The decoded value may exceed the max size of a btree index row, so use a hash index
+CREATE INDEX idx_field_states_decoded_value ON field_states USING HASH (decoded_value);
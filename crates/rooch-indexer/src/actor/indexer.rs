@@ -2,63 +2,108 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::messages::{
-    IndexerDeleteObjectStatesMessage, IndexerEventsMessage,
+    GetLastIndexedTxOrderMessage, IndexerDeleteObjectStatesMessage, IndexerEventsMessage,
     IndexerPersistOrUpdateObjectStatesMessage, IndexerStatesMessage, IndexerTransactionMessage,
-    UpdateIndexerMessage,
+    SkipIndexerTxMessage, UpdateIndexerMessage,
 };
 use crate::store::traits::IndexerStoreTrait;
+use crate::utils::decode_field_value;
 use crate::IndexerStore;
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use move_resource_viewer::MoveValueAnnotator;
+use moveos_store::MoveOSStore;
+use moveos_types::moveos_std::event::Event;
 use moveos_types::moveos_std::object::ObjectMeta;
-use moveos_types::transaction::MoveAction;
+use moveos_types::state::StateChangeSet;
+use moveos_types::state_resolver::{RootObjectResolver, StateResolver};
+use moveos_types::transaction::{MoveAction, TransactionExecutionInfo, VerifiedMoveOSTransaction};
 use rooch_types::indexer::event::IndexerEvent;
+use rooch_types::indexer::field::{
+    collect_field_changes, FieldIndexConfig, IndexerFieldStateChanges,
+};
 use rooch_types::indexer::state::{handle_object_change, IndexerObjectStateChanges};
 use rooch_types::indexer::transaction::IndexerTransaction;
+use rooch_types::transaction::LedgerTransaction;
+use tracing::{debug, warn};
 
 pub struct IndexerActor {
     root: ObjectMeta,
     indexer_store: IndexerStore,
+    moveos_store: MoveOSStore,
+    field_index_config: FieldIndexConfig,
+    /// The last indexed tx order, the transaction is written after its events and states,
+    /// so it is the same as the last tx order in the indexer transactions.
+    last_tx_order: Option<u64>,
+    /// The rows of a tx are not written in one db transaction, a tx failed to be indexed midway
+    /// or the node stopped midway may leave the events after the last indexed tx.
+    /// They are deleted before the txs are indexed again, otherwise the catch up derives the event seqs from them.
+    has_unindexed_rows: bool,
 }
 
 impl IndexerActor {
-    pub fn new(root: ObjectMeta, indexer_store: IndexerStore) -> Result<Self> {
+    pub fn new(
+        root: ObjectMeta,
+        indexer_store: IndexerStore,
+        moveos_store: MoveOSStore,
+        field_index_config: FieldIndexConfig,
+        last_tx_order: Option<u64>,
+    ) -> Result<Self> {
         Ok(Self {
             root,
             indexer_store,
+            moveos_store,
+            field_index_config,
+            last_tx_order,
+            has_unindexed_rows: true,
         })
     }
-}
 
-impl Actor for IndexerActor {}
+    /// Delete the events written after the last indexed tx
+    fn clean_unindexed_rows(&mut self) -> Result<()> {
+        if !self.has_unindexed_rows {
+            return Ok(());
+        }
+        if let Some(last_tx_order) = self.last_tx_order {
+            self.indexer_store
+                .delete_events_after_tx_order(last_tx_order)?;
+        }
+        self.has_unindexed_rows = false;
+        Ok(())
+    }
 
-#[async_trait]
-impl Handler<UpdateIndexerMessage> for IndexerActor {
-    async fn handle(&mut self, msg: UpdateIndexerMessage, _ctx: &mut ActorContext) -> Result<()> {
-        let UpdateIndexerMessage {
-            ledger_transaction,
-            execution_info,
-            moveos_tx,
-            events,
-            state_change_set,
-        } = msg;
+    /// Check the tx is the next one of the last indexed tx, return false if it is already indexed.
+    fn check_next_tx_order(&self, tx_order: u64) -> Result<bool> {
+        if let Some(last_tx_order) = self.last_tx_order {
+            if tx_order <= last_tx_order {
+                debug!("The tx {} is already indexed, skip it", tx_order);
+                return Ok(false);
+            }
+            // Index the tx after the gap is caught up, otherwise the missing tx would never be indexed
+            ensure!(
+                tx_order == last_tx_order + 1,
+                "The indexer misses the txs between {} and {}, they will be caught up later",
+                last_tx_order,
+                tx_order
+            );
+        }
+        Ok(true)
+    }
 
-        self.root = state_change_set.root_metadata();
+    /// Write the events, the field states, the object states and the transaction of the tx
+    fn index_tx(
+        &mut self,
+        ledger_transaction: LedgerTransaction,
+        execution_info: TransactionExecutionInfo,
+        moveos_tx: VerifiedMoveOSTransaction,
+        events: Vec<Event>,
+        state_change_set: StateChangeSet,
+    ) -> Result<()> {
         let tx_order = ledger_transaction.sequence_info.tx_order;
+        self.root = state_change_set.root_metadata();
 
-        // 1. update indexer transaction
-        let move_action = MoveAction::from(moveos_tx.action);
-        let indexer_transaction = IndexerTransaction::new(
-            ledger_transaction.clone(),
-            execution_info.clone(),
-            move_action,
-            moveos_tx.ctx.clone(),
-        )?;
-        let transactions = vec![indexer_transaction];
-        self.indexer_store.persist_transactions(transactions)?;
-
-        // 2. update indexer event
+        // 1. update indexer event
         let events: Vec<_> = events
             .into_iter()
             .map(|event| {
@@ -71,6 +116,13 @@ impl Handler<UpdateIndexerMessage> for IndexerActor {
             .collect();
         self.indexer_store.persist_events(events)?;
 
+        // 2. update indexer field state of the indexed objects
+        let mut indexer_field_state_changes =
+            collect_field_changes(&self.field_index_config, tx_order, &state_change_set);
+        self.decode_field_values(&mut indexer_field_state_changes);
+        self.indexer_store
+            .update_field_states(indexer_field_state_changes)?;
+
         // 3. update indexer object state
         // indexer object state index generator
         let mut state_index_generator = 0u64;
@@ -87,6 +139,121 @@ impl Handler<UpdateIndexerMessage> for IndexerActor {
         self.indexer_store
             .update_object_states(indexer_object_state_changes)?;
 
+        // 4. update indexer transaction, it is written at last to mark the tx is indexed
+        let move_action = MoveAction::from(moveos_tx.action);
+        let indexer_transaction = IndexerTransaction::new(
+            ledger_transaction,
+            execution_info,
+            move_action,
+            moveos_tx.ctx,
+        )?;
+        let transactions = vec![indexer_transaction];
+        self.indexer_store.persist_transactions(transactions)
+    }
+
+    /// Decode the values of the new and updated field states from the current root,
+    /// the field is still indexed without the decoded value if the value can not be decoded.
+    fn decode_field_values(&self, field_state_changes: &mut IndexerFieldStateChanges) {
+        if field_state_changes.new_field_states.is_empty()
+            && field_state_changes.update_field_states.is_empty()
+        {
+            return;
+        }
+        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let annotator = MoveValueAnnotator::new(&resolver);
+        for field_state in field_state_changes
+            .new_field_states
+            .iter_mut()
+            .chain(field_state_changes.update_field_states.iter_mut())
+        {
+            let field_id = field_state.field_id();
+            let decoded_value = resolver
+                .get_object(&field_id)
+                .and_then(|state| match state {
+                    Some(state) => decode_field_value(&annotator, state),
+                    None => Ok(None),
+                });
+            match decoded_value {
+                Ok(decoded_value) => field_state.decoded_value = decoded_value,
+                Err(e) => warn!("Failed to decode the value of field {}: {:?}", field_id, e),
+            }
+        }
+    }
+}
+
+impl Actor for IndexerActor {}
+
+#[async_trait]
+impl Handler<UpdateIndexerMessage> for IndexerActor {
+    async fn handle(&mut self, msg: UpdateIndexerMessage, _ctx: &mut ActorContext) -> Result<()> {
+        let UpdateIndexerMessage {
+            ledger_transaction,
+            execution_info,
+            moveos_tx,
+            events,
+            state_change_set,
+        } = msg;
+
+        let tx_order = ledger_transaction.sequence_info.tx_order;
+        if !self.check_next_tx_order(tx_order)? {
+            return Ok(());
+        }
+        self.clean_unindexed_rows()?;
+
+        self.has_unindexed_rows = true;
+        let result = self.index_tx(
+            ledger_transaction,
+            execution_info,
+            moveos_tx,
+            events,
+            state_change_set,
+        );
+        match result {
+            Ok(()) => {
+                self.has_unindexed_rows = false;
+                self.last_tx_order = Some(tx_order);
+                Ok(())
+            }
+            Err(e) => {
+                if let Err(clean_err) = self.clean_unindexed_rows() {
+                    warn!(
+                        "Failed to clean the rows of the tx {} failed to be indexed: {:?}",
+                        tx_order, clean_err
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<GetLastIndexedTxOrderMessage> for IndexerActor {
+    async fn handle(
+        &mut self,
+        _msg: GetLastIndexedTxOrderMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u64>> {
+        // The catch up gets the last indexed tx order before it indexes the missed txs
+        self.clean_unindexed_rows()?;
+        Ok(self.last_tx_order)
+    }
+}
+
+#[async_trait]
+impl Handler<SkipIndexerTxMessage> for IndexerActor {
+    async fn handle(&mut self, msg: SkipIndexerTxMessage, _ctx: &mut ActorContext) -> Result<()> {
+        let SkipIndexerTxMessage { tx_order, root } = msg;
+        if !self.check_next_tx_order(tx_order)? {
+            return Ok(());
+        }
+        self.clean_unindexed_rows()?;
+        warn!(
+            "The tx {} is skipped by the indexer, its events and states are not indexed",
+            tx_order
+        );
+        self.root = root;
+        self.last_tx_order = Some(tx_order);
         Ok(())
     }
 }
@@ -103,6 +270,12 @@ impl Handler<IndexerStatesMessage> for IndexerActor {
 
         self.root = root;
 
+        let mut indexer_field_state_changes =
+            collect_field_changes(&self.field_index_config, tx_order, &state_change_set);
+        self.decode_field_values(&mut indexer_field_state_changes);
+        self.indexer_store
+            .update_field_states(indexer_field_state_changes)?;
+
         // indexer state index generator
         let mut state_index_generator = 0u64;
         let mut indexer_object_state_changes = IndexerObjectStateChanges::default();
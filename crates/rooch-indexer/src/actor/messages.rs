@@ -9,6 +9,7 @@ use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::StateChangeSet;
 use moveos_types::transaction::{MoveAction, TransactionExecutionInfo, VerifiedMoveOSTransaction};
 use rooch_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
+use rooch_types::indexer::field::{FieldStateFilter, IndexerFieldState};
 use rooch_types::indexer::state::{IndexerObjectState, IndexerStateID, ObjectStateFilter};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
 use rooch_types::transaction::LedgerTransaction;
@@ -28,6 +29,28 @@ impl Message for UpdateIndexerMessage {
     type Result = Result<()>;
 }
 
+/// Skip a tx which can not be indexed, such as the L1 block tx without the block body,
+/// so the indexer continues with the next txs.
+#[derive(Debug, Clone)]
+pub struct SkipIndexerTxMessage {
+    pub tx_order: u64,
+    /// The root after the skipped tx executed
+    pub root: ObjectMeta,
+}
+
+impl Message for SkipIndexerTxMessage {
+    type Result = Result<()>;
+}
+
+/// Get the last tx order indexed by the indexer writer,
+/// it is handled after the queued update messages.
+#[derive(Debug)]
+pub struct GetLastIndexedTxOrderMessage {}
+
+impl Message for GetLastIndexedTxOrderMessage {
+    type Result = Result<Option<u64>>;
+}
+
 /// Indexer Transaction write Message
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IndexerTransactionMessage {
@@ -121,6 +144,19 @@ impl Message for QueryIndexerObjectIdsMessage {
     type Result = Result<Vec<(ObjectID, IndexerStateID)>>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryIndexerFieldStatesMessage {
+    pub filter: FieldStateFilter,
+    // exclusive cursor if `Some`, otherwise start from the beginning
+    pub cursor: Option<IndexerStateID>,
+    pub limit: usize,
+    pub descending_order: bool,
+}
+
+impl Message for QueryIndexerFieldStatesMessage {
+    type Result = Result<Vec<IndexerFieldState>>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IndexerPersistOrUpdateObjectStatesMessage {
     pub states: Vec<IndexerObjectState>,
@@ -146,3 +182,13 @@ pub struct QueryLastStateIndexByTxOrderMessage {
 impl Message for QueryLastStateIndexByTxOrderMessage {
     type Result = Result<u64>;
 }
+
+/// Query the last indexed event seq of the event handles
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryLastEventSeqsMessage {
+    pub event_handle_ids: Vec<ObjectID>,
+}
+
+impl Message for QueryLastEventSeqsMessage {
+    type Result = Result<Vec<(ObjectID, u64)>>;
+}
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::messages::{
-    QueryIndexerEventsMessage, QueryIndexerObjectStatesMessage, QueryIndexerTransactionsMessage,
+    QueryIndexerEventsMessage, QueryIndexerFieldStatesMessage, QueryIndexerObjectStatesMessage,
+    QueryIndexerTransactionsMessage, QueryLastEventSeqsMessage,
     QueryLastStateIndexByTxOrderMessage,
 };
 use crate::indexer_reader::IndexerReader;
@@ -11,6 +12,7 @@ use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use moveos_types::moveos_std::object::ObjectID;
 use rooch_types::indexer::event::IndexerEvent;
+use rooch_types::indexer::field::IndexerFieldState;
 use rooch_types::indexer::state::{IndexerObjectState, IndexerStateID};
 use rooch_types::indexer::transaction::IndexerTransaction;
 
@@ -104,6 +106,25 @@ impl Handler<QueryIndexerObjectIdsMessage> for IndexerReaderActor {
     }
 }
 
+#[async_trait]
+impl Handler<QueryIndexerFieldStatesMessage> for IndexerReaderActor {
+    async fn handle(
+        &mut self,
+        msg: QueryIndexerFieldStatesMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<IndexerFieldState>> {
+        let QueryIndexerFieldStatesMessage {
+            filter,
+            cursor,
+            limit,
+            descending_order,
+        } = msg;
+        self.indexer_reader
+            .query_field_states_with_filter(filter, cursor, limit, descending_order)
+            .map_err(|e| anyhow!(format!("Failed to query indexer field states: {:?}", e)))
+    }
+}
+
 #[async_trait]
 impl Handler<QueryLastStateIndexByTxOrderMessage> for IndexerReaderActor {
     async fn handle(
@@ -123,3 +144,18 @@ impl Handler<QueryLastStateIndexByTxOrderMessage> for IndexerReaderActor {
             })
     }
 }
+
+#[async_trait]
+impl Handler<QueryLastEventSeqsMessage> for IndexerReaderActor {
+    async fn handle(
+        &mut self,
+        msg: QueryLastEventSeqsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<(ObjectID, u64)>> {
+        let QueryLastEventSeqsMessage { event_handle_ids } = msg;
+
+        self.indexer_reader
+            .query_last_event_seqs(event_handle_ids)
+            .map_err(|e| anyhow!(format!("Failed to query indexer last event seqs: {:?}", e)))
+    }
+}
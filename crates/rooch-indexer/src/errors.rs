@@ -25,8 +25,8 @@ pub enum IndexerError {
     )]
     SqlitePoolConnectionError(String),
 
-    #[error("Indexer failed to read SQLiteDB with error: `{0}`")]
-    SQLiteReadError(String),
+    #[error("Indexer failed to read the indexer db with error: `{0}`")]
+    ReadError(String),
 
     #[error("Indexer async read SQLiteDB error: `{0}`")]
     SQLiteAsyncReadError(String),
@@ -34,12 +34,23 @@ pub enum IndexerError {
     #[error("Indexer failed to reset SQLiteDB with error: `{0}`")]
     SQLiteResetError(String),
 
-    #[error("Indexer failed to write SQLiteDB with error: `{0}`")]
-    SQLiteWriteError(String),
+    #[error("Indexer failed to write the indexer db with error: `{0}`")]
+    WriteError(String),
 
     #[error(transparent)]
     SQLiteError(#[from] diesel::result::Error),
 
+    #[error("Indexer failed to build PostgreSQL connection pool with error: `{0}`")]
+    PostgresConnectionPoolInitError(String),
+
+    #[error(
+        "Indexer failed to get a pool connection from PostgreSQL connection pool with error: `{0}`"
+    )]
+    PostgresPoolConnectionError(String),
+
+    #[error("Indexer async read PostgreSQL error: `{0}`")]
+    PostgresAsyncReadError(String),
+
     #[error("Indexer failed to serialize/deserialize with error: `{0}`")]
     SerdeError(String),
 
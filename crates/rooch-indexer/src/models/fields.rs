@@ -0,0 +1,79 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::field_states;
+use diesel::prelude::*;
+use move_core_types::language_storage::TypeTag;
+use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::state::FieldKey;
+use rooch_types::indexer::field::IndexerFieldState;
+use std::str::FromStr;
+
+#[derive(Queryable, QueryableByName, Insertable, Debug, Clone)]
+#[diesel(table_name = field_states)]
+pub struct StoredFieldState {
+    /// The object id of the dynamic field
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    /// The parent object id of the dynamic field
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub parent_id: String,
+    /// The field key in the parent object
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub field_key: String,
+    /// The type of the field name
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name_type: String,
+    /// The type of the field value
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub value_type: String,
+    /// The tx order of this transaction
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub tx_order: i64,
+    /// The state index in the tx
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub state_index: i64,
+    /// The field created timestamp on chain
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub created_at: i64,
+    /// The field updated timestamp on chain
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub updated_at: i64,
+    /// The JSON of the decoded field value
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub decoded_value: Option<String>,
+}
+
+impl From<IndexerFieldState> for StoredFieldState {
+    fn from(state: IndexerFieldState) -> Self {
+        Self {
+            id: state.field_id().to_string(),
+            parent_id: state.parent_id.to_string(),
+            field_key: state.field_key.to_string(),
+            name_type: state.name_type.to_string(),
+            value_type: state.value_type.to_string(),
+            tx_order: state.tx_order as i64,
+            state_index: state.state_index as i64,
+            created_at: state.created_at as i64,
+            updated_at: state.updated_at as i64,
+            decoded_value: state.decoded_value,
+        }
+    }
+}
+
+impl StoredFieldState {
+    pub fn try_parse_indexer_field_state(&self) -> Result<IndexerFieldState, anyhow::Error> {
+        let state = IndexerFieldState {
+            parent_id: ObjectID::from_str(self.parent_id.as_str())?,
+            field_key: FieldKey::from_str(self.field_key.as_str())?,
+            name_type: TypeTag::from_str(self.name_type.as_str())?,
+            value_type: TypeTag::from_str(self.value_type.as_str())?,
+            tx_order: self.tx_order as u64,
+            state_index: self.state_index as u64,
+            created_at: self.created_at as u64,
+            updated_at: self.updated_at as u64,
+            decoded_value: self.decoded_value.clone(),
+        };
+        Ok(state)
+    }
+}
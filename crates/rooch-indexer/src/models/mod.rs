@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod events;
+pub mod fields;
 pub mod states;
 pub mod transactions;
@@ -3,10 +3,11 @@
 
 use crate::actor::indexer::IndexerActor;
 use crate::actor::messages::{
-    IndexerDeleteObjectStatesMessage, IndexerEventsMessage,
+    GetLastIndexedTxOrderMessage, IndexerDeleteObjectStatesMessage, IndexerEventsMessage,
     IndexerPersistOrUpdateObjectStatesMessage, IndexerStatesMessage, IndexerTransactionMessage,
-    QueryIndexerEventsMessage, QueryIndexerObjectIdsMessage, QueryIndexerObjectStatesMessage,
-    QueryIndexerTransactionsMessage, QueryLastStateIndexByTxOrderMessage, UpdateIndexerMessage,
+    QueryIndexerEventsMessage, QueryIndexerFieldStatesMessage, QueryIndexerObjectIdsMessage,
+    QueryIndexerObjectStatesMessage, QueryIndexerTransactionsMessage, QueryLastEventSeqsMessage,
+    QueryLastStateIndexByTxOrderMessage, SkipIndexerTxMessage, UpdateIndexerMessage,
 };
 use crate::actor::reader_indexer::IndexerReaderActor;
 use anyhow::{Ok, Result};
@@ -17,6 +18,7 @@ use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::StateChangeSet;
 use moveos_types::transaction::{MoveAction, TransactionExecutionInfo, VerifiedMoveOSTransaction};
 use rooch_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
+use rooch_types::indexer::field::{FieldStateFilter, IndexerFieldState};
 use rooch_types::indexer::state::{IndexerObjectState, IndexerStateID, ObjectStateFilter};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
 use rooch_types::transaction::LedgerTransaction;
@@ -55,6 +57,38 @@ impl IndexerProxy {
         Ok(())
     }
 
+    /// Update the indexer and wait for the result, it is used to catch up the missing txs
+    pub async fn update_indexer_and_wait(
+        &self,
+        ledger_transaction: LedgerTransaction,
+        execution_info: TransactionExecutionInfo,
+        moveos_tx: VerifiedMoveOSTransaction,
+        events: Vec<Event>,
+        state_change_set: StateChangeSet,
+    ) -> Result<()> {
+        self.actor
+            .send(UpdateIndexerMessage {
+                ledger_transaction,
+                execution_info,
+                moveos_tx,
+                events,
+                state_change_set,
+            })
+            .await?
+    }
+
+    /// Skip the tx which can not be indexed and wait for the result
+    pub async fn skip_indexer_tx(&self, tx_order: u64, root: ObjectMeta) -> Result<()> {
+        self.actor
+            .send(SkipIndexerTxMessage { tx_order, root })
+            .await?
+    }
+
+    /// Get the last indexed tx order after the queued updates are handled
+    pub async fn get_last_indexed_tx_order(&self) -> Result<Option<u64>> {
+        self.actor.send(GetLastIndexedTxOrderMessage {}).await?
+    }
+
     pub async fn indexer_states(
         &self,
         root: ObjectMeta,
@@ -176,6 +210,24 @@ impl IndexerProxy {
             .await?
     }
 
+    pub async fn query_field_states(
+        &self,
+        filter: FieldStateFilter,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateID>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<IndexerFieldState>> {
+        self.reader_actor
+            .send(QueryIndexerFieldStatesMessage {
+                filter,
+                cursor,
+                limit,
+                descending_order,
+            })
+            .await?
+    }
+
     pub async fn persist_or_update_object_states(
         &self,
         states: Vec<IndexerObjectState>,
@@ -196,4 +248,13 @@ impl IndexerProxy {
             .send(QueryLastStateIndexByTxOrderMessage { tx_order })
             .await?
     }
+
+    pub async fn query_last_event_seqs(
+        &self,
+        event_handle_ids: Vec<ObjectID>,
+    ) -> Result<Vec<(ObjectID, u64)>> {
+        self.reader_actor
+            .send(QueryLastEventSeqsMessage { event_handle_ids })
+            .await?
+    }
 }
@@ -17,6 +17,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    field_states (id) {
+        id -> Text,
+        parent_id -> Text,
+        field_key -> Text,
+        name_type -> Text,
+        value_type -> Text,
+        tx_order -> BigInt,
+        state_index -> BigInt,
+        created_at -> BigInt,
+        updated_at -> BigInt,
+        decoded_value -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     object_states (id) {
         id -> Text,
@@ -51,4 +66,4 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(events, object_states, transactions,);
+diesel::allow_tables_to_appear_in_same_query!(events, field_states, object_states, transactions,);
@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod metrics;
+pub mod pg_store;
 pub mod sqlite_store;
 pub mod traits;
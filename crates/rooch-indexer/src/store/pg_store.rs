@@ -0,0 +1,302 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::{Context, IndexerError};
+use anyhow::Result;
+use diesel::upsert::excluded;
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
+use function_name::named;
+use rooch_types::indexer::event::IndexerEvent;
+use rooch_types::indexer::field::IndexerFieldState;
+use rooch_types::indexer::state::IndexerObjectState;
+use rooch_types::indexer::transaction::IndexerTransaction;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::models::events::StoredEvent;
+use crate::models::fields::StoredFieldState;
+use crate::models::states::StoredObjectState;
+use crate::models::transactions::StoredTransaction;
+use crate::schema::{events, field_states, object_states, transactions};
+use crate::store::metrics::IndexerDBMetrics;
+use crate::{get_pg_pool_connection, PgConnectionPool};
+
+/// PostgreSQL limits the bind parameters of a statement to 65535,
+/// so the rows are written in chunks.
+const PG_INSERT_CHUNK_SIZE: usize = 1000;
+
+#[derive(Clone)]
+pub struct PgIndexerStore {
+    pub(crate) connection_pool: PgConnectionPool,
+    db_metrics: Arc<IndexerDBMetrics>,
+}
+
+impl PgIndexerStore {
+    pub fn new(connection_pool: PgConnectionPool, db_metrics: Arc<IndexerDBMetrics>) -> Self {
+        Self {
+            connection_pool,
+            db_metrics,
+        }
+    }
+
+    #[named]
+    pub fn persist_or_update_object_states(
+        &self,
+        states: Vec<IndexerObjectState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        // PostgreSQL can not update the same row twice in one upsert, only the last state of an object is kept
+        let states = states
+            .into_iter()
+            .map(StoredObjectState::from)
+            .map(|state| (state.id.clone(), state))
+            .collect::<HashMap<_, _>>()
+            .into_values()
+            .collect::<Vec<_>>();
+
+        connection
+            .transaction(|conn| {
+                for chunk in states.chunks(PG_INSERT_CHUNK_SIZE) {
+                    diesel::insert_into(object_states::table)
+                        .values(chunk)
+                        .on_conflict(object_states::id)
+                        .do_update()
+                        .set((
+                            object_states::owner.eq(excluded(object_states::owner)),
+                            object_states::flag.eq(excluded(object_states::flag)),
+                            object_states::state_root.eq(excluded(object_states::state_root)),
+                            object_states::size.eq(excluded(object_states::size)),
+                            object_states::tx_order.eq(excluded(object_states::tx_order)),
+                            object_states::state_index.eq(excluded(object_states::state_index)),
+                            object_states::updated_at.eq(excluded(object_states::updated_at)),
+                        ))
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to write or update object states to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn delete_object_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(object_states::table.filter(object_states::id.eq_any(state_pks.as_slice())))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete object states to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn persist_or_update_field_states(
+        &self,
+        states: Vec<IndexerFieldState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        // PostgreSQL can not update the same row twice in one upsert, only the last state of a field is kept
+        let states = states
+            .into_iter()
+            .map(StoredFieldState::from)
+            .map(|state| (state.id.clone(), state))
+            .collect::<HashMap<_, _>>()
+            .into_values()
+            .collect::<Vec<_>>();
+
+        connection
+            .transaction(|conn| {
+                for chunk in states.chunks(PG_INSERT_CHUNK_SIZE) {
+                    diesel::insert_into(field_states::table)
+                        .values(chunk)
+                        .on_conflict(field_states::id)
+                        .do_update()
+                        .set((
+                            field_states::tx_order.eq(excluded(field_states::tx_order)),
+                            field_states::state_index.eq(excluded(field_states::state_index)),
+                            field_states::updated_at.eq(excluded(field_states::updated_at)),
+                            field_states::decoded_value.eq(excluded(field_states::decoded_value)),
+                        ))
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to write or update field states to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn delete_field_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(field_states::table.filter(field_states::id.eq_any(state_pks.as_slice())))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete field states to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn persist_transactions(
+        &self,
+        transactions: Vec<IndexerTransaction>,
+    ) -> Result<(), IndexerError> {
+        if transactions.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        // The values are bound as parameters, so they are not escaped like the SQLite store
+        let transactions = transactions
+            .into_iter()
+            .map(StoredTransaction::from)
+            .collect::<Vec<_>>();
+
+        connection
+            .transaction(|conn| {
+                for chunk in transactions.chunks(PG_INSERT_CHUNK_SIZE) {
+                    // The rows of a tx are written again if the tx failed to be indexed midway
+                    diesel::insert_into(transactions::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to write transactions to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn persist_events(&self, events: Vec<IndexerEvent>) -> Result<(), IndexerError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        let events = events
+            .into_iter()
+            .map(StoredEvent::from)
+            .collect::<Vec<_>>();
+
+        connection
+            .transaction(|conn| {
+                for chunk in events.chunks(PG_INSERT_CHUNK_SIZE) {
+                    // The rows of a tx are written again if the tx failed to be indexed midway
+                    diesel::insert_into(events::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to write events to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn delete_transactions_after_tx_order(&self, tx_order: u64) -> Result<(), IndexerError> {
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(transactions::table.filter(transactions::tx_order.gt(tx_order as i64)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete transactions to PostgreSQL")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn delete_events_after_tx_order(&self, tx_order: u64) -> Result<(), IndexerError> {
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(events::table.filter(events::tx_order.gt(tx_order as i64)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete events to PostgreSQL")?;
+
+        Ok(())
+    }
+}
@@ -7,15 +7,17 @@ use diesel::QueryDsl;
 use diesel::{ExpressionMethods, RunQueryDsl};
 use function_name::named;
 use rooch_types::indexer::event::IndexerEvent;
+use rooch_types::indexer::field::IndexerFieldState;
 use rooch_types::indexer::state::IndexerObjectState;
 use rooch_types::indexer::transaction::IndexerTransaction;
 use std::sync::Arc;
 use tracing::log;
 
 use crate::models::events::StoredEvent;
+use crate::models::fields::StoredFieldState;
 use crate::models::states::StoredObjectState;
 use crate::models::transactions::{escape_transaction, StoredTransaction};
-use crate::schema::{events, object_states, transactions};
+use crate::schema::{events, field_states, object_states, transactions};
 use crate::store::metrics::IndexerDBMetrics;
 use crate::utils::escape_sql_string;
 use crate::{get_sqlite_pool_connection, SqliteConnectionPool};
@@ -105,7 +107,7 @@ impl SqliteIndexerStore {
         //         object_states::updated_at.eq(excluded(object_states::updated_at)),
         //     ))
         //     .execute(&mut connection)
-        //     .map_err(|e| IndexerError::SQLiteWriteError(e.to_string()))
+        //     .map_err(|e| IndexerError::WriteError(e.to_string()))
         //     .context("Failed to write or update object states to SQLiteDB");
 
         // Execute the raw SQL query
@@ -113,7 +115,7 @@ impl SqliteIndexerStore {
             .execute(&mut connection)
             .map_err(|e| {
                 log::error!("Upsert object states Executing Query error: {}", query);
-                IndexerError::SQLiteWriteError(e.to_string())
+                IndexerError::WriteError(e.to_string())
             })
             .context("Failed to write or update object states to SQLiteDB")?;
 
@@ -137,12 +139,100 @@ impl SqliteIndexerStore {
 
         diesel::delete(object_states::table.filter(object_states::id.eq_any(state_pks.as_slice())))
             .execute(&mut connection)
-            .map_err(|e| IndexerError::SQLiteWriteError(e.to_string()))
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
             .context("Failed to delete object states to SQLiteDB")?;
 
         Ok(())
     }
 
+    #[named]
+    pub fn persist_or_update_field_states(
+        &self,
+        states: Vec<IndexerFieldState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_sqlite_pool_connection(&self.connection_pool)?;
+
+        let values_clause = states
+            .into_iter()
+            .map(StoredFieldState::from)
+            .map(|state| {
+                format!(
+                    "('{}', '{}', '{}', '{}', '{}', {}, {}, {}, {}, {})",
+                    escape_sql_string(state.id),
+                    escape_sql_string(state.parent_id),
+                    escape_sql_string(state.field_key),
+                    escape_sql_string(state.name_type),
+                    escape_sql_string(state.value_type),
+                    state.tx_order,
+                    state.state_index,
+                    state.created_at,
+                    state.updated_at,
+                    state
+                        .decoded_value
+                        .map(|value| format!("'{}'", escape_sql_string(value)))
+                        .unwrap_or_else(|| "NULL".to_string()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = format!(
+            "
+                INSERT INTO field_states (id, parent_id, field_key, name_type, value_type, tx_order, state_index, created_at, updated_at, decoded_value) \
+                VALUES {} \
+                ON CONFLICT (id) DO UPDATE SET \
+                tx_order = excluded.tx_order, \
+                state_index = excluded.state_index, \
+                updated_at = excluded.updated_at, \
+                decoded_value = excluded.decoded_value
+            ",
+            values_clause
+        );
+
+        diesel::sql_query(query.clone())
+            .execute(&mut connection)
+            .map_err(|e| {
+                log::error!("Upsert field states Executing Query error: {}", query);
+                IndexerError::WriteError(e.to_string())
+            })
+            .context("Failed to write or update field states to SQLiteDB")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn delete_field_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_sqlite_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(field_states::table.filter(field_states::id.eq_any(state_pks.as_slice())))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete field states to SQLiteDB")?;
+
+        Ok(())
+    }
+
     #[named]
     pub fn persist_transactions(
         &self,
@@ -165,10 +255,11 @@ impl SqliteIndexerStore {
             .map(|v| escape_transaction(StoredTransaction::from(v)))
             .collect::<Vec<_>>();
 
-        diesel::insert_into(transactions::table)
+        // The rows of a tx are written again if the tx failed to be indexed midway
+        diesel::insert_or_ignore_into(transactions::table)
             .values(transactions.as_slice())
             .execute(&mut connection)
-            .map_err(|e| IndexerError::SQLiteWriteError(e.to_string()))
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
             .context("Failed to write transactions to SQLiteDB")?;
 
         Ok(())
@@ -193,12 +284,51 @@ impl SqliteIndexerStore {
             .map(StoredEvent::from)
             .collect::<Vec<_>>();
 
-        diesel::insert_into(events::table)
+        // The rows of a tx are written again if the tx failed to be indexed midway
+        diesel::insert_or_ignore_into(events::table)
             .values(events.as_slice())
             .execute(&mut connection)
-            .map_err(|e| IndexerError::SQLiteWriteError(e.to_string()))
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
             .context("Failed to write events to SQLiteDB")?;
 
         Ok(())
     }
+
+    #[named]
+    pub fn delete_transactions_after_tx_order(&self, tx_order: u64) -> Result<(), IndexerError> {
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_sqlite_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(transactions::table.filter(transactions::tx_order.gt(tx_order as i64)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete transactions to SQLiteDB")?;
+
+        Ok(())
+    }
+
+    #[named]
+    pub fn delete_events_after_tx_order(&self, tx_order: u64) -> Result<(), IndexerError> {
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_sqlite_pool_connection(&self.connection_pool)?;
+
+        diesel::delete(events::table.filter(events::tx_order.gt(tx_order as i64)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::WriteError(e.to_string()))
+            .context("Failed to delete events to SQLiteDB")?;
+
+        Ok(())
+    }
 }
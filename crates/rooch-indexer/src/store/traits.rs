@@ -3,6 +3,7 @@
 
 use crate::errors::IndexerError;
 use rooch_types::indexer::event::IndexerEvent;
+use rooch_types::indexer::field::{IndexerFieldState, IndexerFieldStateChanges};
 use rooch_types::indexer::state::{IndexerObjectState, IndexerObjectStateChanges};
 use rooch_types::indexer::transaction::IndexerTransaction;
 
@@ -18,10 +19,27 @@ pub trait IndexerStoreTrait: Send + Sync {
 
     fn delete_object_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError>;
 
+    fn update_field_states(
+        &self,
+        field_state_change: IndexerFieldStateChanges,
+    ) -> Result<(), IndexerError>;
+    fn persist_or_update_field_states(
+        &self,
+        states: Vec<IndexerFieldState>,
+    ) -> Result<(), IndexerError>;
+
+    fn delete_field_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError>;
+
     fn persist_transactions(
         &self,
         transactions: Vec<IndexerTransaction>,
     ) -> Result<(), IndexerError>;
 
     fn persist_events(&self, events: Vec<IndexerEvent>) -> Result<(), IndexerError>;
+
+    /// Delete the transactions whose tx order is greater than `tx_order`
+    fn delete_transactions_after_tx_order(&self, tx_order: u64) -> Result<(), IndexerError>;
+
+    /// Delete the events whose tx order is greater than `tx_order`
+    fn delete_events_after_tx_order(&self, tx_order: u64) -> Result<(), IndexerError>;
 }
@@ -3,3 +3,4 @@
 
 mod test_concurrence;
 mod test_indexer;
+mod test_pg_indexer;
@@ -75,7 +75,7 @@ mod tests {
                 diesel::insert_into(events::table)
                     .values(events.as_slice())
                     .execute(&mut connection)
-                    .map_err(|e| IndexerError::SQLiteWriteError(e.to_string()))
+                    .map_err(|e| IndexerError::WriteError(e.to_string()))
                     .context("Failed to write test case events to SQLiteDB")
                     .unwrap();
 
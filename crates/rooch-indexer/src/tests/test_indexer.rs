@@ -1,29 +1,44 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::actor::indexer::IndexerActor;
+use crate::actor::reader_indexer::IndexerReaderActor;
 use crate::indexer_reader::IndexerReader;
+use crate::proxy::IndexerProxy;
 use crate::store::traits::IndexerStoreTrait;
 use crate::IndexerStore;
 use anyhow::Result;
+use coerce::actor::system::ActorSystem;
+use coerce::actor::IntoActor;
 use metrics::RegistryService;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::Op;
+use move_core_types::language_storage::TypeTag;
 use move_core_types::vm_status::KeptVMStatus;
+use moveos_store::MoveOSStore;
 use moveos_types::h256::H256;
 use moveos_types::moveos_std::object::{ObjectEntity, ObjectID, ObjectMeta};
 use moveos_types::moveos_std::tx_context::TxContext;
-use moveos_types::state::MoveStructType;
+use moveos_types::state::{MoveStructType, ObjectChange, StateChangeSet};
 use moveos_types::transaction::{TransactionExecutionInfo, VerifiedMoveOSTransaction};
 use rand::random;
 use rooch_config::store_config::DEFAULT_DB_INDEXER_SUBDIR;
 use rooch_types::framework::coin_store::CoinStore;
 use rooch_types::framework::gas_coin::GasCoin;
 use rooch_types::indexer::event::{EventFilter, IndexerEvent};
-use rooch_types::indexer::state::{IndexerObjectState, ObjectStateFilter};
+use rooch_types::indexer::field::{collect_field_changes, FieldIndexConfig, FieldStateFilter};
+use rooch_types::indexer::state::{
+    flatten_object_changes, handle_object_change, IndexerObjectState, IndexerObjectStateChanges,
+    ObjectStateFilter,
+};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
+use rooch_types::indexer::Filter;
 use rooch_types::test_utils::{
-    random_event, random_ledger_transaction, random_new_object_states, random_remove_object_states,
-    random_update_object_states, random_verified_move_action,
+    random_event, random_ledger_transaction, random_new_object_states,
+    random_new_object_states_with_size, random_remove_object_states, random_update_object_states,
+    random_verified_move_action,
 };
+use std::collections::BTreeMap;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_transaction_store() -> Result<()> {
@@ -94,12 +109,20 @@ async fn test_event_store() -> Result<()> {
         random_transaction,
         random_moveos_tx.ctx.clone(),
     );
+    let event_id = indexer_event.event_id.clone();
     let events = vec![indexer_event];
     indexer_store.persist_events(events)?;
 
     let filter = EventFilter::Sender(random_moveos_tx.ctx.sender.into());
     let query_events = indexer_reader.query_events_with_filter(filter, None, 1, true)?;
     assert_eq!(query_events.len(), 1);
+
+    let last_event_seqs = indexer_reader
+        .query_last_event_seqs(vec![event_id.event_handle_id.clone(), ObjectID::random()])?;
+    assert_eq!(
+        last_event_seqs,
+        vec![(event_id.event_handle_id, event_id.event_seq)]
+    );
     Ok(())
 }
 
@@ -232,3 +255,316 @@ async fn test_escape_transaction() -> Result<()> {
     assert_eq!(query_transactions.len(), 1);
     Ok(())
 }
+
+#[test]
+fn test_flatten_object_changes() -> Result<()> {
+    let tx_order = 10;
+    let metas = random_new_object_states_with_size(3)
+        .into_iter()
+        .map(|state| state.metadata)
+        .collect::<Vec<_>>();
+    let mut parent = ObjectChange::meta(metas[0].clone());
+    parent.add_field_change(
+        metas[1].id.field_key(),
+        ObjectChange::new(metas[1].clone(), Op::New(vec![1])),
+    );
+    let mut changes = BTreeMap::new();
+    changes.insert(metas[0].id.field_key(), parent);
+    changes.insert(
+        metas[2].id.field_key(),
+        ObjectChange::new(metas[2].clone(), Op::Modify(vec![2])),
+    );
+    let state_change_set = StateChangeSet {
+        state_root: H256::random(),
+        global_size: 0,
+        changes,
+    };
+
+    let object_changes = flatten_object_changes(tx_order, state_change_set.clone());
+    assert_eq!(object_changes.len(), 3);
+
+    // the state index of the object changes is the same as the indexer
+    let mut indexer_changes = IndexerObjectStateChanges::default();
+    let mut state_index_generator = 0;
+    for (_key, change) in state_change_set.changes {
+        state_index_generator = handle_object_change(
+            state_index_generator,
+            tx_order,
+            &mut indexer_changes,
+            change,
+        )?;
+    }
+    assert_eq!(state_index_generator, object_changes.len() as u64);
+    for state in indexer_changes
+        .new_object_states
+        .iter()
+        .chain(indexer_changes.update_object_states.iter())
+    {
+        let object_change = object_changes
+            .iter()
+            .find(|change| change.state.metadata.id == state.metadata.id)
+            .unwrap();
+        assert_eq!(object_change.state.tx_order, tx_order);
+        assert_eq!(object_change.state.state_index, state.state_index);
+    }
+
+    // the field change is matched by its id
+    let filter = ObjectStateFilter::ObjectId(vec![metas[1].id.clone(), ObjectID::random()]);
+    let matched = object_changes
+        .iter()
+        .filter(|change| filter.matches(&change.state))
+        .collect::<Vec<_>>();
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0].value, Some(Op::New(vec![1])));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_field_state_store() -> Result<()> {
+    let registry_service = RegistryService::default();
+    let tmpdir = moveos_config::temp_dir();
+    let indexer_db = tmpdir.path().join(DEFAULT_DB_INDEXER_SUBDIR);
+    let indexer_store =
+        IndexerStore::new(indexer_db.clone(), &registry_service.default_registry())?;
+    let indexer_reader = IndexerReader::new(indexer_db, &registry_service.default_registry())?;
+
+    let parent = random_new_object_states_with_size(1)
+        .pop()
+        .unwrap()
+        .metadata;
+    let u64_field =
+        ObjectEntity::new_dynamic_field(parent.id.clone(), AccountAddress::random(), 100u64)
+            .metadata();
+    let bool_field =
+        ObjectEntity::new_dynamic_field(parent.id.clone(), AccountAddress::random(), true)
+            .metadata();
+    let mut parent_change = ObjectChange::meta(parent.clone());
+    parent_change.add_field_change(
+        u64_field.id.field_key(),
+        ObjectChange::new(u64_field.clone(), Op::New(vec![1])),
+    );
+    parent_change.add_field_change(
+        bool_field.id.field_key(),
+        ObjectChange::new(bool_field.clone(), Op::New(vec![2])),
+    );
+    let mut changes = BTreeMap::new();
+    changes.insert(parent.id.field_key(), parent_change);
+    let state_change_set = StateChangeSet {
+        state_root: H256::random(),
+        global_size: 0,
+        changes,
+    };
+
+    // the fields are not indexed without config
+    let field_changes = collect_field_changes(&FieldIndexConfig::default(), 1, &state_change_set);
+    assert!(field_changes.new_field_states.is_empty());
+
+    let by_type = FieldIndexConfig::new(vec![], vec![parent.object_struct_tag().clone()]);
+    let field_changes = collect_field_changes(&by_type, 1, &state_change_set);
+    assert_eq!(field_changes.new_field_states.len(), 2);
+
+    let config = FieldIndexConfig::new(vec![parent.id.clone()], vec![]);
+    let mut field_changes = collect_field_changes(&config, 1, &state_change_set);
+    assert_eq!(field_changes.new_field_states.len(), 2);
+    // the values are decoded by the indexer actor from the state
+    for field_state in field_changes.new_field_states.iter_mut() {
+        field_state.decoded_value = Some(if field_state.field_id() == u64_field.id {
+            "\"100\"".to_string()
+        } else {
+            "{\"name\":\"it's true\"}".to_string()
+        });
+    }
+    // the state index of the field is the same as the field object state
+    let object_changes = flatten_object_changes(1, state_change_set);
+    for field_state in &field_changes.new_field_states {
+        let object_change = object_changes
+            .iter()
+            .find(|change| change.state.metadata.id == field_state.field_id())
+            .unwrap();
+        assert_eq!(object_change.state.state_index, field_state.state_index);
+    }
+    indexer_store.update_field_states(field_changes)?;
+
+    let filter = FieldStateFilter::Parent(parent.id.clone());
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 2);
+
+    let filter = FieldStateFilter::ParentWithValueType {
+        parent_id: parent.id.clone(),
+        value_type: TypeTag::U64,
+    };
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 1);
+    assert_eq!(field_states[0].field_id(), u64_field.id);
+    assert_eq!(field_states[0].name_type, TypeTag::Address);
+
+    let filter = FieldStateFilter::ValueType(TypeTag::Bool);
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 1);
+    assert_eq!(field_states[0].field_id(), bool_field.id);
+
+    let filter = FieldStateFilter::Key {
+        parent_id: parent.id.clone(),
+        field_key: bool_field.id.field_key(),
+    };
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 1);
+    assert_eq!(field_states[0].field_id(), bool_field.id);
+
+    let filter = FieldStateFilter::Value {
+        parent_id: parent.id.clone(),
+        decoded_value: "\"100\"".to_string(),
+    };
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 1);
+    assert_eq!(field_states[0].field_id(), u64_field.id);
+    assert_eq!(field_states[0].decoded_value, Some("\"100\"".to_string()));
+
+    // the decoded value is escaped in the query
+    let filter = FieldStateFilter::Value {
+        parent_id: parent.id.clone(),
+        decoded_value: "{\"name\":\"it's true\"}".to_string(),
+    };
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 1);
+    assert_eq!(field_states[0].field_id(), bool_field.id);
+
+    let filter = FieldStateFilter::Value {
+        parent_id: ObjectID::random(),
+        decoded_value: "\"100\"".to_string(),
+    };
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert!(field_states.is_empty());
+
+    // paginate by cursor
+    let filter = FieldStateFilter::Parent(parent.id.clone());
+    let first_page =
+        indexer_reader.query_field_states_with_filter(filter.clone(), None, 1, true)?;
+    assert_eq!(first_page.len(), 1);
+    let cursor = Some(first_page[0].indexer_state_id());
+    let second_page = indexer_reader.query_field_states_with_filter(filter, cursor, 1, true)?;
+    assert_eq!(second_page.len(), 1);
+    assert!(second_page[0].state_index < first_page[0].state_index);
+
+    // remove the field
+    let mut parent_change = ObjectChange::meta(parent.clone());
+    parent_change.add_field_change(
+        u64_field.id.field_key(),
+        ObjectChange::new(u64_field.clone(), Op::Delete),
+    );
+    let mut changes = BTreeMap::new();
+    changes.insert(parent.id.field_key(), parent_change);
+    let state_change_set = StateChangeSet {
+        state_root: H256::random(),
+        global_size: 0,
+        changes,
+    };
+    let field_changes = collect_field_changes(&config, 2, &state_change_set);
+    assert_eq!(field_changes.remove_field_states.len(), 1);
+    indexer_store.update_field_states(field_changes)?;
+
+    let filter = FieldStateFilter::Parent(parent.id.clone());
+    let field_states = indexer_reader.query_field_states_with_filter(filter, None, 10, false)?;
+    assert_eq!(field_states.len(), 1);
+    assert_eq!(field_states[0].field_id(), bool_field.id);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_index_tx_failed_midway() -> Result<()> {
+    let registry_service = RegistryService::default();
+    let tmpdir = moveos_config::temp_dir();
+    let indexer_db = tmpdir.path().join(DEFAULT_DB_INDEXER_SUBDIR);
+    let indexer_store =
+        IndexerStore::new(indexer_db.clone(), &registry_service.default_registry())?;
+    let indexer_reader = IndexerReader::new(indexer_db, &registry_service.default_registry())?;
+    let (moveos_store, _store_dir) = MoveOSStore::mock_moveos_store()?;
+
+    let mut ledger_tx = random_ledger_transaction();
+    ledger_tx.sequence_info.tx_order = 1;
+    let execution_info = TransactionExecutionInfo::new(
+        H256::random(),
+        H256::random(),
+        random(),
+        H256::random(),
+        rand::random(),
+        KeptVMStatus::Executed,
+    );
+    let moveos_tx = VerifiedMoveOSTransaction {
+        root: ObjectMeta::genesis_root(),
+        ctx: TxContext::new_readonly_ctx(AccountAddress::random()),
+        action: random_verified_move_action(),
+    };
+    let events = (0..2)
+        .map(|event_index| {
+            let mut event = random_event();
+            event.event_index = event_index;
+            event
+        })
+        .collect::<Vec<_>>();
+    let event_handle_ids = events
+        .iter()
+        .map(|event| event.event_id.event_handle_id.clone())
+        .collect::<Vec<_>>();
+
+    // The tx 1 failed to be indexed after its events are written
+    let indexer_events = events
+        .iter()
+        .map(|event| IndexerEvent::new(event.clone(), ledger_tx.clone(), moveos_tx.ctx.clone()))
+        .collect::<Vec<_>>();
+    indexer_store.persist_events(indexer_events.clone())?;
+    // The events are written again without conflict
+    indexer_store.persist_events(indexer_events)?;
+    assert_eq!(
+        indexer_reader
+            .query_last_event_seqs(event_handle_ids.clone())?
+            .len(),
+        2
+    );
+
+    let actor_system = ActorSystem::global_system();
+    let indexer_actor = IndexerActor::new(
+        ObjectMeta::genesis_root(),
+        indexer_store,
+        moveos_store,
+        FieldIndexConfig::default(),
+        Some(0),
+    )?
+    .into_actor(None, &actor_system)
+    .await?;
+    let indexer_reader_actor = IndexerReaderActor::new(indexer_reader.clone())?
+        .into_actor(None, &actor_system)
+        .await?;
+    let indexer_proxy = IndexerProxy::new(indexer_actor.into(), indexer_reader_actor.into());
+
+    // The catch up gets the last indexed tx order first, the events left by tx 1 are deleted
+    assert_eq!(indexer_proxy.get_last_indexed_tx_order().await?, Some(0));
+    assert!(indexer_reader
+        .query_last_event_seqs(event_handle_ids.clone())?
+        .is_empty());
+
+    // The catch up indexes the tx 1 again
+    let state_change_set = StateChangeSet {
+        state_root: H256::random(),
+        global_size: 0,
+        changes: BTreeMap::new(),
+    };
+    indexer_proxy
+        .update_indexer_and_wait(
+            ledger_tx,
+            execution_info,
+            moveos_tx,
+            events,
+            state_change_set,
+        )
+        .await?;
+    assert_eq!(indexer_proxy.get_last_indexed_tx_order().await?, Some(1));
+    assert_eq!(indexer_reader.query_last_tx_order()?, Some(1));
+    let filter = EventFilter::TxOrderRange {
+        from_order: 1,
+        to_order: 2,
+    };
+    let indexed_events = indexer_reader.query_events_with_filter(filter, None, 10, false)?;
+    assert_eq!(indexed_events.len(), 2);
+    Ok(())
+}
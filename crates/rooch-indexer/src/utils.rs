@@ -1,13 +1,18 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::SqlitePoolConnection;
-use anyhow::anyhow;
+use crate::{PgPoolConnection, SqlitePoolConnection};
+use anyhow::{anyhow, Result};
 use diesel::{RunQueryDsl, SqliteConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use move_core_types::resolver::MoveResolver;
+use move_resource_viewer::MoveValueAnnotator;
+use moveos_types::state::ObjectState;
+use rooch_rpc_api::jsonrpc_types::AnnotatedMoveValueView;
 use tracing::{debug, info};
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+const PG_MIGRATIONS: EmbeddedMigrations = embed_migrations!("pg_migrations");
 
 /// creates all the tables by applying all migrations.
 pub fn create_all_tables_if_not_exists(
@@ -50,6 +55,17 @@ pub fn create_all_tables_if_not_exists(
     Ok(())
 }
 
+/// creates all the tables in the PostgreSQL db by applying all PostgreSQL migrations.
+pub fn create_all_pg_tables_if_not_exists(
+    conn: &mut PgPoolConnection,
+) -> Result<(), anyhow::Error> {
+    debug!("Indexer creates all tables in the PostgreSQL db ...");
+    conn.run_pending_migrations(PG_MIGRATIONS)
+        .map_err(|e| anyhow!("Failed to run PostgreSQL migrations {e}"))?;
+    debug!("Indexer creates all tables in the PostgreSQL db complete.");
+    Ok(())
+}
+
 /// Resets the db by reverting all migrations and reapplying them.
 ///
 /// If `drop_all` is set to `true`, the function will drop all tables in the db before
@@ -101,6 +117,27 @@ pub fn drop_all_tables(conn: &mut SqliteConnection) -> Result<(), diesel::result
 }
 
 pub fn escape_sql_string(value: String) -> String {
-    // In SQLite, replace single quotes with two single quotes
+    // In SQLite and PostgreSQL, replace single quotes with two single quotes
     value.replace(['\''], "''")
 }
+
+/// Decode the value of the dynamic field object state to the JSON of the annotated value,
+/// the JSON is the same as the field value returned by the RPC, so the field states can be queried by it.
+/// Return None if the state is not a dynamic field.
+pub fn decode_field_value<T: MoveResolver + ?Sized>(
+    annotator: &MoveValueAnnotator<T>,
+    state: ObjectState,
+) -> Result<Option<String>> {
+    if !state.metadata.is_dynamic_field() {
+        return Ok(None);
+    }
+    let (_metadata, _value, decoded_value) = state.into_annotated_state(annotator)?.into_inner();
+    decoded_value
+        .value
+        .into_iter()
+        .find(|(name, _value)| name.as_str() == "value")
+        .map(|(_name, value)| {
+            serde_json::to_string(&AnnotatedMoveValueView::from(value)).map_err(Into::into)
+        })
+        .transpose()
+}
@@ -0,0 +1,131 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::messages::{
+    AddTransactionMessage, GetMempoolStatusMessage, GetPendingTransactionsMessage,
+    PopReadyTransactionsMessage, TransactionExecutedMessage,
+};
+use crate::pool::{ExecuteResultReceiver, Mempool, PendingTransaction};
+use anyhow::Result;
+use async_trait::async_trait;
+use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use log::debug;
+use rooch_config::mempool_config::MempoolConfig;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_types::mempool::{MempoolStatus, PendingTransactionInfo};
+use rooch_types::transaction::RoochTransaction;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::Notify;
+
+/// MempoolActor queues the L2 transactions before they are sequenced,
+/// the drainer takes the executable ones and sends them to the pipeline processor.
+pub struct MempoolActor {
+    mempool: Mempool,
+    executor: ExecutorProxy,
+    notify: Arc<Notify>,
+}
+
+impl MempoolActor {
+    pub fn new(config: &MempoolConfig, executor: ExecutorProxy) -> Self {
+        Self {
+            mempool: Mempool::new(config),
+            executor,
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Notified when new transactions are added to the mempool
+    pub fn notify(&self) -> Arc<Notify> {
+        self.notify.clone()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("system time should be after the unix epoch")
+            .as_millis() as u64
+    }
+
+    async fn add_transaction(&mut self, tx: RoochTransaction) -> Result<ExecuteResultReceiver> {
+        let sender = tx.sender();
+        let sequence_number = tx.sequence_number();
+        let account_sequence_number = self.executor.get_sequence_number(sender.into()).await?;
+        // The transaction can be fully validated only if it is the next one of the account,
+        // the others are validated by the pipeline processor when they become executable.
+        let is_next = self
+            .mempool
+            .next_sequence_number(&sender)
+            .unwrap_or(account_sequence_number)
+            == sequence_number;
+        if is_next && sequence_number == account_sequence_number {
+            self.executor.validate_l2_tx(tx.clone()).await?;
+        }
+        let (pending, receiver) = PendingTransaction::new(tx, Self::now());
+        debug!(
+            "mempool add tx: {}, sender: {}, sequence_number: {}",
+            pending.tx_hash, sender, sequence_number
+        );
+        self.mempool.add(pending, account_sequence_number)?;
+        self.notify.notify_one();
+        Ok(receiver)
+    }
+}
+
+impl Actor for MempoolActor {}
+
+#[async_trait]
+impl Handler<AddTransactionMessage> for MempoolActor {
+    async fn handle(
+        &mut self,
+        msg: AddTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<ExecuteResultReceiver> {
+        self.add_transaction(msg.tx).await
+    }
+}
+
+#[async_trait]
+impl Handler<PopReadyTransactionsMessage> for MempoolActor {
+    async fn handle(
+        &mut self,
+        msg: PopReadyTransactionsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Vec<PendingTransaction> {
+        self.mempool.pop_ready(Self::now(), msg.limit)
+    }
+}
+
+#[async_trait]
+impl Handler<TransactionExecutedMessage> for MempoolActor {
+    async fn handle(&mut self, msg: TransactionExecutedMessage, _ctx: &mut ActorContext) {
+        self.mempool
+            .on_executed(msg.sender, msg.sequence_number, msg.sequenced);
+        if !msg.sequenced {
+            // the following transactions of the sender may be ready again
+            self.notify.notify_one();
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<GetPendingTransactionsMessage> for MempoolActor {
+    async fn handle(
+        &mut self,
+        msg: GetPendingTransactionsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Vec<PendingTransactionInfo> {
+        self.mempool.pending_transactions(msg.sender, msg.limit)
+    }
+}
+
+#[async_trait]
+impl Handler<GetMempoolStatusMessage> for MempoolActor {
+    async fn handle(
+        &mut self,
+        _msg: GetMempoolStatusMessage,
+        _ctx: &mut ActorContext,
+    ) -> MempoolStatus {
+        self.mempool.status()
+    }
+}
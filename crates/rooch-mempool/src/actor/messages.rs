@@ -0,0 +1,59 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::pool::{ExecuteResultReceiver, PendingTransaction};
+use anyhow::Result;
+use coerce::actor::message::Message;
+use rooch_types::address::RoochAddress;
+use rooch_types::mempool::{MempoolStatus, PendingTransactionInfo};
+use rooch_types::transaction::RoochTransaction;
+
+/// Add a transaction to the mempool, the result receiver gets the execution result
+#[derive(Debug)]
+pub struct AddTransactionMessage {
+    pub tx: RoochTransaction,
+}
+
+impl Message for AddTransactionMessage {
+    type Result = Result<ExecuteResultReceiver>;
+}
+
+/// Take the executable transactions from the mempool
+#[derive(Debug)]
+pub struct PopReadyTransactionsMessage {
+    pub limit: usize,
+}
+
+impl Message for PopReadyTransactionsMessage {
+    type Result = Vec<PendingTransaction>;
+}
+
+/// Report the result of a transaction taken from the mempool
+#[derive(Debug)]
+pub struct TransactionExecutedMessage {
+    pub sender: RoochAddress,
+    pub sequence_number: u64,
+    /// Whether the transaction is sequenced, even if its execution is failed
+    pub sequenced: bool,
+}
+
+impl Message for TransactionExecutedMessage {
+    type Result = ();
+}
+
+#[derive(Debug)]
+pub struct GetPendingTransactionsMessage {
+    pub sender: Option<RoochAddress>,
+    pub limit: usize,
+}
+
+impl Message for GetPendingTransactionsMessage {
+    type Result = Vec<PendingTransactionInfo>;
+}
+
+#[derive(Debug)]
+pub struct GetMempoolStatusMessage {}
+
+impl Message for GetMempoolStatusMessage {
+    type Result = MempoolStatus;
+}
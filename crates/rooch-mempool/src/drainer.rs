@@ -0,0 +1,94 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::pool::PendingTransaction;
+use crate::proxy::MempoolProxy;
+use log::{debug, warn};
+use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
+use rooch_types::error::SequencedTransactionError;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// How many transactions are taken from the mempool at a time
+const DRAIN_BATCH_SIZE: usize = 100;
+/// Check the mempool even without notification, so the expired transactions are dropped in time
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// MempoolDrainer takes the executable transactions from the mempool,
+/// sends them to the pipeline processor one by one, and replies the results to the submitters.
+pub struct MempoolDrainer {
+    mempool: MempoolProxy,
+    processor: PipelineProcessorProxy,
+    notify: Arc<Notify>,
+}
+
+impl MempoolDrainer {
+    pub fn new(
+        mempool: MempoolProxy,
+        processor: PipelineProcessorProxy,
+        notify: Arc<Notify>,
+    ) -> Self {
+        Self {
+            mempool,
+            processor,
+            notify,
+        }
+    }
+
+    pub fn start(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        loop {
+            let txs = match self.mempool.pop_ready_transactions(DRAIN_BATCH_SIZE).await {
+                Ok(txs) => txs,
+                Err(e) => {
+                    warn!("Mempool drainer stopped: {:?}", e);
+                    break;
+                }
+            };
+            if txs.is_empty() {
+                let _ = tokio::time::timeout(IDLE_CHECK_INTERVAL, self.notify.notified()).await;
+                continue;
+            }
+            for tx in txs {
+                self.execute(tx).await;
+            }
+        }
+    }
+
+    async fn execute(&self, pending: PendingTransaction) {
+        let sender = pending.sender();
+        let sequence_number = pending.sequence_number();
+        let result = self.processor.execute_l2_tx(pending.tx.clone()).await;
+        // The pipeline processor may fail before sequencing the transaction, e.g. validation failure,
+        // then the sequence number is not used. The errors after sequencing carry the tx order.
+        let sequenced = match &result {
+            Ok(_) => true,
+            Err(e) => match e.downcast_ref::<SequencedTransactionError>() {
+                Some(sequenced_error) => {
+                    warn!(
+                        "mempool tx {} is sequenced at tx_order {} but failed to execute: {:?}",
+                        pending.tx_hash, sequenced_error.tx_order, e
+                    );
+                    true
+                }
+                None => {
+                    debug!("mempool tx {} is not sequenced: {:?}", pending.tx_hash, e);
+                    false
+                }
+            },
+        };
+        if let Err(e) = self
+            .mempool
+            .transaction_executed(sender, sequence_number, sequenced)
+            .await
+        {
+            warn!("Report executed tx to mempool error: {:?}", e);
+        }
+        pending.respond(result);
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod actor;
+pub mod drainer;
+pub mod pool;
+pub mod proxy;
@@ -0,0 +1,509 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use moveos_types::h256::H256;
+use rooch_config::mempool_config::MempoolConfig;
+use rooch_types::address::RoochAddress;
+use rooch_types::mempool::{MempoolStatus, PendingTransactionInfo};
+use rooch_types::transaction::{ExecuteTransactionResponse, RoochTransaction};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use tokio::sync::oneshot;
+
+pub type ExecuteResultSender = oneshot::Sender<Result<ExecuteTransactionResponse>>;
+pub type ExecuteResultReceiver = oneshot::Receiver<Result<ExecuteTransactionResponse>>;
+
+/// A transaction waiting in the mempool, with the channel to send back its execution result
+#[derive(Debug)]
+pub struct PendingTransaction {
+    pub tx: RoochTransaction,
+    pub tx_hash: H256,
+    /// The timestamp when the transaction entered the mempool, in milliseconds
+    pub received_at: u64,
+    responder: Option<ExecuteResultSender>,
+}
+
+impl PendingTransaction {
+    pub fn new(mut tx: RoochTransaction, received_at: u64) -> (Self, ExecuteResultReceiver) {
+        let (responder, receiver) = oneshot::channel();
+        let tx_hash = tx.tx_hash();
+        let pending = Self {
+            tx,
+            tx_hash,
+            received_at,
+            responder: Some(responder),
+        };
+        (pending, receiver)
+    }
+
+    pub fn sender(&self) -> RoochAddress {
+        self.tx.sender()
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.tx.sequence_number()
+    }
+
+    /// Send the execution result to the submitter, who may have stopped waiting
+    pub fn respond(mut self, result: Result<ExecuteTransactionResponse>) {
+        if let Some(responder) = self.responder.take() {
+            let _ = responder.send(result);
+        }
+    }
+
+    fn reject(self, reason: String) {
+        let tx_hash = self.tx_hash;
+        self.respond(Err(anyhow::anyhow!(
+            "Transaction {} is dropped from the mempool: {}",
+            tx_hash,
+            reason
+        )));
+    }
+
+    fn info(&self, ready: bool) -> PendingTransactionInfo {
+        PendingTransactionInfo {
+            tx_hash: self.tx_hash,
+            sender: self.sender(),
+            sequence_number: self.sequence_number(),
+            max_gas_amount: self.tx.max_gas_amount(),
+            received_at: self.received_at,
+            ready,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SenderQueue {
+    /// The sequence number of the next transaction of the sender to be sequenced
+    next_sequence_number: u64,
+    /// The transactions taken from the mempool and not executed yet
+    executing: usize,
+    txs: BTreeMap<u64, PendingTransaction>,
+}
+
+impl SenderQueue {
+    fn is_ready(&self) -> bool {
+        self.txs
+            .first_key_value()
+            .map_or(false, |(sequence_number, _)| {
+                *sequence_number == self.next_sequence_number
+            })
+    }
+
+    /// The transactions which follow the next sequence number without a gap
+    fn ready_count(&self) -> usize {
+        self.txs
+            .keys()
+            .zip(self.next_sequence_number..)
+            .take_while(|(sequence_number, expected)| **sequence_number == *expected)
+            .count()
+    }
+
+    /// Rooch transactions do not carry a gas price to bid with, so the senders are served first in first out
+    /// by the time their next transaction entered the mempool.
+    fn priority(&self) -> Option<Reverse<u64>> {
+        self.txs
+            .first_key_value()
+            .map(|(_, tx)| Reverse(tx.received_at))
+    }
+}
+
+/// Mempool keeps the transactions of each sender ordered by the sequence number,
+/// and hands out the executable ones across the senders in first in first out order.
+pub struct Mempool {
+    max_size: usize,
+    max_size_per_sender: usize,
+    ttl_millis: u64,
+    senders: HashMap<RoochAddress, SenderQueue>,
+    hashes: HashSet<H256>,
+}
+
+impl Mempool {
+    pub fn new(config: &MempoolConfig) -> Self {
+        Self {
+            max_size: config.max_size(),
+            max_size_per_sender: config.max_size_per_sender(),
+            ttl_millis: config.ttl_seconds() * 1000,
+            senders: HashMap::new(),
+            hashes: HashSet::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn contains(&self, tx_hash: &H256) -> bool {
+        self.hashes.contains(tx_hash)
+    }
+
+    /// The sequence number of the next transaction of the sender to be sequenced,
+    /// None if the mempool does not track the sender.
+    pub fn next_sequence_number(&self, sender: &RoochAddress) -> Option<u64> {
+        self.senders
+            .get(sender)
+            .map(|queue| queue.next_sequence_number)
+    }
+
+    /// Add a transaction to the mempool, `account_sequence_number` is the sequence number of the sender on chain.
+    /// A pending transaction with the same sequence number is replaced by the new one of the sender.
+    pub fn add(&mut self, pending: PendingTransaction, account_sequence_number: u64) -> Result<()> {
+        let sender = pending.sender();
+        let sequence_number = pending.sequence_number();
+        if self.contains(&pending.tx_hash) {
+            bail!("Transaction {} is already in the mempool", pending.tx_hash);
+        }
+
+        let next_sequence_number = match self.senders.get_mut(&sender) {
+            Some(queue) => {
+                // the account may be advanced by the transactions out of the mempool
+                if account_sequence_number > queue.next_sequence_number {
+                    queue.next_sequence_number = account_sequence_number;
+                    let mut stale = queue.txs.split_off(&account_sequence_number);
+                    std::mem::swap(&mut stale, &mut queue.txs);
+                    for (_, tx) in stale {
+                        self.hashes.remove(&tx.tx_hash);
+                        tx.reject("sequence number too old".to_string());
+                    }
+                }
+                queue.next_sequence_number
+            }
+            None => account_sequence_number,
+        };
+        if sequence_number < next_sequence_number {
+            bail!(
+                "Sequence number {} of sender {} is too old, expected at least {}",
+                sequence_number,
+                sender,
+                next_sequence_number
+            );
+        }
+        let max_sequence_number = next_sequence_number + self.max_size_per_sender as u64;
+        if sequence_number >= max_sequence_number {
+            bail!(
+                "Sequence number {} of sender {} is too new, expected less than {}",
+                sequence_number,
+                sender,
+                max_sequence_number
+            );
+        }
+
+        if let Some(queue) = self.senders.get_mut(&sender) {
+            if queue.txs.contains_key(&sequence_number) {
+                let tx_hash = pending.tx_hash;
+                let replaced = queue
+                    .txs
+                    .insert(sequence_number, pending)
+                    .expect("the replaced transaction should exist");
+                self.hashes.remove(&replaced.tx_hash);
+                self.hashes.insert(tx_hash);
+                replaced.reject(format!("replaced by transaction {}", tx_hash));
+                return Ok(());
+            }
+        }
+
+        if self.size() >= self.max_size {
+            bail!("The mempool is full, max size: {}", self.max_size);
+        }
+        self.hashes.insert(pending.tx_hash);
+        self.senders
+            .entry(sender)
+            .or_insert_with(|| SenderQueue {
+                next_sequence_number,
+                executing: 0,
+                txs: BTreeMap::new(),
+            })
+            .txs
+            .insert(sequence_number, pending);
+        Ok(())
+    }
+
+    fn remove(
+        &mut self,
+        sender: &RoochAddress,
+        sequence_number: u64,
+    ) -> Option<PendingTransaction> {
+        let queue = self.senders.get_mut(sender)?;
+        let tx = queue.txs.remove(&sequence_number)?;
+        self.hashes.remove(&tx.tx_hash);
+        if queue.txs.is_empty() && queue.executing == 0 {
+            self.senders.remove(sender);
+        }
+        Some(tx)
+    }
+
+    /// Drop the transactions which stay in the mempool longer than the TTL
+    pub fn remove_expired(&mut self, now: u64) -> usize {
+        let expired = self
+            .senders
+            .iter()
+            .flat_map(|(sender, queue)| {
+                queue
+                    .txs
+                    .iter()
+                    .filter(|(_, tx)| tx.received_at + self.ttl_millis <= now)
+                    .map(|(sequence_number, _)| (*sender, *sequence_number))
+            })
+            .collect::<Vec<_>>();
+        let count = expired.len();
+        for (sender, sequence_number) in expired {
+            if let Some(tx) = self.remove(&sender, sequence_number) {
+                tx.reject("expired".to_string());
+            }
+        }
+        count
+    }
+
+    /// Take at most `limit` executable transactions, the senders whose next transaction came earlier go first,
+    /// and the transactions of a sender are taken in the order of the sequence number.
+    pub fn pop_ready(&mut self, now: u64, limit: usize) -> Vec<PendingTransaction> {
+        self.remove_expired(now);
+        let mut candidates = self
+            .senders
+            .iter()
+            .filter(|(_, queue)| queue.is_ready())
+            .filter_map(|(sender, queue)| queue.priority().map(|priority| (priority, *sender)))
+            .collect::<BinaryHeap<_>>();
+
+        let mut ready = vec![];
+        while ready.len() < limit {
+            let (_, sender) = match candidates.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+            let queue = self
+                .senders
+                .get_mut(&sender)
+                .expect("the sender queue should exist");
+            let (_, tx) = queue
+                .txs
+                .pop_first()
+                .expect("the ready queue should not be empty");
+            queue.next_sequence_number += 1;
+            queue.executing += 1;
+            if queue.is_ready() {
+                let priority = queue
+                    .priority()
+                    .expect("the ready queue should not be empty");
+                candidates.push((priority, sender));
+            }
+            self.hashes.remove(&tx.tx_hash);
+            ready.push(tx);
+        }
+        ready
+    }
+
+    /// Update the sender after the transaction taken from the mempool is executed.
+    /// If the transaction is not sequenced, the sequence number is not used on chain,
+    /// so the sender waits for a transaction with the sequence number again.
+    pub fn on_executed(&mut self, sender: RoochAddress, sequence_number: u64, sequenced: bool) {
+        if let Some(queue) = self.senders.get_mut(&sender) {
+            queue.executing = queue.executing.saturating_sub(1);
+            if !sequenced && sequence_number < queue.next_sequence_number {
+                queue.next_sequence_number = sequence_number;
+            }
+            if queue.txs.is_empty() && queue.executing == 0 {
+                self.senders.remove(&sender);
+            }
+        }
+    }
+
+    /// The pending transactions of the sender, or all senders if not set, ordered by sender and sequence number
+    pub fn pending_transactions(
+        &self,
+        sender: Option<RoochAddress>,
+        limit: usize,
+    ) -> Vec<PendingTransactionInfo> {
+        let mut senders = self
+            .senders
+            .iter()
+            .filter(|(address, _)| sender.map_or(true, |sender| sender == **address))
+            .collect::<Vec<_>>();
+        senders.sort_by_key(|(address, _)| **address);
+        senders
+            .into_iter()
+            .flat_map(|(_, queue)| {
+                let ready_count = queue.ready_count();
+                queue
+                    .txs
+                    .values()
+                    .enumerate()
+                    .map(move |(index, tx)| tx.info(index < ready_count))
+            })
+            .take(limit)
+            .collect()
+    }
+
+    pub fn status(&self) -> MempoolStatus {
+        MempoolStatus {
+            pending_transactions: self.size() as u64,
+            ready_transactions: self
+                .senders
+                .values()
+                .map(|queue| queue.ready_count() as u64)
+                .sum(),
+            senders: self
+                .senders
+                .values()
+                .filter(|queue| !queue.txs.is_empty())
+                .count() as u64,
+            executing_transactions: self
+                .senders
+                .values()
+                .map(|queue| queue.executing as u64)
+                .sum(),
+            max_size: self.max_size as u64,
+            max_size_per_sender: self.max_size_per_sender as u64,
+            ttl_seconds: self.ttl_millis / 1000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moveos_types::test_utils::random_move_action_function;
+    use rooch_types::crypto::RoochKeyPair;
+    use rooch_types::transaction::RoochTransactionData;
+
+    fn new_tx(
+        kp: &RoochKeyPair,
+        sequence_number: u64,
+        max_gas_amount: u64,
+        received_at: u64,
+    ) -> (PendingTransaction, ExecuteResultReceiver) {
+        let sender = kp.public().rooch_address().unwrap();
+        let mut data = RoochTransactionData::new_for_test(
+            sender,
+            sequence_number,
+            random_move_action_function(),
+        );
+        data.max_gas_amount = max_gas_amount;
+        PendingTransaction::new(data.sign(kp), received_at)
+    }
+
+    fn new_mempool(max_size: usize) -> Mempool {
+        Mempool::new(&MempoolConfig {
+            max_size: Some(max_size),
+            max_size_per_sender: Some(10),
+            ttl_seconds: Some(60),
+        })
+    }
+
+    fn popped(txs: &[PendingTransaction]) -> Vec<(RoochAddress, u64)> {
+        txs.iter()
+            .map(|tx| (tx.sender(), tx.sequence_number()))
+            .collect()
+    }
+
+    #[test]
+    fn test_nonce_order_and_fifo() {
+        let mut mempool = new_mempool(100);
+        let alice = RoochKeyPair::generate_secp256k1();
+        let bob = RoochKeyPair::generate_secp256k1();
+        let alice_address = alice.public().rooch_address().unwrap();
+        let bob_address = bob.public().rooch_address().unwrap();
+
+        // bob's tx comes first, alice's txs arrive out of order
+        mempool.add(new_tx(&bob, 5, 100, 5).0, 5).unwrap();
+        for (sequence_number, received_at) in [(2, 10), (0, 11), (1, 12)] {
+            mempool
+                .add(new_tx(&alice, sequence_number, 100, received_at).0, 0)
+                .unwrap();
+        }
+        // a gap, waits for bob's tx 6, the max gas amount does not matter
+        mempool.add(new_tx(&bob, 7, 300, 20).0, 5).unwrap();
+        assert_eq!(mempool.size(), 5);
+        assert_eq!(mempool.status().ready_transactions, 4);
+
+        let txs = mempool.pop_ready(0, 10);
+        assert_eq!(
+            popped(&txs),
+            vec![
+                (bob_address, 5),
+                (alice_address, 0),
+                (alice_address, 1),
+                (alice_address, 2)
+            ]
+        );
+        assert_eq!(mempool.size(), 1);
+        assert!(mempool.pop_ready(0, 10).is_empty());
+
+        mempool.add(new_tx(&bob, 6, 100, 30).0, 5).unwrap();
+        assert_eq!(
+            popped(&mempool.pop_ready(0, 10)),
+            vec![(bob_address, 6), (bob_address, 7)]
+        );
+        assert_eq!(mempool.status().executing_transactions, 6);
+
+        // a sequenced nonce can not be used again
+        assert!(mempool.add(new_tx(&bob, 6, 100, 0).0, 5).is_err());
+    }
+
+    #[test]
+    fn test_replace_and_reject() {
+        let mut mempool = new_mempool(100);
+        let alice = RoochKeyPair::generate_secp256k1();
+
+        let (tx, mut replaced_receiver) = new_tx(&alice, 0, 100, 0);
+        mempool.add(tx, 0).unwrap();
+        // the sender replaces the pending tx with the same sequence number
+        let (tx, _receiver) = new_tx(&alice, 0, 50, 1);
+        let tx_hash = tx.tx_hash;
+        mempool.add(tx, 0).unwrap();
+        assert!(replaced_receiver.try_recv().unwrap().is_err());
+        assert_eq!(mempool.size(), 1);
+
+        let txs = mempool.pop_ready(0, 10);
+        assert_eq!(txs[0].tx_hash, tx_hash);
+
+        // too old and too new
+        assert!(mempool.add(new_tx(&alice, 0, 300, 0).0, 0).is_err());
+        assert!(mempool.add(new_tx(&alice, 11, 100, 0).0, 0).is_err());
+    }
+
+    #[test]
+    fn test_not_sequenced() {
+        let mut mempool = new_mempool(100);
+        let alice = RoochKeyPair::generate_secp256k1();
+        let alice_address = alice.public().rooch_address().unwrap();
+        mempool.add(new_tx(&alice, 0, 100, 0).0, 0).unwrap();
+        mempool.add(new_tx(&alice, 1, 100, 0).0, 0).unwrap();
+        let txs = mempool.pop_ready(0, 1);
+        assert_eq!(popped(&txs), vec![(alice_address, 0)]);
+
+        // the tx 0 fails to be sequenced, tx 1 waits for a new tx 0
+        mempool.on_executed(alice_address, 0, false);
+        assert!(mempool.pop_ready(0, 10).is_empty());
+        assert_eq!(mempool.next_sequence_number(&alice_address), Some(0));
+        mempool.add(new_tx(&alice, 0, 100, 0).0, 0).unwrap();
+        assert_eq!(mempool.pop_ready(0, 10).len(), 2);
+        mempool.on_executed(alice_address, 0, true);
+        mempool.on_executed(alice_address, 1, true);
+        assert_eq!(mempool.next_sequence_number(&alice_address), None);
+    }
+
+    #[test]
+    fn test_size_limit_and_ttl() {
+        let mut mempool = new_mempool(2);
+        let alice = RoochKeyPair::generate_secp256k1();
+        let bob = RoochKeyPair::generate_secp256k1();
+        let carol = RoochKeyPair::generate_secp256k1();
+
+        mempool.add(new_tx(&alice, 0, 100, 0).0, 0).unwrap();
+        mempool.add(new_tx(&bob, 0, 50, 1_000).0, 0).unwrap();
+        // the pending txs are not evicted by a higher max gas amount
+        assert!(mempool.add(new_tx(&carol, 0, 200, 30_000).0, 0).is_err());
+        assert_eq!(mempool.size(), 2);
+
+        // alice's tx expires
+        assert_eq!(mempool.remove_expired(60_000), 1);
+        let pending = mempool.pending_transactions(None, 10);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].sender, bob.public().rooch_address().unwrap());
+        assert!(pending[0].ready);
+        assert!(mempool.pop_ready(90_000, 10).is_empty());
+        assert_eq!(mempool.size(), 0);
+    }
+}
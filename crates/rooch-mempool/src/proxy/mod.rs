@@ -0,0 +1,85 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::mempool::MempoolActor;
+use crate::actor::messages::{
+    AddTransactionMessage, GetMempoolStatusMessage, GetPendingTransactionsMessage,
+    PopReadyTransactionsMessage, TransactionExecutedMessage,
+};
+use crate::pool::{ExecuteResultReceiver, PendingTransaction};
+use anyhow::{anyhow, Result};
+use coerce::actor::ActorRef;
+use rooch_types::address::RoochAddress;
+use rooch_types::mempool::{MempoolStatus, PendingTransactionInfo};
+use rooch_types::transaction::{ExecuteTransactionResponse, RoochTransaction};
+
+#[derive(Clone)]
+pub struct MempoolProxy {
+    pub actor: ActorRef<MempoolActor>,
+}
+
+impl MempoolProxy {
+    pub fn new(actor: ActorRef<MempoolActor>) -> Self {
+        Self { actor }
+    }
+
+    /// Add the transaction to the mempool without waiting for its execution
+    pub async fn add_transaction(&self, tx: RoochTransaction) -> Result<ExecuteResultReceiver> {
+        self.actor.send(AddTransactionMessage { tx }).await?
+    }
+
+    /// Add the transaction to the mempool and wait for its execution
+    pub async fn execute_transaction(
+        &self,
+        tx: RoochTransaction,
+    ) -> Result<ExecuteTransactionResponse> {
+        let receiver = self.add_transaction(tx).await?;
+        receiver
+            .await
+            .map_err(|_| anyhow!("The mempool is stopped before the transaction is executed"))?
+    }
+
+    pub async fn pop_ready_transactions(&self, limit: usize) -> Result<Vec<PendingTransaction>> {
+        Ok(self
+            .actor
+            .send(PopReadyTransactionsMessage { limit })
+            .await?)
+    }
+
+    pub async fn transaction_executed(
+        &self,
+        sender: RoochAddress,
+        sequence_number: u64,
+        sequenced: bool,
+    ) -> Result<()> {
+        Ok(self
+            .actor
+            .send(TransactionExecutedMessage {
+                sender,
+                sequence_number,
+                sequenced,
+            })
+            .await?)
+    }
+
+    pub async fn get_pending_transactions(
+        &self,
+        sender: Option<RoochAddress>,
+        limit: usize,
+    ) -> Result<Vec<PendingTransactionInfo>> {
+        Ok(self
+            .actor
+            .send(GetPendingTransactionsMessage { sender, limit })
+            .await?)
+    }
+
+    pub async fn get_status(&self) -> Result<MempoolStatus> {
+        Ok(self.actor.send(GetMempoolStatusMessage {}).await?)
+    }
+}
+
+impl From<ActorRef<MempoolActor>> for MempoolProxy {
+    fn from(actor: ActorRef<MempoolActor>) -> Self {
+        Self::new(actor)
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::messages::{CatchUpIndexerTick, GetIndexerStatusMessage};
+use crate::metrics::IndexerCatchUpMetrics;
+use anyhow::{ensure, Result};
+use async_trait::async_trait;
+use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use moveos_types::moveos_std::event::{Event, EventHandle, EventID, TransactionEvent};
+use moveos_types::transaction::TransactionExecutionInfo;
+use prometheus::Registry;
+use rooch_executor::actor::messages::ReexecuteTransactionResult;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_indexer::proxy::IndexerProxy;
+use rooch_sequencer::proxy::SequencerProxy;
+use rooch_types::indexer::IndexerStatus;
+use rooch_types::service_status::ServiceStatus;
+use rooch_types::transaction::{L1Block, LedgerTransaction, LedgerTxData};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::info;
+
+/// How many saved events of an event handle are read at a time to find the events after the skipped txs
+const EVENT_SEARCH_PAGE_SIZE: u64 = 100;
+
+/// Load the block body of a sequenced L1 block, the ledger transaction only includes the block header.
+#[async_trait]
+pub trait L1BlockBodyLoader: Send + Sync {
+    async fn load_block_body(&self, block: &L1Block) -> Result<Vec<u8>>;
+}
+
+/// IndexerCatchUpActor indexes the txs missed by the indexer in the background,
+/// so the pipeline processor is not blocked by the re-execution.
+pub struct IndexerCatchUpActor {
+    /// The executor of the proxy is dedicated to the re-execution
+    executor: ExecutorProxy,
+    sequencer: SequencerProxy,
+    indexer: IndexerProxy,
+    /// The L1 block txs are skipped if the block bodies can not be loaded
+    l1_block_body_loader: Option<Arc<dyn L1BlockBodyLoader>>,
+    service_status: ServiceStatus,
+    /// How many missed txs are indexed in one catch up
+    max_txs: u64,
+    metrics: Arc<IndexerCatchUpMetrics>,
+    /// The error of the last indexer catch up
+    catch_up_error: Option<String>,
+}
+
+impl IndexerCatchUpActor {
+    pub fn new(
+        executor: ExecutorProxy,
+        sequencer: SequencerProxy,
+        indexer: IndexerProxy,
+        l1_block_body_loader: Option<Arc<dyn L1BlockBodyLoader>>,
+        service_status: ServiceStatus,
+        max_txs: u64,
+        registry: &Registry,
+    ) -> Self {
+        Self {
+            executor,
+            sequencer,
+            indexer,
+            l1_block_body_loader,
+            service_status,
+            max_txs,
+            metrics: Arc::new(IndexerCatchUpMetrics::new(registry)),
+            catch_up_error: None,
+        }
+    }
+
+    /// Index the txs missed by the indexer, such as the txs failed to update the indexer.
+    /// The indexer does not store the state changes, so the txs are re-executed to re-derive them.
+    /// Return the number of the indexed txs.
+    pub async fn catch_up_indexer(&mut self) -> Result<u64> {
+        let result = self.index_missed_txs().await;
+        self.catch_up_error = result.as_ref().err().map(|e| e.to_string());
+        result
+    }
+
+    async fn index_missed_txs(&mut self) -> Result<u64> {
+        let status = self.get_indexer_status().await?;
+        let last_indexed_order = match status.last_indexed_tx_order {
+            Some(last_indexed_order) => last_indexed_order,
+            // The indexer is not initialized by the genesis, it should be rebuilt
+            None => return Ok(0),
+        };
+        if status.lag == 0 {
+            return Ok(0);
+        }
+        let last_order = status.last_tx_order.min(last_indexed_order + self.max_txs);
+        info!(
+            "Catch up the indexer from tx order {} to {}",
+            last_indexed_order + 1,
+            last_order
+        );
+
+        let (_, pre_execution_info) = self.get_executed_tx(last_indexed_order).await?;
+        let mut pre_root = pre_execution_info.root_metadata();
+        for tx_order in (last_indexed_order + 1)..=last_order {
+            let (ledger_tx, execution_info) = self.get_executed_tx(tx_order).await?;
+            let l1_block_body = match &ledger_tx.data {
+                LedgerTxData::L1Block(block) => match &self.l1_block_body_loader {
+                    Some(loader) => Some(loader.load_block_body(block).await?),
+                    None => {
+                        // The L1 block tx can not be re-executed without the block body,
+                        // skip it instead of stopping the indexer at it.
+                        pre_root = execution_info.root_metadata();
+                        self.indexer
+                            .skip_indexer_tx(tx_order, pre_root.clone())
+                            .await?;
+                        continue;
+                    }
+                },
+                _ => None,
+            };
+            let ReexecuteTransactionResult { moveos_tx, output } = self
+                .executor
+                .reexecute_transaction(ledger_tx.clone(), pre_root, l1_block_body)
+                .await?;
+            ensure!(
+                output.status == execution_info.status
+                    && output.changeset.state_root == execution_info.state_root,
+                "The re-executed tx {} diverges from the execution info, status: {:?}, state root: {:?}, expect status: {:?}, state root: {:?}",
+                tx_order,
+                output.status,
+                output.changeset.state_root,
+                execution_info.status,
+                execution_info.state_root
+            );
+            let events = self.recover_events(tx_order, output.events).await?;
+            pre_root = execution_info.root_metadata();
+            self.indexer
+                .update_indexer_and_wait(
+                    ledger_tx,
+                    execution_info,
+                    moveos_tx,
+                    events,
+                    output.changeset,
+                )
+                .await?;
+            self.metrics
+                .pipeline_processor_indexer_lag
+                .set((status.last_tx_order - tx_order) as i64);
+        }
+        info!("The indexer caught up to tx order {}", last_order);
+        Ok(last_order - last_indexed_order)
+    }
+
+    pub async fn get_indexer_status(&self) -> Result<IndexerStatus> {
+        let last_tx_order = self.sequencer.get_sequencer_order().await?;
+        // Ask the indexer writer to count the queued updates in
+        let last_indexed_tx_order = self.indexer.get_last_indexed_tx_order().await?;
+        let status = IndexerStatus::new(
+            last_tx_order,
+            last_indexed_tx_order,
+            self.catch_up_error.clone(),
+        );
+        self.metrics
+            .pipeline_processor_indexer_lag
+            .set(status.lag as i64);
+        Ok(status)
+    }
+
+    async fn get_executed_tx(
+        &self,
+        tx_order: u64,
+    ) -> Result<(LedgerTransaction, TransactionExecutionInfo)> {
+        let tx_hash = self
+            .sequencer
+            .get_tx_hashs(vec![tx_order])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow::anyhow!("The tx with order {} should exists", tx_order))?;
+        let ledger_tx = self
+            .sequencer
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("The tx with hash {} should exists", tx_hash))?;
+        let execution_info = self
+            .executor
+            .get_transaction_execution_infos_by_hash(vec![tx_hash])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow::anyhow!("The execution info of tx {} should exists", tx_hash))?;
+        Ok((ledger_tx, execution_info))
+    }
+
+    /// Recover the event ids of the re-executed tx, the event seqs continue from the indexed events,
+    /// and the recovered events are checked with the events saved when the tx executed.
+    /// The events of the skipped txs are not indexed, so the event seqs are found in the saved events after them.
+    async fn recover_events(
+        &self,
+        tx_order: u64,
+        tx_events: Vec<TransactionEvent>,
+    ) -> Result<Vec<Event>> {
+        if tx_events.is_empty() {
+            return Ok(vec![]);
+        }
+        let event_handle_ids = tx_events
+            .iter()
+            .map(|event| EventHandle::derive_event_handle_id(&event.event_type))
+            .collect::<HashSet<_>>();
+        let mut next_event_seqs = self
+            .indexer
+            .query_last_event_seqs(event_handle_ids.into_iter().collect())
+            .await?
+            .into_iter()
+            .map(|(event_handle_id, event_seq)| (event_handle_id, event_seq + 1))
+            .collect::<HashMap<_, _>>();
+        let events = tx_events
+            .into_iter()
+            .map(|event| {
+                let event_handle_id = EventHandle::derive_event_handle_id(&event.event_type);
+                let event_seq = next_event_seqs.entry(event_handle_id.clone()).or_insert(0);
+                let event_id = EventID::new(event_handle_id, *event_seq);
+                *event_seq += 1;
+                Event::new(
+                    event_id,
+                    event.event_type,
+                    event.event_data,
+                    event.event_index,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let saved_events = self
+            .executor
+            .get_events_by_event_ids(events.iter().map(|event| event.event_id.clone()).collect())
+            .await?;
+        for (event, saved_event) in events.iter().zip(saved_events) {
+            if saved_event.map(|saved_event| saved_event.event) != Some(event.clone()) {
+                return self.find_saved_events(tx_order, events).await;
+            }
+        }
+        Ok(events)
+    }
+
+    /// Find the saved events of the re-executed tx in order, the event seq of each event is not less than
+    /// the one continued from the indexed events.
+    async fn find_saved_events(&self, tx_order: u64, events: Vec<Event>) -> Result<Vec<Event>> {
+        let mut min_event_seqs = HashMap::new();
+        let mut saved_events = Vec::with_capacity(events.len());
+        for event in events {
+            let event_handle_id = event.event_id.event_handle_id.clone();
+            let min_event_seq = *min_event_seqs
+                .entry(event_handle_id.clone())
+                .or_insert(event.event_id.event_seq);
+            let mut cursor = min_event_seq.checked_sub(1);
+            let saved_event = loop {
+                let page = self
+                    .executor
+                    .get_events_by_event_handle(
+                        event.event_type.clone(),
+                        cursor,
+                        EVENT_SEARCH_PAGE_SIZE,
+                        false,
+                    )
+                    .await?;
+                let Some(last_event) = page.last() else {
+                    break None;
+                };
+                cursor = Some(last_event.event_id.event_seq);
+                if let Some(saved_event) = page.into_iter().find(|saved_event| {
+                    saved_event.event_type == event.event_type
+                        && saved_event.event_data == event.event_data
+                        && saved_event.event_index == event.event_index
+                }) {
+                    break Some(saved_event);
+                }
+            };
+            let saved_event = saved_event.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The event {:?} of the re-executed tx {} is not found in the saved events",
+                    event.event_id,
+                    tx_order
+                )
+            })?;
+            min_event_seqs.insert(event_handle_id, saved_event.event_id.event_seq + 1);
+            saved_events.push(saved_event);
+        }
+        Ok(saved_events)
+    }
+}
+
+impl Actor for IndexerCatchUpActor {}
+
+#[async_trait]
+impl Handler<CatchUpIndexerTick> for IndexerCatchUpActor {
+    async fn handle(&mut self, _msg: CatchUpIndexerTick, _ctx: &mut ActorContext) {
+        // The indexer is not updated in the other modes, only the lag is reported
+        if !self.service_status.is_active() {
+            if let Err(e) = self.get_indexer_status().await {
+                log::error!("Get indexer status error: {}", e);
+            }
+            return;
+        }
+        if let Err(e) = self.catch_up_indexer().await {
+            log::error!("Catch up indexer error: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<GetIndexerStatusMessage> for IndexerCatchUpActor {
+    async fn handle(
+        &mut self,
+        _msg: GetIndexerStatusMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<IndexerStatus> {
+        self.get_indexer_status().await
+    }
+}
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use coerce::actor::message::Message;
+use coerce::actor::{message::Message, scheduler::timer::TimerTick};
+use rooch_types::indexer::IndexerStatus;
 use rooch_types::transaction::{
-    ExecuteTransactionResponse, L1BlockWithBody, L1Transaction, RoochTransaction,
+    ExecuteTransactionResponse, ExecutedTransaction, L1BlockWithBody, L1Transaction,
+    RoochTransaction,
 };
+use std::sync::Arc;
+use tokio::sync::broadcast;
 
 #[derive(Clone)]
 pub struct ExecuteL2TxMessage {
@@ -33,3 +37,49 @@ pub struct ExecuteL1TxMessage {
 impl Message for ExecuteL1TxMessage {
     type Result = Result<ExecuteTransactionResponse>;
 }
+
+/// Execute the L1 transactions of a L1 block as a batch
+#[derive(Clone)]
+pub struct ExecuteL1TxsMessage {
+    pub txs: Vec<L1Transaction>,
+}
+
+impl Message for ExecuteL1TxsMessage {
+    type Result = Result<Vec<ExecuteTransactionResponse>>;
+}
+
+/// Trigger the pipeline processor to submit the block commitments to the on-chain SCC
+#[derive(Clone)]
+pub struct CommitBlockTick {}
+
+impl Message for CommitBlockTick {
+    type Result = ();
+}
+
+impl TimerTick for CommitBlockTick {}
+
+/// Trigger the indexer catch up actor to index the txs missed by the indexer
+#[derive(Clone)]
+pub struct CatchUpIndexerTick {}
+
+impl Message for CatchUpIndexerTick {
+    type Result = ();
+}
+
+impl TimerTick for CatchUpIndexerTick {}
+
+/// Get the indexer progress compared with the sequencer
+#[derive(Clone)]
+pub struct GetIndexerStatusMessage {}
+
+impl Message for GetIndexerStatusMessage {
+    type Result = Result<IndexerStatus>;
+}
+
+/// Subscribe to the transactions executed by the pipeline processor
+#[derive(Clone)]
+pub struct SubscribeTransactionsMessage {}
+
+impl Message for SubscribeTransactionsMessage {
+    type Result = broadcast::Receiver<Arc<ExecutedTransaction>>;
+}
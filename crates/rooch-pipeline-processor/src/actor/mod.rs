@@ -1,5 +1,6 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod indexer_catch_up;
 pub mod messages;
 pub mod processor;
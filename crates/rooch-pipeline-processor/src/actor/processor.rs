@@ -1,28 +1,50 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::messages::{ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxMessage};
+use super::messages::{
+    CommitBlockTick, ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL1TxsMessage,
+    ExecuteL2TxMessage, SubscribeTransactionsMessage,
+};
 use crate::metrics::PipelineProcessorMetrics;
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Context, Result};
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use function_name::named;
-use moveos_types::transaction::VerifiedMoveOSTransaction;
+use move_core_types::vm_status::{KeptVMStatus, VMStatus};
+use moveos_types::access_path::AccessPath;
+use moveos_types::module_binding::ModuleBinding;
+use moveos_types::move_std::option::MoveOption;
+use moveos_types::transaction::{
+    MoveAction, TransactionExecutionInfo, TransactionOutput, VerifiedMoveOSTransaction,
+};
 use prometheus::Registry;
 use rooch_executor::proxy::ExecutorProxy;
 use rooch_indexer::proxy::IndexerProxy;
 use rooch_proposer::proxy::ProposerProxy;
 use rooch_sequencer::proxy::SequencerProxy;
+use rooch_types::block::SignedBlockCommitment;
+use rooch_types::error::SequencedTransactionError;
+use rooch_types::framework::state_commitment_chain::StateCommitmentChainModule;
 use rooch_types::transaction::TransactionSequenceInfoV1;
 use rooch_types::{
     service_status::ServiceStatus,
     transaction::{
-        ExecuteTransactionResponse, L1BlockWithBody, L1Transaction, LedgerTransaction,
-        LedgerTxData, RoochTransaction,
+        ExecuteTransactionResponse, ExecutedTransaction, L1BlockWithBody, L1Transaction,
+        LedgerTransaction, LedgerTxData, RoochTransaction,
     },
 };
 use std::sync::Arc;
-use tracing::{debug, info};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// How many executed transactions are buffered for a slow subscriber before it lags behind
+const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 1024;
+/// The first retry delay after submitting the block commitments failed, doubled on each failure in a row
+const COMMIT_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+const COMMIT_RETRY_MAX_DELAY: Duration = Duration::from_secs(600);
+/// Stop submitting the block commitments after the submission failed so many times in a row
+const COMMIT_MAX_ATTEMPTS: u64 = 10;
 
 /// PipelineProcessor aggregates the executor, sequencer, proposer, and indexer to process transactions.
 pub struct PipelineProcessorActor {
@@ -32,6 +54,25 @@ pub struct PipelineProcessorActor {
     pub(crate) indexer: IndexerProxy,
     pub(crate) service_status: ServiceStatus,
     pub(crate) metrics: Arc<PipelineProcessorMetrics>,
+    pub(crate) subscribers: broadcast::Sender<Arc<ExecutedTransaction>>,
+    pub(crate) block_commit_state: BlockCommitState,
+    /// Execute the L1 transactions of a L1 block in parallel
+    pub(crate) parallel_execution: bool,
+}
+
+/// The state of submitting the block commitments to the on-chain SCC
+#[derive(Default)]
+pub(crate) struct BlockCommitState {
+    /// The framework released before the SCC module does not include it,
+    /// the commitments are kept until the framework is upgraded.
+    scc_module_exists: bool,
+    scc_module_missing_warned: bool,
+    /// How many times submitting the commitments failed in a row
+    consecutive_failures: u64,
+    next_retry_at: Option<Instant>,
+    /// The submission is stopped after the on-chain SCC rejected a commitment or too many failures,
+    /// the node needs to be restarted after the cause is fixed.
+    halted: bool,
 }
 
 impl PipelineProcessorActor {
@@ -41,6 +82,7 @@ impl PipelineProcessorActor {
         proposer: ProposerProxy,
         indexer: IndexerProxy,
         service_status: ServiceStatus,
+        parallel_execution: bool,
         registry: &Registry,
     ) -> Self {
         Self {
@@ -50,6 +92,9 @@ impl PipelineProcessorActor {
             indexer,
             service_status,
             metrics: Arc::new(PipelineProcessorMetrics::new(registry)),
+            subscribers: broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY).0,
+            block_commit_state: BlockCommitState::default(),
+            parallel_execution,
         }
     }
 
@@ -110,12 +155,22 @@ impl PipelineProcessorActor {
                     let moveos_tx = self.executor.validate_l2_tx(l2_tx.clone()).await?;
                     self.execute_tx(ledger_tx.clone(), moveos_tx).await?;
                 }
+                LedgerTxData::BlockCommitment(signed_commitment) => {
+                    debug!(
+                        "process_sequenced_tx_on_startup block_commitment: {:?}",
+                        signed_commitment.commitment
+                    );
+                    let moveos_tx = self
+                        .executor
+                        .validate_block_commitment(signed_commitment.clone())
+                        .await?;
+                    self.execute_tx(ledger_tx.clone(), moveos_tx).await?;
+                }
             }
         }
         Ok(())
     }
 
-    #[named]
     pub async fn execute_l1_block(
         &mut self,
         l1_block: L1BlockWithBody,
@@ -175,6 +230,76 @@ impl PipelineProcessorActor {
         Ok(result)
     }
 
+    /// Execute the L1 transactions of a L1 block. If the parallel execution is enabled,
+    /// the transactions are sequenced first and executed as a batch in parallel,
+    /// otherwise they are executed one by one.
+    /// The transactions which fail to validate are skipped, the same as executing them one by one.
+    #[named]
+    pub async fn execute_l1_txs(
+        &mut self,
+        l1_txs: Vec<L1Transaction>,
+    ) -> Result<Vec<ExecuteTransactionResponse>> {
+        if !self.parallel_execution {
+            let mut responses = Vec::with_capacity(l1_txs.len());
+            for l1_tx in l1_txs {
+                let l1_tx_desc = format!("{:?}", l1_tx);
+                match self.execute_l1_tx(l1_tx).await {
+                    Ok(response) => responses.push(response),
+                    Err(e) => warn!("Execute l1 tx {} error: {:?}", l1_tx_desc, e),
+                }
+            }
+            return Ok(responses);
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .pipeline_processor_execution_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut validated_txs = Vec::with_capacity(l1_txs.len());
+        for l1_tx in l1_txs {
+            match self.executor.validate_l1_tx(l1_tx.clone()).await {
+                Ok(moveos_tx) => validated_txs.push((l1_tx, moveos_tx)),
+                Err(e) => warn!("Validate l1 tx {:?} error: {:?}", l1_tx, e),
+            }
+        }
+        // The sequenced txs must be executed even if the sequencing of a later tx fails
+        let mut txs = Vec::with_capacity(validated_txs.len());
+        let mut sequence_error = None;
+        for (l1_tx, moveos_tx) in validated_txs {
+            match self
+                .sequencer
+                .sequence_transaction(LedgerTxData::L1Tx(l1_tx))
+                .await
+            {
+                Ok(ledger_tx) => txs.push((ledger_tx, moveos_tx)),
+                Err(e) => {
+                    sequence_error = Some(e);
+                    break;
+                }
+            }
+        }
+        let size: u64 = txs.iter().map(|(_, moveos_tx)| moveos_tx.ctx.tx_size).sum();
+        let responses = self.execute_txs(txs).await?;
+        if let Some(e) = sequence_error {
+            return Err(e);
+        }
+
+        let gas_used = responses
+            .iter()
+            .map(|response| response.output.gas_used)
+            .sum();
+        self.metrics
+            .pipeline_processor_l1_tx_gas_used
+            .inc_by(gas_used);
+        self.metrics
+            .pipeline_processor_execution_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
+        Ok(responses)
+    }
+
     #[named]
     pub async fn execute_l2_tx(
         &mut self,
@@ -193,7 +318,12 @@ impl PipelineProcessorActor {
             .sequence_transaction(LedgerTxData::L2Tx(tx))
             .await?;
         let size = moveos_tx.ctx.tx_size;
-        let result = self.execute_tx(ledger_tx, moveos_tx).await?;
+        // The mempool needs to know whether the sequence number of the tx is used
+        let tx_order = ledger_tx.sequence_info.tx_order;
+        let result = self
+            .execute_tx(ledger_tx, moveos_tx)
+            .await
+            .context(SequencedTransactionError { tx_order })?;
 
         let gas_used = result.output.gas_used;
         self.metrics
@@ -207,6 +337,158 @@ impl PipelineProcessorActor {
         Ok(result)
     }
 
+    #[named]
+    pub async fn execute_block_commitment(
+        &mut self,
+        signed_commitment: SignedBlockCommitment,
+    ) -> Result<ExecuteTransactionResponse> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .pipeline_processor_execution_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let moveos_tx = self
+            .executor
+            .validate_block_commitment(signed_commitment.clone())
+            .await?;
+        let ledger_tx = self
+            .sequencer
+            .sequence_transaction(LedgerTxData::BlockCommitment(signed_commitment))
+            .await?;
+        let size = moveos_tx.ctx.tx_size;
+        let result = self.execute_tx(ledger_tx, moveos_tx).await?;
+
+        self.metrics
+            .pipeline_processor_execution_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
+        Ok(result)
+    }
+
+    /// Submit the commitments of the proposed blocks to the on-chain SCC.
+    /// The transient failures are retried with backoff, at most `COMMIT_MAX_ATTEMPTS` times in a row.
+    /// A commitment rejected by the on-chain SCC is deterministic, retrying it only sequences more failed txs,
+    /// so the submission is stopped and the error is surfaced.
+    pub async fn commit_blocks(&mut self) -> Result<()> {
+        if self.block_commit_state.halted {
+            return Ok(());
+        }
+        if let Some(next_retry_at) = self.block_commit_state.next_retry_at {
+            if Instant::now() < next_retry_at {
+                return Ok(());
+            }
+        }
+        let result = self.submit_block_commitments().await;
+        let state = &mut self.block_commit_state;
+        match result {
+            Ok(()) => {
+                state.consecutive_failures = 0;
+                state.next_retry_at = None;
+                Ok(())
+            }
+            Err(e) if state.halted => Err(e),
+            Err(e) => {
+                state.consecutive_failures += 1;
+                if state.consecutive_failures >= COMMIT_MAX_ATTEMPTS {
+                    state.halted = true;
+                    return Err(e.context(format!(
+                        "Submit the block commitments failed {} times in a row, stop submitting",
+                        state.consecutive_failures
+                    )));
+                }
+                let exp = (state.consecutive_failures - 1) as u32;
+                let delay = COMMIT_RETRY_BASE_DELAY
+                    .saturating_mul(2u32.saturating_pow(exp))
+                    .min(COMMIT_RETRY_MAX_DELAY);
+                state.next_retry_at = Some(Instant::now() + delay);
+                Err(e.context(format!(
+                    "Submit the block commitments failed {} times in a row, retry in {:?}",
+                    state.consecutive_failures, delay
+                )))
+            }
+        }
+    }
+
+    /// Only the commitments executed successfully are marked as committed,
+    /// the on-chain SCC requires the blocks in order, so the later commitments wait for the failed one.
+    async fn submit_block_commitments(&mut self) -> Result<()> {
+        if !self.check_scc_module().await? {
+            return Ok(());
+        }
+        let onchain_last_block_number = self.get_onchain_last_block_number().await?;
+        let signed_commitments = self.proposer.get_block_commitments().await?;
+        for signed_commitment in signed_commitments {
+            let block_number = signed_commitment.commitment.block_number;
+            // The commitment is executed but the node stopped before marking it as committed
+            if onchain_last_block_number.map_or(false, |last| block_number <= last) {
+                self.proposer.block_committed(block_number).await?;
+                continue;
+            }
+            let result = self.execute_block_commitment(signed_commitment).await?;
+            if result.execution_info.status != KeptVMStatus::Executed {
+                self.block_commit_state.halted = true;
+                self.metrics
+                    .pipeline_processor_block_commitment_rejected
+                    .inc();
+                return Err(anyhow!(
+                    "Block {} commitment is rejected by the on-chain SCC, status: {:?}, stop submitting the block commitments until the node is restarted",
+                    block_number,
+                    result.execution_info.status
+                ));
+            }
+            self.block_commit_state.consecutive_failures = 0;
+            info!("Block {} commitment is submitted", block_number);
+            self.proposer.block_committed(block_number).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_onchain_last_block_number(&self) -> Result<Option<u128>> {
+        let result = self
+            .executor
+            .execute_view_function(
+                StateCommitmentChainModule::create_last_block_number_call(),
+                None,
+            )
+            .await?;
+        ensure!(
+            result.vm_status == VMStatus::Executed,
+            "Get the last block number of the on-chain SCC failed: {:?}",
+            result.vm_status
+        );
+        let value = result
+            .return_values
+            .and_then(|mut values| values.pop())
+            .ok_or_else(|| anyhow!("The last block number of the on-chain SCC should return"))?;
+        Ok(bcs::from_bytes::<MoveOption<u128>>(&value.value.value)?.into())
+    }
+
+    /// Check whether the on-chain SCC module exists
+    async fn check_scc_module(&mut self) -> Result<bool> {
+        if self.block_commit_state.scc_module_exists {
+            return Ok(true);
+        }
+        let module_id = StateCommitmentChainModule::module_id();
+        let exists = self
+            .executor
+            .get_states(AccessPath::module(&module_id), None)
+            .await?
+            .pop()
+            .flatten()
+            .is_some();
+        if exists {
+            self.block_commit_state.scc_module_exists = true;
+        } else if !self.block_commit_state.scc_module_missing_warned {
+            warn!(
+                "The on-chain SCC module {} does not exist, the block commitments are submitted after the framework is upgraded",
+                module_id.short_str_lossless()
+            );
+            self.block_commit_state.scc_module_missing_warned = true;
+        }
+        Ok(exists)
+    }
+
     #[named]
     pub async fn execute_tx(
         &mut self,
@@ -219,16 +501,75 @@ impl PipelineProcessorActor {
             .pipeline_processor_execution_tx_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
+        Self::add_sequence_info(&tx, &mut moveos_tx)?;
+
+        // Then execute
+        let size = moveos_tx.ctx.tx_size;
+        let (output, execution_info) = self.executor.execute_transaction(moveos_tx.clone()).await?;
+        let response = self
+            .handle_executed_tx(tx, moveos_tx, output, execution_info)
+            .await?;
+
+        self.metrics
+            .pipeline_processor_execution_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
+        Ok(response)
+    }
+
+    /// Execute the sequenced transactions as a batch in parallel,
+    /// the results are the same as executing them one by one.
+    pub async fn execute_txs(
+        &mut self,
+        txs: Vec<(LedgerTransaction, VerifiedMoveOSTransaction)>,
+    ) -> Result<Vec<ExecuteTransactionResponse>> {
+        if txs.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut ledger_txs = Vec::with_capacity(txs.len());
+        let mut moveos_txs = Vec::with_capacity(txs.len());
+        for (tx, mut moveos_tx) in txs {
+            Self::add_sequence_info(&tx, &mut moveos_tx)?;
+            ledger_txs.push(tx);
+            moveos_txs.push(moveos_tx);
+        }
+        let results = self
+            .executor
+            .execute_transactions(moveos_txs.clone())
+            .await?;
+        let mut responses = Vec::with_capacity(results.len());
+        for ((tx, moveos_tx), (output, execution_info)) in
+            ledger_txs.into_iter().zip(moveos_txs).zip(results)
+        {
+            responses.push(
+                self.handle_executed_tx(tx, moveos_tx, output, execution_info)
+                    .await?,
+            );
+        }
+        Ok(responses)
+    }
+
+    fn add_sequence_info(
+        tx: &LedgerTransaction,
+        moveos_tx: &mut VerifiedMoveOSTransaction,
+    ) -> Result<()> {
         // Add sequence info to tx context, let the Move contract can get the sequence info
         moveos_tx.ctx.add(tx.sequence_info.clone())?;
         // We must add TransactionSequenceInfo and TransactionSequenceInfoV1 both to the tx_context because the rust code is upgraded first, then the framework is upgraded.
         // The old framework will read the TransactionSequenceInfoV1.
         let tx_sequence_info_v1 = TransactionSequenceInfoV1::from(tx.sequence_info.clone());
         moveos_tx.ctx.add(tx_sequence_info_v1)?;
+        Ok(())
+    }
 
-        // Then execute
-        let size = moveos_tx.ctx.tx_size;
-        let (output, execution_info) = self.executor.execute_transaction(moveos_tx.clone()).await?;
+    /// Propose, publish and index the executed transaction
+    async fn handle_executed_tx(
+        &mut self,
+        tx: LedgerTransaction,
+        moveos_tx: VerifiedMoveOSTransaction,
+        output: TransactionOutput,
+        execution_info: TransactionExecutionInfo,
+    ) -> Result<ExecuteTransactionResponse> {
         self.proposer
             .propose_transaction(tx.clone(), execution_info.clone())
             .await?;
@@ -238,6 +579,19 @@ impl PipelineProcessorActor {
             .refresh_state(root.clone(), output.is_upgrade)
             .await?;
 
+        // Publish the executed transaction to the subscribers before moving it to the indexer
+        if self.subscribers.receiver_count() > 0 {
+            let executed_tx = ExecutedTransaction {
+                transaction: tx.clone(),
+                execution_info: execution_info.clone(),
+                output: output.clone(),
+                tx_context: moveos_tx.ctx.clone(),
+                action: MoveAction::from(moveos_tx.action.clone()),
+            };
+            // The send fails only if all subscribers are gone
+            let _ = self.subscribers.send(Arc::new(executed_tx));
+        }
+
         let indexer = self.indexer.clone();
         let sequence_info = tx.sequence_info.clone();
         let execution_info_clone = execution_info.clone();
@@ -261,11 +615,6 @@ impl PipelineProcessorActor {
             };
         };
 
-        self.metrics
-            .pipeline_processor_execution_tx_bytes
-            .with_label_values(&[fn_name])
-            .observe(size as f64);
-
         Ok(ExecuteTransactionResponse {
             sequence_info,
             execution_info,
@@ -309,3 +658,37 @@ impl Handler<ExecuteL1TxMessage> for PipelineProcessorActor {
         self.execute_l1_tx(msg.tx).await
     }
 }
+
+#[async_trait]
+impl Handler<ExecuteL1TxsMessage> for PipelineProcessorActor {
+    async fn handle(
+        &mut self,
+        msg: ExecuteL1TxsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<ExecuteTransactionResponse>> {
+        self.execute_l1_txs(msg.txs).await
+    }
+}
+
+#[async_trait]
+impl Handler<CommitBlockTick> for PipelineProcessorActor {
+    async fn handle(&mut self, _msg: CommitBlockTick, _ctx: &mut ActorContext) {
+        if !self.service_status.is_active() {
+            return;
+        }
+        if let Err(e) = self.commit_blocks().await {
+            log::error!("Submit block commitments error: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<SubscribeTransactionsMessage> for PipelineProcessorActor {
+    async fn handle(
+        &mut self,
+        _msg: SubscribeTransactionsMessage,
+        _ctx: &mut ActorContext,
+    ) -> broadcast::Receiver<Arc<ExecutedTransaction>> {
+        self.subscribers.subscribe()
+    }
+}
@@ -3,8 +3,8 @@
 
 use metrics::metrics_util::LATENCY_SEC_BUCKETS;
 use prometheus::{
-    register_histogram_vec_with_registry, register_int_counter_with_registry, HistogramVec,
-    IntCounter, Registry,
+    register_histogram_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, HistogramVec, IntCounter, IntGauge, Registry,
 };
 
 #[derive(Debug)]
@@ -14,6 +14,7 @@ pub struct PipelineProcessorMetrics {
     pub pipeline_processor_l1_block_gas_used: IntCounter,
     pub pipeline_processor_l1_tx_gas_used: IntCounter,
     pub pipeline_processor_l2_tx_gas_used: IntCounter,
+    pub pipeline_processor_block_commitment_rejected: IntCounter,
 }
 
 impl PipelineProcessorMetrics {
@@ -55,6 +56,30 @@ impl PipelineProcessorMetrics {
                 registry,
             )
             .unwrap(),
+            pipeline_processor_block_commitment_rejected: register_int_counter_with_registry!(
+                "pipeline_processor_block_commitment_rejected",
+                "Pipeline processor block commitments rejected by the on-chain SCC total",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IndexerCatchUpMetrics {
+    pub pipeline_processor_indexer_lag: IntGauge,
+}
+
+impl IndexerCatchUpMetrics {
+    pub(crate) fn new(registry: &Registry) -> Self {
+        IndexerCatchUpMetrics {
+            pipeline_processor_indexer_lag: register_int_gauge_with_registry!(
+                "pipeline_processor_indexer_lag",
+                "Pipeline processor indexer lag, the number of the sequenced txs not indexed yet",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
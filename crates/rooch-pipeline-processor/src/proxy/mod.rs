@@ -2,23 +2,38 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::{
-    messages::{ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxMessage},
+    indexer_catch_up::IndexerCatchUpActor,
+    messages::{
+        ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL1TxsMessage, ExecuteL2TxMessage,
+        GetIndexerStatusMessage, SubscribeTransactionsMessage,
+    },
     processor::PipelineProcessorActor,
 };
 use anyhow::Result;
 use coerce::actor::ActorRef;
+use rooch_types::indexer::IndexerStatus;
 use rooch_types::transaction::{
-    rooch::RoochTransaction, ExecuteTransactionResponse, L1BlockWithBody, L1Transaction,
+    rooch::RoochTransaction, ExecuteTransactionResponse, ExecutedTransaction, L1BlockWithBody,
+    L1Transaction,
 };
+use std::sync::Arc;
+use tokio::sync::broadcast;
 
 #[derive(Clone)]
 pub struct PipelineProcessorProxy {
     pub actor: ActorRef<PipelineProcessorActor>,
+    pub indexer_catch_up_actor: ActorRef<IndexerCatchUpActor>,
 }
 
 impl PipelineProcessorProxy {
-    pub fn new(actor: ActorRef<PipelineProcessorActor>) -> Self {
-        Self { actor }
+    pub fn new(
+        actor: ActorRef<PipelineProcessorActor>,
+        indexer_catch_up_actor: ActorRef<IndexerCatchUpActor>,
+    ) -> Self {
+        Self {
+            actor,
+            indexer_catch_up_actor,
+        }
     }
 
     pub async fn execute_l2_tx(&self, tx: RoochTransaction) -> Result<ExecuteTransactionResponse> {
@@ -35,10 +50,24 @@ impl PipelineProcessorProxy {
     pub async fn execute_l1_tx(&self, tx: L1Transaction) -> Result<ExecuteTransactionResponse> {
         self.actor.send(ExecuteL1TxMessage { tx }).await?
     }
-}
 
-impl From<ActorRef<PipelineProcessorActor>> for PipelineProcessorProxy {
-    fn from(actor: ActorRef<PipelineProcessorActor>) -> Self {
-        Self::new(actor)
+    pub async fn execute_l1_txs(
+        &self,
+        txs: Vec<L1Transaction>,
+    ) -> Result<Vec<ExecuteTransactionResponse>> {
+        self.actor.send(ExecuteL1TxsMessage { txs }).await?
+    }
+
+    pub async fn get_indexer_status(&self) -> Result<IndexerStatus> {
+        self.indexer_catch_up_actor
+            .send(GetIndexerStatusMessage {})
+            .await?
+    }
+
+    /// Subscribe to the executed transactions, the receiver lags if it can not keep up
+    pub async fn subscribe_transactions(
+        &self,
+    ) -> Result<broadcast::Receiver<Arc<ExecutedTransaction>>> {
+        Ok(self.actor.send(SubscribeTransactionsMessage {}).await?)
     }
 }
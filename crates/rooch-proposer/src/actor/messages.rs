@@ -4,6 +4,7 @@
 use anyhow::Result;
 use coerce::actor::{message::Message, scheduler::timer::TimerTick};
 use moveos_types::transaction::TransactionExecutionInfo;
+use rooch_types::block::{Block, SignedBlockCommitment};
 use rooch_types::transaction::LedgerTransaction;
 
 /// Transaction Propose Message
@@ -30,3 +31,39 @@ impl Message for ProposeBlock {
 }
 
 impl TimerTick for ProposeBlock {}
+
+/// Get the proposed block by block number
+#[derive(Debug)]
+pub struct GetBlockMessage {
+    pub block_number: u128,
+}
+
+impl Message for GetBlockMessage {
+    type Result = Result<Option<Block>>;
+}
+
+/// Get the latest proposed block
+#[derive(Debug)]
+pub struct GetLatestBlockMessage {}
+
+impl Message for GetLatestBlockMessage {
+    type Result = Result<Option<Block>>;
+}
+
+/// Get the signed commitments of the blocks which are not submitted to the on-chain SCC yet
+#[derive(Debug)]
+pub struct GetBlockCommitmentsMessage {}
+
+impl Message for GetBlockCommitmentsMessage {
+    type Result = Result<Vec<SignedBlockCommitment>>;
+}
+
+/// Notify the proposer the block commitment has been submitted to the on-chain SCC
+#[derive(Debug)]
+pub struct BlockCommittedMessage {
+    pub block_number: u128,
+}
+
+impl Message for BlockCommittedMessage {
+    type Result = Result<()>;
+}
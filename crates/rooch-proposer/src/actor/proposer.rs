@@ -9,11 +9,16 @@ use std::sync::Arc;
 
 use crate::metrics::ProposerMetrics;
 use rooch_da::proxy::DAProxy;
+use rooch_store::RoochStore;
+use rooch_types::block::{Block, SignedBlockCommitment};
 use rooch_types::crypto::RoochKeyPair;
 
 use crate::scc::StateCommitmentChain;
 
-use super::messages::{ProposeBlock, TransactionProposeMessage, TransactionProposeResult};
+use super::messages::{
+    BlockCommittedMessage, GetBlockCommitmentsMessage, GetBlockMessage, GetLatestBlockMessage,
+    ProposeBlock, TransactionProposeMessage, TransactionProposeResult,
+};
 
 const TRANSACTION_PROPOSE_FN_NAME: &str = "transaction_propose";
 const PROPOSE_BLOCK_FN_NAME: &str = "propose_block";
@@ -25,12 +30,17 @@ pub struct ProposerActor {
 }
 
 impl ProposerActor {
-    pub fn new(proposer_key: RoochKeyPair, da_proxy: DAProxy, registry: &Registry) -> Self {
-        Self {
+    pub fn new(
+        proposer_key: RoochKeyPair,
+        da_proxy: DAProxy,
+        rooch_store: RoochStore,
+        registry: &Registry,
+    ) -> Result<Self> {
+        Ok(Self {
             proposer_key,
-            scc: StateCommitmentChain::new(da_proxy),
+            scc: StateCommitmentChain::new(da_proxy, rooch_store)?,
             metrics: Arc::new(ProposerMetrics::new(registry)),
-        }
+        })
     }
 }
 
@@ -49,7 +59,7 @@ impl Handler<TransactionProposeMessage> for ProposerActor {
             .proposer_transaction_propose_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
-        self.scc.append_transaction(msg);
+        self.scc.append_transaction(msg)?;
         Ok(TransactionProposeResult {})
     }
 }
@@ -76,11 +86,54 @@ impl Handler<ProposeBlock> for ProposerActor {
                 log::debug!("[ProposeBlock] no transaction to propose block");
             }
         };
-        //TODO submit to the on-chain SCC contract use the proposer key
-        let _proposer_key = &self.proposer_key;
+        // the block commitment is signed and submitted to the on-chain SCC by the pipeline processor
         let batch_size = block.map(|v| v.batch_size).unwrap_or(0u64);
         self.metrics
             .proposer_propose_block_batch_size
             .set(batch_size as i64);
     }
 }
+
+#[async_trait]
+impl Handler<GetBlockMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        msg: GetBlockMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Block>> {
+        self.scc.get_block(msg.block_number)
+    }
+}
+
+#[async_trait]
+impl Handler<GetLatestBlockMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        _msg: GetLatestBlockMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<Block>> {
+        Ok(self.scc.last_block().cloned())
+    }
+}
+
+#[async_trait]
+impl Handler<GetBlockCommitmentsMessage> for ProposerActor {
+    async fn handle(
+        &mut self,
+        _msg: GetBlockCommitmentsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<SignedBlockCommitment>> {
+        self.scc
+            .get_uncommitted_blocks()?
+            .into_iter()
+            .map(|block| block.commitment().sign(&self.proposer_key))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Handler<BlockCommittedMessage> for ProposerActor {
+    async fn handle(&mut self, msg: BlockCommittedMessage, _ctx: &mut ActorContext) -> Result<()> {
+        self.scc.block_committed(msg.block_number)
+    }
+}
@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::{
-    messages::{TransactionProposeMessage, TransactionProposeResult},
+    messages::{
+        BlockCommittedMessage, GetBlockCommitmentsMessage, GetBlockMessage, GetLatestBlockMessage,
+        TransactionProposeMessage, TransactionProposeResult,
+    },
     proposer::ProposerActor,
 };
 use anyhow::Result;
 use coerce::actor::ActorRef;
 use moveos_types::transaction::TransactionExecutionInfo;
+use rooch_types::block::{Block, SignedBlockCommitment};
 use rooch_types::transaction::LedgerTransaction;
 
 #[derive(Clone)]
@@ -32,4 +36,22 @@ impl ProposerProxy {
             })
             .await?
     }
+
+    pub async fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.actor.send(GetBlockMessage { block_number }).await?
+    }
+
+    pub async fn get_latest_block(&self) -> Result<Option<Block>> {
+        self.actor.send(GetLatestBlockMessage {}).await?
+    }
+
+    pub async fn get_block_commitments(&self) -> Result<Vec<SignedBlockCommitment>> {
+        self.actor.send(GetBlockCommitmentsMessage {}).await?
+    }
+
+    pub async fn block_committed(&self, block_number: u128) -> Result<()> {
+        self.actor
+            .send(BlockCommittedMessage { block_number })
+            .await?
+    }
 }
@@ -1,56 +1,141 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
-
 use crate::actor::messages::TransactionProposeMessage;
+use anyhow::Result;
 use moveos_types::h256;
 use moveos_types::h256::H256;
 use rooch_da::messages::Batch;
 use rooch_da::proxy::DAProxy;
+use rooch_store::proposer_store::ProposerStore;
+use rooch_store::RoochStore;
 use rooch_types::block::Block;
+use rooch_types::transaction::LedgerTransaction;
+
+/// The max number of block commitments to submit at a time
+const MAX_COMMITMENTS_PER_SUBMIT: u128 = 10;
 
 /// State Commitment Chain(SCC) is a chain of transaction state root
 /// This SCC is a mirror of the on-chain SCC
 pub struct StateCommitmentChain {
-    //TODO save to the storage
-    blocks: BTreeMap<u128, Block>,
+    last_block: Option<Block>,
     buffer: Vec<TransactionProposeMessage>,
     da: DAProxy,
+    rooch_store: RoochStore,
 }
 
 impl StateCommitmentChain {
-    /// Create a new SCC
-    pub fn new(da_proxy: DAProxy) -> Self {
-        Self {
-            blocks: BTreeMap::new(),
-            buffer: Vec::new(),
-            da: da_proxy,
+    /// Create a new SCC, recover the last block and the pending transactions from the storage
+    pub fn new(da_proxy: DAProxy, rooch_store: RoochStore) -> Result<Self> {
+        let last_block = match rooch_store.get_last_block_number()? {
+            Some(block_number) => {
+                Some(rooch_store.get_block(block_number)?.ok_or_else(|| {
+                    anyhow::anyhow!("The last block {} should exists", block_number)
+                })?)
+            }
+            None => None,
+        };
+        let mut pending_txs = rooch_store.get_pending_txs()?;
+        // If the node stopped after the block saved but before the pending transactions removed,
+        // the transactions already in the last block should be dropped.
+        if let Some(block) = &last_block {
+            if let Some(pos) = pending_txs.iter().position(|(tx, _)| {
+                tx.sequence_info.tx_accumulator_root == block.tx_accumulator_root
+            }) {
+                let proposed_txs = pending_txs.drain(..=pos).collect::<Vec<_>>();
+                rooch_store.remove_pending_txs(
+                    proposed_txs
+                        .iter()
+                        .map(|(tx, _)| tx.sequence_info.tx_order)
+                        .collect(),
+                )?;
+            }
+        }
+        let buffer = pending_txs
+            .into_iter()
+            .map(|(tx, tx_execution_info)| TransactionProposeMessage {
+                tx,
+                tx_execution_info,
+            })
+            .collect::<Vec<_>>();
+        if let Some(block) = &last_block {
+            log::info!(
+                "Load latest proposed block number {}, pending transactions: {}",
+                block.block_number,
+                buffer.len()
+            );
         }
+        Ok(Self {
+            last_block,
+            buffer,
+            da: da_proxy,
+            rooch_store,
+        })
     }
 
-    pub fn append_transaction(&mut self, tx: TransactionProposeMessage) {
+    pub fn append_transaction(&mut self, tx: TransactionProposeMessage) -> Result<()> {
+        self.rooch_store
+            .save_pending_tx(tx.tx.clone(), tx.tx_execution_info.clone())?;
         self.buffer.push(tx);
+        Ok(())
     }
 
     /// Append a new block to the SCC
-    fn append_block(&mut self, block: Block) {
-        self.blocks.insert(block.block_number, block);
+    fn append_block(&mut self, block: Block) -> Result<()> {
+        self.rooch_store.save_block(block.clone())?;
+        self.last_block = Some(block);
+        Ok(())
+    }
+
+    /// Get the block by block number
+    pub fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.rooch_store.get_block(block_number)
     }
 
     /// Get the last block of the SCC
     pub fn last_block(&self) -> Option<&Block> {
-        self.blocks.values().last()
+        self.last_block.as_ref()
     }
 
     /// Get the last block number of the SCC
     pub fn last_block_number(&self) -> Option<u128> {
-        self.blocks.keys().last().copied()
+        self.last_block.as_ref().map(|block| block.block_number)
+    }
+
+    /// Get the blocks whose commitments are not submitted to the on-chain SCC yet
+    pub fn get_uncommitted_blocks(&self) -> Result<Vec<Block>> {
+        let last_block_number = match self.last_block_number() {
+            Some(block_number) => block_number,
+            None => return Ok(vec![]),
+        };
+        let start = match self.rooch_store.get_last_committed_block_number()? {
+            Some(block_number) => block_number + 1,
+            None => 0,
+        };
+        let end = last_block_number.min(start.saturating_add(MAX_COMMITMENTS_PER_SUBMIT - 1));
+        (start..=end)
+            .map(|block_number| {
+                self.get_block(block_number)?
+                    .ok_or_else(|| anyhow::anyhow!("The block {} should exists", block_number))
+            })
+            .collect()
+    }
+
+    /// Mark the block commitment has been submitted to the on-chain SCC
+    pub fn block_committed(&self, block_number: u128) -> Result<()> {
+        self.rooch_store
+            .save_last_committed_block_number(block_number)
     }
 
     /// Trigger the proposer to propose a new block
     pub async fn propose_block(&mut self) -> Option<&Block> {
-        if self.buffer.is_empty() {
+        // Every block produces a block commitment transaction,
+        // do not propose a block only for the block commitments, otherwise the proposer never stops.
+        if self
+            .buffer
+            .iter()
+            .all(|tx| tx.tx.data.is_block_commitment())
+        {
             return None;
         }
         // construct a new block from buffer
@@ -79,7 +164,12 @@ impl StateCommitmentChain {
 
         // submit batch to DA server
         // TODO move batch submit out of proposer
-        let batch_data: Vec<u8> = self.buffer.iter().flat_map(|tx| tx.tx.encode()).collect();
+        let txs = self
+            .buffer
+            .iter()
+            .map(|tx| tx.tx.clone())
+            .collect::<Vec<_>>();
+        let batch_data = LedgerTransaction::encode_batch(&txs);
         // regard batch(tx list) as a blob: easy to check integrity
         let batch_hash = h256::sha3_256_of(&batch_data);
         if let Err(e) = self
@@ -102,7 +192,18 @@ impl StateCommitmentChain {
             tx_accumulator_root,
             state_roots,
         );
-        self.append_block(new_block);
+        if let Err(e) = self.append_block(new_block) {
+            log::error!("save block {} failed: {}", block_number, e);
+            return None;
+        }
+        let tx_orders = txs
+            .iter()
+            .map(|tx| tx.sequence_info.tx_order)
+            .collect::<Vec<_>>();
+        // the block is saved, the pending transactions left in storage would be dropped on restart
+        if let Err(e) = self.rooch_store.remove_pending_txs(tx_orders) {
+            log::warn!("remove pending transactions failed: {}", e);
+        }
         self.buffer.clear();
         self.last_block()
     }
@@ -6,10 +6,14 @@ use crate::actor::messages::{
     BroadcastTransactionMessage, GetBestBlockHashMessage, GetBlockHashMessage,
     GetBlockHeaderInfoMessage, GetBlockMessage,
 };
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use async_trait::async_trait;
+use bitcoin::hashes::Hash;
 use bitcoincore_rpc::bitcoin::Txid;
 use bitcoincore_rpc::json;
 use coerce::actor::ActorRef;
+use rooch_pipeline_processor::actor::indexer_catch_up::L1BlockBodyLoader;
+use rooch_types::transaction::L1Block;
 
 use super::messages::GetChainTipsMessage;
 
@@ -61,3 +65,17 @@ impl BitcoinClientProxy {
             .await?
     }
 }
+
+#[async_trait]
+impl L1BlockBodyLoader for BitcoinClientProxy {
+    async fn load_block_body(&self, block: &L1Block) -> Result<Vec<u8>> {
+        ensure!(
+            block.chain_id.is_bitcoin(),
+            "Unsupported L1 block chain: {:?}",
+            block.chain_id
+        );
+        let block_hash = bitcoin::BlockHash::from_slice(&block.block_hash)?;
+        let bitcoin_block = self.get_block(block_hash).await?;
+        Ok(rooch_types::bitcoin::types::Block::from(bitcoin_block).encode())
+    }
+}
@@ -92,19 +92,24 @@ impl RelayerActor {
         Ok(())
     }
 
-    async fn handle_l1_tx(&mut self, l1_tx: L1Transaction) -> Result<()> {
-        let txid = hex::encode(&l1_tx.txid);
-        let result = self.processor.execute_l1_tx(l1_tx).await?;
+    async fn handle_l1_txs(&mut self, l1_txs: Vec<L1Transaction>) -> Result<()> {
+        if l1_txs.is_empty() {
+            return Ok(());
+        }
+        let results = self.processor.execute_l1_txs(l1_txs).await?;
 
-        match result.execution_info.status {
-            KeptVMStatus::Executed => {
-                info!("Relayer execute relay tx(txid: {}) success", txid);
-            }
-            _ => {
-                error!(
-                    "Relayer execute relay tx(txid: {}) failed, status: {:?}",
-                    txid, result.execution_info.status
-                );
+        for result in results {
+            let tx_hash = result.execution_info.tx_hash;
+            match result.execution_info.status {
+                KeptVMStatus::Executed => {
+                    info!("Relayer execute relay tx(hash: {:?}) success", tx_hash);
+                }
+                _ => {
+                    error!(
+                        "Relayer execute relay tx(hash: {:?}) failed, status: {:?}",
+                        tx_hash, result.execution_info.status
+                    );
+                }
             }
         }
         Ok(())
@@ -121,10 +126,8 @@ impl RelayerActor {
             loop {
                 match relayer.get_ready_l1_txs().await {
                     Ok(txs) => {
-                        for tx in txs {
-                            if let Err(err) = self.handle_l1_tx(tx).await {
-                                warn!("Relayer {} error: {:?}", relayer_name, err);
-                            }
+                        if let Err(err) = self.handle_l1_txs(txs).await {
+                            warn!("Relayer {} error: {:?}", relayer_name, err);
                         }
                     }
                     Err(err) => {
@@ -3,18 +3,25 @@
 
 use crate::jsonrpc_types::account_view::BalanceInfoView;
 use crate::jsonrpc_types::address::UnitedAddressView;
-use crate::jsonrpc_types::event_view::{EventFilterView, IndexerEventIDView};
+use crate::jsonrpc_types::block_view::BlockView;
+use crate::jsonrpc_types::da_view::DAStatusView;
+use crate::jsonrpc_types::event_view::{EventFilterView, IndexerEventIDView, IndexerEventView};
+use crate::jsonrpc_types::indexer_view::IndexerStatusView;
+use crate::jsonrpc_types::mempool_view::{MempoolStatusView, PendingTransactionView};
+use crate::jsonrpc_types::proof_view::{StatesWithProofView, TransactionProofView};
 use crate::jsonrpc_types::repair_view::{RepairIndexerParamsView, RepairIndexerTypeView};
 use crate::jsonrpc_types::transaction_view::{TransactionFilterView, TransactionWithInfoView};
 use crate::jsonrpc_types::{
-    AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView,
+    AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView, DryRunOptions,
     DryRunTransactionResponseView, EventOptions, EventPageView, ExecuteTransactionResponseView,
-    FieldKeyView, FunctionCallView, H256View, IndexerEventPageView, IndexerObjectStatePageView,
-    IndexerStateIDView, ModuleABIView, ObjectIDVecView, ObjectIDView, ObjectStateFilterView,
-    ObjectStateView, QueryOptions, RoochAddressView, StateOptions, StatePageView, StrView,
-    StructTagView, TransactionWithInfoPageView, TxOptions,
+    FieldKeyView, FieldStateFilterView, FunctionCallView, GasEstimateView, H256View,
+    IndexerEventPageView, IndexerFieldStatePageView, IndexerObjectChangeView,
+    IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView, ObjectIDVecView, ObjectIDView,
+    ObjectStateFilterView, ObjectStateView, QueryOptions, RoochAddressView, StateOptions,
+    StatePageView, StrView, StructTagView, TransactionWithInfoPageView, TxOptions,
 };
 use crate::RpcResult;
+use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::proc_macros::rpc;
 use moveos_types::{access_path::AccessPath, state::FieldKey};
 use rooch_open_rpc_macros::open_rpc;
@@ -40,19 +47,33 @@ pub trait RoochAPI {
         tx_option: Option<TxOptions>,
     ) -> RpcResult<ExecuteTransactionResponseView>;
 
+    /// Dry run the transaction in bcs hex format, the states are not changed.
+    /// The Move call tree and the gas profile of the transaction are returned if they are enabled by the DryRunOptions.
     #[method(name = "dryRunRawTransaction")]
-    async fn dry_run(&self, tx_bcs_hex: BytesView) -> RpcResult<DryRunTransactionResponseView>;
+    async fn dry_run(
+        &self,
+        tx_bcs_hex: BytesView,
+        dry_run_option: Option<DryRunOptions>,
+    ) -> RpcResult<DryRunTransactionResponseView>;
+
+    /// Estimate the gas of the transaction data in bcs hex format, the max_gas_amount of the transaction is ignored.
+    /// The recommended max gas amount with a safety margin and the cost in GasCoin are returned.
+    #[method(name = "estimateGas")]
+    async fn estimate_gas(&self, tx_bcs_hex: BytesView) -> RpcResult<GasEstimateView>;
 
     /// Execute a read-only function call
     /// The function do not change the state of Application
+    /// The function is executed at the state_root or tx_order of the StateOptions if it is set.
     #[method(name = "executeViewFunction")]
     async fn execute_view_function(
         &self,
         function_call: FunctionCallView,
+        state_option: Option<StateOptions>,
     ) -> RpcResult<AnnotatedFunctionResultView>;
 
     /// Get the states by access_path
     /// If the StateOptions.decode is true, the state is decoded and the decoded value is returned in the response.
+    /// The states are read at the state_root or tx_order of the StateOptions if it is set.
     #[method(name = "getStates")]
     async fn get_states(
         &self,
@@ -60,6 +81,23 @@ pub trait RoochAPI {
         state_option: Option<StateOptions>,
     ) -> RpcResult<Vec<Option<ObjectStateView>>>;
 
+    /// Get the states by access_path with the merkle proofs chained from the state root of the root object
+    /// The states are proved at the given state_root, or the current state root if it is not set.
+    #[method(name = "getStatesWithProof")]
+    async fn get_states_with_proof(
+        &self,
+        access_path: AccessPathView,
+        state_root: Option<H256View>,
+    ) -> RpcResult<StatesWithProofView>;
+
+    /// Get the proof that the transaction is sequenced at the tx_order, the tx accumulator proof of the tx hash
+    /// by the tx accumulator root in the sequence info signed by the sequencer.
+    #[method(name = "getTransactionProof")]
+    async fn get_transaction_proof(
+        &self,
+        tx_order: StrView<u64>,
+    ) -> RpcResult<Option<TransactionProofView>>;
+
     /// List the states by access_path
     /// If the StateOptions.decode is true, the state is decoded and the decoded value is returned in the response.
     #[method(name = "listStates")]
@@ -133,6 +171,35 @@ pub trait RoochAPI {
         descending_order: Option<bool>,
     ) -> RpcResult<TransactionWithInfoPageView>;
 
+    /// Get the block proposed by the proposer via block number
+    #[method(name = "getBlock")]
+    async fn get_block(&self, block_number: StrView<u128>) -> RpcResult<Option<BlockView>>;
+
+    /// Get the latest block proposed by the proposer
+    #[method(name = "getLatestBlock")]
+    async fn get_latest_block(&self) -> RpcResult<Option<BlockView>>;
+
+    /// Get the status of the DA submission, including the pending chunks and the lagging servers
+    #[method(name = "getDAStatus")]
+    async fn get_da_status(&self) -> RpcResult<DAStatusView>;
+
+    /// Get the transactions waiting in the mempool, of the sender or all senders if not set,
+    /// ordered by the sender and the sequence number
+    #[method(name = "getPendingTransactions")]
+    async fn get_pending_transactions(
+        &self,
+        sender: Option<UnitedAddressView>,
+        limit: Option<StrView<u64>>,
+    ) -> RpcResult<Vec<PendingTransactionView>>;
+
+    /// Get the status of the mempool
+    #[method(name = "getMempoolStatus")]
+    async fn get_mempool_status(&self) -> RpcResult<MempoolStatusView>;
+
+    /// Get the status of the indexer, including the last indexed tx order and the lag behind the sequencer
+    #[method(name = "getIndexerStatus")]
+    async fn get_indexer_status(&self) -> RpcResult<IndexerStatusView>;
+
     /// get account balance by RoochAddress and CoinType
     #[method(name = "getBalance")]
     async fn get_balance(
@@ -191,6 +258,17 @@ pub trait RoochAPI {
         query_option: Option<QueryOptions>,
     ) -> RpcResult<IndexerObjectStatePageView>;
 
+    /// Query the dynamic field states indexer by field filter, only the fields of the indexed objects are queryable
+    #[method(name = "queryFieldStates")]
+    async fn query_field_states(
+        &self,
+        filter: FieldStateFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        query_option: Option<QueryOptions>,
+    ) -> RpcResult<IndexerFieldStatePageView>;
+
     /// Repair indexer by sync from states
     #[method(name = "repairIndexer")]
     async fn repair_indexer(
@@ -198,4 +276,29 @@ pub trait RoochAPI {
         repair_type: RepairIndexerTypeView,
         repair_params: RepairIndexerParamsView,
     ) -> RpcResult<()>;
+
+    /// Subscribe to the events of the newly executed transactions by event filter
+    #[subscription(
+        name = "subscribeEvents",
+        unsubscribe = "unsubscribeEvents",
+        item = IndexerEventView
+    )]
+    async fn subscribe_events(&self, filter: EventFilterView) -> SubscriptionResult;
+
+    /// Subscribe to the newly executed transactions by transaction filter
+    /// The `original_address` filter is not supported.
+    #[subscription(
+        name = "subscribeTransactions",
+        unsubscribe = "unsubscribeTransactions",
+        item = TransactionWithInfoView
+    )]
+    async fn subscribe_transactions(&self, filter: TransactionFilterView) -> SubscriptionResult;
+
+    /// Subscribe to the object changes of the newly executed transactions by state filter
+    #[subscription(
+        name = "subscribeObjectChanges",
+        unsubscribe = "unsubscribeObjectChanges",
+        item = IndexerObjectChangeView
+    )]
+    async fn subscribe_object_changes(&self, filter: ObjectStateFilterView) -> SubscriptionResult;
 }
@@ -0,0 +1,80 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{BytesView, H256View, StrView};
+use rooch_types::block::{Block, BlockCommitment, SignedBlockCommitment};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BlockView {
+    /// The index if the block
+    pub block_number: StrView<u128>,
+    /// How many transactions in the block
+    pub batch_size: StrView<u64>,
+    /// The previous tx accumulator root of the block
+    pub prev_tx_accumulator_root: H256View,
+    /// The tx accumulator root after the last transaction append to the accumulator
+    pub tx_accumulator_root: H256View,
+    /// The all transaction's state root
+    pub state_roots: Vec<H256View>,
+}
+
+impl From<Block> for BlockView {
+    fn from(block: Block) -> Self {
+        Self {
+            block_number: block.block_number.into(),
+            batch_size: block.batch_size.into(),
+            prev_tx_accumulator_root: block.prev_tx_accumulator_root.into(),
+            tx_accumulator_root: block.tx_accumulator_root.into(),
+            state_roots: block.state_roots.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<BlockView> for Block {
+    fn from(view: BlockView) -> Self {
+        Block::new(
+            view.block_number.0,
+            view.batch_size.0,
+            view.prev_tx_accumulator_root.0,
+            view.tx_accumulator_root.0,
+            view.state_roots.into_iter().map(|root| root.0).collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BlockCommitmentView {
+    pub block_number: StrView<u128>,
+    pub prev_tx_accumulator_root: H256View,
+    pub tx_accumulator_root: H256View,
+    pub state_root: H256View,
+}
+
+impl From<BlockCommitment> for BlockCommitmentView {
+    fn from(commitment: BlockCommitment) -> Self {
+        Self {
+            block_number: commitment.block_number.into(),
+            prev_tx_accumulator_root: commitment.prev_tx_accumulator_root.into(),
+            tx_accumulator_root: commitment.tx_accumulator_root.into(),
+            state_root: commitment.state_root.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SignedBlockCommitmentView {
+    pub commitment: BlockCommitmentView,
+    /// The proposer signature of the commitment, `flag || signature || public_key`
+    pub signature: BytesView,
+}
+
+impl From<SignedBlockCommitment> for SignedBlockCommitmentView {
+    fn from(signed_commitment: SignedBlockCommitment) -> Self {
+        Self {
+            commitment: signed_commitment.commitment.into(),
+            signature: signed_commitment.signature.as_ref().to_vec().into(),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{
+    AccountAddressView, AnnotatedMoveValueView, BytesView, HumanReadableDisplay, IdentifierView,
+    ModuleIdView, ObjectIDView, StrView, TypeTagView,
+};
+use moveos_types::call_trace::{
+    AnnotatedCallTrace, AnnotatedTracedValue, ObjectOperation, TracedObjectChange,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TracedValueView {
+    pub type_tag: TypeTagView,
+    pub value: BytesView,
+    /// None if the value can not be decoded with the states before the transaction
+    pub decoded_value: Option<AnnotatedMoveValueView>,
+}
+
+impl std::fmt::Display for TracedValueView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.decoded_value {
+            Some(decoded_value) => write!(
+                f,
+                "{}",
+                serde_json::to_string(decoded_value).map_err(|_| std::fmt::Error)?
+            ),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl From<AnnotatedTracedValue> for TracedValueView {
+    fn from(value: AnnotatedTracedValue) -> Self {
+        Self {
+            type_tag: value.value.type_tag.into(),
+            value: StrView(value.value.value),
+            decoded_value: value.decoded_value.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectOperationView {
+    Add,
+    Remove,
+    BorrowMut,
+    Take,
+    Transfer { new_owner: AccountAddressView },
+    ToShared,
+    ToFrozen,
+}
+
+impl std::fmt::Display for ObjectOperationView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add => write!(f, "add"),
+            Self::Remove => write!(f, "remove"),
+            Self::BorrowMut => write!(f, "borrow_mut"),
+            Self::Take => write!(f, "take"),
+            Self::Transfer { new_owner } => write!(f, "transfer to {}", new_owner),
+            Self::ToShared => write!(f, "to_shared"),
+            Self::ToFrozen => write!(f, "to_frozen"),
+        }
+    }
+}
+
+impl From<ObjectOperation> for ObjectOperationView {
+    fn from(operation: ObjectOperation) -> Self {
+        match operation {
+            ObjectOperation::Add => Self::Add,
+            ObjectOperation::Remove => Self::Remove,
+            ObjectOperation::BorrowMut => Self::BorrowMut,
+            ObjectOperation::Take => Self::Take,
+            ObjectOperation::Transfer { new_owner } => Self::Transfer {
+                new_owner: new_owner.into(),
+            },
+            ObjectOperation::ToShared => Self::ToShared,
+            ObjectOperation::ToFrozen => Self::ToFrozen,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TracedObjectChangeView {
+    pub object_id: ObjectIDView,
+    pub operation: ObjectOperationView,
+}
+
+impl From<TracedObjectChange> for TracedObjectChangeView {
+    fn from(change: TracedObjectChange) -> Self {
+        Self {
+            object_id: change.object_id.into(),
+            operation: change.operation.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallTraceView {
+    /// None if the function is the main function of a script
+    pub module_id: Option<ModuleIdView>,
+    pub function_name: IdentifierView,
+    pub ty_args: Vec<TypeTagView>,
+    pub args: Vec<TracedValueView>,
+    /// The gas used by the call, including the gas used by its sub calls
+    pub gas_used: StrView<u64>,
+    pub is_native: bool,
+    pub success: bool,
+    pub events: Vec<TracedValueView>,
+    pub object_changes: Vec<TracedObjectChangeView>,
+    pub sub_calls: Vec<CallTraceView>,
+}
+
+impl From<AnnotatedCallTrace> for CallTraceView {
+    fn from(trace: AnnotatedCallTrace) -> Self {
+        Self {
+            module_id: trace.module_id.map(Into::into),
+            function_name: trace.function_name.into(),
+            ty_args: trace.ty_args.into_iter().map(Into::into).collect(),
+            args: trace.args.into_iter().map(Into::into).collect(),
+            gas_used: trace.gas_used.into(),
+            is_native: trace.is_native,
+            success: trace.success,
+            events: trace.events.into_iter().map(Into::into).collect(),
+            object_changes: trace.object_changes.into_iter().map(Into::into).collect(),
+            sub_calls: trace.sub_calls.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl HumanReadableDisplay for CallTraceView {
+    fn to_human_readable_string(&self, verbose: bool, indent: usize) -> String {
+        let function = match &self.module_id {
+            Some(module_id) => format!("{}::{}", module_id, self.function_name),
+            None => format!("script::{}", self.function_name),
+        };
+        let ty_args = if self.ty_args.is_empty() {
+            "".to_string()
+        } else {
+            let ty_args: Vec<_> = self.ty_args.iter().map(|ty| ty.to_string()).collect();
+            format!("<{}>", ty_args.join(", "))
+        };
+        let args: Vec<_> = self.args.iter().map(|arg| arg.to_string()).collect();
+        let mut flags = vec![];
+        if self.is_native {
+            flags.push("native");
+        }
+        if !self.success {
+            flags.push("failed");
+        }
+        let flags = if flags.is_empty() {
+            "".to_string()
+        } else {
+            format!(" [{}]", flags.join(", "))
+        };
+        let mut output = format!(
+            "{indent}{}{}({}) gas used: {}{}",
+            function,
+            ty_args,
+            args.join(", "),
+            self.gas_used,
+            flags,
+            indent = " ".repeat(indent),
+        );
+        let inner_indent = " ".repeat(indent + 4);
+        for event in &self.events {
+            output.push_str(&format!(
+                "\n{}event: {} {}",
+                inner_indent, event.type_tag, event
+            ));
+        }
+        for change in &self.object_changes {
+            output.push_str(&format!(
+                "\n{}object change: {} {}",
+                inner_indent, change.object_id, change.operation
+            ));
+        }
+        for sub_call in &self.sub_calls {
+            output.push('\n');
+            output.push_str(&sub_call.to_human_readable_string(verbose, indent + 4));
+        }
+        output
+    }
+}
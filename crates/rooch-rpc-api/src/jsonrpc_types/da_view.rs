@@ -0,0 +1,59 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::StrView;
+use rooch_types::da::{DAServerStatus, DAStatus};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DAServerStatusView {
+    pub name: String,
+    /// The chunks in the outbox not acknowledged by the server
+    pub pending_chunks: StrView<u64>,
+    /// The oldest chunk not acknowledged by the server
+    pub first_pending_chunk_id: Option<StrView<u128>>,
+    /// How many times the submission to the server failed in a row
+    pub consecutive_failures: StrView<u64>,
+    pub last_error: Option<String>,
+    /// Seconds to wait before the next retry
+    pub next_retry_in_seconds: StrView<u64>,
+}
+
+impl From<DAServerStatus> for DAServerStatusView {
+    fn from(status: DAServerStatus) -> Self {
+        Self {
+            name: status.name,
+            pending_chunks: status.pending_chunks.into(),
+            first_pending_chunk_id: status.first_pending_chunk_id.map(Into::into),
+            consecutive_failures: status.consecutive_failures.into(),
+            last_error: status.last_error,
+            next_retry_in_seconds: status.next_retry_in_seconds.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DAStatusView {
+    /// How many servers must acknowledge a chunk before it is available
+    pub submit_threshold: StrView<u64>,
+    /// The batches buffered in the chunk builder, not sealed into a chunk yet
+    pub buffered_batches: StrView<u64>,
+    /// The chunks not acknowledged by all servers
+    pub pending_chunks: StrView<u64>,
+    /// The chunks acknowledged by less servers than the submit threshold
+    pub unavailable_chunks: StrView<u64>,
+    pub servers: Vec<DAServerStatusView>,
+}
+
+impl From<DAStatus> for DAStatusView {
+    fn from(status: DAStatus) -> Self {
+        Self {
+            submit_threshold: status.submit_threshold.into(),
+            buffered_batches: status.buffered_batches.into(),
+            pending_chunks: status.pending_chunks.into(),
+            unavailable_chunks: status.unavailable_chunks.into(),
+            servers: status.servers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
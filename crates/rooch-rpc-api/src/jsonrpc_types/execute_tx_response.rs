@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::BytesView;
-use super::{HumanReadableDisplay, ModuleIdView, StateChangeSetView, StrView};
+use super::{
+    CallTraceView, GasProfileView, HumanReadableDisplay, ModuleIdView, StateChangeSetView, StrView,
+};
 use crate::jsonrpc_types::event_view::EventView;
 use crate::jsonrpc_types::H256View;
+use move_core_types::u256::U256;
 use move_core_types::vm_status::{AbortLocation, KeptVMStatus};
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::transaction::{TransactionExecutionInfo, VMErrorInfo};
@@ -81,6 +84,29 @@ impl From<KeptVMStatus> for KeptVMStatusView {
     }
 }
 
+impl From<KeptVMStatusView> for KeptVMStatus {
+    fn from(vm_status: KeptVMStatusView) -> Self {
+        match vm_status {
+            KeptVMStatusView::Executed => Self::Executed,
+            KeptVMStatusView::OutOfGas => Self::OutOfGas,
+            KeptVMStatusView::MoveAbort {
+                location,
+                abort_code,
+            } => Self::MoveAbort(location.0, abort_code.0),
+            KeptVMStatusView::ExecutionFailure {
+                location,
+                function,
+                code_offset,
+            } => Self::ExecutionFailure {
+                location: location.0,
+                function,
+                code_offset,
+            },
+            KeptVMStatusView::MiscellaneousError => Self::MiscellaneousError,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuthenticatorView {
     pub auth_validator_id: StrView<u64>,
@@ -190,6 +216,36 @@ pub struct RawTransactionOutputView {
 pub struct DryRunTransactionResponseView {
     pub raw_output: RawTransactionOutputView,
     pub vm_error_info: VMErrorInfo,
+    /// The traces of the top-level calls, only returned if the trace is enabled
+    pub call_traces: Option<Vec<CallTraceView>>,
+    /// The gas profile of the transaction, only returned if the gas profiling is enabled
+    pub gas_profile: Option<GasProfileView>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasEstimateView {
+    /// The minimal max gas amount to execute the transaction
+    pub gas_used: StrView<u64>,
+    /// The gas used with a safety margin, it is recommended to use it as the max gas amount
+    pub recommended_max_gas_amount: StrView<u64>,
+    /// The gas factor to convert the gas amount to the amount of GasCoin
+    pub gas_factor: StrView<u64>,
+    /// The amount of GasCoin charged for the gas used
+    pub gas_fee: StrView<U256>,
+    /// The amount of GasCoin deposited for the recommended max gas amount, the unused part is refunded
+    pub max_gas_fee: StrView<U256>,
+}
+
+impl GasEstimateView {
+    pub fn new(gas_used: u64, recommended_max_gas_amount: u64, gas_factor: u64) -> Self {
+        Self {
+            gas_used: gas_used.into(),
+            recommended_max_gas_amount: recommended_max_gas_amount.into(),
+            gas_factor: gas_factor.into(),
+            gas_fee: (U256::from(gas_used) * U256::from(gas_factor)).into(),
+            max_gas_fee: (U256::from(recommended_max_gas_amount) * U256::from(gas_factor)).into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -0,0 +1,72 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::StrView;
+use moveos_types::gas_profile::{GasCategory, GasProfile, GasProfileEntry};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GasCategoryView {
+    Instruction,
+    Native,
+    Storage,
+    Io,
+}
+
+impl From<GasCategory> for GasCategoryView {
+    fn from(category: GasCategory) -> Self {
+        match category {
+            GasCategory::Instruction => Self::Instruction,
+            GasCategory::Native => Self::Native,
+            GasCategory::Storage => Self::Storage,
+            GasCategory::Io => Self::Io,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasProfileEntryView {
+    /// The function frames from the root to the current function
+    pub stack: Vec<String>,
+    pub category: GasCategoryView,
+    /// The gas used by the current function, the gas used by its sub calls is not included
+    pub gas_used: StrView<u64>,
+}
+
+impl From<GasProfileEntry> for GasProfileEntryView {
+    fn from(entry: GasProfileEntry) -> Self {
+        Self {
+            stack: entry.stack,
+            category: entry.category.into(),
+            gas_used: entry.gas_used.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasProfileView {
+    pub total_gas_used: StrView<u64>,
+    pub instruction_gas_used: StrView<u64>,
+    pub native_gas_used: StrView<u64>,
+    pub storage_gas_used: StrView<u64>,
+    pub io_gas_used: StrView<u64>,
+    pub entries: Vec<GasProfileEntryView>,
+    /// The profile in the folded stacks format, which can be rendered by flamegraph tools
+    pub folded_stacks: String,
+}
+
+impl From<GasProfile> for GasProfileView {
+    fn from(profile: GasProfile) -> Self {
+        Self {
+            total_gas_used: profile.total_gas_used().into(),
+            instruction_gas_used: profile.gas_used_of(GasCategory::Instruction).into(),
+            native_gas_used: profile.gas_used_of(GasCategory::Native).into(),
+            storage_gas_used: profile.gas_used_of(GasCategory::Storage).into(),
+            io_gas_used: profile.gas_used_of(GasCategory::Io).into(),
+            folded_stacks: profile.to_folded_stacks(),
+            entries: profile.entries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
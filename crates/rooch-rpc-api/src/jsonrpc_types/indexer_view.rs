@@ -0,0 +1,30 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::StrView;
+use rooch_types::indexer::IndexerStatus;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexerStatusView {
+    /// The last sequenced tx order
+    pub last_tx_order: StrView<u64>,
+    /// The last indexed tx order, None if nothing is indexed
+    pub last_indexed_tx_order: Option<StrView<u64>>,
+    /// The number of the sequenced txs which are not indexed yet
+    pub lag: StrView<u64>,
+    /// The error of the last indexer catch up
+    pub last_error: Option<String>,
+}
+
+impl From<IndexerStatus> for IndexerStatusView {
+    fn from(status: IndexerStatus) -> Self {
+        Self {
+            last_tx_order: status.last_tx_order.into(),
+            last_indexed_tx_order: status.last_indexed_tx_order.map(Into::into),
+            lag: status.lag.into(),
+            last_error: status.last_error,
+        }
+    }
+}
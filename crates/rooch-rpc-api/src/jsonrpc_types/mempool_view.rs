@@ -0,0 +1,61 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{H256View, RoochAddressView, StrView};
+use rooch_types::mempool::{MempoolStatus, PendingTransactionInfo};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PendingTransactionView {
+    pub tx_hash: H256View,
+    pub sender: RoochAddressView,
+    pub sequence_number: StrView<u64>,
+    pub max_gas_amount: StrView<u64>,
+    /// The timestamp when the transaction entered the mempool, in milliseconds
+    pub received_at: StrView<u64>,
+    /// Whether the transaction can be sequenced now, or it waits for the transactions with lower sequence numbers
+    pub ready: bool,
+}
+
+impl From<PendingTransactionInfo> for PendingTransactionView {
+    fn from(tx: PendingTransactionInfo) -> Self {
+        Self {
+            tx_hash: tx.tx_hash.into(),
+            sender: tx.sender.into(),
+            sequence_number: tx.sequence_number.into(),
+            max_gas_amount: tx.max_gas_amount.into(),
+            received_at: tx.received_at.into(),
+            ready: tx.ready,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MempoolStatusView {
+    /// The transactions waiting in the mempool
+    pub pending_transactions: StrView<u64>,
+    /// The pending transactions which can be sequenced now
+    pub ready_transactions: StrView<u64>,
+    /// The senders with pending transactions
+    pub senders: StrView<u64>,
+    /// The transactions taken from the mempool and not executed yet
+    pub executing_transactions: StrView<u64>,
+    pub max_size: StrView<u64>,
+    pub max_size_per_sender: StrView<u64>,
+    pub ttl_seconds: StrView<u64>,
+}
+
+impl From<MempoolStatus> for MempoolStatusView {
+    fn from(status: MempoolStatus) -> Self {
+        Self {
+            pending_transactions: status.pending_transactions.into(),
+            ready_transactions: status.ready_transactions.into(),
+            senders: status.senders.into(),
+            executing_transactions: status.executing_transactions.into(),
+            max_size: status.max_size.into(),
+            max_size_per_sender: status.max_size_per_sender.into(),
+            ttl_seconds: status.ttl_seconds.into(),
+        }
+    }
+}
@@ -4,8 +4,10 @@
 #[macro_use]
 
 mod str_view;
+mod call_trace_view;
 mod execute_tx_response;
 mod function_return_value_view;
+mod gas_profile_view;
 mod module_abi_view;
 mod move_types;
 mod rooch_types;
@@ -17,8 +19,13 @@ mod tests;
 mod transaction_argument_view;
 
 pub mod account_view;
+pub mod block_view;
+pub mod da_view;
 pub mod event_view;
 pub mod export_view;
+pub mod indexer_view;
+pub mod mempool_view;
+pub mod proof_view;
 pub mod transaction_view;
 
 pub mod address;
@@ -27,8 +34,10 @@ pub mod repair_view;
 
 pub use self::rooch_types::*;
 pub use address::*;
+pub use call_trace_view::*;
 pub use execute_tx_response::*;
 pub use function_return_value_view::*;
+pub use gas_profile_view::*;
 pub use module_abi_view::*;
 pub use move_types::*;
 pub use rpc_options::*;
@@ -0,0 +1,152 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{
+    FieldKeyView, H256View, ObjectIDView, ObjectStateView, StrView, TransactionSequenceInfoView,
+};
+use accumulator::proof::AccumulatorProof;
+use moveos_types::state_proof::StateWithProof;
+use rooch_types::transaction::proof::TransactionProof;
+use rooch_types::transaction::TransactionSequenceInfo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smt::SparseMerkleProof;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SparseMerkleLeafView {
+    pub key_hash: H256View,
+    pub value_hash: H256View,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SparseMerkleProofView {
+    /// The only leaf in the subtree of the key, None if the subtree is empty
+    pub leaf: Option<SparseMerkleLeafView>,
+    /// The siblings from the bottom level to the root level
+    pub siblings: Vec<H256View>,
+}
+
+impl From<SparseMerkleProof> for SparseMerkleProofView {
+    fn from(proof: SparseMerkleProof) -> Self {
+        Self {
+            leaf: proof
+                .leaf
+                .map(|(key_hash, value_hash)| SparseMerkleLeafView {
+                    key_hash: key_hash.into(),
+                    value_hash: value_hash.into(),
+                }),
+            siblings: proof.siblings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SparseMerkleProofView> for SparseMerkleProof {
+    fn from(proof: SparseMerkleProofView) -> Self {
+        SparseMerkleProof::new(
+            proof
+                .leaf
+                .map(|leaf| (leaf.key_hash.into(), leaf.value_hash.into())),
+            proof.siblings.into_iter().map(Into::into).collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateWithProofView {
+    pub object_id: ObjectIDView,
+    pub field_key: FieldKeyView,
+    /// The ancestor objects of the state, from the child of the root object to the object of the field
+    pub ancestors: Vec<ObjectStateView>,
+    pub state: Option<ObjectStateView>,
+    /// The proofs of the ancestors and the state, each one is proved by the state root of the previous one
+    pub proofs: Vec<SparseMerkleProofView>,
+}
+
+impl From<StateWithProof> for StateWithProofView {
+    fn from(state: StateWithProof) -> Self {
+        Self {
+            object_id: state.object_id.into(),
+            field_key: state.field_key.into(),
+            ancestors: state.ancestors.into_iter().map(Into::into).collect(),
+            state: state.state.map(Into::into),
+            proofs: state.proofs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<StateWithProofView> for StateWithProof {
+    fn from(state: StateWithProofView) -> Self {
+        Self {
+            object_id: state.object_id.0,
+            field_key: state.field_key.0,
+            ancestors: state.ancestors.into_iter().map(Into::into).collect(),
+            state: state.state.map(Into::into),
+            proofs: state.proofs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StatesWithProofView {
+    /// The state root of the root object which the states are proved by
+    pub state_root: H256View,
+    pub states: Vec<StateWithProofView>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccumulatorProofView {
+    /// The siblings from the bottom level to the root level
+    pub siblings: Vec<H256View>,
+}
+
+impl From<AccumulatorProof> for AccumulatorProofView {
+    fn from(proof: AccumulatorProof) -> Self {
+        Self {
+            siblings: proof.siblings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<AccumulatorProofView> for AccumulatorProof {
+    fn from(proof: AccumulatorProofView) -> Self {
+        AccumulatorProof::new(proof.siblings.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionProofView {
+    pub tx_hash: H256View,
+    /// The sequence info signed by the sequencer, the tx accumulator root is the one right after the tx is appended
+    pub sequence_info: TransactionSequenceInfoView,
+    /// The number of leaves of the tx accumulator right after the tx is appended
+    pub tx_accumulator_num_leaves: StrView<u64>,
+    /// The proof of the tx hash at the tx order in the tx accumulator
+    pub proof: AccumulatorProofView,
+}
+
+impl From<TransactionProof> for TransactionProofView {
+    fn from(proof: TransactionProof) -> Self {
+        Self {
+            tx_hash: proof.tx_hash.into(),
+            tx_accumulator_num_leaves: proof.sequence_info.tx_accumulator_num_leaves.into(),
+            sequence_info: proof.sequence_info.into(),
+            proof: proof.proof.into(),
+        }
+    }
+}
+
+impl From<TransactionProofView> for TransactionProof {
+    fn from(proof: TransactionProofView) -> Self {
+        let sequence_info = TransactionSequenceInfo {
+            tx_order: proof.sequence_info.tx_order.0,
+            tx_order_signature: proof.sequence_info.tx_order_signature.0,
+            tx_accumulator_root: proof.sequence_info.tx_accumulator_root.into(),
+            tx_timestamp: proof.sequence_info.tx_timestamp.0,
+            // the frozen subtree roots and the number of nodes are not needed to verify the proof
+            tx_accumulator_frozen_subtree_roots: vec![],
+            tx_accumulator_num_leaves: proof.tx_accumulator_num_leaves.0,
+            tx_accumulator_num_nodes: 0,
+        };
+        TransactionProof::new(proof.tx_hash.into(), sequence_info, proof.proof.into())
+    }
+}
@@ -10,7 +10,7 @@ use crate::jsonrpc_types::event_view::{EventView, IndexerEventView};
 use crate::jsonrpc_types::transaction_view::TransactionWithInfoView;
 use crate::jsonrpc_types::{
     move_types::{MoveActionTypeView, MoveActionView},
-    BytesView, IndexerObjectStateView, StateKVView, StrView, StructTagView,
+    BytesView, IndexerFieldStateView, IndexerObjectStateView, StateKVView, StrView, StructTagView,
 };
 use move_core_types::u256::U256;
 use rooch_types::framework::coin::CoinInfo;
@@ -26,6 +26,7 @@ pub type BalanceInfoPageView = PageView<BalanceInfoView, IndexerStateIDView>;
 pub type IndexerEventPageView = PageView<IndexerEventView, IndexerEventIDView>;
 
 pub type IndexerObjectStatePageView = PageView<IndexerObjectStateView, IndexerStateIDView>;
+pub type IndexerFieldStatePageView = PageView<IndexerFieldStateView, IndexerStateIDView>;
 
 pub type UTXOPageView = PageView<UTXOStateView, IndexerStateIDView>;
 pub type InscriptionPageView = PageView<InscriptionStateView, IndexerStateIDView>;
@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::jsonrpc_types::{H256View, StrView};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,11 @@ pub struct StateOptions {
     pub decode: bool,
     /// If true, result with display rendered is returned
     pub show_display: bool,
+    /// Read the states at the state root instead of the latest state root.
+    /// The size of the root object is unknown at an arbitrary state root, it is returned as 0.
+    pub state_root: Option<H256View>,
+    /// Read the states after the transaction of the tx_order is executed, instead of the latest state.
+    pub tx_order: Option<StrView<u64>>,
 }
 
 impl StateOptions {
@@ -27,6 +33,16 @@ impl StateOptions {
         self.show_display = show_display;
         self
     }
+
+    pub fn state_root(mut self, state_root: H256View) -> Self {
+        self.state_root = Some(state_root);
+        self
+    }
+
+    pub fn tx_order(mut self, tx_order: u64) -> Self {
+        self.tx_order = Some(StrView(tx_order));
+        self
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
@@ -68,6 +84,31 @@ impl TxOptions {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DryRunOptions {
+    /// If true, the Move call tree of the transaction is traced and returned in the response.
+    pub trace: bool,
+    /// If true, the gas used by the transaction is profiled by the call stacks and returned in the response.
+    pub profile_gas: bool,
+}
+
+impl DryRunOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn profile_gas(mut self, profile_gas: bool) -> Self {
+        self.profile_gas = profile_gas;
+        self
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq)]
 #[serde(rename_all = "camelCase", default)]
 pub struct QueryOptions {
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    AnnotatedMoveStructView, BytesView, H256View, HumanReadableDisplay, ObjectIDVecView,
-    QueryOptions, RoochAddressView, StrView, StructTagView, TypeTagView, UnitedAddressView,
+    AnnotatedMoveStructView, AnnotatedMoveValueView, BytesView, H256View, HumanReadableDisplay,
+    ObjectIDVecView, ObjectIDView, QueryOptions, RoochAddressView, StrView, StructTagView,
+    TypeTagView, UnitedAddressView,
 };
 use anyhow::Result;
 use move_core_types::effects::Op;
@@ -14,7 +15,8 @@ use moveos_types::{
     moveos_std::object::{human_readable_flag, ObjectID},
     state::{AnnotatedState, ObjectState, StateChangeSet},
 };
-use rooch_types::indexer::state::{IndexerStateID, ObjectStateFilter};
+use rooch_types::indexer::field::{FieldStateFilter, IndexerFieldState};
+use rooch_types::indexer::state::{IndexerObjectChange, IndexerStateID, ObjectStateFilter};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -337,6 +339,27 @@ impl IndexerObjectStateView {
     }
 }
 
+/// An object change of an executed transaction. Used as the item of `subscribeObjectChanges`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct IndexerObjectChangeView {
+    #[serde(flatten)]
+    pub metadata: ObjectMetaView,
+    /// The bcs bytes of the new object value, None if only the metadata is changed
+    pub value: Option<OpView>,
+    #[serde(flatten)]
+    pub indexer_id: IndexerStateIDView,
+}
+
+impl From<IndexerObjectChange> for IndexerObjectChangeView {
+    fn from(change: IndexerObjectChange) -> Self {
+        Self {
+            metadata: change.state.metadata.into(),
+            value: change.value.map(Into::into),
+            indexer_id: IndexerStateID::new(change.state.tx_order, change.state.state_index).into(),
+        }
+    }
+}
+
 impl HumanReadableDisplay for IndexerObjectStateView {
     fn to_human_readable_string(&self, verbose: bool, indent: usize) -> String {
         let _ = verbose; // TODO: implement verbose string
@@ -405,6 +428,136 @@ impl ObjectStateFilterView {
     }
 }
 
+/// The dynamic field state view. Used as the item of `queryFieldStates`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct IndexerFieldStateView {
+    /// The object id of the dynamic field
+    pub id: ObjectID,
+    pub parent_id: ObjectID,
+    pub field_key: FieldKeyView,
+    pub name_type: TypeTagView,
+    pub value_type: TypeTagView,
+    /// bcs bytes of the DynamicField, include the field name and value.
+    pub value: BytesView,
+    pub decoded_name: Option<AnnotatedMoveValueView>,
+    pub decoded_value: Option<AnnotatedMoveValueView>,
+    pub created_at: StrView<u64>,
+    pub updated_at: StrView<u64>,
+    #[serde(flatten)]
+    pub indexer_id: IndexerStateIDView,
+}
+
+impl IndexerFieldStateView {
+    pub fn new_from_object_state(
+        field: IndexerFieldState,
+        state: ObjectState,
+    ) -> IndexerFieldStateView {
+        let indexer_id = field.indexer_state_id().into();
+        let (metadata, value) = state.into_inner();
+        IndexerFieldStateView {
+            id: metadata.id,
+            parent_id: field.parent_id,
+            field_key: field.field_key.into(),
+            name_type: field.name_type.into(),
+            value_type: field.value_type.into(),
+            value: value.into(),
+            decoded_name: None,
+            decoded_value: None,
+            created_at: metadata.created_at.into(),
+            updated_at: metadata.updated_at.into(),
+            indexer_id,
+        }
+    }
+
+    pub fn new_from_annotated_state(
+        field: IndexerFieldState,
+        state: AnnotatedState,
+    ) -> IndexerFieldStateView {
+        let indexer_id = field.indexer_state_id().into();
+        let (metadata, value, decoded_value) = state.into_inner();
+        let mut decoded_name = None;
+        let mut decoded_field_value = None;
+        for (name, value) in decoded_value.value {
+            match name.as_str() {
+                "name" => decoded_name = Some(AnnotatedMoveValueView::from(value)),
+                "value" => decoded_field_value = Some(AnnotatedMoveValueView::from(value)),
+                _ => {}
+            }
+        }
+        IndexerFieldStateView {
+            id: metadata.id,
+            parent_id: field.parent_id,
+            field_key: field.field_key.into(),
+            name_type: field.name_type.into(),
+            value_type: field.value_type.into(),
+            value: value.into(),
+            decoded_name,
+            decoded_value: decoded_field_value,
+            created_at: metadata.created_at.into(),
+            updated_at: metadata.updated_at.into(),
+            indexer_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldStateFilterView {
+    /// Query by parent object id and field value type.
+    ParentWithValueType {
+        parent_id: ObjectIDView,
+        value_type: TypeTagView,
+    },
+    /// Query by parent object id.
+    Parent(ObjectIDView),
+    /// Query by field value type.
+    ValueType(TypeTagView),
+    /// Query by parent object id and field key.
+    Key {
+        parent_id: ObjectIDView,
+        field_key: FieldKeyView,
+    },
+    /// Query by parent object id and field value,
+    /// the value is the same JSON as the `decoded_value` of the field state.
+    Value {
+        parent_id: ObjectIDView,
+        decoded_value: serde_json::Value,
+    },
+}
+
+impl From<FieldStateFilterView> for FieldStateFilter {
+    fn from(filter: FieldStateFilterView) -> Self {
+        match filter {
+            FieldStateFilterView::ParentWithValueType {
+                parent_id,
+                value_type,
+            } => FieldStateFilter::ParentWithValueType {
+                parent_id: parent_id.into(),
+                value_type: value_type.into(),
+            },
+            FieldStateFilterView::Parent(parent_id) => FieldStateFilter::Parent(parent_id.into()),
+            FieldStateFilterView::ValueType(value_type) => {
+                FieldStateFilter::ValueType(value_type.into())
+            }
+            FieldStateFilterView::Key {
+                parent_id,
+                field_key,
+            } => FieldStateFilter::Key {
+                parent_id: parent_id.into(),
+                field_key: field_key.0,
+            },
+            FieldStateFilterView::Value {
+                parent_id,
+                decoded_value,
+            } => FieldStateFilter::Value {
+                parent_id: parent_id.into(),
+                // The decoded value is indexed as compact JSON
+                decoded_value: decoded_value.to_string(),
+            },
+        }
+    }
+}
+
 /// Object state view. Used as return type of `getObjectStates`.
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ObjectStateView {
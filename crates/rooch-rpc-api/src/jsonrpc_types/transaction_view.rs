@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{BytesView, StrView};
+use crate::jsonrpc_types::block_view::SignedBlockCommitmentView;
 use crate::jsonrpc_types::{
     H256View, TransactionExecutionInfoView, TransactionSequenceInfoView, TransactionView,
     UnitedAddressView,
@@ -70,6 +71,7 @@ pub enum LedgerTxDataView {
     L1Block(L1BlockView),
     L1Tx(L1TransactionView),
     L2Tx(TransactionView),
+    BlockCommitment(SignedBlockCommitmentView),
 }
 
 impl LedgerTxDataView {
@@ -83,6 +85,9 @@ impl LedgerTxDataView {
             LedgerTxData::L2Tx(tx) => LedgerTxDataView::L2Tx(
                 TransactionView::new_from_rooch_transaction(tx, sender_bitcoin_address),
             ),
+            LedgerTxData::BlockCommitment(commitment) => {
+                LedgerTxDataView::BlockCommitment(commitment.into())
+            }
         }
     }
 }
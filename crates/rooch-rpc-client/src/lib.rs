@@ -4,6 +4,7 @@
 use anyhow::Result;
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::ws_client::WsClientBuilder;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::metadata::Metadata;
 use move_core_types::resolver::ModuleResolver;
@@ -21,6 +22,7 @@ use std::time::Duration;
 use tokio::runtime::Handle;
 
 pub mod client_config;
+pub mod proof;
 pub mod rooch_client;
 pub mod wallet_context;
 
@@ -50,9 +52,20 @@ impl ClientBuilder {
                 .build(http)?,
         );
 
+        let mut rooch = RoochRpcClient::new(http_client.clone());
+        // the subscriptions are only available over websocket
+        if let Some(ws_url) = self.ws_url {
+            let ws_client = WsClientBuilder::default()
+                .max_request_size(2 << 30)
+                .request_timeout(self.request_timeout)
+                .build(ws_url)
+                .await?;
+            rooch = rooch.with_ws(Arc::new(ws_client));
+        }
+
         Ok(Client {
             http: http_client.clone(),
-            rooch: RoochRpcClient::new(http_client.clone()),
+            rooch,
         })
     }
 }
@@ -95,7 +108,7 @@ impl MoveFunctionCaller for Client {
         function_call: FunctionCall,
     ) -> Result<FunctionResult> {
         let function_result =
-            futures::executor::block_on(self.rooch.execute_view_function(function_call))?;
+            futures::executor::block_on(self.rooch.execute_view_function(function_call, None))?;
         function_result.try_into()
     }
 }
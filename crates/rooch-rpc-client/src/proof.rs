@@ -0,0 +1,49 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use moveos_types::access_path::AccessPath;
+use moveos_types::h256::H256;
+use moveos_types::state::ObjectState;
+use moveos_types::state_proof::StateWithProof;
+use rooch_rpc_api::jsonrpc_types::proof_view::StatesWithProofView;
+
+/// Verify the `getStatesWithProof` response of the access path against a trusted state root,
+/// returns the verified states in the order of the access path.
+pub fn verify_states_with_proof(
+    trusted_state_root: H256,
+    access_path: AccessPath,
+    response: StatesWithProofView,
+) -> Result<Vec<Option<ObjectState>>> {
+    let state_root: H256 = response.state_root.into();
+    ensure!(
+        state_root == trusted_state_root,
+        "State root mismatch, trusted: {:?}, response: {:?}",
+        trusted_state_root,
+        state_root
+    );
+    let query = access_path.into_state_query().into_fields_query()?;
+    ensure!(
+        query.len() == response.states.len(),
+        "States count mismatch, exp: {}, act: {}",
+        query.len(),
+        response.states.len()
+    );
+    query
+        .into_iter()
+        .zip(response.states)
+        .map(|((object_id, field_key), state)| {
+            let state = StateWithProof::from(state);
+            ensure!(
+                state.object_id == object_id && state.field_key == field_key,
+                "Unexpected state of object {} field {}, exp: object {} field {}",
+                state.object_id,
+                state.field_key,
+                object_id,
+                field_key
+            );
+            state.verify(trusted_state_root)?;
+            Ok(state.state)
+        })
+        .collect()
+}
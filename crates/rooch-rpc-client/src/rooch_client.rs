@@ -1,15 +1,24 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{Ok, Result};
+use crate::proof::verify_states_with_proof;
+use anyhow::{anyhow, Ok, Result};
+use futures::{Stream, StreamExt};
 use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::ws_client::WsClient;
 use moveos_types::h256::H256;
 use moveos_types::moveos_std::account::Account;
 use moveos_types::{access_path::AccessPath, state::ObjectState, transaction::FunctionCall};
 use rooch_rpc_api::api::rooch_api::RoochAPIClient;
 use rooch_rpc_api::jsonrpc_types::{
-    account_view::BalanceInfoView, transaction_view::TransactionWithInfoView,
-    DryRunTransactionResponseView,
+    account_view::BalanceInfoView,
+    block_view::BlockView,
+    da_view::DAStatusView,
+    event_view::{EventFilterView, IndexerEventView},
+    mempool_view::{MempoolStatusView, PendingTransactionView},
+    proof_view::{StatesWithProofView, TransactionProofView},
+    transaction_view::{TransactionFilterView, TransactionWithInfoView},
+    DryRunOptions, DryRunTransactionResponseView, GasEstimateView, IndexerObjectChangeView,
 };
 use rooch_rpc_api::jsonrpc_types::{
     AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, EventOptions, EventPageView,
@@ -21,6 +30,7 @@ use rooch_rpc_api::jsonrpc_types::{
 };
 use rooch_rpc_api::jsonrpc_types::{TransactionWithInfoPageView, TxOptions};
 use rooch_types::indexer::state::IndexerStateID;
+use rooch_types::transaction::proof::TransactionProof;
 use rooch_types::transaction::RoochTransactionData;
 use rooch_types::{address::RoochAddress, transaction::rooch::RoochTransaction};
 use std::sync::Arc;
@@ -28,6 +38,7 @@ use std::sync::Arc;
 #[derive(Clone, Debug)]
 pub struct RoochRpcClient {
     http: Arc<HttpClient>,
+    ws: Option<Arc<WsClient>>,
 }
 
 // TODO: call args are uniformly defined in jsonrpc types?
@@ -35,7 +46,18 @@ pub struct RoochRpcClient {
 
 impl RoochRpcClient {
     pub fn new(http: Arc<HttpClient>) -> Self {
-        Self { http }
+        Self { http, ws: None }
+    }
+
+    pub fn with_ws(mut self, ws: Arc<WsClient>) -> Self {
+        self.ws = Some(ws);
+        self
+    }
+
+    fn ws(&self) -> Result<&WsClient> {
+        self.ws
+            .as_deref()
+            .ok_or_else(|| anyhow!("The subscription requires a websocket url"))
     }
 
     pub async fn get_chain_id(&self) -> Result<u64> {
@@ -57,20 +79,32 @@ impl RoochRpcClient {
     pub async fn dry_run_tx(
         &self,
         tx: RoochTransactionData,
+        dry_run_option: Option<DryRunOptions>,
     ) -> Result<DryRunTransactionResponseView> {
         let tx_payload = bcs::to_bytes(&tx)?;
         self.http
-            .dry_run(tx_payload.into())
+            .dry_run(tx_payload.into(), dry_run_option)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// Estimate the gas used by the transaction and the recommended max gas amount
+    pub async fn estimate_gas(&self, tx: RoochTransactionData) -> Result<GasEstimateView> {
+        let tx_payload = bcs::to_bytes(&tx)?;
+        self.http
+            .estimate_gas(tx_payload.into())
             .await
             .map_err(|e| anyhow::anyhow!(e))
     }
 
+    /// Execute the view function at the state_root or tx_order of the state option if it is set
     pub async fn execute_view_function(
         &self,
         function_call: FunctionCall,
+        state_option: Option<StateOptions>,
     ) -> Result<AnnotatedFunctionResultView> {
         self.http
-            .execute_view_function(function_call.into())
+            .execute_view_function(function_call.into(), state_option)
             .await
             .map_err(|e| anyhow::anyhow!(e))
     }
@@ -82,6 +116,17 @@ impl RoochRpcClient {
         Ok(self.http.get_states(access_path.into(), None).await?)
     }
 
+    pub async fn get_states_with_option(
+        &self,
+        access_path: AccessPath,
+        state_option: StateOptions,
+    ) -> Result<Vec<Option<ObjectStateView>>> {
+        Ok(self
+            .http
+            .get_states(access_path.into(), Some(state_option))
+            .await?)
+    }
+
     pub async fn get_decoded_states(
         &self,
         access_path: AccessPath,
@@ -134,6 +179,114 @@ impl RoochRpcClient {
             .await?)
     }
 
+    pub async fn get_block(&self, block_number: u128) -> Result<Option<BlockView>> {
+        Ok(self.http.get_block(block_number.into()).await?)
+    }
+
+    pub async fn get_latest_block(&self) -> Result<Option<BlockView>> {
+        Ok(self.http.get_latest_block().await?)
+    }
+
+    pub async fn get_states_with_proof(
+        &self,
+        access_path: AccessPath,
+        state_root: Option<H256>,
+    ) -> Result<StatesWithProofView> {
+        Ok(self
+            .http
+            .get_states_with_proof(access_path.into(), state_root.map(Into::into))
+            .await?)
+    }
+
+    /// Get the states and verify them against the trusted state root
+    pub async fn get_verified_states(
+        &self,
+        access_path: AccessPath,
+        trusted_state_root: H256,
+    ) -> Result<Vec<Option<ObjectState>>> {
+        let response = self
+            .get_states_with_proof(access_path.clone(), Some(trusted_state_root))
+            .await?;
+        verify_states_with_proof(trusted_state_root, access_path, response)
+    }
+
+    pub async fn get_transaction_proof(
+        &self,
+        tx_order: u64,
+    ) -> Result<Option<TransactionProofView>> {
+        Ok(self.http.get_transaction_proof(tx_order.into()).await?)
+    }
+
+    /// Get the proof of the transaction at the tx order and verify it is sequenced by the sequencer
+    pub async fn get_verified_transaction_proof(
+        &self,
+        tx_order: u64,
+        sequencer: RoochAddress,
+    ) -> Result<Option<TransactionProof>> {
+        let proof: Option<TransactionProof> =
+            self.get_transaction_proof(tx_order).await?.map(Into::into);
+        if let Some(proof) = &proof {
+            if proof.sequence_info.tx_order != tx_order {
+                return Err(anyhow!(
+                    "The proof is for tx order {}, not {}",
+                    proof.sequence_info.tx_order,
+                    tx_order
+                ));
+            }
+            proof.verify(sequencer)?;
+        }
+        Ok(proof)
+    }
+
+    pub async fn get_da_status(&self) -> Result<DAStatusView> {
+        Ok(self.http.get_da_status().await?)
+    }
+
+    pub async fn get_pending_transactions(
+        &self,
+        sender: Option<RoochAddress>,
+        limit: Option<u64>,
+    ) -> Result<Vec<PendingTransactionView>> {
+        Ok(self
+            .http
+            .get_pending_transactions(
+                sender.map(|sender| RoochAddressView::from(sender).into()),
+                limit.map(Into::into),
+            )
+            .await?)
+    }
+
+    pub async fn get_mempool_status(&self) -> Result<MempoolStatusView> {
+        Ok(self.http.get_mempool_status().await?)
+    }
+
+    /// Stream the events of the newly executed transactions which match the filter
+    pub async fn subscribe_events(
+        &self,
+        filter: EventFilterView,
+    ) -> Result<impl Stream<Item = Result<IndexerEventView>>> {
+        let subscription = self.ws()?.subscribe_events(filter).await?;
+        Ok(subscription.map(|event| event.map_err(Into::into)))
+    }
+
+    /// Stream the newly executed transactions which match the filter
+    pub async fn subscribe_transactions(
+        &self,
+        filter: TransactionFilterView,
+    ) -> Result<impl Stream<Item = Result<TransactionWithInfoView>>> {
+        let subscription = self.ws()?.subscribe_transactions(filter).await?;
+        Ok(subscription.map(|tx| tx.map_err(Into::into)))
+    }
+
+    /// Stream the object changes of the newly executed transactions which match the filter
+    pub async fn subscribe_object_changes(
+        &self,
+        filter: ObjectStateFilterView,
+    ) -> Result<impl Stream<Item = Result<IndexerObjectChangeView>>> {
+        let subscription = self.ws()?.subscribe_object_changes(filter).await?;
+        Ok(subscription.map(|change| change.map_err(Into::into)))
+    }
+
     pub async fn get_sequence_number(&self, sender: RoochAddress) -> Result<u64> {
         Ok(self
             .get_states(AccessPath::object(Account::account_object_id(
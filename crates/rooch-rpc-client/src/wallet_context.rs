@@ -13,7 +13,8 @@ use rooch_key::keystore::account_keystore::AccountKeystore;
 use rooch_key::keystore::file_keystore::FileBasedKeystore;
 use rooch_key::keystore::Keystore;
 use rooch_rpc_api::jsonrpc_types::{
-    DryRunTransactionResponseView, ExecuteTransactionResponseView, KeptVMStatusView, TxOptions,
+    DryRunOptions, DryRunTransactionResponseView, ExecuteTransactionResponseView, KeptVMStatusView,
+    TxOptions,
 };
 use rooch_types::address::ParsedAddress;
 use rooch_types::address::RoochAddress;
@@ -141,14 +142,30 @@ impl WalletContext {
             .await
             .map_err(RoochError::from)?;
         log::debug!("use sequence_number: {}", sequence_number);
-        //TODO max gas amount from cli option or dry run estimate
-        let tx_data = RoochTransactionData::new(
+        let mut tx_data = RoochTransactionData::new(
             sender,
             sequence_number,
             chain_id,
             max_gas_amount.unwrap_or(GasScheduleConfig::CLI_DEFAULT_MAX_GAS_AMOUNT),
             action,
         );
+        if max_gas_amount.is_none() {
+            // If the estimation fails, keep the default max gas amount,
+            // so the transaction still reports the real error when it is executed.
+            match client.rooch.estimate_gas(tx_data.clone()).await {
+                Ok(estimate) => {
+                    log::debug!(
+                        "estimated gas_used: {}, use max_gas_amount: {}",
+                        estimate.gas_used.0,
+                        estimate.recommended_max_gas_amount.0
+                    );
+                    tx_data.max_gas_amount = estimate.recommended_max_gas_amount.0;
+                }
+                Err(e) => {
+                    log::warn!("Failed to estimate the gas of the transaction: {:?}", e);
+                }
+            }
+        }
         Ok(tx_data)
     }
 
@@ -196,11 +213,19 @@ impl WalletContext {
     pub async fn dry_run(
         &self,
         tx: RoochTransactionData,
+    ) -> RoochResult<DryRunTransactionResponseView> {
+        self.dry_run_with_option(tx, None).await
+    }
+
+    pub async fn dry_run_with_option(
+        &self,
+        tx: RoochTransactionData,
+        dry_run_option: Option<DryRunOptions>,
     ) -> RoochResult<DryRunTransactionResponseView> {
         let client = self.get_client().await?;
         client
             .rooch
-            .dry_run_tx(tx)
+            .dry_run_tx(tx, dry_run_option)
             .await
             .map_err(|e| RoochError::DryRunTransactionError(e.to_string()))
     }
@@ -0,0 +1,235 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use moveos_store::state_store::RemoteStateReader;
+use moveos_store::transaction_store::TransactionStore;
+use moveos_types::access_path::AccessPath;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::startup_info::StartupInfo;
+use moveos_types::state::ObjectState;
+use moveos_types::state_resolver::StateKV;
+use moveos_types::transaction::TransactionExecutionInfo;
+use rooch_db::RoochDB;
+use rooch_genesis::RoochGenesis;
+use rooch_rpc_api::api::MAX_RESULT_LIMIT;
+use rooch_rpc_api::jsonrpc_types::StateOptions;
+use rooch_rpc_client::{Client, ClientBuilder};
+use rooch_types::rooch_network::RoochNetwork;
+use rooch_types::sequencer::SequencerInfo;
+use rooch_types::transaction::proof::TransactionProof;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use tracing::info;
+
+/// Read the states of the remote node at the forked state root by `listFieldStates`
+pub struct RpcRemoteStateReader {
+    client: Client,
+    state_root: H256,
+    handle: Handle,
+}
+
+impl RpcRemoteStateReader {
+    pub fn new(client: Client, state_root: H256) -> Self {
+        Self {
+            client,
+            state_root,
+            handle: Handle::current(),
+        }
+    }
+
+    async fn list_all_fields_async(
+        &self,
+        object_id: &ObjectID,
+        max_fields: u64,
+    ) -> Result<Vec<StateKV>> {
+        let mut fields = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .client
+                .rooch
+                .list_field_states(
+                    object_id.clone().into(),
+                    cursor,
+                    Some(MAX_RESULT_LIMIT),
+                    Some(StateOptions::new().state_root(self.state_root.into())),
+                )
+                .await?;
+            fields.extend(
+                page.data
+                    .into_iter()
+                    .map(|kv| (kv.field_key.0, ObjectState::from(kv.state))),
+            );
+            // Stop downloading the fields once the limit is exceeded
+            ensure!(
+                fields.len() as u64 <= max_fields,
+                "The remote object {} has more than the max fields({}) of a forked object",
+                object_id,
+                max_fields
+            );
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        Ok(fields)
+    }
+}
+
+impl RemoteStateReader for RpcRemoteStateReader {
+    fn list_all_fields(&self, object_id: &ObjectID, max_fields: u64) -> Result<Vec<StateKV>> {
+        let fields = self.list_all_fields_async(object_id, max_fields);
+        match Handle::try_current() {
+            // The actors read the states in the runtime
+            Ok(_) => tokio::task::block_in_place(|| self.handle.block_on(fields)),
+            // The parallel executor reads the states in its own threads
+            Err(_) => self.handle.block_on(fields),
+        }
+    }
+}
+
+/// Fork the remote node at the tx order, the trees of the objects are lazily loaded from the remote node
+/// by `listFieldStates` when they are read, and the new transactions are executed on top of the forked state locally.
+/// The objects with more fields than `max_object_fields` can not be loaded, reading them fails.
+/// The tx order defaults to the latest tx order of the remote node, and it is ignored
+/// if the store is already forked, the node resumes from the local latest state.
+pub async fn init_fork(
+    rooch_db: &RoochDB,
+    network: &RoochNetwork,
+    fork_url: &str,
+    fork_tx_order: Option<u64>,
+    max_object_fields: u64,
+) -> Result<()> {
+    let client = ClientBuilder::default().build(fork_url).await?;
+    let remote_chain_id = client.rooch.get_chain_id().await?;
+    ensure!(
+        remote_chain_id == network.chain_id.id,
+        "The chain id({}) of the remote node is not equal to the chain id({}) of the local node",
+        remote_chain_id,
+        network.chain_id.id
+    );
+    let moveos_store = &rooch_db.moveos_store;
+
+    if let Some(root) = rooch_db.latest_root()? {
+        let fork_startup_info = moveos_store
+            .get_config_store()
+            .get_fork_startup_info()?
+            .ok_or_else(|| format_err!("The store is not forked from a remote node"))?;
+        let fork_root =
+            ObjectMeta::root_metadata(fork_startup_info.state_root, fork_startup_info.size);
+        set_remote_reader(rooch_db, client, fork_root, max_object_fields)?;
+        info!(
+            "Resume the node forked from {}, the latest state root: {:?}",
+            fork_url,
+            root.state_root()
+        );
+        return Ok(());
+    }
+
+    let tx_order = match fork_tx_order {
+        Some(tx_order) => tx_order,
+        None => get_latest_tx_order(&client).await?,
+    };
+    info!("Fork the remote node {} at tx order {}", fork_url, tx_order);
+
+    // The sequencer continues to append the new transactions to the tx accumulator of the remote node
+    let proof: TransactionProof = client
+        .rooch
+        .get_transaction_proof(tx_order)
+        .await?
+        .ok_or_else(|| format_err!("The tx with order {} is not found", tx_order))?
+        .into();
+    let tx_accumulator_info = proof.proof.last_leaf_accumulator_info(
+        proof.sequence_info.tx_accumulator_root,
+        proof.tx_hash,
+        tx_order,
+    )?;
+    let execution_info = client
+        .rooch
+        .get_transactions_by_hash(vec![proof.tx_hash])
+        .await?
+        .pop()
+        .flatten()
+        .and_then(|tx| tx.execution_info)
+        .ok_or_else(|| format_err!("The tx with order {} is not executed", tx_order))?;
+    let root = client
+        .rooch
+        .get_states_with_option(
+            AccessPath::object(ObjectID::root()),
+            StateOptions::new().tx_order(tx_order),
+        )
+        .await?
+        .pop()
+        .flatten()
+        .map(|state| ObjectState::from(state).metadata)
+        .ok_or_else(|| format_err!("The root object at tx order {} is not found", tx_order))?;
+    ensure!(
+        root.state_root() == execution_info.state_root.0,
+        "The state root({:?}) of the root object is not equal to the state root({:?}) of the tx",
+        root.state_root(),
+        execution_info.state_root.0
+    );
+
+    set_remote_reader(rooch_db, client, root.clone(), max_object_fields)?;
+
+    let genesis = RoochGenesis::load_or_build(network.clone())?;
+    moveos_store
+        .get_config_store()
+        .save_genesis(genesis.genesis_info())?;
+    // The tx at the fork order is regarded as executed, the transactions before it are not synced
+    moveos_store
+        .get_transaction_store()
+        .save_tx_execution_info(TransactionExecutionInfo::new(
+            proof.tx_hash,
+            root.state_root(),
+            root.size(),
+            execution_info.event_root.0,
+            execution_info.gas_used.0,
+            execution_info.status.into(),
+        ))?;
+    rooch_db
+        .rooch_store
+        .get_transaction_store()
+        .save_tx_hash(tx_order, proof.tx_hash)?;
+    rooch_db
+        .rooch_store
+        .get_meta_store()
+        .save_sequencer_info(SequencerInfo::new(tx_order, tx_accumulator_info))?;
+    moveos_store
+        .get_config_store()
+        .save_fork_startup_info(StartupInfo::new(root.state_root(), root.size()))?;
+    // Save the startup info at last, the store is regarded as forked once it is saved
+    moveos_store
+        .get_config_store()
+        .save_startup_info(StartupInfo::new(root.state_root(), root.size()))?;
+    Ok(())
+}
+
+fn set_remote_reader(
+    rooch_db: &RoochDB,
+    client: Client,
+    fork_root: ObjectMeta,
+    max_object_fields: u64,
+) -> Result<()> {
+    rooch_db
+        .moveos_store
+        .get_state_node_store()
+        .set_remote_reader(
+            Arc::new(RpcRemoteStateReader::new(client, fork_root.state_root())),
+            fork_root,
+            max_object_fields,
+        )
+}
+
+async fn get_latest_tx_order(client: &Client) -> Result<u64> {
+    let page = client
+        .rooch
+        .get_transactions_by_order(None, Some(1), Some(true))
+        .await?;
+    page.data
+        .first()
+        .map(|tx| tx.transaction.sequence_info.tx_order.0)
+        .ok_or_else(|| format_err!("The remote node has no transactions"))
+}
@@ -18,15 +18,23 @@ use raw_store::errors::RawStoreError;
 use rooch_config::server_config::ServerConfig;
 use rooch_config::{RoochOpt, ServerOpt};
 use rooch_da::actor::da::DAActor;
+use rooch_da::messages::FlushChunkTick;
 use rooch_da::proxy::DAProxy;
 use rooch_db::RoochDB;
 use rooch_executor::actor::executor::ExecutorActor;
+use rooch_executor::actor::messages::PruneStateTick;
+use rooch_executor::actor::pruner::StatePrunerActor;
 use rooch_executor::actor::reader_executor::ReaderExecutorActor;
 use rooch_executor::proxy::ExecutorProxy;
 use rooch_genesis::RoochGenesis;
 use rooch_indexer::actor::indexer::IndexerActor;
 use rooch_indexer::actor::reader_indexer::IndexerReaderActor;
 use rooch_indexer::proxy::IndexerProxy;
+use rooch_mempool::actor::mempool::MempoolActor;
+use rooch_mempool::drainer::MempoolDrainer;
+use rooch_mempool::proxy::MempoolProxy;
+use rooch_pipeline_processor::actor::indexer_catch_up::{IndexerCatchUpActor, L1BlockBodyLoader};
+use rooch_pipeline_processor::actor::messages::{CatchUpIndexerTick, CommitBlockTick};
 use rooch_pipeline_processor::actor::processor::PipelineProcessorActor;
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
 use rooch_proposer::actor::messages::ProposeBlock;
@@ -46,12 +54,15 @@ use rooch_types::rooch_network::BuiltinChainID;
 use serde_json::json;
 use std::fmt::Debug;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{env, panic, process};
+use tokio::task::JoinHandle;
 use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::{error, info};
 
+pub mod fork;
 pub mod metrics_server;
 pub mod server;
 pub mod service;
@@ -62,6 +73,7 @@ static R_EXIT_CODE_NEED_HELP: i32 = 120;
 pub struct ServerHandle {
     handle: jsonrpsee::server::ServerHandle,
     timers: Vec<Timer>,
+    mempool_drainer: JoinHandle<()>,
     _opt: RoochOpt,
     _prometheus_registry: prometheus::Registry,
 }
@@ -71,6 +83,7 @@ impl ServerHandle {
         for timer in self.timers {
             timer.stop();
         }
+        self.mempool_drainer.abort();
         self.handle.stop()?;
         Ok(())
     }
@@ -213,7 +226,8 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     if network.chain_id == BuiltinChainID::Local.chain_id() {
         // local chain use current active account as sequencer account
         network.set_sequencer_account(sequencer_bitcoin_address);
-    } else {
+    } else if !opt.is_fork_mode() {
+        // the forked node sequences the new transactions by the local sequencer
         ensure!(
             network.genesis_config.sequencer_account == sequencer_bitcoin_address,
             "Sequencer({:?}) in genesis config is not equal to sequencer({:?}) in cli config",
@@ -222,6 +236,17 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         );
     }
 
+    if let Some(fork_url) = &opt.fork_url {
+        fork::init_fork(
+            &rooch_db,
+            &network,
+            fork_url,
+            opt.fork_tx_order,
+            opt.fork_max_object_fields(),
+        )
+        .await?;
+    }
+
     let genesis = RoochGenesis::load_or_init(network.clone(), &rooch_db)?;
 
     let root = match rooch_db.latest_root()? {
@@ -249,11 +274,40 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         .await?;
     let executor_proxy = ExecutorProxy::new(executor.into(), reader_executor.into());
 
+    let mut timers = vec![];
+    // Prune the stale state nodes in the background, keep all the states in archive mode
+    match opt.store_config().state_prune_retain_roots() {
+        // the object trees of the forked node are partially loaded from the remote node
+        Some(_) if opt.is_fork_mode() => info!("State pruner is disabled in fork mode"),
+        Some(retain_roots) => {
+            let state_prune_interval = opt.store_config().state_prune_interval();
+            info!(
+                "State pruner retains the latest {} state roots, prune interval: {}s",
+                retain_roots, state_prune_interval
+            );
+            // The stale state nodes are indexed only in prune mode
+            moveos_store
+                .get_state_store()
+                .get_prune_store()
+                .enable(root.state_root())?;
+            let pruner = StatePrunerActor::new(moveos_store.clone(), retain_roots)
+                .into_actor(Some("StatePruner"), &actor_system)
+                .await?;
+            let pruner_timer = Timer::start(
+                pruner,
+                Duration::from_secs(state_prune_interval),
+                PruneStateTick {},
+            );
+            timers.push(pruner_timer);
+        }
+        None => info!("State pruner is disabled, the node runs in archive mode"),
+    }
+
     // Init sequencer
     info!("RPC Server sequencer address: {:?}", sequencer_account);
     let sequencer = SequencerActor::new(
         sequencer_keypair.copy(),
-        rooch_store,
+        rooch_store.clone(),
         service_status,
         &prometheus_registry,
     )?
@@ -264,25 +318,35 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     // Init DA
     let da_config = opt.da_config().clone();
 
-    let da_proxy = DAProxy::new(
-        DAActor::new(da_config, &actor_system)
-            .await?
-            .into_actor(Some("DAProxy"), &actor_system)
-            .await?
-            .into(),
+    let da_actor = DAActor::new(da_config, rooch_store.clone(), &actor_system)
+        .await?
+        .into_actor(Some("DAProxy"), &actor_system)
+        .await?;
+    let da_proxy = DAProxy::new(da_actor.clone().into());
+    // the buffered batches are submitted when reaching the size or time threshold
+    let da_flush_duration_in_seconds: u64 = 1;
+    let da_flush_timer = Timer::start(
+        da_actor,
+        Duration::from_secs(da_flush_duration_in_seconds),
+        FlushChunkTick {},
     );
+    timers.push(da_flush_timer);
 
     // Init proposer
     let proposer_keypair = server_opt.proposer_keypair.unwrap();
     let proposer_account: RoochAddress = proposer_keypair.public().rooch_address()?;
     info!("RPC Server proposer address: {:?}", proposer_account);
-    let proposer = ProposerActor::new(proposer_keypair, da_proxy, &prometheus_registry)
-        .into_actor(Some("Proposer"), &actor_system)
-        .await?;
+    let proposer = ProposerActor::new(
+        proposer_keypair,
+        da_proxy.clone(),
+        rooch_store,
+        &prometheus_registry,
+    )?
+    .into_actor(Some("Proposer"), &actor_system)
+    .await?;
     let proposer_proxy = ProposerProxy::new(proposer.clone().into());
     //TODO load from config
     let block_propose_duration_in_seconds: u64 = 5;
-    let mut timers = vec![];
     let proposer_timer = Timer::start(
         proposer,
         Duration::from_secs(block_propose_duration_in_seconds),
@@ -291,34 +355,114 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     timers.push(proposer_timer);
 
     // Init indexer
-    let indexer_executor = IndexerActor::new(root, indexer_store)?
-        .into_actor(Some("Indexer"), &actor_system)
-        .await?;
+    let field_index_config = opt.store_config().field_index_config();
+    let last_indexed_tx_order = indexer_reader.query_last_tx_order()?;
+    let indexer_executor = IndexerActor::new(
+        root,
+        indexer_store,
+        moveos_store.clone(),
+        field_index_config,
+        last_indexed_tx_order,
+    )?
+    .into_actor(Some("Indexer"), &actor_system)
+    .await?;
     let indexer_reader_executor = IndexerReaderActor::new(indexer_reader)?
         .into_actor(Some("IndexerReader"), &actor_system)
         .await?;
     let indexer_proxy = IndexerProxy::new(indexer_executor.into(), indexer_reader_executor.into());
 
+    let ethereum_relayer_config = opt.ethereum_relayer_config();
+    let bitcoin_relayer_config = opt.bitcoin_relayer_config();
+    let bitcoin_client_proxy = match &bitcoin_relayer_config {
+        Some(bitcoin_relayer_config) if service_status.is_active() => {
+            let bitcoin_client = BitcoinClientActor::new(bitcoin_relayer_config.clone())?;
+            let bitcoin_client_actor_ref = bitcoin_client
+                .into_actor(Some("bitcoin_client_for_rpc_service"), &actor_system)
+                .await?;
+            Some(BitcoinClientProxy::new(bitcoin_client_actor_ref.into()))
+        }
+        _ => None,
+    };
+
     let mut processor = PipelineProcessorActor::new(
         executor_proxy.clone(),
         sequencer_proxy.clone(),
         proposer_proxy.clone(),
         indexer_proxy.clone(),
         service_status,
+        opt.parallel_execution,
+        &prometheus_registry,
+    );
+
+    // The missed txs are re-executed on a dedicated executor, so the new txs are not blocked by them.
+    // The metrics of the dedicated executor are not exported.
+    let catch_up_executor = ExecutorActor::new(
+        root.clone(),
+        moveos_store.clone(),
+        rooch_store.clone(),
+        &prometheus::Registry::new(),
+    )?
+    .into_actor(Some("IndexerCatchUpExecutor"), &actor_system)
+    .await?;
+    let catch_up_executor_proxy = ExecutorProxy::new(
+        catch_up_executor.into(),
+        executor_proxy.reader_actor.clone(),
+    );
+    let mut indexer_catch_up = IndexerCatchUpActor::new(
+        catch_up_executor_proxy,
+        sequencer_proxy.clone(),
+        indexer_proxy.clone(),
+        bitcoin_client_proxy
+            .clone()
+            .map(|proxy| Arc::new(proxy) as Arc<dyn L1BlockBodyLoader>),
+        service_status,
+        opt.store_config().indexer_catch_up_max_txs(),
         &prometheus_registry,
     );
 
     // Only process sequenced tx on startup when service is active
     if service_status.is_active() {
         processor.process_sequenced_tx_on_startup().await?;
+        // The indexer may miss some txs before the restart, the rest are caught up by the timer
+        match indexer_catch_up.catch_up_indexer().await {
+            Ok(txs) => info!("The indexer caught up {} txs on startup", txs),
+            Err(e) => error!("Catch up indexer on startup error: {}", e),
+        }
     }
     let processor_actor = processor
         .into_actor(Some("PipelineProcessor"), &actor_system)
         .await?;
-    let processor_proxy = PipelineProcessorProxy::new(processor_actor.into());
-
-    let ethereum_relayer_config = opt.ethereum_relayer_config();
-    let bitcoin_relayer_config = opt.bitcoin_relayer_config();
+    let indexer_catch_up_actor = indexer_catch_up
+        .into_actor(Some("IndexerCatchUp"), &actor_system)
+        .await?;
+    let processor_proxy = PipelineProcessorProxy::new(
+        processor_actor.clone().into(),
+        indexer_catch_up_actor.clone().into(),
+    );
+    let block_commit_timer = Timer::start(
+        processor_actor,
+        Duration::from_secs(opt.proposer_config().block_commit_interval()),
+        CommitBlockTick {},
+    );
+    timers.push(block_commit_timer);
+    let indexer_catch_up_timer = Timer::start(
+        indexer_catch_up_actor,
+        Duration::from_secs(opt.store_config().indexer_catch_up_interval()),
+        CatchUpIndexerTick {},
+    );
+    timers.push(indexer_catch_up_timer);
+
+    // Init mempool, the L2 transactions are queued in the mempool before they are sequenced
+    let mempool = MempoolActor::new(opt.mempool_config(), executor_proxy.clone());
+    let mempool_notify = mempool.notify();
+    let mempool_actor = mempool.into_actor(Some("Mempool"), &actor_system).await?;
+    let mempool_proxy = MempoolProxy::new(mempool_actor.into());
+    let mempool_drainer = MempoolDrainer::new(
+        mempool_proxy.clone(),
+        processor_proxy.clone(),
+        mempool_notify,
+    )
+    .start();
 
     if service_status.is_active()
         && (ethereum_relayer_config.is_some() || bitcoin_relayer_config.is_some())
@@ -341,17 +485,6 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         timers.push(relayer_timer);
     }
 
-    let bitcoin_client_proxy = if service_status.is_active() && bitcoin_relayer_config.is_some() {
-        let bitcoin_client = BitcoinClientActor::new(bitcoin_relayer_config.unwrap())?;
-        let bitcoin_client_actor_ref = bitcoin_client
-            .into_actor(Some("bitcoin_client_for_rpc_service"), &actor_system)
-            .await?;
-        let bitcoin_client_proxy = BitcoinClientProxy::new(bitcoin_client_actor_ref.into());
-        Some(bitcoin_client_proxy)
-    } else {
-        None
-    };
-
     let rpc_service = RpcService::new(
         network.chain_id.id,
         network.genesis_config.bitcoin_network,
@@ -359,6 +492,9 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         sequencer_proxy,
         indexer_proxy,
         processor_proxy,
+        proposer_proxy,
+        da_proxy,
+        mempool_proxy,
         bitcoin_client_proxy,
     );
     let aggregate_service = AggregateService::new(rpc_service.clone());
@@ -421,6 +557,7 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     Ok(ServerHandle {
         handle,
         timers,
+        mempool_drainer,
         _opt: opt,
         _prometheus_registry: prometheus_registry,
     })
@@ -4,25 +4,36 @@
 use crate::service::aggregate_service::AggregateService;
 use crate::service::rpc_service::RpcService;
 use anyhow::Result;
-use jsonrpsee::{core::async_trait, RpcModule};
+use jsonrpsee::core::SubscriptionResult;
+use jsonrpsee::{core::async_trait, PendingSubscriptionSink, RpcModule, SubscriptionMessage};
 use move_core_types::{
     account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
 };
+use moveos::moveos::ExecuteOptions;
 use moveos_types::{
     access_path::AccessPath,
     h256::H256,
     move_std::string::MoveString,
-    moveos_std::{move_module::MoveModule, object::ObjectID},
+    moveos_std::{
+        move_module::MoveModule,
+        object::{ObjectID, ObjectMeta},
+    },
     state::{AnnotatedState, FieldKey},
 };
 use rooch_rpc_api::jsonrpc_types::repair_view::{RepairIndexerParamsView, RepairIndexerTypeView};
 use rooch_rpc_api::jsonrpc_types::{
     account_view::BalanceInfoView,
+    block_view::BlockView,
+    da_view::DAStatusView,
     event_view::{EventFilterView, EventView, IndexerEventIDView, IndexerEventView},
+    indexer_view::IndexerStatusView,
+    mempool_view::{MempoolStatusView, PendingTransactionView},
+    proof_view::{StatesWithProofView, TransactionProofView},
     transaction_view::{TransactionFilterView, TransactionWithInfoView},
-    AccessPathView, BalanceInfoPageView, DryRunTransactionResponseView, EventOptions,
-    EventPageView, ExecuteTransactionResponseView, FunctionCallView, H256View,
-    IndexerEventPageView, IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView,
+    AccessPathView, BalanceInfoPageView, DryRunOptions, DryRunTransactionResponseView,
+    EventOptions, EventPageView, ExecuteTransactionResponseView, FieldStateFilterView,
+    FunctionCallView, GasEstimateView, H256View, IndexerEventPageView, IndexerFieldStatePageView,
+    IndexerObjectChangeView, IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView,
     ObjectIDVecView, ObjectStateFilterView, ObjectStateView, QueryOptions,
     RawTransactionOutputView, RoochAddressView, StateKVView, StateOptions, StatePageView, StrView,
     StructTagView, TransactionWithInfoPageView, TxOptions, UnitedAddressView,
@@ -36,11 +47,20 @@ use rooch_rpc_api::{
     jsonrpc_types::BytesView,
     RpcError, RpcResult,
 };
+use rooch_types::indexer::event::EventFilter;
 use rooch_types::indexer::state::IndexerStateID;
-use rooch_types::transaction::{RoochTransaction, RoochTransactionData, TransactionWithInfo};
+use rooch_types::indexer::transaction::TransactionFilter;
+use rooch_types::indexer::Filter;
+use rooch_types::transaction::{
+    ExecutedTransaction, RoochTransaction, RoochTransactionData, TransactionWithInfo,
+};
+use serde::Serialize;
 use std::cmp::min;
+use std::future::Future;
 use std::str::FromStr;
-use tracing::{debug, info};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
 
 pub struct RoochServer {
     rpc_service: RpcService,
@@ -55,6 +75,47 @@ impl RoochServer {
         }
     }
 
+    /// Send the items of each newly executed transaction to the subscriber, until it unsubscribes
+    async fn pipe_executed_transactions<T, F, Fut>(
+        &self,
+        pending: PendingSubscriptionSink,
+        to_items: F,
+    ) -> SubscriptionResult
+    where
+        T: Serialize,
+        F: Fn(Arc<ExecutedTransaction>) -> Fut,
+        Fut: Future<Output = Result<Vec<T>>>,
+    {
+        let mut receiver = match self.rpc_service.subscribe_transactions().await {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                pending.reject(RpcError::from(e)).await;
+                return Ok(());
+            }
+        };
+        let sink = pending.accept().await?;
+        loop {
+            let executed_tx = tokio::select! {
+                _ = sink.closed() => break,
+                result = receiver.recv() => match result {
+                    Ok(executed_tx) => executed_tx,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(
+                            "Subscriber lagged behind, {} executed transactions skipped",
+                            skipped
+                        );
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+            };
+            for item in to_items(executed_tx).await? {
+                sink.send(SubscriptionMessage::from_json(&item)?).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn transactions_to_view(
         &self,
         data: Vec<TransactionWithInfo>,
@@ -87,6 +148,16 @@ impl RoochServer {
             .collect::<Result<Vec<_>>>()?;
         Ok(data)
     }
+
+    /// The root object selected by the state_root or tx_order of the options, None for the latest root
+    async fn get_root(&self, state_option: &StateOptions) -> Result<Option<ObjectMeta>> {
+        self.rpc_service
+            .get_root(
+                state_option.state_root.map(Into::into),
+                state_option.tx_order.map(|tx_order| tx_order.0),
+            )
+            .await
+    }
 }
 
 #[async_trait]
@@ -159,9 +230,18 @@ impl RoochAPIServer for RoochServer {
         Ok(result)
     }
 
-    async fn dry_run(&self, payload: BytesView) -> RpcResult<DryRunTransactionResponseView> {
+    async fn dry_run(
+        &self,
+        payload: BytesView,
+        dry_run_option: Option<DryRunOptions>,
+    ) -> RpcResult<DryRunTransactionResponseView> {
+        let dry_run_option = dry_run_option.unwrap_or_default();
         let tx = bcs::from_bytes::<RoochTransactionData>(&payload.0)?;
-        let tx_result = self.rpc_service.dry_run_tx(tx).await?;
+        let options = ExecuteOptions {
+            trace: dry_run_option.trace,
+            profile_gas: dry_run_option.profile_gas,
+        };
+        let tx_result = self.rpc_service.dry_run_tx(tx, options).await?;
         let raw_output = tx_result.raw_output;
 
         let raw_output_view = RawTransactionOutputView {
@@ -173,18 +253,34 @@ impl RoochAPIServer for RoochServer {
         let tx_response = DryRunTransactionResponseView {
             raw_output: raw_output_view,
             vm_error_info: tx_result.vm_error_info.unwrap_or_default(),
+            call_traces: tx_result
+                .call_traces
+                .map(|call_traces| call_traces.into_iter().map(Into::into).collect()),
+            gas_profile: tx_result.gas_profile.map(Into::into),
         };
 
         Ok(tx_response)
     }
 
+    async fn estimate_gas(&self, payload: BytesView) -> RpcResult<GasEstimateView> {
+        let tx = bcs::from_bytes::<RoochTransactionData>(&payload.0)?;
+        let estimate = self.rpc_service.estimate_gas(tx).await?;
+        Ok(GasEstimateView::new(
+            estimate.gas_used,
+            estimate.recommended_max_gas_amount,
+            estimate.gas_factor,
+        ))
+    }
+
     async fn execute_view_function(
         &self,
         function_call: FunctionCallView,
+        state_option: Option<StateOptions>,
     ) -> RpcResult<AnnotatedFunctionResultView> {
+        let root = self.get_root(&state_option.unwrap_or_default()).await?;
         Ok(self
             .rpc_service
-            .execute_view_function(function_call.into())
+            .execute_view_function(function_call.into(), root)
             .await?
             .into())
     }
@@ -195,13 +291,14 @@ impl RoochAPIServer for RoochServer {
         state_option: Option<StateOptions>,
     ) -> RpcResult<Vec<Option<ObjectStateView>>> {
         let state_option = state_option.unwrap_or_default();
+        let root = self.get_root(&state_option).await?;
         let show_display =
             state_option.show_display && (access_path.0.is_object() || access_path.0.is_resource());
 
         let state_views = if state_option.decode || show_display {
             let states = self
                 .rpc_service
-                .get_annotated_states(access_path.into())
+                .get_annotated_states(access_path.into(), root)
                 .await?;
 
             if show_display {
@@ -232,7 +329,7 @@ impl RoochAPIServer for RoochServer {
             }
         } else {
             self.rpc_service
-                .get_states(access_path.into())
+                .get_states(access_path.into(), root)
                 .await?
                 .into_iter()
                 .map(|s| s.map(ObjectStateView::from))
@@ -241,6 +338,32 @@ impl RoochAPIServer for RoochServer {
         Ok(state_views)
     }
 
+    async fn get_states_with_proof(
+        &self,
+        access_path: AccessPathView,
+        state_root: Option<H256View>,
+    ) -> RpcResult<StatesWithProofView> {
+        let (state_root, states) = self
+            .rpc_service
+            .get_states_with_proof(access_path.into(), state_root.map(Into::into))
+            .await?;
+        Ok(StatesWithProofView {
+            state_root: state_root.into(),
+            states: states.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn get_transaction_proof(
+        &self,
+        tx_order: StrView<u64>,
+    ) -> RpcResult<Option<TransactionProofView>> {
+        Ok(self
+            .rpc_service
+            .get_transaction_proof(tx_order.0)
+            .await?
+            .map(Into::into))
+    }
+
     async fn list_states(
         &self,
         access_path: AccessPathView,
@@ -249,6 +372,7 @@ impl RoochAPIServer for RoochServer {
         state_option: Option<StateOptions>,
     ) -> RpcResult<StatePageView> {
         let state_option = state_option.unwrap_or_default();
+        let root = self.get_root(&state_option).await?;
         let show_display =
             state_option.show_display && (access_path.0.is_object() || access_path.0.is_resource());
 
@@ -263,7 +387,7 @@ impl RoochAPIServer for RoochServer {
         let mut data: Vec<StateKVView> = if state_option.decode || show_display {
             let (key_states, states): (Vec<FieldKey>, Vec<AnnotatedState>) = self
                 .rpc_service
-                .list_annotated_states(access_path.into(), cursor_of, limit_of + 1)
+                .list_annotated_states(access_path.into(), cursor_of, limit_of + 1, root)
                 .await?
                 .into_iter()
                 .unzip();
@@ -293,7 +417,7 @@ impl RoochAPIServer for RoochServer {
             }
         } else {
             self.rpc_service
-                .list_states(access_path.into(), cursor_of, limit_of + 1)
+                .list_states(access_path.into(), cursor_of, limit_of + 1, root)
                 .await?
                 .into_iter()
                 .map(|(key, state)| StateKVView::new(key.into(), state.into()))
@@ -321,12 +445,15 @@ impl RoochAPIServer for RoochServer {
         let object_ids: Vec<ObjectID> = object_ids.into();
         let access_path = AccessPath::objects(object_ids);
         let state_option = state_option.unwrap_or_default();
+        let root = self.get_root(&state_option).await?;
         let decode = state_option.decode;
         let show_display = state_option.show_display;
 
         let mut objects_view = if decode || show_display {
-            let states: Vec<Option<AnnotatedState>> =
-                self.rpc_service.get_annotated_states(access_path).await?;
+            let states: Vec<Option<AnnotatedState>> = self
+                .rpc_service
+                .get_annotated_states(access_path, root)
+                .await?;
 
             let mut valid_display_field_views = if show_display {
                 let valid_states = states.iter().filter_map(|s| s.as_ref()).collect::<Vec<_>>();
@@ -364,7 +491,7 @@ impl RoochAPIServer for RoochServer {
             }
         } else {
             self.rpc_service
-                .get_states(access_path)
+                .get_states(access_path, root)
                 .await?
                 .into_iter()
                 .map(|s| s.map(Into::into))
@@ -558,6 +685,47 @@ impl RoochAPIServer for RoochServer {
         })
     }
 
+    async fn get_block(&self, block_number: StrView<u128>) -> RpcResult<Option<BlockView>> {
+        let block = self.rpc_service.get_block(block_number.0).await?;
+        Ok(block.map(Into::into))
+    }
+
+    async fn get_latest_block(&self) -> RpcResult<Option<BlockView>> {
+        let block = self.rpc_service.get_latest_block().await?;
+        Ok(block.map(Into::into))
+    }
+
+    async fn get_da_status(&self) -> RpcResult<DAStatusView> {
+        let status = self.rpc_service.get_da_status().await?;
+        Ok(status.into())
+    }
+
+    async fn get_pending_transactions(
+        &self,
+        sender: Option<UnitedAddressView>,
+        limit: Option<StrView<u64>>,
+    ) -> RpcResult<Vec<PendingTransactionView>> {
+        let limit_of = min(
+            limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
+            MAX_RESULT_LIMIT_USIZE,
+        );
+        let txs = self
+            .rpc_service
+            .get_pending_transactions(sender.map(Into::into), limit_of)
+            .await?;
+        Ok(txs.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_mempool_status(&self) -> RpcResult<MempoolStatusView> {
+        let status = self.rpc_service.get_mempool_status().await?;
+        Ok(status.into())
+    }
+
+    async fn get_indexer_status(&self) -> RpcResult<IndexerStatusView> {
+        let status = self.rpc_service.get_indexer_status().await?;
+        Ok(status.into())
+    }
+
     async fn get_balance(
         &self,
         account_addr: UnitedAddressView,
@@ -617,7 +785,7 @@ impl RoochAPIServer for RoochServer {
         let access_path = AccessPath::module(&module_id);
         let module = self
             .rpc_service
-            .get_states(access_path)
+            .get_states(access_path, None)
             .await?
             .pop()
             .flatten();
@@ -762,6 +930,47 @@ impl RoochAPIServer for RoochServer {
         })
     }
 
+    async fn query_field_states(
+        &self,
+        filter: FieldStateFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        query_option: Option<QueryOptions>,
+    ) -> RpcResult<IndexerFieldStatePageView> {
+        let limit_of = min(
+            limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
+            MAX_RESULT_LIMIT_USIZE,
+        );
+        let query_option = query_option.unwrap_or_default();
+        let descending_order = query_option.descending;
+
+        let mut field_states = self
+            .rpc_service
+            .query_field_states(
+                filter.into(),
+                cursor.map(Into::into),
+                limit_of + 1,
+                descending_order,
+                query_option.decode,
+            )
+            .await?;
+
+        let has_next_page = field_states.len() > limit_of;
+        field_states.truncate(limit_of);
+
+        let next_cursor = field_states
+            .last()
+            .cloned()
+            .map_or(cursor, |t| Some(t.indexer_id));
+
+        Ok(IndexerFieldStatePageView {
+            data: field_states,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
     async fn repair_indexer(
         &self,
         repair_type: RepairIndexerTypeView,
@@ -772,6 +981,79 @@ impl RoochAPIServer for RoochServer {
             .await?;
         Ok(())
     }
+
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: EventFilterView,
+    ) -> SubscriptionResult {
+        let filter: EventFilter = filter.into();
+        let filter = &filter;
+        self.pipe_executed_transactions(pending, move |executed_tx| async move {
+            Ok(executed_tx
+                .indexer_events()
+                .into_iter()
+                .filter(|event| filter.matches(event))
+                .map(IndexerEventView::from)
+                .collect())
+        })
+        .await
+    }
+
+    async fn subscribe_transactions(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: TransactionFilterView,
+    ) -> SubscriptionResult {
+        let filter: TransactionFilter = filter.into();
+        if let TransactionFilter::OriginalAddress(_) = filter {
+            pending
+                .reject(RpcError::UnexpectedError(
+                    "The original_address filter is not supported by subscription".to_string(),
+                ))
+                .await;
+            return Ok(());
+        }
+        let filter = &filter;
+        self.pipe_executed_transactions(pending, move |executed_tx| async move {
+            if !filter.matches(&executed_tx.indexer_transaction()?) {
+                return Ok(vec![]);
+            }
+            let tx = TransactionWithInfo {
+                transaction: executed_tx.transaction.clone(),
+                execution_info: Some(executed_tx.execution_info.clone()),
+            };
+            self.transactions_to_view(vec![tx]).await
+        })
+        .await
+    }
+
+    async fn subscribe_object_changes(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: ObjectStateFilterView,
+    ) -> SubscriptionResult {
+        let filter = match ObjectStateFilterView::try_into_object_state_filter(
+            filter,
+            QueryOptions::default(),
+        ) {
+            Ok(filter) => filter,
+            Err(e) => {
+                pending.reject(RpcError::from(e)).await;
+                return Ok(());
+            }
+        };
+        let filter = &filter;
+        self.pipe_executed_transactions(pending, move |executed_tx| async move {
+            Ok(executed_tx
+                .object_changes()
+                .into_iter()
+                .filter(|change| filter.matches(&change.state))
+                .map(IndexerObjectChangeView::from)
+                .collect())
+        })
+        .await
+    }
 }
 
 impl RoochRpcModule for RoochServer {
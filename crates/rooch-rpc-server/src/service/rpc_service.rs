@@ -5,32 +5,48 @@ use anyhow::{format_err, Ok, Result};
 use bitcoincore_rpc::bitcoin::Txid;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::{ModuleId, StructTag};
+use moveos::moveos::ExecuteOptions;
 use moveos_types::access_path::AccessPath;
 use moveos_types::function_return_value::AnnotatedFunctionResult;
 use moveos_types::h256::H256;
 use moveos_types::moveos_std::display::{get_object_display_id, RawDisplay};
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
-use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState};
+use moveos_types::state_proof::StateWithProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::{FunctionCall, TransactionExecutionInfo};
-use rooch_executor::actor::messages::DryRunTransactionResult;
+use rooch_da::proxy::DAProxy;
+use rooch_executor::actor::messages::{DryRunTransactionResult, GasEstimateResult};
 use rooch_executor::proxy::ExecutorProxy;
 use rooch_indexer::proxy::IndexerProxy;
+use rooch_mempool::proxy::MempoolProxy;
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
+use rooch_proposer::proxy::ProposerProxy;
 use rooch_relayer::actor::bitcoin_client_proxy::BitcoinClientProxy;
-use rooch_rpc_api::jsonrpc_types::{DisplayFieldsView, IndexerObjectStateView, ObjectMetaView};
+use rooch_rpc_api::jsonrpc_types::{
+    DisplayFieldsView, IndexerFieldStateView, IndexerObjectStateView, ObjectMetaView,
+};
 use rooch_sequencer::proxy::SequencerProxy;
 use rooch_types::address::{BitcoinAddress, RoochAddress};
+use rooch_types::block::Block;
+use rooch_types::da::DAStatus;
 use rooch_types::framework::address_mapping::RoochToBitcoinAddressMapping;
 use rooch_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
+use rooch_types::indexer::field::FieldStateFilter;
 use rooch_types::indexer::state::{IndexerObjectState, IndexerStateID, ObjectStateFilter};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
+use rooch_types::indexer::IndexerStatus;
+use rooch_types::mempool::{MempoolStatus, PendingTransactionInfo};
 use rooch_types::repair::{RepairIndexerParams, RepairIndexerType};
+use rooch_types::transaction::proof::TransactionProof;
 use rooch_types::transaction::{
-    ExecuteTransactionResponse, LedgerTransaction, RoochTransaction, RoochTransactionData,
+    ExecuteTransactionResponse, ExecutedTransaction, LedgerTransaction, RoochTransaction,
+    RoochTransactionData,
 };
 use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::broadcast;
 
 /// RpcService is the implementation of the RPC service.
 /// It is the glue between the RPC server(EthAPIServer,RoochApiServer) and the rooch's actors.
@@ -43,6 +59,9 @@ pub struct RpcService {
     pub(crate) sequencer: SequencerProxy,
     pub(crate) indexer: IndexerProxy,
     pub(crate) pipeline_processor: PipelineProcessorProxy,
+    pub(crate) proposer: ProposerProxy,
+    pub(crate) da: DAProxy,
+    pub(crate) mempool: MempoolProxy,
     pub(crate) bitcoin_client: Option<BitcoinClientProxy>,
 }
 
@@ -54,6 +73,9 @@ impl RpcService {
         sequencer: SequencerProxy,
         indexer: IndexerProxy,
         pipeline_processor: PipelineProcessorProxy,
+        proposer: ProposerProxy,
+        da: DAProxy,
+        mempool: MempoolProxy,
         bitcoin_client: Option<BitcoinClientProxy>,
     ) -> Self {
         Self {
@@ -63,6 +85,9 @@ impl RpcService {
             sequencer,
             indexer,
             pipeline_processor,
+            proposer,
+            da,
+            mempool,
             bitcoin_client,
         }
     }
@@ -77,48 +102,123 @@ impl RpcService {
         self.bitcoin_network
     }
 
+    /// Add the transaction to the mempool, do not wait for its execution
     pub async fn queue_tx(&self, tx: RoochTransaction) -> Result<()> {
-        //TODO implement queue tx and do not wait to execute
-        let _ = self.execute_tx(tx).await?;
+        let _ = self.mempool.add_transaction(tx).await?;
         Ok(())
     }
 
     pub async fn execute_tx(&self, tx: RoochTransaction) -> Result<ExecuteTransactionResponse> {
-        self.pipeline_processor.execute_l2_tx(tx).await
+        self.mempool.execute_transaction(tx).await
     }
 
-    pub async fn dry_run_tx(&self, tx: RoochTransactionData) -> Result<DryRunTransactionResult> {
+    pub async fn dry_run_tx(
+        &self,
+        tx: RoochTransactionData,
+        options: ExecuteOptions,
+    ) -> Result<DryRunTransactionResult> {
         let verified_tx = self.executor.convert_to_verified_tx(tx).await?;
-        self.executor.dry_run_transaction(verified_tx).await
+        self.executor
+            .dry_run_transaction(verified_tx, options)
+            .await
+    }
+
+    pub async fn estimate_gas(&self, tx: RoochTransactionData) -> Result<GasEstimateResult> {
+        self.executor.estimate_gas(tx).await
     }
 
     pub async fn execute_view_function(
         &self,
         function_call: FunctionCall,
+        root: Option<ObjectMeta>,
     ) -> Result<AnnotatedFunctionResult> {
         let module_id = function_call.function_id.module_id.clone();
-        if !self.exists_module(module_id.clone()).await? {
+        if !self.exists_module(module_id.clone(), root.clone()).await? {
             return Err(anyhow::anyhow!("Module does not exist: {}", module_id));
         }
 
-        let resp = self.executor.execute_view_function(function_call).await?;
+        let resp = self
+            .executor
+            .execute_view_function(function_call, root)
+            .await?;
         Ok(resp)
     }
 
-    pub async fn get_states(&self, access_path: AccessPath) -> Result<Vec<Option<ObjectState>>> {
-        self.executor.get_states(access_path).await
+    pub async fn get_states(
+        &self,
+        access_path: AccessPath,
+        root: Option<ObjectMeta>,
+    ) -> Result<Vec<Option<ObjectState>>> {
+        self.executor.get_states(access_path, root).await
     }
 
-    pub async fn exists_module(&self, module_id: ModuleId) -> Result<bool> {
-        let mut resp = self.get_states(AccessPath::module(&module_id)).await?;
+    /// Get the root object to read the historical states, by the state root or
+    /// after the transaction of the tx_order is executed.
+    /// Returns None if both are not set, the states are read from the latest root.
+    pub async fn get_root(
+        &self,
+        state_root: Option<H256>,
+        tx_order: Option<u64>,
+    ) -> Result<Option<ObjectMeta>> {
+        match (state_root, tx_order) {
+            (None, None) => Ok(None),
+            (Some(_), Some(_)) => Err(format_err!(
+                "Only one of state_root and tx_order can be specified"
+            )),
+            // The size of the root object is not recorded by the state root
+            (Some(state_root), None) => Ok(Some(ObjectMeta::root_metadata(state_root, 0))),
+            (None, Some(tx_order)) => {
+                let tx_hash = self
+                    .get_tx_hashs(vec![tx_order])
+                    .await?
+                    .pop()
+                    .flatten()
+                    .ok_or_else(|| {
+                        format_err!("Transaction with tx_order {} not found", tx_order)
+                    })?;
+                let execution_info = self
+                    .get_transaction_execution_infos_by_hash(vec![tx_hash])
+                    .await?
+                    .pop()
+                    .flatten()
+                    .ok_or_else(|| {
+                        format_err!("Transaction with tx_order {} is not executed", tx_order)
+                    })?;
+                Ok(Some(ObjectMeta::root_metadata(
+                    execution_info.state_root,
+                    execution_info.size,
+                )))
+            }
+        }
+    }
+
+    pub async fn get_states_with_proof(
+        &self,
+        access_path: AccessPath,
+        state_root: Option<H256>,
+    ) -> Result<(H256, Vec<StateWithProof>)> {
+        self.executor
+            .get_states_with_proof(access_path, state_root)
+            .await
+    }
+
+    pub async fn exists_module(
+        &self,
+        module_id: ModuleId,
+        root: Option<ObjectMeta>,
+    ) -> Result<bool> {
+        let mut resp = self
+            .get_states(AccessPath::module(&module_id), root)
+            .await?;
         Ok(resp.pop().flatten().is_some())
     }
 
     pub async fn get_annotated_states(
         &self,
         access_path: AccessPath,
+        root: Option<ObjectMeta>,
     ) -> Result<Vec<Option<AnnotatedState>>> {
-        self.executor.get_annotated_states(access_path).await
+        self.executor.get_annotated_states(access_path, root).await
     }
 
     pub async fn list_states(
@@ -126,8 +226,11 @@ impl RpcService {
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
+        root: Option<ObjectMeta>,
     ) -> Result<Vec<StateKV>> {
-        self.executor.list_states(access_path, cursor, limit).await
+        self.executor
+            .list_states(access_path, cursor, limit, root)
+            .await
     }
 
     pub async fn list_annotated_states(
@@ -135,9 +238,10 @@ impl RpcService {
         access_path: AccessPath,
         cursor: Option<FieldKey>,
         limit: usize,
+        root: Option<ObjectMeta>,
     ) -> Result<Vec<AnnotatedStateKV>> {
         self.executor
-            .list_annotated_states(access_path, cursor, limit)
+            .list_annotated_states(access_path, cursor, limit, root)
             .await
     }
 
@@ -200,6 +304,11 @@ impl RpcService {
         Ok(resp)
     }
 
+    pub async fn get_transaction_proof(&self, tx_order: u64) -> Result<Option<TransactionProof>> {
+        let resp = self.sequencer.get_transaction_proof(tx_order).await?;
+        Ok(resp)
+    }
+
     pub async fn get_transaction_execution_infos_by_hash(
         &self,
         tx_hashes: Vec<H256>,
@@ -216,6 +325,42 @@ impl RpcService {
         Ok(resp)
     }
 
+    pub async fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        let resp = self.proposer.get_block(block_number).await?;
+        Ok(resp)
+    }
+
+    pub async fn get_latest_block(&self) -> Result<Option<Block>> {
+        let resp = self.proposer.get_latest_block().await?;
+        Ok(resp)
+    }
+
+    pub async fn get_da_status(&self) -> Result<DAStatus> {
+        self.da.get_status().await
+    }
+
+    pub async fn get_pending_transactions(
+        &self,
+        sender: Option<RoochAddress>,
+        limit: usize,
+    ) -> Result<Vec<PendingTransactionInfo>> {
+        self.mempool.get_pending_transactions(sender, limit).await
+    }
+
+    pub async fn get_mempool_status(&self) -> Result<MempoolStatus> {
+        self.mempool.get_status().await
+    }
+
+    pub async fn get_indexer_status(&self) -> Result<IndexerStatus> {
+        self.pipeline_processor.get_indexer_status().await
+    }
+
+    pub async fn subscribe_transactions(
+        &self,
+    ) -> Result<broadcast::Receiver<Arc<ExecutedTransaction>>> {
+        self.pipeline_processor.subscribe_transactions().await
+    }
+
     pub async fn query_transactions(
         &self,
         filter: TransactionFilter,
@@ -264,7 +409,7 @@ impl RpcService {
 
         let access_path = AccessPath::objects(object_ids.clone());
         let mut object_states = if decode || show_display {
-            let annotated_states = self.get_annotated_states(access_path).await?;
+            let annotated_states = self.get_annotated_states(access_path, None).await?;
             let mut displays: BTreeMap<ObjectID, Option<DisplayFieldsView>> = if show_display {
                 let valid_states = annotated_states
                     .iter()
@@ -308,7 +453,7 @@ impl RpcService {
             }
             object_states
         } else {
-            let states = self.get_states(access_path).await?;
+            let states = self.get_states(access_path, None).await?;
             states
                 .into_iter()
                 .zip(indexer_ids)
@@ -335,6 +480,68 @@ impl RpcService {
         Ok(object_states)
     }
 
+    pub async fn query_field_states(
+        &self,
+        filter: FieldStateFilter,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateID>,
+        limit: usize,
+        descending_order: bool,
+        decode: bool,
+    ) -> Result<Vec<IndexerFieldStateView>> {
+        let field_states = self
+            .indexer
+            .query_field_states(filter, cursor, limit, descending_order)
+            .await?;
+        let field_ids = field_states
+            .iter()
+            .map(|field| field.field_id())
+            .collect::<Vec<_>>();
+        if field_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let access_path = AccessPath::objects(field_ids);
+        let field_state_views = if decode {
+            let annotated_states = self.get_annotated_states(access_path, None).await?;
+            annotated_states
+                .into_iter()
+                .zip(field_states)
+                .filter_map(|(state_opt, field)| match state_opt {
+                    Some(state) => Some(IndexerFieldStateView::new_from_annotated_state(
+                        field, state,
+                    )),
+                    None => {
+                        // Sometime the indexer is delayed, maybe the field is deleted in the state
+                        tracing::trace!(
+                            "Field {} in the indexer but can not found in state",
+                            field.field_id()
+                        );
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        } else {
+            let states = self.get_states(access_path, None).await?;
+            states
+                .into_iter()
+                .zip(field_states)
+                .filter_map(|(state_opt, field)| match state_opt {
+                    Some(state) => Some(IndexerFieldStateView::new_from_object_state(field, state)),
+                    None => {
+                        // Sometime the indexer is delayed, maybe the field is deleted in the state
+                        tracing::trace!(
+                            "Field {} in the indexer but can not found in state",
+                            field.field_id()
+                        );
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        Ok(field_state_views)
+    }
+
     pub async fn fill_bitcoin_addresses(
         &self,
         mut metadatas: Vec<&mut ObjectMetaView>,
@@ -369,7 +576,7 @@ impl RpcService {
 
         let access_path = AccessPath::fields(mapping_object_id, owner_keys);
         let address_mapping = self
-            .get_states(access_path)
+            .get_states(access_path, None)
             .await?
             .into_iter()
             .zip(user_addresses)
@@ -407,7 +614,7 @@ impl RpcService {
         // get display fields
         let path = AccessPath::objects(display_ids);
         let mut display_fields = self
-            .get_states(path)
+            .get_states(path, None)
             .await?
             .into_iter()
             .map(|option_s| {
@@ -467,7 +674,7 @@ impl RpcService {
                     match repair_params {
                         RepairIndexerParams::ObjectId(object_ids) => {
                             let states = self
-                                .get_states(AccessPath::objects(object_ids.clone()))
+                                .get_states(AccessPath::objects(object_ids.clone()), None)
                                 .await?;
 
                             let mut remove_object_ids = vec![];
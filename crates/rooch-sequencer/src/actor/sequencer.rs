@@ -5,8 +5,8 @@ use std::sync::Arc;
 use std::time::SystemTime;
 
 use crate::messages::{
-    GetSequencerOrderMessage, GetTransactionByHashMessage, GetTransactionsByHashMessage,
-    GetTxHashsMessage, TransactionSequenceMessage,
+    GetSequencerOrderMessage, GetTransactionByHashMessage, GetTransactionProofMessage,
+    GetTransactionsByHashMessage, GetTxHashsMessage, TransactionSequenceMessage,
 };
 use crate::metrics::SequencerMetrics;
 use accumulator::{Accumulator, MerkleAccumulator};
@@ -14,7 +14,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor};
 use function_name::named;
-use moveos_types::h256::{self, H256};
+use moveos_types::h256::H256;
 use prometheus::Registry;
 use rooch_store::meta_store::MetaStore;
 use rooch_store::transaction_store::TransactionStore;
@@ -22,6 +22,7 @@ use rooch_store::RoochStore;
 use rooch_types::crypto::{RoochKeyPair, Signature};
 use rooch_types::sequencer::SequencerInfo;
 use rooch_types::service_status::ServiceStatus;
+use rooch_types::transaction::proof::{tx_order_witness_hash, TransactionProof};
 use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
 use tracing::info;
 
@@ -113,9 +114,7 @@ impl SequencerActor {
         let tx_order = self.last_sequencer_info.last_order + 1;
 
         let hash = tx_data.tx_hash();
-        let mut witness_data = hash.as_ref().to_vec();
-        witness_data.extend(tx_order.to_le_bytes().iter());
-        let witness_hash = h256::sha3_256_of(&witness_data);
+        let witness_hash = tx_order_witness_hash(hash, tx_order);
         let tx_order_signature = Signature::sign(&witness_hash.0, &self.sequencer_key)
             .as_ref()
             .to_vec();
@@ -201,3 +200,40 @@ impl Handler<GetSequencerOrderMessage> for SequencerActor {
         Ok(self.last_sequencer_info.last_order)
     }
 }
+
+#[async_trait]
+impl Handler<GetTransactionProofMessage> for SequencerActor {
+    async fn handle(
+        &mut self,
+        msg: GetTransactionProofMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<TransactionProof>> {
+        let GetTransactionProofMessage { tx_order } = msg;
+        let tx_hash = match self
+            .rooch_store
+            .get_tx_hashs(vec![tx_order])?
+            .pop()
+            .flatten()
+        {
+            Some(tx_hash) => tx_hash,
+            None => return Ok(None),
+        };
+        let tx = match self.rooch_store.get_transaction_by_hash(tx_hash)? {
+            Some(tx) => tx,
+            None => return Ok(None),
+        };
+        // Prove the tx by the accumulator root recorded in its sequence info,
+        // so the proof can be verified with the signed sequence info alone.
+        let tx_accumulator = self
+            .tx_accumulator
+            .fork(Some(tx.sequence_info.tx_accumulator_info()));
+        let proof = tx_accumulator
+            .get_proof(tx_order)?
+            .ok_or_else(|| anyhow::anyhow!("Get proof of tx order {} failed", tx_order))?;
+        Ok(Some(TransactionProof::new(
+            tx_hash,
+            tx.sequence_info,
+            proof,
+        )))
+    }
+}
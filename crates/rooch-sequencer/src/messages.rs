@@ -4,6 +4,7 @@
 use anyhow::Result;
 use coerce::actor::message::Message;
 use moveos_types::h256::H256;
+use rooch_types::transaction::proof::TransactionProof;
 use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
 use serde::{Deserialize, Serialize};
 
@@ -52,3 +53,13 @@ pub struct GetSequencerOrderMessage {}
 impl Message for GetSequencerOrderMessage {
     type Result = Result<u64>;
 }
+
+/// Get the accumulator proof of the transaction at the tx order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTransactionProofMessage {
+    pub tx_order: u64,
+}
+
+impl Message for GetTransactionProofMessage {
+    type Result = Result<Option<TransactionProof>>;
+}
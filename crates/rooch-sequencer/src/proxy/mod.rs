@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::messages::{
-    GetSequencerOrderMessage, GetTransactionByHashMessage, GetTransactionsByHashMessage,
-    GetTxHashsMessage,
+    GetSequencerOrderMessage, GetTransactionByHashMessage, GetTransactionProofMessage,
+    GetTransactionsByHashMessage, GetTxHashsMessage,
 };
 use crate::{actor::sequencer::SequencerActor, messages::TransactionSequenceMessage};
 use anyhow::Result;
 use coerce::actor::ActorRef;
 use moveos_types::h256::H256;
+use rooch_types::transaction::proof::TransactionProof;
 use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
 
 #[derive(Clone)]
@@ -47,4 +48,10 @@ impl SequencerProxy {
     pub async fn get_sequencer_order(&self) -> Result<u64> {
         self.actor.send(GetSequencerOrderMessage {}).await?
     }
+
+    pub async fn get_transaction_proof(&self, tx_order: u64) -> Result<Option<TransactionProof>> {
+        self.actor
+            .send(GetTransactionProofMessage { tx_order })
+            .await?
+    }
 }
@@ -0,0 +1,184 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    DA_CELESTIA_BATCH_HASH_COLUMN_FAMILY_NAME, DA_CELESTIA_BLOCK_CHUNK_COLUMN_FAMILY_NAME,
+    DA_CELESTIA_CHUNK_HEIGHTS_COLUMN_FAMILY_NAME, DA_CHUNK_SUBMISSION_COLUMN_FAMILY_NAME,
+    DA_PENDING_BATCH_COLUMN_FAMILY_NAME,
+};
+use anyhow::Result;
+use moveos_types::h256::H256;
+use raw_store::{derive_store, CodecKVStore, CodecWriteBatch, StoreInstance};
+use rooch_types::da::{DABatch, DAChunkSubmission};
+
+derive_store!(
+    DAPendingBatchStore,
+    u128,
+    DABatch,
+    DA_PENDING_BATCH_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    DAChunkSubmissionStore,
+    u128,
+    DAChunkSubmission,
+    DA_CHUNK_SUBMISSION_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    DACelestiaChunkHeightsStore,
+    u128,
+    Vec<u64>,
+    DA_CELESTIA_CHUNK_HEIGHTS_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    DACelestiaBlockChunkStore,
+    u128,
+    u128,
+    DA_CELESTIA_BLOCK_CHUNK_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    DACelestiaBatchHashStore,
+    H256,
+    u128,
+    DA_CELESTIA_BATCH_HASH_COLUMN_FAMILY_NAME
+);
+
+/// The DA outbox, it keeps the batches until all DA servers acknowledge them
+pub trait DAMetaStore {
+    fn save_pending_batch(&self, batch: DABatch) -> Result<()>;
+
+    fn get_pending_batch(&self, block_number: u128) -> Result<Option<DABatch>>;
+
+    /// Get all pending batches, sorted by block number
+    fn get_pending_batches(&self) -> Result<Vec<DABatch>>;
+
+    fn save_chunk_submission(&self, submission: DAChunkSubmission) -> Result<()>;
+
+    /// Get all chunk submissions, sorted by chunk id
+    fn get_chunk_submissions(&self) -> Result<Vec<DAChunkSubmission>>;
+
+    /// Remove the chunk submission and the batches in it
+    fn remove_chunk_submission(&self, submission: DAChunkSubmission) -> Result<()>;
+
+    /// Celestia blobs could only be fetched by height, record the heights of the submitted chunk
+    /// and the blocks in it for reading back.
+    /// `batches` are the (block_number, batch_hash) of the batches in the chunk.
+    fn save_celestia_chunk_index(
+        &self,
+        chunk_id: u128,
+        heights: Vec<u64>,
+        batches: Vec<(u128, H256)>,
+    ) -> Result<()>;
+
+    fn get_celestia_chunk_heights(&self, chunk_id: u128) -> Result<Option<Vec<u64>>>;
+
+    /// Get the chunk id of the chunk including the block
+    fn get_celestia_block_chunk(&self, block_number: u128) -> Result<Option<u128>>;
+
+    /// Get the block number of the batch
+    fn get_celestia_batch_block_number(&self, batch_hash: H256) -> Result<Option<u128>>;
+}
+
+#[derive(Clone)]
+pub struct DAMetaDBStore {
+    pending_batch_store: DAPendingBatchStore,
+    chunk_submission_store: DAChunkSubmissionStore,
+    celestia_chunk_heights_store: DACelestiaChunkHeightsStore,
+    celestia_block_chunk_store: DACelestiaBlockChunkStore,
+    celestia_batch_hash_store: DACelestiaBatchHashStore,
+}
+
+impl DAMetaDBStore {
+    pub fn new(instance: StoreInstance) -> Self {
+        DAMetaDBStore {
+            pending_batch_store: DAPendingBatchStore::new(instance.clone()),
+            chunk_submission_store: DAChunkSubmissionStore::new(instance.clone()),
+            celestia_chunk_heights_store: DACelestiaChunkHeightsStore::new(instance.clone()),
+            celestia_block_chunk_store: DACelestiaBlockChunkStore::new(instance.clone()),
+            celestia_batch_hash_store: DACelestiaBatchHashStore::new(instance),
+        }
+    }
+
+    pub fn save_pending_batch(&self, batch: DABatch) -> Result<()> {
+        self.pending_batch_store.put_sync(batch.block_number, batch)
+    }
+
+    pub fn get_pending_batch(&self, block_number: u128) -> Result<Option<DABatch>> {
+        self.pending_batch_store.kv_get(block_number)
+    }
+
+    pub fn get_pending_batches(&self) -> Result<Vec<DABatch>> {
+        let mut iter = self.pending_batch_store.iter()?;
+        iter.seek_to_first();
+        let mut batches = iter
+            .map(|item| item.map(|(_block_number, batch)| batch))
+            .collect::<Result<Vec<_>>>()?;
+        // the key is encoded in little endian, so the iterator is not sorted by block number
+        batches.sort_by_key(|batch| batch.block_number);
+        Ok(batches)
+    }
+
+    pub fn save_chunk_submission(&self, submission: DAChunkSubmission) -> Result<()> {
+        self.chunk_submission_store
+            .put_sync(submission.chunk_id, submission)
+    }
+
+    pub fn get_chunk_submissions(&self) -> Result<Vec<DAChunkSubmission>> {
+        let mut iter = self.chunk_submission_store.iter()?;
+        iter.seek_to_first();
+        let mut submissions = iter
+            .map(|item| item.map(|(_chunk_id, submission)| submission))
+            .collect::<Result<Vec<_>>>()?;
+        submissions.sort_by_key(|submission| submission.chunk_id);
+        Ok(submissions)
+    }
+
+    pub fn remove_chunk_submission(&self, submission: DAChunkSubmission) -> Result<()> {
+        // remove the submission first, the left batches would be submitted again on restart
+        self.chunk_submission_store.remove(submission.chunk_id)?;
+        self.pending_batch_store
+            .write_batch_sync(CodecWriteBatch::new_deletes(
+                (submission.chunk_id..=submission.end_block_number).collect(),
+            ))
+    }
+
+    pub fn save_celestia_chunk_index(
+        &self,
+        chunk_id: u128,
+        heights: Vec<u64>,
+        batches: Vec<(u128, H256)>,
+    ) -> Result<()> {
+        // save the block and batch index first, the chunk is readable after the heights saved
+        self.celestia_block_chunk_store
+            .write_batch_sync(CodecWriteBatch::new_puts(
+                batches
+                    .iter()
+                    .map(|(block_number, _)| (*block_number, chunk_id))
+                    .collect(),
+            ))?;
+        self.celestia_batch_hash_store
+            .write_batch_sync(CodecWriteBatch::new_puts(
+                batches
+                    .into_iter()
+                    .map(|(block_number, batch_hash)| (batch_hash, block_number))
+                    .collect(),
+            ))?;
+        self.celestia_chunk_heights_store
+            .put_sync(chunk_id, heights)
+    }
+
+    pub fn get_celestia_chunk_heights(&self, chunk_id: u128) -> Result<Option<Vec<u64>>> {
+        self.celestia_chunk_heights_store.kv_get(chunk_id)
+    }
+
+    pub fn get_celestia_block_chunk(&self, block_number: u128) -> Result<Option<u128>> {
+        self.celestia_block_chunk_store.kv_get(block_number)
+    }
+
+    pub fn get_celestia_batch_block_number(&self, batch_hash: H256) -> Result<Option<u128>> {
+        self.celestia_batch_hash_store.kv_get(batch_hash)
+    }
+}
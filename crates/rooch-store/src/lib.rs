@@ -2,18 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::accumulator_store::{AccumulatorStore, TransactionAccumulatorStore};
+use crate::da_store::{DAMetaDBStore, DAMetaStore};
 use crate::meta_store::{MetaDBStore, MetaStore};
+use crate::proposer_store::{ProposerDBStore, ProposerStore};
 use crate::transaction_store::{TransactionDBStore, TransactionStore};
 use accumulator::AccumulatorTreeStore;
 use anyhow::Result;
 use moveos_config::store_config::RocksdbConfig;
 use moveos_config::DataDirPath;
 use moveos_types::h256::H256;
+use moveos_types::transaction::TransactionExecutionInfo;
 use once_cell::sync::Lazy;
 use prometheus::Registry;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::RocksDB;
 use raw_store::{ColumnFamilyName, StoreInstance};
+use rooch_types::block::Block;
+use rooch_types::da::{DABatch, DAChunkSubmission};
 use rooch_types::sequencer::SequencerInfo;
 use rooch_types::transaction::LedgerTransaction;
 use std::fmt::{Debug, Display, Formatter};
@@ -21,7 +26,9 @@ use std::path::Path;
 use std::sync::Arc;
 
 pub mod accumulator_store;
+pub mod da_store;
 pub mod meta_store;
+pub mod proposer_store;
 #[cfg(test)]
 mod tests;
 pub mod transaction_store;
@@ -32,6 +39,15 @@ pub const TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME: ColumnFamilyName =
     "tx_sequence_info_mapping";
 pub const META_SEQUENCER_INFO_COLUMN_FAMILY_NAME: ColumnFamilyName = "meta_sequencer_info";
 pub const TX_ACCUMULATOR_NODE_COLUMN_FAMILY_NAME: ColumnFamilyName = "transaction_acc_node";
+pub const PROPOSER_BLOCK_COLUMN_FAMILY_NAME: ColumnFamilyName = "proposer_block";
+pub const PROPOSER_LAST_BLOCK_COLUMN_FAMILY_NAME: ColumnFamilyName = "proposer_last_block";
+pub const PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME: ColumnFamilyName = "proposer_pending_tx";
+pub const DA_PENDING_BATCH_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_pending_batch";
+pub const DA_CHUNK_SUBMISSION_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_chunk_submission";
+pub const DA_CELESTIA_CHUNK_HEIGHTS_COLUMN_FAMILY_NAME: ColumnFamilyName =
+    "da_celestia_chunk_heights";
+pub const DA_CELESTIA_BLOCK_CHUNK_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_celestia_block_chunk";
+pub const DA_CELESTIA_BATCH_HASH_COLUMN_FAMILY_NAME: ColumnFamilyName = "da_celestia_batch_hash";
 
 ///db store use cf_name vec to init
 /// Please note that adding a column family needs to be added in vec simultaneously, remember！！
@@ -41,6 +57,14 @@ static VEC_COLUMN_FAMILY_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TX_SEQUENCE_INFO_MAPPING_COLUMN_FAMILY_NAME,
         META_SEQUENCER_INFO_COLUMN_FAMILY_NAME,
         TX_ACCUMULATOR_NODE_COLUMN_FAMILY_NAME,
+        PROPOSER_BLOCK_COLUMN_FAMILY_NAME,
+        PROPOSER_LAST_BLOCK_COLUMN_FAMILY_NAME,
+        PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME,
+        DA_PENDING_BATCH_COLUMN_FAMILY_NAME,
+        DA_CHUNK_SUBMISSION_COLUMN_FAMILY_NAME,
+        DA_CELESTIA_CHUNK_HEIGHTS_COLUMN_FAMILY_NAME,
+        DA_CELESTIA_BLOCK_CHUNK_COLUMN_FAMILY_NAME,
+        DA_CELESTIA_BATCH_HASH_COLUMN_FAMILY_NAME,
     ]
 });
 
@@ -57,6 +81,8 @@ impl StoreMeta {
 pub struct RoochStore {
     pub transaction_store: TransactionDBStore,
     pub meta_store: MetaDBStore,
+    pub proposer_store: ProposerDBStore,
+    pub da_meta_store: DAMetaDBStore,
     pub transaction_accumulator_store: AccumulatorStore<TransactionAccumulatorStore>,
 }
 
@@ -78,6 +104,8 @@ impl RoochStore {
         let store = Self {
             transaction_store: TransactionDBStore::new(instance.clone()),
             meta_store: MetaDBStore::new(instance.clone()),
+            proposer_store: ProposerDBStore::new(instance.clone()),
+            da_meta_store: DAMetaDBStore::new(instance.clone()),
             transaction_accumulator_store: AccumulatorStore::new_transaction_accumulator_store(
                 instance,
             ),
@@ -101,6 +129,14 @@ impl RoochStore {
         &self.meta_store
     }
 
+    pub fn get_proposer_store(&self) -> &ProposerDBStore {
+        &self.proposer_store
+    }
+
+    pub fn get_da_meta_store(&self) -> &DAMetaDBStore {
+        &self.da_meta_store
+    }
+
     pub fn get_transaction_accumulator_store(&self) -> Arc<dyn AccumulatorTreeStore> {
         Arc::new(self.transaction_accumulator_store.clone())
     }
@@ -151,3 +187,94 @@ impl MetaStore for RoochStore {
         self.get_meta_store().save_sequencer_info(sequencer_info)
     }
 }
+
+impl ProposerStore for RoochStore {
+    fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.get_proposer_store().get_block(block_number)
+    }
+
+    fn get_last_block_number(&self) -> Result<Option<u128>> {
+        self.get_proposer_store().get_last_block_number()
+    }
+
+    fn save_block(&self, block: Block) -> Result<()> {
+        self.get_proposer_store().save_block(block)
+    }
+
+    fn get_last_committed_block_number(&self) -> Result<Option<u128>> {
+        self.get_proposer_store().get_last_committed_block_number()
+    }
+
+    fn save_last_committed_block_number(&self, block_number: u128) -> Result<()> {
+        self.get_proposer_store()
+            .save_last_committed_block_number(block_number)
+    }
+
+    fn save_pending_tx(
+        &self,
+        tx: LedgerTransaction,
+        tx_execution_info: TransactionExecutionInfo,
+    ) -> Result<()> {
+        self.get_proposer_store()
+            .save_pending_tx(tx, tx_execution_info)
+    }
+
+    fn get_pending_txs(&self) -> Result<Vec<(LedgerTransaction, TransactionExecutionInfo)>> {
+        self.get_proposer_store().get_pending_txs()
+    }
+
+    fn remove_pending_txs(&self, tx_orders: Vec<u64>) -> Result<()> {
+        self.get_proposer_store().remove_pending_txs(tx_orders)
+    }
+}
+
+impl DAMetaStore for RoochStore {
+    fn save_pending_batch(&self, batch: DABatch) -> Result<()> {
+        self.get_da_meta_store().save_pending_batch(batch)
+    }
+
+    fn get_pending_batch(&self, block_number: u128) -> Result<Option<DABatch>> {
+        self.get_da_meta_store().get_pending_batch(block_number)
+    }
+
+    fn get_pending_batches(&self) -> Result<Vec<DABatch>> {
+        self.get_da_meta_store().get_pending_batches()
+    }
+
+    fn save_chunk_submission(&self, submission: DAChunkSubmission) -> Result<()> {
+        self.get_da_meta_store().save_chunk_submission(submission)
+    }
+
+    fn get_chunk_submissions(&self) -> Result<Vec<DAChunkSubmission>> {
+        self.get_da_meta_store().get_chunk_submissions()
+    }
+
+    fn remove_chunk_submission(&self, submission: DAChunkSubmission) -> Result<()> {
+        self.get_da_meta_store().remove_chunk_submission(submission)
+    }
+
+    fn save_celestia_chunk_index(
+        &self,
+        chunk_id: u128,
+        heights: Vec<u64>,
+        batches: Vec<(u128, H256)>,
+    ) -> Result<()> {
+        self.get_da_meta_store()
+            .save_celestia_chunk_index(chunk_id, heights, batches)
+    }
+
+    fn get_celestia_chunk_heights(&self, chunk_id: u128) -> Result<Option<Vec<u64>>> {
+        self.get_da_meta_store()
+            .get_celestia_chunk_heights(chunk_id)
+    }
+
+    fn get_celestia_block_chunk(&self, block_number: u128) -> Result<Option<u128>> {
+        self.get_da_meta_store()
+            .get_celestia_block_chunk(block_number)
+    }
+
+    fn get_celestia_batch_block_number(&self, batch_hash: H256) -> Result<Option<u128>> {
+        self.get_da_meta_store()
+            .get_celestia_batch_block_number(batch_hash)
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    PROPOSER_BLOCK_COLUMN_FAMILY_NAME, PROPOSER_LAST_BLOCK_COLUMN_FAMILY_NAME,
+    PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME,
+};
+use anyhow::Result;
+use moveos_types::transaction::TransactionExecutionInfo;
+use raw_store::{derive_store, CodecKVStore, CodecWriteBatch, StoreInstance};
+use rooch_types::block::Block;
+use rooch_types::transaction::LedgerTransaction;
+use std::string::ToString;
+
+pub const LAST_BLOCK_NUMBER_KEY: &str = "last_block_number";
+pub const LAST_COMMITTED_BLOCK_NUMBER_KEY: &str = "last_committed_block_number";
+
+derive_store!(
+    ProposerBlockStore,
+    u128,
+    Block,
+    PROPOSER_BLOCK_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    ProposerLastBlockStore,
+    String,
+    u128,
+    PROPOSER_LAST_BLOCK_COLUMN_FAMILY_NAME
+);
+
+derive_store!(
+    ProposerPendingTxStore,
+    u64,
+    (LedgerTransaction, TransactionExecutionInfo),
+    PROPOSER_PENDING_TX_COLUMN_FAMILY_NAME
+);
+
+pub trait ProposerStore {
+    fn get_block(&self, block_number: u128) -> Result<Option<Block>>;
+
+    fn get_last_block_number(&self) -> Result<Option<u128>>;
+
+    /// Save the block and mark it as the last block
+    fn save_block(&self, block: Block) -> Result<()>;
+
+    /// The last block number whose commitment is submitted to the on-chain SCC
+    fn get_last_committed_block_number(&self) -> Result<Option<u128>>;
+
+    fn save_last_committed_block_number(&self, block_number: u128) -> Result<()>;
+
+    fn save_pending_tx(
+        &self,
+        tx: LedgerTransaction,
+        tx_execution_info: TransactionExecutionInfo,
+    ) -> Result<()>;
+
+    /// Get all pending transactions which are not proposed yet, sorted by tx order
+    fn get_pending_txs(&self) -> Result<Vec<(LedgerTransaction, TransactionExecutionInfo)>>;
+
+    fn remove_pending_txs(&self, tx_orders: Vec<u64>) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct ProposerDBStore {
+    block_store: ProposerBlockStore,
+    last_block_store: ProposerLastBlockStore,
+    pending_tx_store: ProposerPendingTxStore,
+}
+
+impl ProposerDBStore {
+    pub fn new(instance: StoreInstance) -> Self {
+        ProposerDBStore {
+            block_store: ProposerBlockStore::new(instance.clone()),
+            last_block_store: ProposerLastBlockStore::new(instance.clone()),
+            pending_tx_store: ProposerPendingTxStore::new(instance),
+        }
+    }
+
+    pub fn get_block(&self, block_number: u128) -> Result<Option<Block>> {
+        self.block_store.kv_get(block_number)
+    }
+
+    pub fn get_last_block_number(&self) -> Result<Option<u128>> {
+        self.last_block_store
+            .kv_get(LAST_BLOCK_NUMBER_KEY.to_string())
+    }
+
+    pub fn save_block(&self, block: Block) -> Result<()> {
+        let block_number = block.block_number;
+        self.block_store.put_sync(block_number, block)?;
+        self.last_block_store
+            .put_sync(LAST_BLOCK_NUMBER_KEY.to_string(), block_number)
+    }
+
+    pub fn get_last_committed_block_number(&self) -> Result<Option<u128>> {
+        self.last_block_store
+            .kv_get(LAST_COMMITTED_BLOCK_NUMBER_KEY.to_string())
+    }
+
+    pub fn save_last_committed_block_number(&self, block_number: u128) -> Result<()> {
+        self.last_block_store
+            .put_sync(LAST_COMMITTED_BLOCK_NUMBER_KEY.to_string(), block_number)
+    }
+
+    pub fn save_pending_tx(
+        &self,
+        tx: LedgerTransaction,
+        tx_execution_info: TransactionExecutionInfo,
+    ) -> Result<()> {
+        let tx_order = tx.sequence_info.tx_order;
+        self.pending_tx_store
+            .kv_put(tx_order, (tx, tx_execution_info))
+    }
+
+    pub fn get_pending_txs(&self) -> Result<Vec<(LedgerTransaction, TransactionExecutionInfo)>> {
+        let mut iter = self.pending_tx_store.iter()?;
+        iter.seek_to_first();
+        let mut pending_txs = iter
+            .map(|item| item.map(|(_tx_order, pending_tx)| pending_tx))
+            .collect::<Result<Vec<_>>>()?;
+        // the key is encoded in little endian, so the iterator is not sorted by tx order
+        pending_txs.sort_by_key(|(tx, _)| tx.sequence_info.tx_order);
+        Ok(pending_txs)
+    }
+
+    pub fn remove_pending_txs(&self, tx_orders: Vec<u64>) -> Result<()> {
+        self.pending_tx_store
+            .write_batch_sync(CodecWriteBatch::new_deletes(tx_orders))
+    }
+}
@@ -2,3 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod test_accumulator;
+mod test_da_store;
+mod test_proposer_store;
@@ -0,0 +1,88 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::da_store::DAMetaStore;
+use crate::RoochStore;
+use moveos_types::h256::{self, H256};
+use rooch_types::da::{DABatch, DAChunkSubmission};
+
+#[tokio::test]
+async fn test_da_meta_store() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+
+    let block_numbers = vec![300u128, 2, 256, 1, 3];
+    for block_number in block_numbers {
+        let data = block_number.to_le_bytes().to_vec();
+        rooch_store
+            .save_pending_batch(DABatch {
+                block_number,
+                batch_hash: h256::sha3_256_of(&data),
+                data,
+            })
+            .unwrap();
+    }
+    let pending_batches = rooch_store.get_pending_batches().unwrap();
+    assert_eq!(
+        pending_batches
+            .iter()
+            .map(|batch| batch.block_number)
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3, 256, 300]
+    );
+    assert!(rooch_store.get_pending_batch(2).unwrap().is_some());
+
+    let mut submission = DAChunkSubmission::new(1, 3);
+    rooch_store
+        .save_chunk_submission(DAChunkSubmission::new(256, 300))
+        .unwrap();
+    rooch_store
+        .save_chunk_submission(submission.clone())
+        .unwrap();
+    submission.acked_servers.push("open-da-fs".to_string());
+    rooch_store
+        .save_chunk_submission(submission.clone())
+        .unwrap();
+    let submissions = rooch_store.get_chunk_submissions().unwrap();
+    assert_eq!(submissions.len(), 2);
+    assert_eq!(submissions[0], submission);
+    assert!(submissions[0].is_acked_by("open-da-fs"));
+
+    rooch_store.remove_chunk_submission(submission).unwrap();
+    assert_eq!(rooch_store.get_chunk_submissions().unwrap().len(), 1);
+    assert!(rooch_store.get_pending_batch(2).unwrap().is_none());
+    assert_eq!(
+        rooch_store
+            .get_pending_batches()
+            .unwrap()
+            .iter()
+            .map(|batch| batch.block_number)
+            .collect::<Vec<_>>(),
+        vec![256, 300]
+    );
+}
+
+#[tokio::test]
+async fn test_da_celestia_chunk_index() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+
+    let batch_hash = h256::sha3_256_of(&[2u8]);
+    rooch_store
+        .save_celestia_chunk_index(
+            1,
+            vec![100, 101],
+            vec![(1, H256::random()), (2, batch_hash)],
+        )
+        .unwrap();
+    assert_eq!(rooch_store.get_celestia_block_chunk(2).unwrap(), Some(1));
+    assert_eq!(rooch_store.get_celestia_block_chunk(3).unwrap(), None);
+    assert_eq!(
+        rooch_store
+            .get_celestia_batch_block_number(batch_hash)
+            .unwrap(),
+        Some(2)
+    );
+    assert_eq!(
+        rooch_store.get_celestia_chunk_heights(1).unwrap(),
+        Some(vec![100, 101])
+    );
+}
@@ -0,0 +1,52 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proposer_store::ProposerStore;
+use crate::RoochStore;
+use moveos_types::h256::H256;
+use moveos_types::transaction::TransactionExecutionInfo;
+use rooch_types::block::Block;
+use rooch_types::transaction::{LedgerTransaction, RoochTransaction, TransactionSequenceInfo};
+
+#[tokio::test]
+async fn test_proposer_store() {
+    let (rooch_store, _) = RoochStore::mock_rooch_store().unwrap();
+
+    assert_eq!(rooch_store.get_last_block_number().unwrap(), None);
+    let block = Block::new(0, 2, H256::zero(), H256::random(), vec![H256::random(); 2]);
+    rooch_store.save_block(block.clone()).unwrap();
+    let block2 = Block::new(1, 1, block.tx_accumulator_root, H256::random(), vec![]);
+    rooch_store.save_block(block2.clone()).unwrap();
+    assert_eq!(rooch_store.get_last_block_number().unwrap(), Some(1));
+    assert_eq!(rooch_store.get_block(0).unwrap(), Some(block));
+    assert_eq!(rooch_store.get_block(1).unwrap(), Some(block2));
+    assert_eq!(rooch_store.get_block(2).unwrap(), None);
+
+    assert_eq!(rooch_store.get_last_committed_block_number().unwrap(), None);
+    rooch_store.save_last_committed_block_number(0).unwrap();
+    assert_eq!(
+        rooch_store.get_last_committed_block_number().unwrap(),
+        Some(0)
+    );
+    assert_eq!(rooch_store.get_last_block_number().unwrap(), Some(1));
+
+    let tx_orders = vec![300u64, 2, 256, 1];
+    for tx_order in tx_orders.clone() {
+        let mut sequence_info = TransactionSequenceInfo::random();
+        sequence_info.tx_order = tx_order;
+        let tx = LedgerTransaction::new_l2_tx(RoochTransaction::mock(), sequence_info);
+        rooch_store
+            .save_pending_tx(tx, TransactionExecutionInfo::random())
+            .unwrap();
+    }
+    let pending_tx_orders = rooch_store
+        .get_pending_txs()
+        .unwrap()
+        .into_iter()
+        .map(|(tx, _)| tx.sequence_info.tx_order)
+        .collect::<Vec<_>>();
+    assert_eq!(pending_tx_orders, vec![1, 2, 256, 300]);
+
+    rooch_store.remove_pending_txs(vec![1, 2]).unwrap();
+    assert_eq!(rooch_store.get_pending_txs().unwrap().len(), 2);
+}
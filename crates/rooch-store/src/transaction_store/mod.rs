@@ -70,6 +70,13 @@ impl TransactionDBStore {
             .kv_put(tx_order, tx_hash)
     }
 
+    /// Save the tx hash at the tx order without the transaction,
+    /// used by the fork mode to mark the tx order the node is forked at.
+    pub fn save_tx_hash(&self, tx_order: u64, tx_hash: H256) -> Result<()> {
+        self.tx_sequence_info_mapping_store
+            .kv_put(tx_order, tx_hash)
+    }
+
     pub fn remove_transaction(&self, tx_hash: H256, tx_order: u64) -> Result<()> {
         self.tx_store.remove(tx_hash)?;
         self.tx_sequence_info_mapping_store.remove(tx_order)
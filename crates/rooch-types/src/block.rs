@@ -1,6 +1,12 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::address::RoochAddress;
+use crate::crypto::{
+    PublicKey, RoochKeyPair, RoochSignature, Signature, SignatureScheme, ToFromBytes,
+};
+use anyhow::{ensure, Result};
+use fastcrypto::secp256k1::Secp256k1PublicKey;
 use moveos_types::h256::H256;
 use serde::{Deserialize, Serialize};
 
@@ -39,3 +45,97 @@ impl Block {
         }
     }
 }
+
+impl Block {
+    /// The state root after the last transaction of the block executed
+    pub fn state_root(&self) -> H256 {
+        self.state_roots.last().cloned().unwrap_or_default()
+    }
+
+    pub fn commitment(&self) -> BlockCommitment {
+        BlockCommitment {
+            block_number: self.block_number,
+            prev_tx_accumulator_root: self.prev_tx_accumulator_root,
+            tx_accumulator_root: self.tx_accumulator_root,
+            state_root: self.state_root(),
+        }
+    }
+}
+
+/// The state commitment of a block, it is signed by the proposer and submitted to the on-chain SCC
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockCommitment {
+    pub block_number: u128,
+    pub prev_tx_accumulator_root: H256,
+    pub tx_accumulator_root: H256,
+    pub state_root: H256,
+}
+
+/// The Move `BlockCommitment` in the `state_commitment_chain` module, the hashes are `vector<u8>`.
+/// The `H256` is serialized as a hex string by serde, so the commitment is signed as this struct
+/// to keep the message same as the message verified by the on-chain SCC.
+#[derive(Serialize)]
+struct MoveBlockCommitment {
+    block_number: u128,
+    prev_tx_accumulator_root: Vec<u8>,
+    tx_accumulator_root: Vec<u8>,
+    state_root: Vec<u8>,
+}
+
+impl BlockCommitment {
+    /// The BCS encoding of the Move `BlockCommitment`, it is the message signed by the proposer
+    pub fn encode(&self) -> Vec<u8> {
+        let commitment = MoveBlockCommitment {
+            block_number: self.block_number,
+            prev_tx_accumulator_root: self.prev_tx_accumulator_root.as_bytes().to_vec(),
+            tx_accumulator_root: self.tx_accumulator_root.as_bytes().to_vec(),
+            state_root: self.state_root.as_bytes().to_vec(),
+        };
+        bcs::to_bytes(&commitment).expect("encode block commitment should success")
+    }
+
+    /// Sign the commitment with the proposer key, only secp256k1 key is supported,
+    /// because the on-chain SCC verifies the signature via `ecdsa_k1`.
+    pub fn sign(self, proposer_key: &RoochKeyPair) -> Result<SignedBlockCommitment> {
+        ensure!(
+            proposer_key.public().scheme() == SignatureScheme::Secp256k1,
+            "The proposer key must be a secp256k1 key"
+        );
+        let signature = proposer_key.sign(&self.encode());
+        Ok(SignedBlockCommitment {
+            commitment: self,
+            signature,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedBlockCommitment {
+    pub commitment: BlockCommitment,
+    /// The signature of the commitment, `flag || signature || public_key`
+    pub signature: Signature,
+}
+
+impl SignedBlockCommitment {
+    pub fn verify(&self) -> Result<()> {
+        Ok(self.signature.verify(&self.commitment.encode())?)
+    }
+
+    /// The proposer address derived from the public key in the signature
+    pub fn proposer(&self) -> Result<RoochAddress> {
+        let public_key = Secp256k1PublicKey::from_bytes(self.signature.public_key_bytes())?;
+        PublicKey::Secp256k1((&public_key).into()).rooch_address()
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("encode signed block commitment should success")
+    }
+
+    pub fn tx_hash(&self) -> H256 {
+        moveos_types::h256::sha3_256_of(self.encode().as_slice())
+    }
+
+    pub fn tx_size(&self) -> u64 {
+        bcs::serialized_size(self).expect("serialize transaction size should success") as u64
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use moveos_types::h256::H256;
+use serde::{Deserialize, Serialize};
+
+/// The batch waiting for DA submission, it is kept in the DA outbox
+/// until all DA servers acknowledge the chunk including it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DABatch {
+    /// Each batch maps to a L2 block
+    pub block_number: u128,
+    /// sha3_256 hash of the batch data
+    pub batch_hash: H256,
+    /// Encoded tx list
+    pub data: Vec<u8>,
+}
+
+/// The chunk sealed from the pending batches, from `chunk_id` to `end_block_number`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DAChunkSubmission {
+    /// The block number of the first batch in the chunk
+    pub chunk_id: u128,
+    /// The block number of the last batch in the chunk
+    pub end_block_number: u128,
+    /// The DA servers which have acknowledged the chunk
+    pub acked_servers: Vec<String>,
+}
+
+impl DAChunkSubmission {
+    pub fn new(chunk_id: u128, end_block_number: u128) -> Self {
+        Self {
+            chunk_id,
+            end_block_number,
+            acked_servers: vec![],
+        }
+    }
+
+    pub fn is_acked_by(&self, server: &str) -> bool {
+        self.acked_servers.iter().any(|acked| acked == server)
+    }
+}
+
+/// The submission status of a DA server
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DAServerStatus {
+    pub name: String,
+    /// The chunks in the outbox not acknowledged by the server
+    pub pending_chunks: u64,
+    /// The oldest chunk not acknowledged by the server
+    pub first_pending_chunk_id: Option<u128>,
+    /// How many times the submission to the server failed in a row
+    pub consecutive_failures: u64,
+    pub last_error: Option<String>,
+    /// Seconds to wait before the next retry
+    pub next_retry_in_seconds: u64,
+}
+
+/// The status of the DA submission
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DAStatus {
+    /// How many servers must acknowledge a chunk before it is available
+    pub submit_threshold: u64,
+    /// The batches buffered in the chunk builder, not sealed into a chunk yet
+    pub buffered_batches: u64,
+    /// The chunks not acknowledged by all servers
+    pub pending_chunks: u64,
+    /// The chunks acknowledged by less servers than the submit threshold
+    pub unavailable_chunks: u64,
+    pub servers: Vec<DAServerStatus>,
+}
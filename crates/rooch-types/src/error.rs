@@ -180,3 +180,11 @@ pub enum GenesisError {
     #[error("Genesis block not exist in {0}.")]
     GenesisNotExist(String),
 }
+
+/// The context of the error raised after the transaction is sequenced,
+/// the sequence number of the transaction is used even if the execution fails.
+#[derive(Debug, Error, Eq, PartialEq)]
+#[error("The transaction is sequenced at tx_order {tx_order}")]
+pub struct SequencedTransactionError {
+    pub tx_order: u64,
+}
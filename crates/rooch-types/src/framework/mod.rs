@@ -24,7 +24,9 @@ pub mod genesis;
 pub mod onchain_config;
 pub mod session_key;
 pub mod session_validator;
+pub mod state_commitment_chain;
 pub mod timestamp;
+pub mod transaction_fee;
 pub mod transaction_validator;
 pub mod transfer;
 
@@ -0,0 +1,79 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use crate::block::SignedBlockCommitment;
+use crate::crypto::RoochSignature;
+use anyhow::Result;
+use move_core_types::{
+    account_address::AccountAddress, ident_str, identifier::IdentStr, value::MoveValue,
+};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    move_std::option::MoveOption,
+    moveos_std::tx_context::TxContext,
+    transaction::FunctionCall,
+};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("state_commitment_chain");
+
+/// Rust bindings for RoochFramework state_commitment_chain module
+pub struct StateCommitmentChainModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> StateCommitmentChainModule<'a> {
+    pub const COMMIT_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("commit_block");
+    pub const LAST_BLOCK_NUMBER_FUNCTION_NAME: &'static IdentStr = ident_str!("last_block_number");
+
+    pub fn create_commit_block_call(signed_commitment: SignedBlockCommitment) -> FunctionCall {
+        let SignedBlockCommitment {
+            commitment,
+            signature,
+        } = signed_commitment;
+        Self::create_function_call(
+            Self::COMMIT_BLOCK_FUNCTION_NAME,
+            vec![],
+            vec![
+                MoveValue::U128(commitment.block_number),
+                MoveValue::vector_u8(commitment.prev_tx_accumulator_root.as_bytes().to_vec()),
+                MoveValue::vector_u8(commitment.tx_accumulator_root.as_bytes().to_vec()),
+                MoveValue::vector_u8(commitment.state_root.as_bytes().to_vec()),
+                MoveValue::vector_u8(signature.public_key_bytes().to_vec()),
+                MoveValue::vector_u8(signature.signature_bytes().to_vec()),
+            ],
+        )
+    }
+
+    pub fn create_last_block_number_call() -> FunctionCall {
+        Self::create_function_call(Self::LAST_BLOCK_NUMBER_FUNCTION_NAME, vec![], vec![])
+    }
+
+    /// Get the last block number committed to the on-chain SCC
+    pub fn last_block_number(&self) -> Result<Option<u128>> {
+        let call = Self::create_last_block_number_call();
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        let block_number =
+            self.caller
+                .call_function(&ctx, call)?
+                .into_result()
+                .map(|mut values| {
+                    let value = values.pop().expect("should have one return value");
+                    bcs::from_bytes::<MoveOption<u128>>(&value.value)
+                        .expect("should be a valid MoveOption<u128>")
+                })?;
+        Ok(block_number.into())
+    }
+}
+
+impl<'a> ModuleBinding<'a> for StateCommitmentChainModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use anyhow::Result;
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    moveos_std::tx_context::TxContext,
+    transaction::FunctionCall,
+};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("transaction_fee");
+
+/// Rust bindings for RoochFramework transaction_fee module
+pub struct TransactionFeeModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> TransactionFeeModule<'a> {
+    pub const GET_GAS_FACTOR_FUNCTION_NAME: &'static IdentStr = ident_str!("get_gas_factor");
+
+    /// The gas factor to convert the gas amount to the amount of GasCoin
+    pub fn get_gas_factor(&self) -> Result<u64> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::GET_GAS_FACTOR_FUNCTION_NAME),
+            vec![],
+            vec![],
+        );
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        Ok(self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<u64>(&value.value).expect("should be a valid u64")
+            })?)
+    }
+}
+
+impl<'a> ModuleBinding<'a> for TransactionFeeModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
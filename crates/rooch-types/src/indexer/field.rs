@@ -0,0 +1,246 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::indexer::state::IndexerStateID;
+use crate::indexer::Filter;
+use anyhow::Result;
+use move_core_types::effects::Op;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::state::{FieldKey, ObjectChange, StateChangeSet};
+use serde::{Deserialize, Serialize};
+
+/// The objects whose dynamic fields are indexed, the field index is disabled if it is empty.
+#[derive(Clone, Debug, Default)]
+pub struct FieldIndexConfig {
+    /// Index the fields of the objects, such as the table handle objects
+    pub parents: Vec<ObjectID>,
+    /// Index the fields of the objects of the types, the type params are ignored if the type has no type params
+    pub object_types: Vec<StructTag>,
+}
+
+impl FieldIndexConfig {
+    pub fn new(parents: Vec<ObjectID>, object_types: Vec<StructTag>) -> Self {
+        Self {
+            parents,
+            object_types,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty() && self.object_types.is_empty()
+    }
+
+    /// Whether the fields of the parent object are indexed
+    pub fn is_indexed_parent(&self, parent: &ObjectMeta) -> bool {
+        if self.parents.contains(&parent.id) {
+            return true;
+        }
+        match &parent.object_type {
+            TypeTag::Struct(parent_type) => self.object_types.iter().any(|object_type| {
+                if object_type.type_params.is_empty() {
+                    object_type.address == parent_type.address
+                        && object_type.module == parent_type.module
+                        && object_type.name == parent_type.name
+                } else {
+                    object_type == parent_type.as_ref()
+                }
+            }),
+            _ => false,
+        }
+    }
+}
+
+/// Index the dynamic field of an object, the full field value is read from the state when querying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerFieldState {
+    pub parent_id: ObjectID,
+    pub field_key: FieldKey,
+    pub name_type: TypeTag,
+    pub value_type: TypeTag,
+    // The tx order of this transaction
+    pub tx_order: u64,
+    // The state index in the tx, it is the same as the state index of the field object state
+    pub state_index: u64,
+    /// The field created timestamp on chain
+    pub created_at: u64,
+    /// The field updated timestamp on chain
+    pub updated_at: u64,
+    /// The JSON of the decoded field value, None if the value is not decoded
+    pub decoded_value: Option<String>,
+}
+
+impl IndexerFieldState {
+    /// Create the field state from the dynamic field object metadata, None if it is not a dynamic field
+    pub fn new_from_field_metadata(
+        parent_id: ObjectID,
+        field_key: FieldKey,
+        metadata: &ObjectMeta,
+        tx_order: u64,
+        state_index: u64,
+    ) -> Option<Self> {
+        if !metadata.is_dynamic_field() {
+            return None;
+        }
+        let type_params = &metadata.object_struct_tag().type_params;
+        if type_params.len() != 2 {
+            return None;
+        }
+        Some(IndexerFieldState {
+            parent_id,
+            field_key,
+            name_type: type_params[0].clone(),
+            value_type: type_params[1].clone(),
+            tx_order,
+            state_index,
+            created_at: metadata.created_at,
+            updated_at: metadata.updated_at,
+            decoded_value: None,
+        })
+    }
+
+    pub fn indexer_state_id(&self) -> IndexerStateID {
+        IndexerStateID::new(self.tx_order, self.state_index)
+    }
+
+    /// The object id of the dynamic field
+    pub fn field_id(&self) -> ObjectID {
+        self.parent_id.child_id(self.field_key)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IndexerFieldStateChanges {
+    pub new_field_states: Vec<IndexerFieldState>,
+    pub update_field_states: Vec<IndexerFieldState>,
+    pub remove_field_states: Vec<String>,
+}
+
+/// Collect the dynamic field changes of the indexed objects in the state change set.
+/// The state index is generated in the same order as `handle_object_change`.
+pub fn collect_field_changes(
+    config: &FieldIndexConfig,
+    tx_order: u64,
+    state_change_set: &StateChangeSet,
+) -> IndexerFieldStateChanges {
+    fn handle_field_change(
+        config: &FieldIndexConfig,
+        state_index_generator: &mut u64,
+        tx_order: u64,
+        parent: Option<(&ObjectMeta, &FieldKey)>,
+        object_change: &ObjectChange,
+        field_state_changes: &mut IndexerFieldStateChanges,
+    ) {
+        let state_index = *state_index_generator;
+        *state_index_generator += 1;
+        if let Some((parent, field_key)) = parent {
+            if config.is_indexed_parent(parent) {
+                let metadata = &object_change.metadata;
+                match &object_change.value {
+                    Some(Op::Delete) => {
+                        if metadata.is_dynamic_field() {
+                            field_state_changes
+                                .remove_field_states
+                                .push(metadata.id.to_string());
+                        }
+                    }
+                    op => {
+                        if let Some(state) = IndexerFieldState::new_from_field_metadata(
+                            parent.id.clone(),
+                            *field_key,
+                            metadata,
+                            tx_order,
+                            state_index,
+                        ) {
+                            match op {
+                                Some(Op::New(_)) => {
+                                    field_state_changes.new_field_states.push(state)
+                                }
+                                //If value is not changed, we should update the metadata.
+                                _ => field_state_changes.update_field_states.push(state),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for (key, change) in &object_change.fields {
+            handle_field_change(
+                config,
+                state_index_generator,
+                tx_order,
+                Some((&object_change.metadata, key)),
+                change,
+                field_state_changes,
+            );
+        }
+    }
+
+    let mut field_state_changes = IndexerFieldStateChanges::default();
+    if config.is_empty() {
+        return field_state_changes;
+    }
+    let mut state_index_generator = 0u64;
+    for (_field_key, object_change) in &state_change_set.changes {
+        handle_field_change(
+            config,
+            &mut state_index_generator,
+            tx_order,
+            None,
+            object_change,
+            &mut field_state_changes,
+        );
+    }
+    field_state_changes
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldStateFilter {
+    /// Query by parent object id and field value type.
+    ParentWithValueType {
+        parent_id: ObjectID,
+        value_type: TypeTag,
+    },
+    /// Query by parent object id.
+    Parent(ObjectID),
+    /// Query by field value type.
+    ValueType(TypeTag),
+    /// Query by parent object id and field key.
+    Key {
+        parent_id: ObjectID,
+        field_key: FieldKey,
+    },
+    /// Query by parent object id and the JSON of the decoded field value.
+    Value {
+        parent_id: ObjectID,
+        decoded_value: String,
+    },
+}
+
+impl FieldStateFilter {
+    fn try_matches(&self, item: &IndexerFieldState) -> Result<bool> {
+        Ok(match self {
+            FieldStateFilter::ParentWithValueType {
+                parent_id,
+                value_type,
+            } => parent_id == &item.parent_id && value_type == &item.value_type,
+            FieldStateFilter::Parent(parent_id) => parent_id == &item.parent_id,
+            FieldStateFilter::ValueType(value_type) => value_type == &item.value_type,
+            FieldStateFilter::Key {
+                parent_id,
+                field_key,
+            } => parent_id == &item.parent_id && field_key == &item.field_key,
+            FieldStateFilter::Value {
+                parent_id,
+                decoded_value,
+            } => parent_id == &item.parent_id && item.decoded_value.as_ref() == Some(decoded_value),
+        })
+    }
+}
+
+impl Filter<IndexerFieldState> for FieldStateFilter {
+    fn matches(&self, item: &IndexerFieldState) -> bool {
+        self.try_matches(item).unwrap_or_default()
+    }
+}
@@ -2,9 +2,45 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod event;
+pub mod field;
 pub mod state;
 pub mod transaction;
 
+use serde::{Deserialize, Serialize};
+
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
+
+/// The progress of the indexer compared with the sequencer
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IndexerStatus {
+    /// The last sequenced tx order
+    pub last_tx_order: u64,
+    /// The last indexed tx order, None if nothing is indexed
+    pub last_indexed_tx_order: Option<u64>,
+    /// The number of the sequenced txs which are not indexed yet
+    pub lag: u64,
+    /// The error of the last indexer catch up, None if it succeeded
+    pub last_error: Option<String>,
+}
+
+impl IndexerStatus {
+    pub fn new(
+        last_tx_order: u64,
+        last_indexed_tx_order: Option<u64>,
+        last_error: Option<String>,
+    ) -> Self {
+        let lag = match last_indexed_tx_order {
+            Some(last_indexed_tx_order) => last_tx_order.saturating_sub(last_indexed_tx_order),
+            // The genesis tx is not indexed either
+            None => last_tx_order + 1,
+        };
+        Self {
+            last_tx_order,
+            last_indexed_tx_order,
+            lag,
+            last_error,
+        }
+    }
+}
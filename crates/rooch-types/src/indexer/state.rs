@@ -51,6 +51,53 @@ pub struct IndexerObjectStateChanges {
     pub remove_object_states: Vec<String>,
 }
 
+/// An object change in the state change set, the object state index is the same as the indexer
+#[derive(Debug, Clone)]
+pub struct IndexerObjectChange {
+    pub state: IndexerObjectState,
+    /// None if only the metadata is changed
+    pub value: Option<Op<Vec<u8>>>,
+}
+
+/// Flatten the object changes and their field changes in the order of the indexer state index
+pub fn flatten_object_changes(
+    tx_order: u64,
+    state_change_set: StateChangeSet,
+) -> Vec<IndexerObjectChange> {
+    fn flatten(
+        state_index_generator: &mut u64,
+        tx_order: u64,
+        object_change: ObjectChange,
+        changes: &mut Vec<IndexerObjectChange>,
+    ) {
+        let ObjectChange {
+            metadata,
+            value,
+            fields,
+        } = object_change;
+        changes.push(IndexerObjectChange {
+            state: IndexerObjectState::new(metadata, tx_order, *state_index_generator),
+            value,
+        });
+        *state_index_generator += 1;
+        for (_key, change) in fields {
+            flatten(state_index_generator, tx_order, change, changes);
+        }
+    }
+
+    let mut state_index_generator = 0u64;
+    let mut changes = vec![];
+    for (_field_key, object_change) in state_change_set.changes {
+        flatten(
+            &mut state_index_generator,
+            tx_order,
+            object_change,
+            &mut changes,
+        );
+    }
+    changes
+}
+
 pub fn handle_object_change(
     mut state_index_generator: u64,
     tx_order: u64,
@@ -153,9 +200,7 @@ impl ObjectStateFilter {
             } => object_type == item.object_struct_tag() && owner == &item.metadata.owner,
             ObjectStateFilter::ObjectType(object_type) => object_type == item.object_struct_tag(),
             ObjectStateFilter::Owner(owner) => owner == &item.metadata.owner,
-            ObjectStateFilter::ObjectId(object_ids) => {
-                object_ids.len() == 1 && object_ids[0] == item.metadata.id
-            }
+            ObjectStateFilter::ObjectId(object_ids) => object_ids.contains(&item.metadata.id),
         })
     }
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::address::RoochAddress;
+use crate::indexer::Filter;
 use crate::transaction::{LedgerTransaction, LedgerTxData};
 use anyhow::Result;
 use moveos_types::h256::H256;
@@ -45,6 +46,7 @@ impl IndexerTransaction {
         let (auth_validator_id, authenticator_payload) = match &transaction.data {
             LedgerTxData::L1Block(_block) => (0, vec![]),
             LedgerTxData::L1Tx(_tx) => (0, vec![]),
+            LedgerTxData::BlockCommitment(_commitment) => (0, vec![]),
             LedgerTxData::L2Tx(tx) => (
                 tx.authenticator().auth_validator_id,
                 tx.authenticator().payload.clone(),
@@ -101,3 +103,28 @@ pub enum TransactionFilter {
         to_order: u64,
     },
 }
+
+impl TransactionFilter {
+    fn try_matches(&self, item: &IndexerTransaction) -> Result<bool> {
+        Ok(match self {
+            TransactionFilter::Sender(sender) => sender == &item.sender,
+            // the original address is resolved by the indexer store, not available in the transaction
+            TransactionFilter::OriginalAddress(_address) => false,
+            TransactionFilter::TxHashes(tx_hashes) => tx_hashes.contains(&item.tx_hash),
+            TransactionFilter::TimeRange {
+                start_time,
+                end_time,
+            } => *start_time <= item.created_at && *end_time > item.created_at,
+            TransactionFilter::TxOrderRange {
+                from_order,
+                to_order,
+            } => *from_order <= item.tx_order && *to_order > item.tx_order,
+        })
+    }
+}
+
+impl Filter<IndexerTransaction> for TransactionFilter {
+    fn matches(&self, item: &IndexerTransaction) -> bool {
+        self.try_matches(item).unwrap_or_default()
+    }
+}
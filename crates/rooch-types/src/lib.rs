@@ -8,6 +8,7 @@ pub mod bitcoin;
 pub mod block;
 pub mod coin_type;
 pub mod crypto;
+pub mod da;
 pub mod error;
 pub mod framework;
 pub mod function_arg;
@@ -15,6 +16,7 @@ pub mod genesis_config;
 pub mod indexer;
 pub mod into_address;
 pub mod key_struct;
+pub mod mempool;
 pub mod multichain_id;
 pub mod nursery;
 pub mod repair;
@@ -0,0 +1,35 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::address::RoochAddress;
+use moveos_types::h256::H256;
+use serde::{Deserialize, Serialize};
+
+/// A transaction waiting in the mempool to be sequenced
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PendingTransactionInfo {
+    pub tx_hash: H256,
+    pub sender: RoochAddress,
+    pub sequence_number: u64,
+    pub max_gas_amount: u64,
+    /// The timestamp when the transaction entered the mempool, in milliseconds
+    pub received_at: u64,
+    /// Whether the transaction can be sequenced now, or it waits for the transactions with lower sequence numbers
+    pub ready: bool,
+}
+
+/// The status of the mempool
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MempoolStatus {
+    /// The transactions waiting in the mempool
+    pub pending_transactions: u64,
+    /// The pending transactions which can be sequenced now
+    pub ready_transactions: u64,
+    /// The senders with pending transactions
+    pub senders: u64,
+    /// The transactions taken from the mempool and not executed yet
+    pub executing_transactions: u64,
+    pub max_size: u64,
+    pub max_size_per_sender: u64,
+    pub ttl_seconds: u64,
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{RoochTransaction, TransactionSequenceInfo};
-use crate::{address::RoochAddress, multichain_id::MultiChainID};
+use crate::{address::RoochAddress, block::SignedBlockCommitment, multichain_id::MultiChainID};
 use accumulator::accumulator_info::AccumulatorInfo;
 use anyhow::Result;
 use bitcoin::hashes::Hash;
@@ -96,6 +96,8 @@ pub enum LedgerTxData {
     L1Block(L1Block),
     L1Tx(L1Transaction),
     L2Tx(RoochTransaction),
+    /// The state commitment of a L2 block signed by the proposer, it is submitted to the on-chain SCC as a system transaction
+    BlockCommitment(SignedBlockCommitment),
 }
 
 impl LedgerTxData {
@@ -104,6 +106,7 @@ impl LedgerTxData {
             LedgerTxData::L1Block(block) => block.tx_hash(),
             LedgerTxData::L2Tx(tx) => tx.tx_hash(),
             LedgerTxData::L1Tx(tx) => tx.tx_hash(),
+            LedgerTxData::BlockCommitment(commitment) => commitment.tx_hash(),
         }
     }
 
@@ -112,6 +115,7 @@ impl LedgerTxData {
             LedgerTxData::L1Block(_) => None,
             LedgerTxData::L2Tx(tx) => Some(tx.sender()),
             LedgerTxData::L1Tx(_) => None,
+            LedgerTxData::BlockCommitment(_) => None,
         }
     }
 
@@ -126,6 +130,10 @@ impl LedgerTxData {
     pub fn is_l2_tx(&self) -> bool {
         matches!(self, LedgerTxData::L2Tx(_))
     }
+
+    pub fn is_block_commitment(&self) -> bool {
+        matches!(self, LedgerTxData::BlockCommitment(_))
+    }
 }
 
 /// The transaction which is recorded in the L2 DA ledger.
@@ -182,6 +190,16 @@ impl LedgerTransaction {
         Ok(bcs::from_bytes(bytes)?)
     }
 
+    /// Encode the transaction list as the data of a DA batch
+    pub fn encode_batch(txs: &[LedgerTransaction]) -> Vec<u8> {
+        bcs::to_bytes(txs).expect("encode transaction list should success")
+    }
+
+    /// Decode the transaction list from the data of a DA batch
+    pub fn decode_batch(bytes: &[u8]) -> Result<Vec<LedgerTransaction>> {
+        Ok(bcs::from_bytes(bytes)?)
+    }
+
     pub fn build_ledger_transaction(
         tx_data: LedgerTxData,
         tx_timestamp: u64,
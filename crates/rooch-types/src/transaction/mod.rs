@@ -8,15 +8,19 @@ use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
 use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::{MoveState, MoveStructState, MoveStructType};
-use moveos_types::transaction::TransactionExecutionInfo;
+use moveos_types::transaction::{MoveAction, TransactionExecutionInfo};
 use moveos_types::{h256::H256, transaction::TransactionOutput};
 use serde::{Deserialize, Deserializer, Serialize};
 
 pub mod authenticator;
 mod ledger_transaction;
+pub mod proof;
 pub mod rooch;
 
+use crate::indexer::event::IndexerEvent;
+use crate::indexer::state::{flatten_object_changes, IndexerObjectChange};
 use crate::indexer::transaction::IndexerTransaction;
 use crate::test_utils::random_accumulator_info;
 pub use authenticator::Authenticator;
@@ -283,11 +287,53 @@ pub struct ExecuteTransactionResponse {
     pub output: TransactionOutput,
 }
 
+/// The transaction executed by the pipeline processor, it is published to the subscribers
+#[derive(Debug, Clone)]
+pub struct ExecutedTransaction {
+    pub transaction: LedgerTransaction,
+    pub execution_info: TransactionExecutionInfo,
+    pub output: TransactionOutput,
+    pub tx_context: TxContext,
+    pub action: MoveAction,
+}
+
+impl ExecutedTransaction {
+    pub fn indexer_transaction(&self) -> Result<IndexerTransaction> {
+        IndexerTransaction::new(
+            self.transaction.clone(),
+            self.execution_info.clone(),
+            self.action.clone(),
+            self.tx_context.clone(),
+        )
+    }
+
+    pub fn indexer_events(&self) -> Vec<IndexerEvent> {
+        self.output
+            .events
+            .iter()
+            .map(|event| {
+                IndexerEvent::new(
+                    event.clone(),
+                    self.transaction.clone(),
+                    self.tx_context.clone(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn object_changes(&self) -> Vec<IndexerObjectChange> {
+        flatten_object_changes(
+            self.transaction.sequence_info.tx_order,
+            self.output.changeset.clone(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::rooch::RoochTransaction;
     use crate::test_utils::random_accumulator_info;
-    use crate::transaction::TransactionSequenceInfo;
+    use crate::transaction::{LedgerTransaction, TransactionSequenceInfo};
     use ethers::types::H256;
     use moveos_types::state::MoveState;
     use moveos_types::test_utils::random_bytes;
@@ -320,4 +366,19 @@ mod tests {
         //     _h256_bcs_bytes.len()
         // );
     }
+
+    #[test]
+    fn test_encode_decode_batch() {
+        let txs = (0..3)
+            .map(|_| {
+                LedgerTransaction::new_l2_tx(
+                    RoochTransaction::mock(),
+                    TransactionSequenceInfo::random(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let bytes = LedgerTransaction::encode_batch(&txs);
+        let decoded = LedgerTransaction::decode_batch(&bytes).unwrap();
+        assert_eq!(txs, decoded);
+    }
 }
@@ -0,0 +1,152 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::address::RoochAddress;
+use crate::crypto::{PublicKey, RoochSignature, Signature, SignatureScheme, ToFromBytes};
+use crate::transaction::TransactionSequenceInfo;
+use accumulator::proof::AccumulatorProof;
+use anyhow::{anyhow, ensure, Result};
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::secp256k1::Secp256k1PublicKey;
+use moveos_types::h256::{self, H256};
+use serde::{Deserialize, Serialize};
+
+/// The hash signed by the sequencer to commit the tx order of a transaction
+pub fn tx_order_witness_hash(tx_hash: H256, tx_order: u64) -> H256 {
+    let mut witness_data = tx_hash.as_ref().to_vec();
+    witness_data.extend(tx_order.to_le_bytes().iter());
+    h256::sha3_256_of(&witness_data)
+}
+
+/// The proof that a transaction is sequenced at its tx order.
+/// The tx hash is the leaf at the tx order of the sequencer's tx accumulator,
+/// and the accumulator root is the one recorded in the sequence info right after the tx is appended.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionProof {
+    pub tx_hash: H256,
+    pub sequence_info: TransactionSequenceInfo,
+    pub proof: AccumulatorProof,
+}
+
+impl TransactionProof {
+    pub fn new(
+        tx_hash: H256,
+        sequence_info: TransactionSequenceInfo,
+        proof: AccumulatorProof,
+    ) -> Self {
+        Self {
+            tx_hash,
+            sequence_info,
+            proof,
+        }
+    }
+
+    /// Verify the tx hash is included in the tx accumulator at the tx order
+    pub fn verify_inclusion(&self) -> Result<()> {
+        let tx_order = self.sequence_info.tx_order;
+        let num_leaves = self.sequence_info.tx_accumulator_num_leaves;
+        // the accumulator info is absent in the sequence info of the early transactions
+        if num_leaves > 0 {
+            ensure!(
+                tx_order + 1 == num_leaves,
+                "The accumulator root is not the one after the tx is appended, tx_order: {}, num_leaves: {}",
+                tx_order,
+                num_leaves
+            );
+        }
+        self.proof.verify(
+            self.sequence_info.tx_accumulator_root,
+            self.tx_hash,
+            tx_order,
+        )
+    }
+
+    /// Verify the tx order is signed by the sequencer
+    pub fn verify_order_signature(&self, sequencer: RoochAddress) -> Result<()> {
+        let signature = Signature::from_bytes(&self.sequence_info.tx_order_signature)
+            .map_err(|e| anyhow!("Invalid tx order signature: {:?}", e))?;
+        let signer = signer_public_key(&signature)?.rooch_address()?;
+        ensure!(
+            signer == sequencer,
+            "The tx order is signed by {}, not the sequencer {}",
+            signer,
+            sequencer
+        );
+        let witness_hash = tx_order_witness_hash(self.tx_hash, self.sequence_info.tx_order);
+        signature.verify(&witness_hash.0)?;
+        Ok(())
+    }
+
+    /// Verify the transaction is sequenced at the tx order by the sequencer
+    pub fn verify(&self, sequencer: RoochAddress) -> Result<()> {
+        self.verify_order_signature(sequencer)?;
+        self.verify_inclusion()
+    }
+}
+
+fn signer_public_key(signature: &Signature) -> Result<PublicKey> {
+    let bytes = signature.public_key_bytes();
+    let public_key = match signature.scheme() {
+        SignatureScheme::Ed25519 => {
+            PublicKey::Ed25519((&Ed25519PublicKey::from_bytes(bytes)?).into())
+        }
+        SignatureScheme::Secp256k1 => {
+            PublicKey::Secp256k1((&Secp256k1PublicKey::from_bytes(bytes)?).into())
+        }
+    };
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::RoochKeyPair;
+    use accumulator::tree_store::mock::MockAccumulatorStore;
+    use accumulator::{Accumulator, MerkleAccumulator};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_transaction_proof() {
+        let sequencer_key = RoochKeyPair::generate_secp256k1();
+        let sequencer = sequencer_key.public().rooch_address().unwrap();
+        let accumulator = MerkleAccumulator::new_empty(Arc::new(MockAccumulatorStore::new()));
+
+        let mut sequence_infos = vec![];
+        let tx_hashes = (0..10).map(|_| H256::random()).collect::<Vec<_>>();
+        for (tx_order, tx_hash) in tx_hashes.iter().enumerate() {
+            let tx_order = tx_order as u64;
+            accumulator.append(&[*tx_hash]).unwrap();
+            accumulator.flush().unwrap();
+            let signature =
+                Signature::sign(&tx_order_witness_hash(*tx_hash, tx_order).0, &sequencer_key);
+            sequence_infos.push(TransactionSequenceInfo::new(
+                tx_order,
+                signature.as_ref().to_vec(),
+                accumulator.get_info(),
+                0,
+            ));
+        }
+
+        for (tx_hash, sequence_info) in tx_hashes.iter().zip(sequence_infos) {
+            // the proof is generated by the accumulator at the time the tx is appended
+            let proof = accumulator
+                .fork(Some(sequence_info.tx_accumulator_info()))
+                .get_proof(sequence_info.tx_order)
+                .unwrap()
+                .unwrap();
+            let tx_proof = TransactionProof::new(*tx_hash, sequence_info, proof);
+            tx_proof.verify(sequencer).unwrap();
+
+            let mut wrong_hash = tx_proof.clone();
+            wrong_hash.tx_hash = H256::random();
+            assert!(wrong_hash.verify_inclusion().is_err());
+            assert!(wrong_hash.verify_order_signature(sequencer).is_err());
+
+            let other = RoochKeyPair::generate_secp256k1()
+                .public()
+                .rooch_address()
+                .unwrap();
+            assert!(tx_proof.verify_order_signature(other).is_err());
+        }
+    }
+}
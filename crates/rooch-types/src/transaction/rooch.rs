@@ -154,6 +154,10 @@ impl RoochTransaction {
         &self.authenticator
     }
 
+    pub fn data(&self) -> &RoochTransactionData {
+        &self.data
+    }
+
     pub fn tx_size(&self) -> u64 {
         bcs::serialized_size(self).expect("serialize transaction size should success") as u64
     }
@@ -0,0 +1,6 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod status;
+pub mod sync;
+pub mod verify_celestia;
@@ -0,0 +1,24 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use rooch_rpc_api::jsonrpc_types::da_view::DAStatusView;
+use rooch_types::error::RoochResult;
+
+/// Show the DA submission status of the node, including the pending chunks and the lagging servers
+#[derive(Debug, Parser)]
+pub struct StatusCommand {
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<DAStatusView> for StatusCommand {
+    async fn execute(self) -> RoochResult<DAStatusView> {
+        let context = self.context_options.build()?;
+        let client = context.get_client().await?;
+        Ok(client.rooch.get_da_status().await?)
+    }
+}
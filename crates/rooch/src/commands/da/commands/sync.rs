@@ -0,0 +1,471 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use accumulator::{Accumulator, MerkleAccumulator};
+use anyhow::{anyhow, ensure, Result};
+use bitcoin::hashes::Hash;
+use bitcoincore_rpc::{Auth, RpcApi};
+use clap::Parser;
+use coerce::actor::system::ActorSystem;
+use coerce::actor::IntoActor;
+use metrics::RegistryService;
+use moveos_store::transaction_store::TransactionStore as TxExecutionInfoStore;
+use moveos_store::MoveOSStore;
+use moveos_types::h256::H256;
+use moveos_types::transaction::TransactionExecutionInfo;
+use rooch_config::da_config::DAConfig;
+use rooch_config::{RoochOpt, R_OPT_NET_HELP};
+use rooch_da::actor::da::DAActor;
+use rooch_da::proxy::DAProxy;
+use rooch_db::RoochDB;
+use rooch_executor::actor::executor::ExecutorActor;
+use rooch_genesis::RoochGenesis;
+use rooch_rpc_client::{Client, ClientBuilder};
+use rooch_store::meta_store::MetaStore;
+use rooch_store::transaction_store::TransactionStore;
+use rooch_store::RoochStore;
+use rooch_types::block::BlockCommitment;
+use rooch_types::error::RoochResult;
+use rooch_types::rooch_network::RoochChainID;
+use rooch_types::sequencer::SequencerInfo;
+use rooch_types::transaction::{
+    L1Block, L1BlockWithBody, LedgerTransaction, LedgerTxData, TransactionSequenceInfoV1,
+};
+use serde::{Deserialize, Serialize};
+
+/// Rebuild a node's ledger and states by replaying the batches published to DA.
+/// The data dir should be a fresh one, or a data dir synced by this command before.
+/// The tx accumulator root of each tx and the state root of each block commitment in DA
+/// are always verified against the replayed ones.
+#[derive(Debug, Parser)]
+pub struct SyncCommand {
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+
+    #[clap(long)]
+    /// The genesis config file path for custom chain network, must be the same as the sequencer's.
+    pub genesis_config: Option<String>,
+
+    /// The DA config to read batches from, the same format as `rooch server start --da`
+    #[clap(long, default_value_t)]
+    pub da: DAConfig,
+
+    /// The block number to start syncing from.
+    /// Transactions which are already in the local ledger are skipped.
+    #[clap(long, default_value_t = 0)]
+    pub start_block: u128,
+
+    /// The block number to stop syncing at(inclusive), sync until no more batch in DA if not set.
+    #[clap(long)]
+    pub end_block: Option<u128>,
+
+    /// The RPC URL of a trusted node, if set, the state root after each transaction
+    /// is also checked against the state roots of the block proposed by the trusted node.
+    #[clap(long)]
+    pub verify_rpc_url: Option<String>,
+
+    /// The Bitcoin RPC URL to fetch the L1 block body for replaying L1 block transactions.
+    #[clap(
+        long,
+        env = "BITCOIN_RPC_URL",
+        requires = "btc-rpc-username",
+        requires = "btc-rpc-password"
+    )]
+    pub btc_rpc_url: Option<String>,
+
+    #[clap(long, id = "btc-rpc-username", env = "BTC_RPC_USERNAME")]
+    pub btc_rpc_username: Option<String>,
+
+    #[clap(long, id = "btc-rpc-password", env = "BTC_RPC_PASSWORD")]
+    pub btc_rpc_password: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResult {
+    /// The number of blocks read from DA
+    pub synced_blocks: u64,
+    /// The number of transactions replayed
+    pub replayed_txs: u64,
+    /// The number of block commitments in DA verified against the replayed state roots
+    pub verified_commitments: u64,
+    /// The last block number read from DA
+    pub last_block_number: Option<u128>,
+    /// The last tx order in the local ledger
+    pub last_tx_order: u64,
+    /// The latest state root in the local store
+    pub state_root: H256,
+}
+
+impl SyncCommand {
+    pub async fn execute(self) -> RoochResult<SyncResult> {
+        let mut opt = RoochOpt {
+            base_data_dir: self.base_data_dir.clone(),
+            chain_id: self.chain_id.clone(),
+            genesis_config: self.genesis_config.clone(),
+            da: self.da.clone(),
+            ..Default::default()
+        };
+        opt.init()?;
+
+        let registry_service = RegistryService::default();
+        let registry = registry_service.default_registry();
+        let rooch_db = RoochDB::init(opt.store_config(), &registry)?;
+        let genesis = RoochGenesis::load_or_init(opt.network(), &rooch_db)?;
+        let root = match rooch_db.latest_root()? {
+            Some(root) => root,
+            None => genesis.genesis_root().clone(),
+        };
+
+        let actor_system = ActorSystem::global_system();
+        let da_proxy = DAProxy::new(
+            DAActor::new(
+                opt.da_config().clone(),
+                rooch_db.rooch_store.clone(),
+                &actor_system,
+            )
+            .await?
+            .into_actor(Some("DAProxy"), &actor_system)
+            .await?
+            .into(),
+        );
+
+        let verify_client = match &self.verify_rpc_url {
+            Some(url) => Some(ClientBuilder::default().build(url).await?),
+            None => None,
+        };
+        let bitcoin_client = match &self.btc_rpc_url {
+            Some(url) => Some(bitcoincore_rpc::Client::new(
+                url,
+                Auth::UserPass(
+                    self.btc_rpc_username.clone().unwrap_or_default(),
+                    self.btc_rpc_password.clone().unwrap_or_default(),
+                ),
+            )?),
+            None => None,
+        };
+
+        let executor = ExecutorActor::new(
+            root,
+            rooch_db.moveos_store.clone(),
+            rooch_db.rooch_store.clone(),
+            &registry,
+        )?;
+        let mut replayer = Replayer::new(
+            executor,
+            rooch_db.rooch_store.clone(),
+            rooch_db.moveos_store.clone(),
+            bitcoin_client,
+        )?;
+
+        let mut result = SyncResult {
+            synced_blocks: 0,
+            replayed_txs: 0,
+            verified_commitments: 0,
+            last_block_number: None,
+            last_tx_order: replayer.last_order(),
+            state_root: rooch_db
+                .latest_root()?
+                .map(|root| root.state_root())
+                .unwrap_or_else(|| genesis.genesis_root().state_root()),
+        };
+
+        let mut block_number = self.start_block;
+        loop {
+            if let Some(end_block) = self.end_block {
+                if block_number > end_block {
+                    break;
+                }
+            }
+            let batch = match da_proxy.get_batch(block_number).await? {
+                Some(batch) => batch,
+                None => {
+                    println!("No more batch in DA after block {}", block_number);
+                    break;
+                }
+            };
+            let txs = LedgerTransaction::decode_batch(&batch.data)?;
+            let expected_state_roots = match &verify_client {
+                Some(client) => Some(get_state_roots(client, block_number, txs.len()).await?),
+                None => None,
+            };
+
+            let last_tx = txs.last().cloned();
+            for (i, tx) in txs.into_iter().enumerate() {
+                let tx_order = tx.sequence_info.tx_order;
+                if replayer.skip(&tx)? {
+                    continue;
+                }
+                if let LedgerTxData::BlockCommitment(signed_commitment) = &tx.data {
+                    if replayer.verify_block_commitment(&signed_commitment.commitment)? {
+                        result.verified_commitments += 1;
+                    }
+                }
+                let execution_info = replayer.replay(tx)?;
+                if let Some(expected_state_roots) = &expected_state_roots {
+                    let expected_state_root = expected_state_roots[i];
+                    ensure!(
+                        execution_info.state_root == expected_state_root,
+                        "state root mismatch at block {}, tx_order {}, exp: {:?}, act: {:?}",
+                        block_number,
+                        tx_order,
+                        expected_state_root,
+                        execution_info.state_root
+                    );
+                }
+                result.replayed_txs += 1;
+                result.last_tx_order = tx_order;
+                result.state_root = execution_info.state_root;
+            }
+
+            if let Some(last_tx) = last_tx {
+                replayer.record_block(block_number, &last_tx)?;
+            }
+
+            println!(
+                "Synced block {}, last tx order: {}, state root: {:?}",
+                block_number, result.last_tx_order, result.state_root
+            );
+            result.synced_blocks += 1;
+            result.last_block_number = Some(block_number);
+            block_number += 1;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Replay the ledger transactions in order against the local store,
+/// it does what the sequencer and `PipelineProcessorActor::execute_tx` do, without proposing and indexing.
+struct Replayer {
+    executor: ExecutorActor,
+    rooch_store: RoochStore,
+    moveos_store: MoveOSStore,
+    tx_accumulator: MerkleAccumulator,
+    last_sequencer_info: SequencerInfo,
+    bitcoin_client: Option<bitcoincore_rpc::Client>,
+    /// The (tx_accumulator_root, state_root) at the end of the synced blocks,
+    /// waiting for the block commitments in DA to verify them.
+    block_roots: BTreeMap<u128, (H256, H256)>,
+}
+
+impl Replayer {
+    fn new(
+        executor: ExecutorActor,
+        rooch_store: RoochStore,
+        moveos_store: MoveOSStore,
+        bitcoin_client: Option<bitcoincore_rpc::Client>,
+    ) -> Result<Self> {
+        // The sequencer info would be inited when genesis, so the sequencer info should not be None
+        let last_sequencer_info = rooch_store
+            .get_meta_store()
+            .get_sequencer_info()?
+            .ok_or_else(|| anyhow!("Load sequencer info failed"))?;
+        let tx_accumulator = MerkleAccumulator::new_with_info(
+            last_sequencer_info.last_accumulator_info.clone(),
+            rooch_store.get_transaction_accumulator_store(),
+        );
+        Ok(Self {
+            executor,
+            rooch_store,
+            moveos_store,
+            tx_accumulator,
+            last_sequencer_info,
+            bitcoin_client,
+            block_roots: BTreeMap::new(),
+        })
+    }
+
+    fn last_order(&self) -> u64 {
+        self.last_sequencer_info.last_order
+    }
+
+    /// Check whether the tx is already in the local ledger, and ensure there is no gap.
+    fn skip(&self, tx: &LedgerTransaction) -> Result<bool> {
+        let tx_order = tx.sequence_info.tx_order;
+        let last_order = self.last_order();
+        if tx_order <= last_order {
+            let mut tx = tx.clone();
+            let local_tx_hash = self
+                .rooch_store
+                .get_tx_hashs(vec![tx_order])?
+                .pop()
+                .flatten();
+            ensure!(
+                local_tx_hash == Some(tx.tx_hash()),
+                "tx_order {} in DA conflicts with local ledger, local tx hash: {:?}",
+                tx_order,
+                local_tx_hash
+            );
+            return Ok(true);
+        }
+        ensure!(
+            tx_order == last_order + 1,
+            "tx order is not continuous, last order: {}, tx order: {}",
+            last_order,
+            tx_order
+        );
+        Ok(false)
+    }
+
+    fn replay(&mut self, mut tx: LedgerTransaction) -> Result<TransactionExecutionInfo> {
+        let tx_hash = tx.tx_hash();
+        let tx_order = tx.sequence_info.tx_order;
+
+        // The rebuilt tx accumulator must match the one the sequencer signed
+        self.tx_accumulator.append(vec![tx_hash].as_slice())?;
+        self.tx_accumulator.flush()?;
+        let tx_accumulator_info = self.tx_accumulator.get_info();
+        ensure!(
+            tx_accumulator_info.accumulator_root == tx.sequence_info.tx_accumulator_root,
+            "tx accumulator root mismatch at tx_order {}, exp: {:?}, act: {:?}",
+            tx_order,
+            tx.sequence_info.tx_accumulator_root,
+            tx_accumulator_info.accumulator_root
+        );
+
+        let mut moveos_tx = match &tx.data {
+            LedgerTxData::L1Block(block) => {
+                let block_body = self.get_l1_block_body(block)?;
+                self.executor.validate_l1_block(L1BlockWithBody {
+                    block: block.clone(),
+                    block_body,
+                })?
+            }
+            LedgerTxData::L1Tx(l1_tx) => self.executor.validate_l1_tx(l1_tx.clone())?,
+            LedgerTxData::L2Tx(l2_tx) => self.executor.validate_l2_tx(l2_tx.clone())?,
+            LedgerTxData::BlockCommitment(signed_commitment) => self
+                .executor
+                .validate_block_commitment(signed_commitment.clone())?,
+        };
+        // Same as `PipelineProcessorActor::execute_tx`, add sequence info to tx context
+        moveos_tx.ctx.add(tx.sequence_info.clone())?;
+        let tx_sequence_info_v1 = TransactionSequenceInfoV1::from(tx.sequence_info.clone());
+        moveos_tx.ctx.add(tx_sequence_info_v1)?;
+
+        let result = match self.executor.execute(moveos_tx) {
+            Ok(result) => result,
+            Err(e) => {
+                // Discard the appended tx hash, the ledger is only persisted after a successful execution
+                self.tx_accumulator = MerkleAccumulator::new_with_info(
+                    self.last_sequencer_info.last_accumulator_info.clone(),
+                    self.rooch_store.get_transaction_accumulator_store(),
+                );
+                return Err(e);
+            }
+        };
+
+        // Save the sequencer info last, it is the cursor to resume the sync
+        let sequencer_info = SequencerInfo::new(tx_order, tx_accumulator_info);
+        self.rooch_store.save_transaction(tx)?;
+        self.rooch_store
+            .save_sequencer_info(sequencer_info.clone())?;
+        self.last_sequencer_info = sequencer_info;
+        Ok(result.transaction_info)
+    }
+
+    /// Record the roots at the end of the block, the last tx of the block is executed or skipped.
+    fn record_block(&mut self, block_number: u128, last_tx: &LedgerTransaction) -> Result<()> {
+        let mut last_tx = last_tx.clone();
+        let execution_info = self
+            .moveos_store
+            .get_tx_execution_info(last_tx.tx_hash())?
+            .ok_or_else(|| {
+                anyhow!(
+                    "The execution info of tx_order {} not found",
+                    last_tx.sequence_info.tx_order
+                )
+            })?;
+        self.block_roots.insert(
+            block_number,
+            (
+                last_tx.sequence_info.tx_accumulator_root,
+                execution_info.state_root,
+            ),
+        );
+        Ok(())
+    }
+
+    /// Verify the block commitment in DA against the replayed block.
+    /// Return false if the block is synced before this run and can not be verified.
+    fn verify_block_commitment(&mut self, commitment: &BlockCommitment) -> Result<bool> {
+        let (tx_accumulator_root, state_root) = match self
+            .block_roots
+            .remove(&commitment.block_number)
+        {
+            Some(roots) => roots,
+            None => {
+                println!(
+                        "Skip verifying the commitment of block {}, the block is not synced in this run",
+                        commitment.block_number
+                    );
+                return Ok(false);
+            }
+        };
+        ensure!(
+            commitment.tx_accumulator_root == tx_accumulator_root,
+            "tx accumulator root mismatch with the commitment of block {}, exp: {:?}, act: {:?}",
+            commitment.block_number,
+            commitment.tx_accumulator_root,
+            tx_accumulator_root
+        );
+        ensure!(
+            commitment.state_root == state_root,
+            "state root mismatch with the commitment of block {}, exp: {:?}, act: {:?}",
+            commitment.block_number,
+            commitment.state_root,
+            state_root
+        );
+        // The commitments are in order, the earlier blocks would never be committed
+        self.block_roots = self.block_roots.split_off(&commitment.block_number);
+        Ok(true)
+    }
+
+    fn get_l1_block_body(&self, block: &L1Block) -> Result<Vec<u8>> {
+        let bitcoin_client = self.bitcoin_client.as_ref().ok_or_else(|| {
+            anyhow!(
+                "Replay L1 block {} requires the block body, please set --btc-rpc-url",
+                block.block_height
+            )
+        })?;
+        ensure!(
+            block.chain_id.is_bitcoin(),
+            "Unsupported L1 block chain: {:?}",
+            block.chain_id
+        );
+        let block_hash = bitcoin::BlockHash::from_slice(&block.block_hash)?;
+        let bitcoin_block = bitcoin_client.get_block(&block_hash)?;
+        Ok(rooch_types::bitcoin::types::Block::from(bitcoin_block).encode())
+    }
+}
+
+/// Get the state roots of the block proposed by the trusted node
+async fn get_state_roots(
+    client: &Client,
+    block_number: u128,
+    tx_count: usize,
+) -> Result<Vec<H256>> {
+    let block = client
+        .rooch
+        .get_block(block_number)
+        .await?
+        .ok_or_else(|| anyhow!("Block {} not found in the trusted node", block_number))?;
+    ensure!(
+        block.state_roots.len() == tx_count,
+        "state roots count mismatch at block {}, exp: {}, act: {}",
+        block_number,
+        tx_count,
+        block.state_roots.len()
+    );
+    Ok(block
+        .state_roots
+        .into_iter()
+        .map(|state_root| state_root.0)
+        .collect())
+}
@@ -0,0 +1,134 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use clap::Parser;
+use rooch_config::da_config::DAServerCelestiaConfig;
+use rooch_da::server::celestia::scanner::{verify_block_batch, ChunkScanner};
+use rooch_rpc_client::ClientBuilder;
+use rooch_types::block::Block;
+use rooch_types::error::RoochResult;
+use serde::{Deserialize, Serialize};
+
+/// Verify every block proposed by a node has a complete and checksum-valid chunk on Celestia,
+/// by scanning the blobs in the namespace of a height range.
+#[derive(Debug, Parser)]
+pub struct VerifyCelestiaCommand {
+    #[clap(flatten)]
+    pub celestia: DAServerCelestiaConfig,
+
+    /// The first Celestia height to scan
+    #[clap(long)]
+    pub start_height: u64,
+
+    /// The last Celestia height to scan(inclusive)
+    #[clap(long)]
+    pub end_height: u64,
+
+    /// The RPC URL of the node which proposed the blocks
+    #[clap(long)]
+    pub rpc_url: String,
+
+    /// The first block number to verify
+    #[clap(long, default_value_t = 0)]
+    pub start_block: u128,
+
+    /// The last block number to verify(inclusive), the latest block of the node if not set
+    #[clap(long)]
+    pub end_block: Option<u128>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockVerifyError {
+    pub block_number: u128,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyCelestiaResult {
+    /// The number of complete and checksum-valid chunks found on Celestia
+    pub chunks: u64,
+    /// The number of blocks whose batches are available and match the blocks
+    pub verified_blocks: u64,
+    /// The blocks without batch on Celestia
+    pub missing_blocks: Vec<u128>,
+    /// The blocks whose batches do not match the blocks
+    pub mismatched_blocks: Vec<BlockVerifyError>,
+    /// The chunks missing some segments
+    pub incomplete_chunks: Vec<u128>,
+    /// The chunks failed to be rebuilt from their segments
+    pub invalid_chunks: Vec<u128>,
+    /// The blobs in the namespace which are not valid segments
+    pub invalid_blobs: u64,
+}
+
+impl VerifyCelestiaResult {
+    pub fn is_available(&self) -> bool {
+        self.missing_blocks.is_empty() && self.mismatched_blocks.is_empty()
+    }
+}
+
+impl VerifyCelestiaCommand {
+    pub async fn execute(self) -> RoochResult<VerifyCelestiaResult> {
+        let client = ClientBuilder::default().build(&self.rpc_url).await?;
+        let end_block = match self.end_block {
+            Some(end_block) => Some(end_block),
+            None => client
+                .rooch
+                .get_latest_block()
+                .await?
+                .map(|block| block.block_number.0),
+        };
+
+        let mut scanner = ChunkScanner::new(&self.celestia).await?;
+        scanner.scan(self.start_height, self.end_height).await?;
+        let scanned = scanner.finish();
+
+        let mut result = VerifyCelestiaResult {
+            chunks: scanned.chunks.len() as u64,
+            verified_blocks: 0,
+            missing_blocks: vec![],
+            mismatched_blocks: vec![],
+            incomplete_chunks: scanned.incomplete_chunks.clone(),
+            invalid_chunks: scanned
+                .invalid_chunks
+                .iter()
+                .map(|(chunk_id, _)| *chunk_id)
+                .collect(),
+            invalid_blobs: scanned.invalid_blobs,
+        };
+        for (chunk_id, error) in &scanned.invalid_chunks {
+            println!("Invalid chunk {}: {}", chunk_id, error);
+        }
+
+        if let Some(end_block) = end_block {
+            for block_number in self.start_block..=end_block {
+                let block: Block = client
+                    .rooch
+                    .get_block(block_number)
+                    .await?
+                    .ok_or_else(|| anyhow!("Block {} not found in the node", block_number))?
+                    .into();
+                match scanned.get_batch(block_number) {
+                    Some(batch) => match verify_block_batch(&block, &batch) {
+                        Ok(()) => result.verified_blocks += 1,
+                        Err(e) => result.mismatched_blocks.push(BlockVerifyError {
+                            block_number,
+                            error: e.to_string(),
+                        }),
+                    },
+                    None => result.missing_blocks.push(block_number),
+                }
+            }
+        }
+
+        if !result.is_available() {
+            return Err(anyhow!(
+                "Data availability check failed: {}",
+                serde_json::to_string_pretty(&result)?
+            )
+            .into());
+        }
+        Ok(result)
+    }
+}
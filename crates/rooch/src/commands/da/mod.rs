@@ -0,0 +1,44 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use clap::Parser;
+
+use rooch_types::error::RoochResult;
+
+use crate::cli_types::CommandAction;
+use crate::commands::da::commands::status::StatusCommand;
+use crate::commands::da::commands::sync::SyncCommand;
+use crate::commands::da::commands::verify_celestia::VerifyCelestiaCommand;
+
+pub mod commands;
+
+/// DA Commands
+#[derive(Parser)]
+pub struct DA {
+    #[clap(subcommand)]
+    cmd: DACommand,
+}
+
+#[async_trait]
+impl CommandAction<String> for DA {
+    async fn execute(self) -> RoochResult<String> {
+        match self.cmd {
+            DACommand::Sync(sync) => sync.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
+            DACommand::Status(status) => status.execute_serialized().await,
+            DACommand::VerifyCelestia(verify) => verify.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
+        }
+    }
+}
+
+#[derive(clap::Subcommand)]
+#[clap(name = "da")]
+pub enum DACommand {
+    Sync(SyncCommand),
+    Status(StatusCommand),
+    VerifyCelestia(VerifyCelestiaCommand),
+}
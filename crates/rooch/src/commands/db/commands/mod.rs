@@ -1,3 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
+pub mod prune;
+pub mod restore;
 pub mod revert_tx;
+pub mod rollback;
+pub mod snapshot;
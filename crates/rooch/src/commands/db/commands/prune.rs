@@ -0,0 +1,76 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use metrics::RegistryService;
+use serde::{Deserialize, Serialize};
+
+use rooch_config::{RoochOpt, R_OPT_NET_HELP};
+use rooch_db::RoochDB;
+use rooch_types::error::RoochResult;
+use rooch_types::rooch_network::RoochChainID;
+
+use crate::cli_types::WalletContextOptions;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneOutput {
+    pub pruned_versions: u64,
+    pub removed_nodes: u64,
+    /// The versions before this are pruned
+    pub pruned_version: u64,
+    /// The version of the next state root
+    pub next_version: u64,
+}
+
+/// Prune the stale state nodes offline, the server should be stopped.
+#[derive(Debug, Parser)]
+pub struct PruneCommand {
+    #[clap(long)]
+    /// The number of the latest state roots to retain
+    pub retain_roots: u64,
+
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    /// If local chainid, start the service with a temporary data store.
+    /// All data will be deleted when the service is stopped.
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+impl PruneCommand {
+    pub async fn execute(self) -> RoochResult<PruneOutput> {
+        let opt = RoochOpt::new_with_default(self.base_data_dir, self.chain_id, None)?;
+        let registry_service = RegistryService::default();
+        let rooch_db = RoochDB::init(opt.store_config(), &registry_service.default_registry())?;
+        let prune_store = rooch_db
+            .moveos_store
+            .get_state_store()
+            .get_prune_store()
+            .clone();
+        // Check the stale node index is complete up to the latest state root
+        if let Some(root) = rooch_db.latest_root()? {
+            prune_store.enable(root.state_root())?;
+        }
+
+        let info = prune_store.get_prune_info();
+        println!(
+            "Prune state versions [{}, {}), retain the latest {} state roots",
+            info.pruned_version, info.next_version, self.retain_roots
+        );
+        let result = prune_store.prune(self.retain_roots, None)?;
+        let info = prune_store.get_prune_info();
+        Ok(PruneOutput {
+            pruned_versions: result.pruned_versions,
+            removed_nodes: result.removed_nodes,
+            pruned_version: info.pruned_version,
+            next_version: info.next_version,
+        })
+    }
+}
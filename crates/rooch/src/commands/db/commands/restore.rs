@@ -0,0 +1,210 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use metrics::RegistryService;
+use serde::{Deserialize, Serialize};
+
+use moveos_store::MoveOSStore;
+use rooch_config::store_config::StoreConfig;
+use rooch_config::{RoochOpt, R_OPT_NET_HELP};
+use rooch_db::RoochDB;
+use rooch_store::RoochStore;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::rooch_network::RoochChainID;
+
+use crate::cli_types::WalletContextOptions;
+use crate::commands::db::commands::snapshot::{
+    verify_store, SnapshotMeta, SNAPSHOT_INDEXER_DIR, SNAPSHOT_STORE_DIR,
+};
+
+const RESTORE_DIR_SUFFIX: &str = ".restore";
+const OLD_DIR_SUFFIX: &str = ".old";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreOutput {
+    pub store_dir: PathBuf,
+    pub indexer_dir: PathBuf,
+    pub meta: SnapshotMeta,
+}
+
+/// Restore the db from a snapshot, the server should be stopped.
+/// The snapshot is verified before and after it is copied to the data dir,
+/// the existing store and indexer are only replaced after the copy is verified.
+#[derive(Debug, Parser)]
+pub struct RestoreCommand {
+    #[clap(long, short = 'i')]
+    /// The snapshot dir created by `rooch db snapshot`
+    pub input: PathBuf,
+
+    #[clap(long)]
+    /// Replace the existing store and indexer in the data dir
+    pub force: bool,
+
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    /// If local chainid, start the service with a temporary data store.
+    /// All data will be deleted when the service is stopped.
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+impl RestoreCommand {
+    pub async fn execute(self) -> RoochResult<RestoreOutput> {
+        let opt = RoochOpt::new_with_default(self.base_data_dir, self.chain_id, None)?;
+        let chain_id = opt.chain_id().chain_id().id;
+        let store_config = opt.store_config();
+        let meta = SnapshotMeta::load(&self.input)?;
+        if meta.chain_id != chain_id {
+            return Err(RoochError::from(anyhow::anyhow!(
+                "The snapshot chain id {} not match the chain id {}",
+                meta.chain_id,
+                chain_id
+            )));
+        }
+
+        let snapshot_store_dir = self.input.join(SNAPSHOT_STORE_DIR);
+        let snapshot_indexer_dir = self.input.join(SNAPSHOT_INDEXER_DIR);
+        verify_snapshot_store(&snapshot_store_dir, store_config, &meta)?;
+        println!(
+            "Verified snapshot at state root {}, last order {}",
+            meta.state_root, meta.last_order
+        );
+
+        let store_dir = store_config.get_store_dir();
+        let indexer_dir = store_config.get_indexer_dir();
+        for dir in [&store_dir, &indexer_dir] {
+            if !dir_is_empty(dir)? && !self.force {
+                return Err(RoochError::from(anyhow::anyhow!(
+                    "The dir {:?} is not empty, please use --force to overwrite it",
+                    dir
+                )));
+            }
+        }
+
+        // Restore into the sibling temp dirs first, the existing dirs are only replaced
+        // after the restored store is verified, so a broken snapshot never destroys them.
+        let restore_store_dir = sibling_dir(&store_dir, RESTORE_DIR_SUFFIX)?;
+        let restore_indexer_dir = sibling_dir(&indexer_dir, RESTORE_DIR_SUFFIX)?;
+        for dir in [&restore_store_dir, &restore_indexer_dir] {
+            // Left by a failed restore
+            if dir.exists() {
+                std::fs::remove_dir_all(dir)?;
+            }
+        }
+        copy_dir(&snapshot_store_dir, &restore_store_dir)?;
+        copy_dir(&snapshot_indexer_dir, &restore_indexer_dir)?;
+        // Verify the restored store again, so the node is never started on a broken copy.
+        verify_snapshot_store(&restore_store_dir, store_config, &meta)?;
+
+        replace_dir(&restore_store_dir, &store_dir)?;
+        replace_dir(&restore_indexer_dir, &indexer_dir)?;
+
+        Ok(RestoreOutput {
+            store_dir,
+            indexer_dir,
+            meta,
+        })
+    }
+}
+
+fn verify_snapshot_store(
+    store_dir: &Path,
+    store_config: &StoreConfig,
+    meta: &SnapshotMeta,
+) -> Result<()> {
+    ensure!(
+        store_dir.exists(),
+        "The store dir {:?} not exists",
+        store_dir
+    );
+    let registry_service = RegistryService::default();
+    let registry = registry_service.default_registry();
+    let mut instance =
+        RoochDB::generate_readonly_store_instance(store_dir, store_config, &registry)?;
+    let moveos_store = MoveOSStore::new_with_instance(instance.clone(), &registry)?;
+    let rooch_store = RoochStore::new_with_instance(instance.clone(), &registry)?;
+
+    let store_meta = verify_store(meta.chain_id, &moveos_store, &rooch_store);
+    // Release the db opened by the metrics task before the store is used by the node.
+    instance.cancel_metrics_task()?;
+    let store_meta = store_meta?;
+    ensure!(
+        store_meta.state_root == meta.state_root && store_meta.size == meta.size,
+        "The state root {}(size: {}) of the store not match the snapshot state root {}(size: {})",
+        store_meta.state_root,
+        store_meta.size,
+        meta.state_root,
+        meta.size
+    );
+    ensure!(
+        store_meta.last_order == meta.last_order
+            && store_meta.last_tx_hash == meta.last_tx_hash
+            && store_meta.last_accumulator_info == meta.last_accumulator_info,
+        "The sequencer info (last order: {}, accumulator root: {}) of the store not match the snapshot (last order: {}, accumulator root: {})",
+        store_meta.last_order,
+        store_meta.last_accumulator_info.accumulator_root,
+        meta.last_order,
+        meta.last_accumulator_info.accumulator_root
+    );
+    Ok(())
+}
+
+/// The dir with the suffix in the same parent dir, so it can be renamed to the dir
+fn sibling_dir(dir: &Path, suffix: &str) -> Result<PathBuf> {
+    let name = dir
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid dir {:?}", dir))?;
+    let mut sibling_name = name.to_os_string();
+    sibling_name.push(suffix);
+    Ok(dir.with_file_name(sibling_name))
+}
+
+/// Replace the dir with the restored dir, the existing dir is moved back if the replacement fails
+fn replace_dir(restored_dir: &Path, dir: &Path) -> Result<()> {
+    if !dir.exists() {
+        std::fs::rename(restored_dir, dir)?;
+        return Ok(());
+    }
+    let old_dir = sibling_dir(dir, OLD_DIR_SUFFIX)?;
+    if old_dir.exists() {
+        std::fs::remove_dir_all(&old_dir)?;
+    }
+    std::fs::rename(dir, &old_dir)?;
+    if let Err(e) = std::fs::rename(restored_dir, dir) {
+        std::fs::rename(&old_dir, dir)?;
+        return Err(e.into());
+    }
+    println!("Replace the existing dir {:?}", dir);
+    std::fs::remove_dir_all(&old_dir)?;
+    Ok(())
+}
+
+fn dir_is_empty(dir: &Path) -> Result<bool> {
+    if !dir.exists() {
+        return Ok(true);
+    }
+    Ok(std::fs::read_dir(dir)?.next().is_none())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,472 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use metrics::RegistryService;
+use serde::{Deserialize, Serialize};
+
+use move_core_types::language_storage::StructTag;
+use move_resource_viewer::MoveValueAnnotator;
+use moveos_store::state_store::statedb::StateDBStore;
+use moveos_store::transaction_store::TransactionStore as TxExecutionInfoStore;
+use moveos_store::MoveOSStore;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::startup_info::StartupInfo;
+use moveos_types::state::{FieldKey, ObjectState};
+use moveos_types::state_resolver::{RootObjectResolver, StateResolver};
+use rooch_config::{RoochOpt, R_OPT_NET_HELP};
+use rooch_db::RoochDB;
+use rooch_indexer::indexer_reader::IndexerReader;
+use rooch_indexer::store::traits::IndexerStoreTrait;
+use rooch_indexer::utils::decode_field_value;
+use rooch_indexer::IndexerStore;
+use rooch_store::RoochStore;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::indexer::field::{FieldIndexConfig, IndexerFieldState};
+use rooch_types::indexer::state::IndexerObjectState;
+use rooch_types::rooch_network::RoochChainID;
+use rooch_types::sequencer::SequencerInfo;
+
+use crate::cli_types::WalletContextOptions;
+use crate::commands::db::commands::snapshot::verify_store;
+
+const ROLLBACK_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexerRollbackReport {
+    /// The last tx order in the indexer
+    pub last_tx_order: Option<u64>,
+    pub transactions: u64,
+    pub events: u64,
+    /// The object states updated after the tx order, they are restored from the state of the tx order
+    pub object_states: u64,
+    /// The object states removed after the tx order, they are restored from the state of the tx order.
+    /// They are found by comparing the state trees, so they are not counted in the dry run.
+    pub removed_object_states: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RollbackOutput {
+    pub dry_run: bool,
+    pub to_order: u64,
+    pub last_order: u64,
+    pub state_root: H256,
+    pub size: u64,
+    pub tx_accumulator_root: H256,
+    pub transactions: u64,
+    pub execution_infos: u64,
+    pub events: u64,
+    pub pending_proposer_txs: u64,
+    /// The versions removed from the stale state node index
+    pub state_prune_versions: u64,
+    pub indexer: IndexerRollbackReport,
+    pub warnings: Vec<String>,
+}
+
+/// Roll back the db to the given tx order, the txs after it are removed. The server should be stopped.
+#[derive(Debug, Parser)]
+pub struct RollbackCommand {
+    #[clap(long)]
+    /// The tx order to roll back to, the tx of the order is kept
+    pub to_order: u64,
+
+    #[clap(long)]
+    /// Only report what would be rolled back
+    pub dry_run: bool,
+
+    #[clap(long, value_delimiter = ',')]
+    /// The objects whose dynamic fields are indexed, the same as the server `--field-index-parents`.
+    /// The fields of them removed after the tx order are restored in the indexer.
+    pub field_index_parents: Vec<ObjectID>,
+
+    #[clap(long, value_delimiter = ',')]
+    /// The types of the objects whose dynamic fields are indexed, the same as the server `--field-index-object-types`
+    pub field_index_object_types: Vec<StructTag>,
+
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    /// If local chainid, start the service with a temporary data store.
+    /// All data will be deleted when the service is stopped.
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+impl RollbackCommand {
+    pub async fn execute(self) -> RoochResult<RollbackOutput> {
+        let opt = RoochOpt::new_with_default(self.base_data_dir, self.chain_id, None)?;
+        let chain_id = opt.chain_id().chain_id().id;
+        let registry_service = RegistryService::default();
+        let rooch_db = RoochDB::init(opt.store_config(), &registry_service.default_registry())?;
+        let RoochDB {
+            moveos_store,
+            rooch_store,
+            indexer_store,
+            indexer_reader,
+        } = rooch_db;
+
+        let to_order = self.to_order;
+        let sequencer_info = rooch_store
+            .get_meta_store()
+            .get_sequencer_info()?
+            .ok_or_else(|| anyhow::anyhow!("Load sequencer info failed"))?;
+        let last_order = sequencer_info.last_order;
+        if to_order > last_order {
+            return Err(RoochError::from(anyhow::anyhow!(
+                "The tx order {} is greater than the last order {}",
+                to_order,
+                last_order
+            )));
+        }
+
+        let tx_hash = get_tx_hash(&rooch_store, to_order)?
+            .ok_or_else(|| anyhow::anyhow!("The tx hash not exist via tx order {}", to_order))?;
+        let ledger_tx = rooch_store
+            .transaction_store
+            .get_transaction_by_hash(tx_hash)?
+            .ok_or_else(|| anyhow::anyhow!("The ledger tx not exist via tx_hash {}", tx_hash))?;
+        let execution_info = moveos_store
+            .transaction_store
+            .get_tx_execution_info(tx_hash)?
+            .ok_or_else(|| {
+                anyhow::anyhow!("The tx execution info not exist via tx_hash {}", tx_hash)
+            })?;
+        let tx_accumulator_info = ledger_tx.sequence_info.tx_accumulator_info();
+        // The latest state root is used to find the objects removed after the tx order,
+        // it is saved last, so it is kept if the rollback is interrupted
+        let latest_state_root = moveos_store
+            .config_store
+            .get_startup_info()?
+            .ok_or_else(|| anyhow::anyhow!("Load startup info failed"))?
+            .state_root;
+        let prune_store = moveos_store.get_state_store().get_prune_store().clone();
+        // Fail before any change if the states of the tx order are pruned
+        let state_prune_next_version =
+            prune_store.rollback_next_version(execution_info.state_root)?;
+
+        let mut output = RollbackOutput {
+            dry_run: self.dry_run,
+            to_order,
+            last_order,
+            state_root: execution_info.state_root,
+            size: execution_info.size,
+            tx_accumulator_root: tx_accumulator_info.accumulator_root,
+            state_prune_versions: prune_store
+                .get_prune_info()
+                .next_version
+                .saturating_sub(state_prune_next_version),
+            ..Default::default()
+        };
+
+        // The txs after the tx order, the tx hash may be removed by a previous interrupted rollback
+        let mut removed_txs = vec![];
+        for tx_order in to_order + 1..=last_order {
+            if let Some(tx_hash) = get_tx_hash(&rooch_store, tx_order)? {
+                removed_txs.push((tx_order, tx_hash));
+            }
+        }
+        output.transactions = removed_txs.len() as u64;
+        output.execution_infos = moveos_store
+            .transaction_store
+            .multi_get_tx_execution_infos(removed_txs.iter().map(|(_, hash)| *hash).collect())?
+            .into_iter()
+            .flatten()
+            .count() as u64;
+        let pending_proposer_txs = rooch_store
+            .get_proposer_store()
+            .get_pending_txs()?
+            .into_iter()
+            .map(|(tx, _)| tx.sequence_info.tx_order)
+            .filter(|tx_order| *tx_order > to_order)
+            .collect::<Vec<_>>();
+        output.pending_proposer_txs = pending_proposer_txs.len() as u64;
+        if let Some(block_number) = rooch_store.get_proposer_store().get_last_block_number()? {
+            output.warnings.push(format!(
+                "The proposed blocks (last block number {}) and DA batches are not rolled back",
+                block_number
+            ));
+        }
+
+        // The event ids of the txs are only recorded in the indexer
+        let indexer_last_tx_order = indexer_reader.query_last_tx_order()?;
+        if indexer_last_tx_order.map_or(true, |order| order < last_order) && to_order < last_order {
+            output.warnings.push(format!(
+                "The indexer is behind the last order {}, the events of the txs not indexed are not rolled back",
+                last_order
+            ));
+        }
+        let event_seqs = indexer_reader.query_first_event_seqs_after_tx_order(to_order)?;
+        for (event_handle_id, event_seq) in &event_seqs {
+            if let Some(event_handle) = moveos_store
+                .event_store
+                .get_event_handle(event_handle_id.clone())?
+            {
+                output.events += event_handle.count.saturating_sub(*event_seq);
+            }
+        }
+        let (transactions, events, object_states) =
+            indexer_reader.query_count_after_tx_order(to_order)?;
+        output.indexer = IndexerRollbackReport {
+            last_tx_order: indexer_last_tx_order,
+            transactions,
+            events,
+            object_states,
+            removed_object_states: 0,
+        };
+
+        if self.dry_run {
+            return Ok(output);
+        }
+
+        // Roll back the stale node index first, so the states of the tx order are never pruned.
+        // The indexer is rolled back before the startup info is saved, the removed objects
+        // are found with the latest state root.
+        // The sequencer info is saved last, so an interrupted rollback can be run again.
+        prune_store.rollback(execution_info.state_root)?;
+        output.indexer.removed_object_states = rollback_indexer(
+            &moveos_store,
+            &indexer_store,
+            &indexer_reader,
+            &FieldIndexConfig::new(self.field_index_parents, self.field_index_object_types),
+            to_order,
+            ObjectMeta::root_metadata(execution_info.state_root, execution_info.size),
+            latest_state_root,
+        )?;
+        for (event_handle_id, event_seq) in event_seqs {
+            moveos_store
+                .event_store
+                .truncate_events(event_handle_id, event_seq)?;
+        }
+        moveos_store
+            .transaction_store
+            .remove_tx_execution_infos(removed_txs.iter().map(|(_, hash)| *hash).collect())?;
+        for (tx_order, tx_hash) in removed_txs {
+            rooch_store
+                .transaction_store
+                .remove_transaction(tx_hash, tx_order)?;
+        }
+        rooch_store
+            .get_proposer_store()
+            .remove_pending_txs(pending_proposer_txs)?;
+        moveos_store
+            .config_store
+            .save_startup_info(StartupInfo::new(
+                execution_info.state_root,
+                execution_info.size,
+            ))?;
+        rooch_store
+            .meta_store
+            .save_sequencer_info_ignore_check(SequencerInfo::new(to_order, tx_accumulator_info))?;
+        verify_store(chain_id, &moveos_store, &rooch_store)?;
+
+        println!(
+            "Rollback to tx order {}, state root {}",
+            to_order, execution_info.state_root
+        );
+        Ok(output)
+    }
+}
+
+fn get_tx_hash(rooch_store: &RoochStore, tx_order: u64) -> Result<Option<H256>> {
+    Ok(rooch_store
+        .transaction_store
+        .get_tx_hashs(vec![tx_order])?
+        .pop()
+        .flatten())
+}
+
+/// Remove the indexed transactions and events after the tx order,
+/// and restore the object states updated after the tx order from the state of the tx order.
+/// The objects removed after the tx order are not indexed anymore, they are found by comparing
+/// the state tree of the tx order with the latest state tree.
+/// The indexed fields are restored in the same way.
+/// Return the number of the restored removed object states.
+fn rollback_indexer(
+    moveos_store: &MoveOSStore,
+    indexer_store: &IndexerStore,
+    indexer_reader: &IndexerReader,
+    field_index_config: &FieldIndexConfig,
+    to_order: u64,
+    root: ObjectMeta,
+    latest_state_root: H256,
+) -> Result<u64> {
+    let state_root = root.state_root();
+    indexer_store.delete_transactions_after_tx_order(to_order)?;
+    indexer_store.delete_events_after_tx_order(to_order)?;
+
+    let resolver = RootObjectResolver::new(root, moveos_store);
+    let annotator = MoveValueAnnotator::new(&resolver);
+    let mut state_index_generator = indexer_reader.query_last_state_index_by_tx_order(to_order)?;
+    loop {
+        let object_ids =
+            indexer_reader.query_object_ids_after_tx_order(to_order, ROLLBACK_BATCH_SIZE)?;
+        if object_ids.is_empty() {
+            break;
+        }
+        let mut update_object_states = vec![];
+        let mut remove_object_ids = vec![];
+        for object_id in object_ids {
+            match resolver.get_object(&object_id)? {
+                Some(state) => {
+                    update_object_states.push(IndexerObjectState::new(
+                        state.metadata,
+                        to_order,
+                        state_index_generator,
+                    ));
+                    state_index_generator += 1;
+                }
+                None => remove_object_ids.push(object_id.to_string()),
+            }
+        }
+        indexer_store.persist_or_update_object_states(update_object_states)?;
+        indexer_store.delete_object_states(remove_object_ids)?;
+    }
+
+    loop {
+        let field_states =
+            indexer_reader.query_field_states_after_tx_order(to_order, ROLLBACK_BATCH_SIZE)?;
+        if field_states.is_empty() {
+            break;
+        }
+        let mut update_field_states = vec![];
+        let mut remove_field_ids = vec![];
+        for field_state in field_states {
+            let field_id = field_state.field_id();
+            let restored_field_state = match resolver.get_object(&field_id)? {
+                Some(state) => IndexerFieldState::new_from_field_metadata(
+                    field_state.parent_id,
+                    field_state.field_key,
+                    &state.metadata,
+                    to_order,
+                    state_index_generator,
+                )
+                .map(|restored_field_state| (restored_field_state, state)),
+                None => None,
+            };
+            match restored_field_state {
+                Some((mut restored_field_state, state)) => {
+                    restored_field_state.decoded_value = decode_field_value(&annotator, state)?;
+                    update_field_states.push(restored_field_state);
+                    state_index_generator += 1;
+                }
+                None => remove_field_ids.push(field_id.to_string()),
+            }
+        }
+        indexer_store.persist_or_update_field_states(update_field_states)?;
+        indexer_store.delete_field_states(remove_field_ids)?;
+    }
+
+    let mut restorer = RemovedStateRestorer {
+        state_store: moveos_store.get_state_store(),
+        annotator: &annotator,
+        indexer_store,
+        field_index_config,
+        to_order,
+        state_index_generator,
+        object_states: vec![],
+        field_states: vec![],
+        restored: 0,
+    };
+    restorer.restore_diff(None, state_root, latest_state_root)?;
+    restorer.flush()?;
+    Ok(restorer.restored)
+}
+
+/// Restore the indexed object states and field states of the objects removed after the tx order.
+/// The fields of a removed object are restored too, they may be removed with the object.
+struct RemovedStateRestorer<'a> {
+    state_store: &'a StateDBStore,
+    /// Decode the removed field values with the modules at the tx order
+    annotator: &'a MoveValueAnnotator<'a, RootObjectResolver<'a, MoveOSStore>>,
+    indexer_store: &'a IndexerStore,
+    field_index_config: &'a FieldIndexConfig,
+    to_order: u64,
+    state_index_generator: u64,
+    object_states: Vec<IndexerObjectState>,
+    field_states: Vec<IndexerFieldState>,
+    restored: u64,
+}
+
+impl RemovedStateRestorer<'_> {
+    /// Compare the fields of the object at the tx order with the latest fields
+    fn restore_diff(
+        &mut self,
+        parent: Option<&ObjectMeta>,
+        old_state_root: H256,
+        new_state_root: H256,
+    ) -> Result<()> {
+        for (field_key, old_state, new_state) in
+            self.state_store.diff(old_state_root, new_state_root)?
+        {
+            match (old_state, new_state) {
+                (Some(old_state), None) => self.restore_object(parent, field_key, old_state)?,
+                (Some(old_state), Some(new_state)) => self.restore_diff(
+                    Some(&old_state.metadata),
+                    old_state.state_root(),
+                    new_state.state_root(),
+                )?,
+                // The objects created after the tx order are removed by the tx order
+                (None, _) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn restore_object(
+        &mut self,
+        parent: Option<&ObjectMeta>,
+        field_key: FieldKey,
+        state: ObjectState,
+    ) -> Result<()> {
+        let state_index = self.state_index_generator;
+        self.state_index_generator += 1;
+        if let Some(parent) =
+            parent.filter(|parent| self.field_index_config.is_indexed_parent(parent))
+        {
+            if let Some(mut field_state) = IndexerFieldState::new_from_field_metadata(
+                parent.id.clone(),
+                field_key,
+                &state.metadata,
+                self.to_order,
+                state_index,
+            ) {
+                field_state.decoded_value = decode_field_value(self.annotator, state.clone())?;
+                self.field_states.push(field_state);
+            }
+        }
+        self.object_states.push(IndexerObjectState::new(
+            state.metadata.clone(),
+            self.to_order,
+            state_index,
+        ));
+        self.restored += 1;
+        if self.object_states.len() >= ROLLBACK_BATCH_SIZE {
+            self.flush()?;
+        }
+
+        if state.metadata.size > 0 {
+            let state_store = self.state_store;
+            for item in state_store.iter(state.state_root(), None)? {
+                let (child_key, child_state) = item?;
+                self.restore_object(Some(&state.metadata), child_key, child_state)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.indexer_store
+            .persist_or_update_object_states(std::mem::take(&mut self.object_states))?;
+        self.indexer_store
+            .persist_or_update_field_states(std::mem::take(&mut self.field_states))?;
+        Ok(())
+    }
+}
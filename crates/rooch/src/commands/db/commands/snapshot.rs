@@ -0,0 +1,202 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use accumulator::accumulator_info::AccumulatorInfo;
+use accumulator::{Accumulator, MerkleAccumulator};
+use anyhow::{ensure, Result};
+use clap::Parser;
+use metrics::RegistryService;
+use serde::{Deserialize, Serialize};
+
+use moveos_store::transaction_store::TransactionStore as TxExecutionInfoStore;
+use moveos_store::MoveOSStore;
+use moveos_types::h256::H256;
+use rooch_config::{RoochOpt, R_OPT_NET_HELP};
+use rooch_db::RoochDB;
+use rooch_store::transaction_store::TransactionStore;
+use rooch_store::RoochStore;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::rooch_network::RoochChainID;
+
+use crate::cli_types::WalletContextOptions;
+
+pub const SNAPSHOT_META_FILE: &str = "snapshot.json";
+pub const SNAPSHOT_STORE_DIR: &str = "store";
+pub const SNAPSHOT_INDEXER_DIR: &str = "indexer";
+
+/// The metadata of a db snapshot, it records the state and the sequencer info of the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotMeta {
+    pub chain_id: u64,
+    /// The state root of the StartupInfo
+    pub state_root: H256,
+    /// The state tree size of the StartupInfo
+    pub size: u64,
+    /// The last tx order of the sequencer
+    pub last_order: u64,
+    /// The tx hash of the last tx order
+    pub last_tx_hash: H256,
+    /// The tx accumulator info of the sequencer
+    pub last_accumulator_info: AccumulatorInfo,
+    /// The create time of the snapshot, in milliseconds
+    pub created_at: u64,
+}
+
+impl SnapshotMeta {
+    pub fn load(snapshot_dir: &Path) -> Result<Self> {
+        let meta_path = snapshot_dir.join(SNAPSHOT_META_FILE);
+        ensure!(
+            meta_path.exists(),
+            "Snapshot meta file {:?} not exists",
+            meta_path
+        );
+        let content = std::fs::read_to_string(meta_path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, snapshot_dir: &Path) -> Result<()> {
+        let meta_path = snapshot_dir.join(SNAPSHOT_META_FILE);
+        std::fs::write(meta_path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Check the StartupInfo and the sequencer info of the store are consistent, and return the snapshot meta.
+/// The tx at the sequencer `last_order` should be the last leaf of the tx accumulator,
+/// and its execution info should point to the StartupInfo state root.
+pub fn verify_store(
+    chain_id: u64,
+    moveos_store: &MoveOSStore,
+    rooch_store: &RoochStore,
+) -> Result<SnapshotMeta> {
+    let startup_info = moveos_store
+        .config_store
+        .get_startup_info()?
+        .ok_or_else(|| anyhow::anyhow!("Load startup info failed"))?;
+    let sequencer_info = rooch_store
+        .get_meta_store()
+        .get_sequencer_info()?
+        .ok_or_else(|| anyhow::anyhow!("Load sequencer info failed"))?;
+    let last_order = sequencer_info.last_order;
+    let last_accumulator_info = sequencer_info.last_accumulator_info;
+    ensure!(
+        last_accumulator_info.num_leaves == last_order + 1,
+        "The tx accumulator leaves {} not match the last order {}",
+        last_accumulator_info.num_leaves,
+        last_order
+    );
+
+    let last_tx_hash = rooch_store
+        .transaction_store
+        .get_tx_hashs(vec![last_order])?
+        .pop()
+        .flatten()
+        .ok_or_else(|| anyhow::anyhow!("The tx hash not exist via tx order {}", last_order))?;
+    let tx_accumulator = MerkleAccumulator::new_with_info(
+        last_accumulator_info.clone(),
+        rooch_store.get_transaction_accumulator_store(),
+    );
+    let leaf = tx_accumulator.get_leaf(last_order)?;
+    ensure!(
+        leaf == Some(last_tx_hash),
+        "The tx accumulator leaf {:?} at order {} not match the tx hash {}",
+        leaf,
+        last_order,
+        last_tx_hash
+    );
+
+    let execution_info = moveos_store
+        .transaction_store
+        .get_tx_execution_info(last_tx_hash)?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "The tx execution info not exist via tx_hash {}, please revert the tx first",
+                last_tx_hash
+            )
+        })?;
+    ensure!(
+        execution_info.state_root == startup_info.state_root,
+        "The startup state root {} not match the state root {} of the last tx {}",
+        startup_info.state_root,
+        execution_info.state_root,
+        last_tx_hash
+    );
+
+    Ok(SnapshotMeta {
+        chain_id,
+        state_root: startup_info.state_root,
+        size: startup_info.size,
+        last_order,
+        last_tx_hash,
+        last_accumulator_info,
+        created_at: chrono::Utc::now().timestamp_millis() as u64,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotOutput {
+    pub snapshot_dir: PathBuf,
+    pub meta: SnapshotMeta,
+}
+
+/// Create a snapshot of the moveos store, rooch store and indexer, the server should be stopped.
+#[derive(Debug, Parser)]
+pub struct SnapshotCommand {
+    #[clap(long, short = 'o')]
+    /// The dir to save the snapshot, it should not exist
+    pub output: PathBuf,
+
+    #[clap(long = "data-dir", short = 'd')]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    /// If local chainid, start the service with a temporary data store.
+    /// All data will be deleted when the service is stopped.
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: Option<RoochChainID>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+impl SnapshotCommand {
+    pub async fn execute(self) -> RoochResult<SnapshotOutput> {
+        if self.output.exists() {
+            return Err(RoochError::from(anyhow::anyhow!(
+                "The snapshot dir {:?} already exists",
+                self.output
+            )));
+        }
+        let opt = RoochOpt::new_with_default(self.base_data_dir, self.chain_id, None)?;
+        let chain_id = opt.chain_id().chain_id().id;
+        let registry_service = RegistryService::default();
+        let registry = registry_service.default_registry();
+        let store_config = opt.store_config();
+        // Open the store exclusively, so the store is not changed during the snapshot.
+        let instance = RoochDB::generate_store_instance(store_config, &registry)?;
+        let rooch_db = RoochDB::init_with_instance(store_config, instance.clone(), &registry)?;
+
+        let meta = verify_store(chain_id, &rooch_db.moveos_store, &rooch_db.rooch_store)?;
+        println!(
+            "Create snapshot at state root {}, last order {}",
+            meta.state_root, meta.last_order
+        );
+
+        std::fs::create_dir_all(&self.output)?;
+        instance
+            .db()
+            .ok_or_else(|| anyhow::anyhow!("The store instance is not a db instance"))?
+            .create_checkpoint(self.output.join(SNAPSHOT_STORE_DIR))?;
+        rooch_db
+            .indexer_store
+            .create_snapshot(&self.output.join(SNAPSHOT_INDEXER_DIR))?;
+        meta.save(&self.output)?;
+
+        Ok(SnapshotOutput {
+            snapshot_dir: self.output,
+            meta,
+        })
+    }
+}
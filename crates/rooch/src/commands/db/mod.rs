@@ -7,7 +7,11 @@ use clap::Parser;
 use rooch_types::error::RoochResult;
 
 use crate::cli_types::CommandAction;
+use crate::commands::db::commands::prune::PruneCommand;
+use crate::commands::db::commands::restore::RestoreCommand;
 use crate::commands::db::commands::revert_tx::RevertTxCommand;
+use crate::commands::db::commands::rollback::RollbackCommand;
+use crate::commands::db::commands::snapshot::SnapshotCommand;
 
 pub mod commands;
 
@@ -25,6 +29,18 @@ impl CommandAction<String> for DB {
             DBCommand::RevertTx(revert_tx) => revert_tx.execute().await.map(|resp| {
                 serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
             }),
+            DBCommand::Rollback(rollback) => rollback.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
+            DBCommand::Prune(prune) => prune.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
+            DBCommand::Snapshot(snapshot) => snapshot.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
+            DBCommand::Restore(restore) => restore.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
         }
     }
 }
@@ -33,4 +49,8 @@ impl CommandAction<String> for DB {
 #[clap(name = "db")]
 pub enum DBCommand {
     RevertTx(RevertTxCommand),
+    Rollback(RollbackCommand),
+    Prune(PruneCommand),
+    Snapshot(SnapshotCommand),
+    Restore(RestoreCommand),
 }
@@ -42,6 +42,10 @@ pub struct RebuildCommand {
 
     #[clap(long, short = 'b', default_value = "20971")]
     pub batch_size: Option<usize>,
+
+    #[clap(long)]
+    /// The PostgreSQL url of the indexer, if not set, the SQLite indexer under the data dir is used
+    pub indexer_db_url: Option<String>,
 }
 
 impl RebuildCommand {
@@ -68,8 +72,12 @@ impl RebuildCommand {
 
     fn init(self) -> (IndexerStore, IndexerReader, Instant) {
         let start_time = Instant::now();
-        let (indexer_store, indexer_reader) =
-            init_indexer(self.base_data_dir.clone(), self.chain_id.clone()).unwrap();
+        let (indexer_store, indexer_reader) = init_indexer(
+            self.base_data_dir.clone(),
+            self.chain_id.clone(),
+            self.indexer_db_url.clone(),
+        )
+        .unwrap();
         log::info!("indexer rebuild started");
         (indexer_store, indexer_reader, start_time)
     }
@@ -3,6 +3,7 @@
 
 pub mod abi;
 pub mod account;
+pub mod da;
 pub mod db;
 pub mod dynamic_field;
 pub mod env;
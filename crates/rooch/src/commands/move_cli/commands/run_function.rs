@@ -6,11 +6,14 @@ use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
 use move_command_line_common::types::ParsedStructType;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::TypeTag;
 use moveos_types::transaction::MoveAction;
 use rooch_key::key_derive::verify_password;
 use rooch_key::keystore::account_keystore::AccountKeystore;
-use rooch_rpc_api::jsonrpc_types::{ExecuteTransactionResponseView, HumanReadableDisplay};
+use rooch_rpc_api::jsonrpc_types::{
+    DryRunOptions, ExecuteTransactionResponseView, HumanReadableDisplay,
+};
 use rooch_types::function_arg::parse_function_arg;
 use rooch_types::{
     address::RoochAddress,
@@ -19,6 +22,7 @@ use rooch_types::{
     transaction::rooch::RoochTransaction,
 };
 use rpassword::prompt_password;
+use std::path::PathBuf;
 
 /// Run a Move function
 #[derive(Parser)]
@@ -53,34 +57,109 @@ pub struct RunFunction {
     #[clap(flatten)]
     tx_options: TransactionOptions,
 
+    /// Dry run the function and output the Move call tree instead of executing it
+    #[clap(long)]
+    trace: bool,
+
+    /// Dry run the function and output the gas profile instead of executing it
+    #[clap(long)]
+    profile_gas: bool,
+
+    /// Write the gas profile in the folded stacks format to the file, which can be rendered by flamegraph tools
+    #[clap(long, requires = "profile_gas")]
+    profile_output: Option<PathBuf>,
+
     /// Return command outputs in json format
     #[clap(long, default_value = "false")]
     json: bool,
 }
 
-#[async_trait]
-impl CommandAction<ExecuteTransactionResponseView> for RunFunction {
-    async fn execute(self) -> RoochResult<ExecuteTransactionResponseView> {
-        let context = self.context.build()?;
-        let address_mapping = context.address_mapping();
-        let sender: RoochAddress = context.resolve_address(self.tx_options.sender)?.into();
-        let max_gas_amount: Option<u64> = self.tx_options.max_gas_amount;
-        let function_id = self.function.into_function_id(&address_mapping)?;
-        let args = self
-            .args
+impl RunFunction {
+    fn build_action(
+        function: ParsedFunctionId,
+        type_args: Vec<ParsedStructType>,
+        args: Vec<FunctionArg>,
+        address_mapping: &impl Fn(&str) -> Option<AccountAddress>,
+    ) -> Result<MoveAction> {
+        let function_id = function.into_function_id(address_mapping)?;
+        let args = args
             .into_iter()
-            .map(|arg| arg.into_bytes(&address_mapping))
+            .map(|arg| arg.into_bytes(address_mapping))
             .collect::<Result<Vec<_>>>()?;
-        let type_args = self
-            .type_args
+        let type_args = type_args
             .into_iter()
             .map(|tag| {
                 Ok(TypeTag::Struct(Box::new(
-                    tag.into_struct_tag(&address_mapping)?,
+                    tag.into_struct_tag(address_mapping)?,
                 )))
             })
             .collect::<Result<Vec<_>>>()?;
-        let action = MoveAction::new_function_call(function_id, type_args, args);
+        Ok(MoveAction::new_function_call(function_id, type_args, args))
+    }
+
+    /// Dry run the function with the trace or the gas profiling enabled
+    async fn dry_run(self) -> RoochResult<String> {
+        let json = self.json;
+        let dry_run_option = DryRunOptions::new()
+            .trace(self.trace)
+            .profile_gas(self.profile_gas);
+        let context = self.context.build()?;
+        let address_mapping = context.address_mapping();
+        let sender: RoochAddress = context.resolve_address(self.tx_options.sender)?.into();
+        let action =
+            Self::build_action(self.function, self.type_args, self.args, &address_mapping)?;
+        let tx_data = context
+            .build_tx_data(sender, action, self.tx_options.max_gas_amount)
+            .await?;
+        let result = context
+            .dry_run_with_option(tx_data, Some(dry_run_option))
+            .await?;
+
+        if let (Some(profile_output), Some(gas_profile)) =
+            (self.profile_output, result.gas_profile.as_ref())
+        {
+            std::fs::write(profile_output, &gas_profile.folded_stacks)?;
+        }
+        if json {
+            return Ok(serde_json::to_string_pretty(&result).unwrap());
+        }
+        let mut output = format!(
+            "Dry run status: {:?}\nGas used: {}",
+            result.raw_output.status, result.raw_output.gas_used
+        );
+        if !result.vm_error_info.error_message.is_empty() {
+            output.push_str(&format!("\nError: {}", result.vm_error_info.error_message));
+        }
+        if let Some(call_traces) = result.call_traces {
+            output.push_str("\n\nCall traces:");
+            for call_trace in call_traces {
+                output.push('\n');
+                output.push_str(&call_trace.to_human_readable_string(false, 4));
+            }
+        }
+        if let Some(gas_profile) = result.gas_profile {
+            output.push_str(&format!(
+                "\n\nGas profile:\n    instruction: {}\n    native: {}\n    storage: {}\n    io: {}\n\nFolded stacks:\n{}",
+                gas_profile.instruction_gas_used,
+                gas_profile.native_gas_used,
+                gas_profile.storage_gas_used,
+                gas_profile.io_gas_used,
+                gas_profile.folded_stacks,
+            ));
+        }
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl CommandAction<ExecuteTransactionResponseView> for RunFunction {
+    async fn execute(self) -> RoochResult<ExecuteTransactionResponseView> {
+        let context = self.context.build()?;
+        let address_mapping = context.address_mapping();
+        let sender: RoochAddress = context.resolve_address(self.tx_options.sender)?.into();
+        let max_gas_amount: Option<u64> = self.tx_options.max_gas_amount;
+        let action =
+            Self::build_action(self.function, self.type_args, self.args, &address_mapping)?;
         match (self.tx_options.authenticator, self.tx_options.session_key) {
             (Some(authenticator), _) => {
                 let tx_data = context
@@ -155,6 +234,9 @@ impl CommandAction<ExecuteTransactionResponseView> for RunFunction {
 
     /// Executes the command, and serializes it to the common JSON output type
     async fn execute_serialized(self) -> RoochResult<String> {
+        if self.trace || self.profile_gas {
+            return self.dry_run().await;
+        }
         let json = self.json;
         let result = self.execute().await?;
 
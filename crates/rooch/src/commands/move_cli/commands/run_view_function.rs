@@ -7,8 +7,9 @@ use async_trait::async_trait;
 use clap::Parser;
 use move_command_line_common::types::ParsedStructType;
 use move_core_types::language_storage::TypeTag;
+use moveos_types::h256::H256;
 use moveos_types::transaction::FunctionCall;
-use rooch_rpc_api::jsonrpc_types::AnnotatedFunctionResultView;
+use rooch_rpc_api::jsonrpc_types::{AnnotatedFunctionResultView, StateOptions};
 use rooch_types::{
     error::{RoochError, RoochResult},
     function_arg::{FunctionArg, ParsedFunctionId},
@@ -40,6 +41,14 @@ pub struct RunViewFunction {
     #[clap(long = "args")]
     pub args: Vec<FunctionArg>,
 
+    /// Execute the function at the state root instead of the latest state
+    #[clap(long, conflicts_with = "tx_order")]
+    pub state_root: Option<H256>,
+
+    /// Execute the function on the state after the transaction of the tx order is executed
+    #[clap(long)]
+    pub tx_order: Option<u64>,
+
     /// RPC client options.
     #[clap(flatten)]
     context: WalletContextOptions,
@@ -68,10 +77,18 @@ impl CommandAction<AnnotatedFunctionResultView> for RunViewFunction {
 
         let function_call = FunctionCall::new(function_id, type_args, args);
 
+        let mut state_option = StateOptions::default();
+        if let Some(state_root) = self.state_root {
+            state_option = state_option.state_root(state_root.into());
+        }
+        if let Some(tx_order) = self.tx_order {
+            state_option = state_option.tx_order(tx_order);
+        }
+
         let client = context.get_client().await?;
         client
             .rooch
-            .execute_view_function(function_call)
+            .execute_view_function(function_call, Some(state_option))
             .await
             .map_err(|e| RoochError::ViewFunctionError(e.to_string()))
     }
@@ -52,11 +52,19 @@ impl CommandAction<()> for StartCommand {
             )?
         };
         let proposer_account = if self.opt.proposer_account.is_none() {
-            let active_address_opt = context.client_config.active_address;
-            if active_address_opt.is_none() {
-                return Err(RoochError::ActiveAddressDoesNotExistError);
+            // The proposer key signs the state commitments, it must be configured explicitly on the public networks
+            let chain_id = self.opt.chain_id();
+            if !chain_id.is_local() && !chain_id.is_dev() {
+                return Err(RoochError::CommandArgumentError(
+                    "The proposer account is required, please set --proposer-account".to_owned(),
+                ));
             }
-            active_address_opt.unwrap()
+            info!(
+                "The proposer account is not set, use the sequencer account {} as the proposer on the {} chain",
+                sequencer_account,
+                chain_id.chain_name()
+            );
+            sequencer_account
         } else {
             RoochAddress::from_str(self.opt.proposer_account.clone().unwrap().as_str()).map_err(
                 |e| {
@@ -5,7 +5,8 @@ use crate::cli_types::{CommandAction, WalletContextOptions};
 use async_trait::async_trait;
 use clap::Parser;
 use moveos_types::access_path::AccessPath;
-use rooch_rpc_api::jsonrpc_types::ObjectStateView;
+use moveos_types::h256::H256;
+use rooch_rpc_api::jsonrpc_types::{ObjectStateView, StateOptions};
 use rooch_types::error::{RoochError, RoochResult};
 
 /// Get states by accessPath
@@ -26,6 +27,14 @@ pub struct StateCommand {
     /// Render and return display fields.
     #[clap(long)]
     pub show_display: bool,
+
+    /// Get the states at the state root instead of the latest state
+    #[clap(long, conflicts_with = "tx_order")]
+    pub state_root: Option<H256>,
+
+    /// Get the states after the transaction of the tx order is executed
+    #[clap(long)]
+    pub tx_order: Option<u64>,
 }
 
 #[async_trait]
@@ -33,19 +42,20 @@ impl CommandAction<Vec<Option<ObjectStateView>>> for StateCommand {
     async fn execute(self) -> RoochResult<Vec<Option<ObjectStateView>>> {
         let client = self.context_options.build()?.get_client().await?;
 
-        let resp = if self.show_display {
-            client
-                .rooch
-                .get_decoded_states_with_display(self.access_path)
-                .await
-                .map_err(RoochError::from)?
-        } else {
-            client
-                .rooch
-                .get_decoded_states(self.access_path)
-                .await
-                .map_err(RoochError::from)?
-        };
+        let mut state_option = StateOptions::default()
+            .decode(true)
+            .show_display(self.show_display);
+        if let Some(state_root) = self.state_root {
+            state_option = state_option.state_root(state_root.into());
+        }
+        if let Some(tx_order) = self.tx_order {
+            state_option = state_option.tx_order(tx_order);
+        }
+        let resp = client
+            .rooch
+            .get_states_with_option(self.access_path, state_option)
+            .await
+            .map_err(RoochError::from)?;
         Ok(resp)
     }
 }
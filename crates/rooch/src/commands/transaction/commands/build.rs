@@ -0,0 +1,119 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::write_bcs_output;
+use crate::cli_types::{CommandAction, FunctionArg, WalletContextOptions};
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+use move_command_line_common::types::ParsedStructType;
+use move_core_types::language_storage::TypeTag;
+use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
+use moveos_types::transaction::MoveAction;
+use rooch_types::address::{ParsedAddress, RoochAddress};
+use rooch_types::error::RoochResult;
+use rooch_types::function_arg::{parse_function_arg, ParsedFunctionId};
+use rooch_types::transaction::rooch::RoochTransactionData;
+use std::path::PathBuf;
+
+/// Build an unsigned transaction for a Move function call
+/// The RPC is only used to get the sequence number and the chain id if they are not set,
+/// so the transaction can be built offline.
+#[derive(Parser)]
+pub struct BuildCommand {
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    /// Example: `0x42::message::set_message`, `rooch_framework::empty::empty`
+    #[clap(long)]
+    pub function: ParsedFunctionId,
+
+    /// TypeTag arguments separated by spaces.
+    ///
+    /// Example: `0x1::M::T1 0x1::M::T2 rooch_framework::empty::Empty`
+    #[clap(
+        long = "type-args",
+        value_parser=ParsedStructType::parse,
+    )]
+    pub type_args: Vec<ParsedStructType>,
+
+    /// Arguments combined with their type separated by spaces.
+    ///
+    /// Supported types [u8, u16, u32, u64, u128, u256, bool, object_id, string, address, vector<inner_type>]
+    ///
+    /// Example: `address:0x1 bool:true u8:0 u256:1234 'vector<u32>:a,b,c,d'`
+    ///     address and uint can be written in short form like `@0x1 1u8 4123u256`.
+    #[clap(long = "args", value_parser=parse_function_arg)]
+    pub args: Vec<FunctionArg>,
+
+    /// Sender account address.
+    #[clap(long, value_parser=ParsedAddress::parse, default_value = "default")]
+    pub sender: ParsedAddress,
+
+    /// The sequence number of the transaction, get it from the chain if not set
+    #[clap(long)]
+    pub sequence_number: Option<u64>,
+
+    /// The chain id of the transaction, get it from the chain if not set
+    #[clap(long)]
+    pub chain_id: Option<u64>,
+
+    /// Custom the transaction's gas limit.
+    /// [default: 1_000_000_000] [alias: "gas-limit"]
+    #[clap(long, alias = "gas-limit")]
+    pub max_gas_amount: Option<u64>,
+
+    /// Write the BCS encoded transaction data to the file, return the hex string if not set
+    #[clap(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<String> for BuildCommand {
+    async fn execute(self) -> RoochResult<String> {
+        let context = self.context_options.build()?;
+        let address_mapping = context.address_mapping();
+        let sender: RoochAddress = context.resolve_address(self.sender)?.into();
+        let function_id = self.function.into_function_id(&address_mapping)?;
+        let args = self
+            .args
+            .into_iter()
+            .map(|arg| arg.into_bytes(&address_mapping))
+            .collect::<Result<Vec<_>>>()?;
+        let type_args = self
+            .type_args
+            .into_iter()
+            .map(|tag| {
+                Ok(TypeTag::Struct(Box::new(
+                    tag.into_struct_tag(&address_mapping)?,
+                )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let action = MoveAction::new_function_call(function_id, type_args, args);
+
+        let sequence_number = match self.sequence_number {
+            Some(sequence_number) => sequence_number,
+            None => {
+                let client = context.get_client().await?;
+                client.rooch.get_sequence_number(sender).await?
+            }
+        };
+        let chain_id = match self.chain_id {
+            Some(chain_id) => chain_id,
+            None => {
+                let client = context.get_client().await?;
+                client.rooch.get_chain_id().await?
+            }
+        };
+        let tx_data = RoochTransactionData::new(
+            sender,
+            sequence_number,
+            chain_id,
+            self.max_gas_amount
+                .unwrap_or(GasScheduleConfig::CLI_DEFAULT_MAX_GAS_AMOUNT),
+            action,
+        );
+        write_bcs_output(&tx_data, self.output)
+    }
+}
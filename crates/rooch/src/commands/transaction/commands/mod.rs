@@ -1,5 +1,43 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use rooch_types::error::{RoochError, RoochResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+pub mod build;
 pub mod get_transactions_by_hash;
 pub mod get_transactions_by_order;
+pub mod sign;
+pub mod submit;
+
+/// Read a BCS encoded value from the input, the input is a file path or a hex string
+pub(crate) fn read_bcs_input<T: DeserializeOwned>(input: &str) -> RoochResult<T> {
+    let bytes = if Path::new(input).is_file() {
+        std::fs::read(input)?
+    } else {
+        hex::decode(input.strip_prefix("0x").unwrap_or(input)).map_err(|e| {
+            RoochError::CommandArgumentError(format!(
+                "Input is neither a file nor a hex string: {}",
+                e
+            ))
+        })?
+    };
+    Ok(bcs::from_bytes(&bytes)?)
+}
+
+/// Write the BCS encoded value to the output file if it is set, otherwise return the hex string
+pub(crate) fn write_bcs_output<T: Serialize>(
+    value: &T,
+    output: Option<PathBuf>,
+) -> RoochResult<String> {
+    let bytes = bcs::to_bytes(value)?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, bytes)?;
+            Ok(path.display().to_string())
+        }
+        None => Ok(format!("0x{}", hex::encode(bytes))),
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{read_bcs_input, write_bcs_output};
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use rooch_key::keystore::account_keystore::AccountKeystore;
+use rooch_types::authentication_key::AuthenticationKey;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::transaction::rooch::RoochTransactionData;
+use std::path::PathBuf;
+
+/// Sign the transaction data built by `rooch transaction build` with the key of the sender in the keystore.
+/// It does not require the network.
+#[derive(Debug, Parser)]
+pub struct SignCommand {
+    /// The BCS encoded transaction data, a file path or a hex string
+    #[clap(long, short = 'i')]
+    pub input: String,
+
+    /// Sign the transaction via session key
+    #[clap(long)]
+    pub session_key: Option<AuthenticationKey>,
+
+    /// Write the BCS encoded signed transaction to the file, return the hex string if not set
+    #[clap(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<String> for SignCommand {
+    async fn execute(self) -> RoochResult<String> {
+        let context = self.context_options.build_require_password()?;
+        let password = context.get_password();
+        let tx_data: RoochTransactionData = read_bcs_input(&self.input)?;
+        let sender = tx_data.sender;
+        let tx = match self.session_key {
+            Some(session_key) => context.keystore.sign_transaction_via_session_key(
+                &sender,
+                tx_data,
+                &session_key,
+                password,
+            ),
+            None => context
+                .keystore
+                .sign_transaction(&sender, tx_data, password),
+        }
+        .map_err(|e| RoochError::SignMessageError(e.to_string()))?;
+        write_bcs_output(&tx, self.output)
+    }
+}
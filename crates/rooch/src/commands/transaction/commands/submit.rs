@@ -0,0 +1,38 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::read_bcs_input;
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use rooch_types::error::RoochResult;
+use rooch_types::transaction::rooch::RoochTransaction;
+
+/// Submit the transaction signed by `rooch transaction sign` to the chain
+#[derive(Debug, Parser)]
+pub struct SubmitCommand {
+    /// The BCS encoded signed transaction, a file path or a hex string
+    #[clap(long, short = 'i')]
+    pub input: String,
+
+    /// Dry run the transaction instead of executing it
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<serde_json::Value> for SubmitCommand {
+    async fn execute(self) -> RoochResult<serde_json::Value> {
+        let context = self.context_options.build()?;
+        let tx: RoochTransaction = read_bcs_input(&self.input)?;
+        let result = if self.dry_run {
+            serde_json::to_value(context.dry_run(tx.data().clone()).await?)?
+        } else {
+            serde_json::to_value(context.execute(tx).await?)?
+        };
+        Ok(result)
+    }
+}
@@ -3,8 +3,9 @@
 
 use crate::cli_types::CommandAction;
 use crate::commands::transaction::commands::{
-    get_transactions_by_hash::GetTransactionsByHashCommand,
-    get_transactions_by_order::GetTransactionsByOrderCommand,
+    build::BuildCommand, get_transactions_by_hash::GetTransactionsByHashCommand,
+    get_transactions_by_order::GetTransactionsByOrderCommand, sign::SignCommand,
+    submit::SubmitCommand,
 };
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
@@ -25,6 +26,9 @@ impl CommandAction<String> for Transaction {
         match self.cmd {
             TransactionCommand::GetTransactionsByOrder(cmd) => cmd.execute_serialized().await,
             TransactionCommand::GetTransactionsByHash(cmd) => cmd.execute_serialized().await,
+            TransactionCommand::Build(cmd) => cmd.execute_serialized().await,
+            TransactionCommand::Sign(cmd) => cmd.execute_serialized().await,
+            TransactionCommand::Submit(cmd) => cmd.execute_serialized().await,
         }
     }
 }
@@ -33,4 +37,7 @@ impl CommandAction<String> for Transaction {
 pub enum TransactionCommand {
     GetTransactionsByOrder(GetTransactionsByOrderCommand),
     GetTransactionsByHash(GetTransactionsByHashCommand),
+    Build(BuildCommand),
+    Sign(SignCommand),
+    Submit(SubmitCommand),
 }
@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::commands::da::DA;
 use crate::commands::db::DB;
 use crate::commands::event::EventCommand;
 use crate::commands::indexer::Indexer;
@@ -50,6 +51,7 @@ pub enum Command {
     Object(ObjectCommand),
     DynamicField(DynamicField),
     Resource(ResourceCommand),
+    #[clap(alias = "tx")]
     Transaction(Transaction),
     Event(EventCommand),
     ABI(ABI),
@@ -61,6 +63,7 @@ pub enum Command {
     Genesis(Genesis),
     Upgrade(Upgrade),
     DB(DB),
+    DA(DA),
 }
 
 pub async fn run_cli(opt: RoochCli) -> RoochResult<String> {
@@ -85,5 +88,6 @@ pub async fn run_cli(opt: RoochCli) -> RoochResult<String> {
         Command::Genesis(genesis) => genesis.execute().await,
         Command::Upgrade(upgrade) => upgrade.execute().await,
         Command::DB(db) => db.execute().await,
+        Command::DA(da) => da.execute().await,
     }
 }
@@ -68,6 +68,14 @@ Feature: Rooch CLI integration tests
       Then cmd: "transaction get-transactions-by-order --cursor 0 --limit 1 --descending-order false"
       Then cmd: "transaction get-transactions-by-hash --hashes {{$.transaction[-1].data[0].execution_info.tx_hash}}"
 
+      # offline transaction
+      Then cmd: "transaction build --function 0x3::empty::empty"
+      Then cmd: "transaction sign --input {{$.transaction[-1]}}"
+      Then cmd: "transaction submit --input {{$.transaction[-1]}} --dry-run"
+      Then assert: "{{$.transaction[-1].raw_output.status.type}} == executed"
+      Then cmd: "transaction submit --input {{$.transaction[-2]}}"
+      Then assert: "{{$.transaction[-1].execution_info.status.type}} == executed"
+
       # account balance
       Then cmd: "account balance"
       Then cmd: "account balance --coin-type rooch_framework::gas_coin::GasCoin"
@@ -107,6 +115,14 @@ Feature: Rooch CLI integration tests
     Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
     Then cmd: "move run --function default::event_test::emit_event  --args 11u64 --json"
     Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+    # trace the call tree without executing the transaction
+    Then cmd: "move run --function default::event_test::emit_event  --args 12u64 --trace --json"
+    Then assert: "{{$.move[-1].raw_output.status.type}} == executed"
+    Then assert: "{{$.move[-1].call_traces[0].function_name}} == emit_event"
+    Then assert: "{{$.move[-1].call_traces[0].success}} == true"
+    # profile the gas without executing the transaction
+    Then cmd: "move run --function default::event_test::emit_event  --args 12u64 --profile-gas --json"
+    Then assert: "{{$.move[-1].gas_profile.total_gas_used}} == {{$.move[-1].raw_output.gas_used}}"
     #cursor is None
     Then cmd: "event get-events-by-event-handle -t default::event_test::WithdrawEvent --limit 1  --descending-order false"
     Then assert: "{{$.event[-1].data[0].event_id.event_seq}} == 0"
@@ -237,6 +253,9 @@ Feature: Rooch CLI integration tests
       Then cmd: "move run --function default::counter::increase  --json"
       Then cmd: "move view --function default::counter::value"
       Then assert: "{{$.move[-1].return_values[0].decoded_value}} == 1"
+      # The counter value at the state root of the publish transaction
+      Then cmd: "move view --function default::counter::value --state-root {{$.move[-4].execution_info.state_root}}"
+      Then assert: "{{$.move[-1].return_values[0].decoded_value}} == 0"
       Then cmd: "resource --address default --resource default::counter::Counter"
       Then assert: "{{$.resource[-1].decoded_value.value.value.value.value}} == 1"
 
@@ -250,12 +269,39 @@ Feature: Rooch CLI integration tests
       Then cmd: "move run --function default::entry_function::emit_mix --args 3u8 --args "vector<object_id>:0x2342,0x3132"  --json"
       Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
 
-      # check compatibility
+      # check compatibility, the gas estimation fails and the CLI falls back to the default max gas amount
       Then cmd: "move publish -p ../../examples/entry_function_arguments_old/  --named-addresses rooch_examples=default --json"
       Then assert: "'{{$.move[-1].execution_info.status.type}}' == 'moveabort'"
 
       Then stop the server
 
+  @serial
+  Scenario: fork mode lazily loads the states from the remote server
+      Given a server for fork_mode
+      Then cmd: "move publish -p ../../examples/counter  --named-addresses rooch_examples=default --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "move run --function default::counter::increase  --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+
+      # Fork the server at the latest tx order, and execute the new transactions on the fork server
+      Given a fork server for fork_mode
+      Then cmd: "env add --alias fork --rpc http://127.0.0.1:6768"
+      Then cmd: "env switch --alias fork"
+      Then cmd: "move view --function default::counter::value"
+      Then assert: "{{$.move[-1].return_values[0].decoded_value}} == 1"
+      Then cmd: "move run --function default::counter::increase  --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "move view --function default::counter::value"
+      Then assert: "{{$.move[-1].return_values[0].decoded_value}} == 2"
+
+      # The remote server is not affected by the fork server
+      Then cmd: "env switch --alias local"
+      Then cmd: "move view --function default::counter::value"
+      Then assert: "{{$.move[-1].return_values[0].decoded_value}} == 1"
+
+      Then stop the fork server
+      Then stop the server
+
   @serial
   Scenario: coins example
       Given a server for coins
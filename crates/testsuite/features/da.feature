@@ -0,0 +1,27 @@
+Feature: Rooch DA tests
+
+    @serial
+    Scenario: open-da s3 submit test
+      # prepare servers
+      Given a minio server for rooch_da_s3_test
+      Given a server for rooch_da_s3_test
+
+      # propose some blocks
+      Then cmd: "account create"
+      Then cmd: "move run --function rooch_framework::gas_coin::faucet_entry --args u256:10000000000 --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "move run --function 0x3::empty::empty --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then sleep: "10" # wait for the proposer and the DA submission
+
+      # the chunks are acknowledged by the s3 backend
+      Then cmd: "rpc request --method rooch_getLatestBlock --params '[]' --json"
+      Then assert: "'{{$.rpc[-1]}}' contains tx_accumulator_root"
+      Then cmd: "da status"
+      Then assert: "{{$.da[-1].servers[0].name}} == open-da-s3"
+      Then assert: "{{$.da[-1].servers[0].pending_chunks}} == 0"
+      Then assert: "{{$.da[-1].pending_chunks}} == 0"
+      Then assert: "{{$.da[-1].unavailable_chunks}} == 0"
+
+      Then stop the server
+      Then stop the minio server
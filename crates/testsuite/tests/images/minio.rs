@@ -0,0 +1,88 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use testcontainers::{
+    core::{ContainerState, ExecCommand, WaitFor},
+    Image, ImageArgs,
+};
+
+const NAME: &str = "minio/minio";
+const TAG: &str = "RELEASE.2024-08-17T01-24-54Z";
+
+pub const MINIO_PORT: u16 = 9000;
+
+#[derive(Debug, Default, Clone)]
+pub struct MinioImageArgs {
+    pub data_dir: String,
+}
+
+impl ImageArgs for MinioImageArgs {
+    fn into_iterator(self) -> Box<dyn Iterator<Item = String>> {
+        Box::new(vec!["server".to_string(), self.data_dir].into_iter())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Minio {
+    bucket: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl Minio {
+    pub fn new(
+        access_key_id: String,
+        secret_access_key: String,
+        bucket: String,
+    ) -> (Self, MinioImageArgs) {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("MINIO_ROOT_USER".to_string(), access_key_id.clone());
+        env_vars.insert("MINIO_ROOT_PASSWORD".to_string(), secret_access_key.clone());
+        // let the bundled mc client talk to the server without `mc alias set`
+        env_vars.insert(
+            "MC_HOST_local".to_string(),
+            format!(
+                "http://{}:{}@127.0.0.1:{}",
+                access_key_id, secret_access_key, MINIO_PORT
+            ),
+        );
+        (
+            Minio { bucket, env_vars },
+            MinioImageArgs {
+                data_dir: "/data".to_string(),
+            },
+        )
+    }
+}
+
+impl Image for Minio {
+    type Args = MinioImageArgs;
+
+    fn name(&self) -> String {
+        NAME.to_owned()
+    }
+
+    fn tag(&self) -> String {
+        TAG.to_owned()
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout("API:")]
+    }
+
+    fn expose_ports(&self) -> Vec<u16> {
+        vec![MINIO_PORT]
+    }
+
+    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
+        Box::new(self.env_vars.iter())
+    }
+
+    fn exec_after_start(&self, _cs: ContainerState) -> Vec<ExecCommand> {
+        vec![ExecCommand {
+            cmd: format!("mc mb --ignore-existing local/{}", self.bucket),
+            ready_conditions: vec![WaitFor::Nothing],
+        }]
+    }
+}
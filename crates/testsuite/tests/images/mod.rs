@@ -3,4 +3,5 @@
 
 pub mod bitcoin;
 pub mod bitseed;
+pub mod minio;
 pub mod ord;
@@ -8,14 +8,20 @@ use clap::Parser;
 use cucumber::{given, then, World as _};
 use images::bitcoin::BitcoinD;
 use images::bitseed::Bitseed;
+use images::minio::{Minio, MINIO_PORT};
 use images::ord::Ord;
 use jpst::TemplateContext;
 use rooch::RoochCli;
+use rooch_config::da_config::{
+    DAConfig, DAServerOpenDAConfig, InternalDAServerConfig, InternalDAServerConfigType,
+    OpenDAScheme,
+};
 use rooch_config::{RoochOpt, ServerOpt, ROOCH_CONFIR_DIR};
 use rooch_rpc_client::wallet_context::WalletContext;
 use rooch_rpc_server::Service;
 use rooch_types::crypto::RoochKeyPair;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::time::Duration;
 use std::{path::Path, vec};
 use testcontainers::{
@@ -30,6 +36,9 @@ const RPC_USER: &str = "roochuser";
 const RPC_PASS: &str = "roochpass";
 const RPC_PORT: u16 = 18443;
 const ORD_RPC_PORT: u16 = 80;
+const MINIO_ACCESS_KEY_ID: &str = "roochuser";
+const MINIO_SECRET_ACCESS_KEY: &str = "roochpass";
+const MINIO_BUCKET: &str = "rooch-da";
 
 #[derive(cucumber::World, Debug)]
 struct World {
@@ -37,8 +46,10 @@ struct World {
     docker: Cli,
     container_network: String,
     service: Option<Service>,
+    fork_service: Option<Service>,
     bitcoind: Option<Container<BitcoinD>>,
     ord: Option<Container<Ord>>,
+    minio: Option<Container<Minio>>,
     tpl_ctx: Option<TemplateContext>,
 }
 
@@ -51,8 +62,10 @@ impl Default for World {
             docker: Cli::default(),
             container_network: format!("test_network_{}", network_uuid),
             service: None,
+            fork_service: None,
             bitcoind: None,
             ord: None,
+            minio: None,
             tpl_ctx: None,
         }
     }
@@ -83,6 +96,35 @@ async fn start_server(w: &mut World, _scenario: String) {
         }
     }
 
+    if let Some(minio) = w.minio.as_ref() {
+        let mut config = HashMap::new();
+        config.insert("bucket".to_string(), MINIO_BUCKET.to_string());
+        config.insert(
+            "endpoint".to_string(),
+            format!("http://127.0.0.1:{}", minio.get_host_port_ipv4(MINIO_PORT)),
+        );
+        config.insert("access_key_id".to_string(), MINIO_ACCESS_KEY_ID.to_string());
+        config.insert(
+            "secret_access_key".to_string(),
+            MINIO_SECRET_ACCESS_KEY.to_string(),
+        );
+        w.opt.da = DAConfig {
+            internal_da_server: Some(InternalDAServerConfig {
+                submit_strategy: None,
+                servers: vec![InternalDAServerConfigType::OpenDa(DAServerOpenDAConfig {
+                    scheme: OpenDAScheme::S3,
+                    config,
+                    max_segment_size: None,
+                })],
+            }),
+            // submit each batch immediately, so the test does not wait for the chunk interval
+            max_chunk_interval: Some(0),
+            ..Default::default()
+        };
+
+        info!("config open-da s3 ok");
+    }
+
     let mut server_opt = ServerOpt::new();
     //TODO we should load keypair from cli config
     let kp: RoochKeyPair = RoochKeyPair::generate_secp256k1();
@@ -109,6 +151,40 @@ async fn stop_server(w: &mut World) {
     }
 }
 
+#[given(expr = "a fork server for {word}")] // Cucumber Expression
+async fn start_fork_server(w: &mut World, _scenario: String) {
+    let mut opt = RoochOpt::new_with_temp_store().expect("new rooch opt should be ok");
+    // fork the server of the scenario at the latest tx order
+    opt.port = Some(w.opt.port() + 1);
+    opt.fork_url = Some(format!("http://127.0.0.1:{}", w.opt.port()));
+    wait_port_available(opt.port()).await;
+
+    let mut server_opt = ServerOpt::new();
+    let kp: RoochKeyPair = RoochKeyPair::generate_secp256k1();
+    server_opt.sequencer_keypair = Some(kp.copy());
+    server_opt.proposer_keypair = Some(kp.copy());
+
+    let mut service = Service::new();
+    service.start(opt, server_opt).await.unwrap();
+
+    w.fork_service = Some(service);
+}
+
+#[then(expr = "stop the fork server")] // Cucumber Expression
+async fn stop_fork_server(w: &mut World) {
+    println!("stop fork server");
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    match w.fork_service.take() {
+        Some(service) => {
+            service.stop().unwrap();
+            info!("Shutdown fork server");
+        }
+        None => {
+            info!("fork service is none");
+        }
+    }
+}
+
 #[given(expr = "a bitcoind server for {word}")] // Cucumber Expression
 async fn start_bitcoind_server(w: &mut World, _scenario: String) {
     tokio::time::sleep(Duration::from_secs(5)).await;
@@ -181,6 +257,39 @@ async fn stop_ord_server(w: &mut World) {
     }
 }
 
+#[given(expr = "a minio server for {word}")] // Cucumber Expression
+async fn start_minio_server(w: &mut World, _scenario: String) {
+    tokio::time::sleep(Duration::from_secs(5)).await;
+
+    let minio_image: RunnableImage<Minio> = Minio::new(
+        MINIO_ACCESS_KEY_ID.to_string(),
+        MINIO_SECRET_ACCESS_KEY.to_string(),
+        MINIO_BUCKET.to_string(),
+    )
+    .into();
+
+    let minio = w.docker.run(minio_image);
+    debug!("minio ok");
+
+    w.minio = Some(minio);
+}
+
+#[then(expr = "stop the minio server")] // Cucumber Expression
+async fn stop_minio_server(w: &mut World) {
+    println!("stop minio server");
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+    match w.minio.take() {
+        Some(minio) => {
+            minio.stop();
+            info!("shutdown minio server");
+        }
+        None => {
+            info!("minio server is none");
+        }
+    }
+}
+
 #[then(regex = r#"sleep: "(.*)?""#)]
 async fn sleep(_world: &mut World, args: String) {
     let args = args.trim().parse::<u64>().unwrap();
@@ -0,0 +1,208 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// The on-chain State Commitment Chain(SCC), it records the state commitment of each L2 block signed by the proposer.
+/// The commitments are submitted by the sequencer as system transactions,
+/// light clients and bridges can verify the L2 state against it.
+module rooch_framework::state_commitment_chain {
+
+    use std::option::{Self, Option};
+    use std::vector;
+    use moveos_std::bcs;
+    use moveos_std::event;
+    use moveos_std::object::{Self, Object};
+    use moveos_std::table::{Self, Table};
+    use rooch_framework::bitcoin_address;
+    use rooch_framework::ecdsa_k1;
+    use rooch_framework::onchain_config;
+
+    const HASH_LENGTH: u64 = 32;
+
+    /// The block number is not the next block number of the SCC
+    const ErrorInvalidBlockNumber: u64 = 1;
+    /// The prev tx accumulator root is not equal to the tx accumulator root of the last block
+    const ErrorInvalidPrevTxAccumulatorRoot: u64 = 2;
+    /// The signature of the commitment is invalid
+    const ErrorInvalidSignature: u64 = 3;
+    /// The commitment is not signed by the proposer
+    const ErrorNotProposer: u64 = 4;
+    /// The hash length of the commitment is invalid
+    const ErrorInvalidHashLength: u64 = 5;
+    /// The block commitment does not exist
+    const ErrorBlockNotFound: u64 = 6;
+
+    /// The state commitment of a L2 block, the BCS encoding of it is the message signed by the proposer
+    struct BlockCommitment has copy, drop, store {
+        block_number: u128,
+        prev_tx_accumulator_root: vector<u8>,
+        tx_accumulator_root: vector<u8>,
+        state_root: vector<u8>,
+    }
+
+    struct SignedBlockCommitment has copy, drop, store {
+        commitment: BlockCommitment,
+        /// The secp256k1 compressed public key of the proposer
+        public_key: vector<u8>,
+        /// The secp256k1 signature of the commitment
+        signature: vector<u8>,
+    }
+
+    struct StateCommitmentChain has key {
+        /// The address of the proposer, only the commitments signed by the proposer are accepted
+        proposer: address,
+        last_block_number: Option<u128>,
+        /// block number -> signed commitment
+        blocks: Table<u128, SignedBlockCommitment>,
+    }
+
+    struct BlockCommittedEvent has copy, drop {
+        block_number: u128,
+        tx_accumulator_root: vector<u8>,
+        state_root: vector<u8>,
+    }
+
+    /// The SCC is initialized lazily, the proposer defaults to the sequencer
+    fun borrow_mut_scc(): &mut StateCommitmentChain {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        if (!object::exists_object_with_type<StateCommitmentChain>(object_id)) {
+            let scc = StateCommitmentChain {
+                proposer: onchain_config::sequencer(),
+                last_block_number: option::none(),
+                blocks: table::new(),
+            };
+            let obj = object::new_named_object(scc);
+            object::transfer_extend(obj, @rooch_framework);
+        };
+        let obj = object::borrow_mut_object_extend<StateCommitmentChain>(object_id);
+        object::borrow_mut(obj)
+    }
+
+    fun exists_scc(): bool {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        object::exists_object_with_type<StateCommitmentChain>(object_id)
+    }
+
+    fun borrow_scc(): &StateCommitmentChain {
+        let object_id = object::named_object_id<StateCommitmentChain>();
+        let obj: &Object<StateCommitmentChain> = object::borrow_object(object_id);
+        object::borrow(obj)
+    }
+
+    /// Update the proposer of the SCC, only the sequencer can update the proposer
+    public entry fun update_proposer(account: &signer, proposer: address) {
+        onchain_config::ensure_sequencer(account);
+        let scc = borrow_mut_scc();
+        scc.proposer = proposer;
+    }
+
+    /// The sequencer submits a signed block commitment to the SCC
+    /// This function is a system function, is the block commitment transaction entry point
+    fun commit_block(
+        block_number: u128,
+        prev_tx_accumulator_root: vector<u8>,
+        tx_accumulator_root: vector<u8>,
+        state_root: vector<u8>,
+        public_key: vector<u8>,
+        signature: vector<u8>,
+    ) {
+        assert!(vector::length(&prev_tx_accumulator_root) == HASH_LENGTH, ErrorInvalidHashLength);
+        assert!(vector::length(&tx_accumulator_root) == HASH_LENGTH, ErrorInvalidHashLength);
+        assert!(vector::length(&state_root) == HASH_LENGTH, ErrorInvalidHashLength);
+
+        let commitment = BlockCommitment {
+            block_number,
+            prev_tx_accumulator_root,
+            tx_accumulator_root,
+            state_root,
+        };
+        let message = bcs::to_bytes(&commitment);
+        assert!(
+            ecdsa_k1::verify(&signature, &public_key, &message, ecdsa_k1::sha256()),
+            ErrorInvalidSignature
+        );
+        let proposer = bitcoin_address::to_rooch_address(
+            &bitcoin_address::derive_bitcoin_taproot_address_from_pubkey(&public_key)
+        );
+
+        let scc = borrow_mut_scc();
+        assert!(proposer == scc.proposer, ErrorNotProposer);
+        if (option::is_some(&scc.last_block_number)) {
+            let last_block_number = *option::borrow(&scc.last_block_number);
+            assert!(block_number == last_block_number + 1, ErrorInvalidBlockNumber);
+            let last_block = table::borrow(&scc.blocks, last_block_number);
+            assert!(
+                commitment.prev_tx_accumulator_root == last_block.commitment.tx_accumulator_root,
+                ErrorInvalidPrevTxAccumulatorRoot
+            );
+        } else {
+            assert!(block_number == 0, ErrorInvalidBlockNumber);
+        };
+
+        let committed_event = BlockCommittedEvent {
+            block_number,
+            tx_accumulator_root: commitment.tx_accumulator_root,
+            state_root: commitment.state_root,
+        };
+        table::add(&mut scc.blocks, block_number, SignedBlockCommitment {
+            commitment,
+            public_key,
+            signature,
+        });
+        scc.last_block_number = option::some(block_number);
+        event::emit(committed_event);
+    }
+
+    public fun proposer(): address {
+        if (exists_scc()) {
+            borrow_scc().proposer
+        } else {
+            onchain_config::sequencer()
+        }
+    }
+
+    /// The last committed block number, return none if no block committed
+    public fun last_block_number(): Option<u128> {
+        if (exists_scc()) {
+            borrow_scc().last_block_number
+        } else {
+            option::none()
+        }
+    }
+
+    public fun exists_block(block_number: u128): bool {
+        exists_scc() && table::contains(&borrow_scc().blocks, block_number)
+    }
+
+    public fun get_block(block_number: u128): SignedBlockCommitment {
+        assert!(exists_block(block_number), ErrorBlockNotFound);
+        *table::borrow(&borrow_scc().blocks, block_number)
+    }
+
+    public fun commitment(self: &SignedBlockCommitment): &BlockCommitment {
+        &self.commitment
+    }
+
+    public fun public_key(self: &SignedBlockCommitment): &vector<u8> {
+        &self.public_key
+    }
+
+    public fun signature(self: &SignedBlockCommitment): &vector<u8> {
+        &self.signature
+    }
+
+    public fun block_number(self: &BlockCommitment): u128 {
+        self.block_number
+    }
+
+    public fun prev_tx_accumulator_root(self: &BlockCommitment): vector<u8> {
+        self.prev_tx_accumulator_root
+    }
+
+    public fun tx_accumulator_root(self: &BlockCommitment): vector<u8> {
+        self.tx_accumulator_root
+    }
+
+    public fun state_root(self: &BlockCommitment): vector<u8> {
+        self.state_root
+    }
+}
@@ -4,6 +4,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::accumulator_info::AccumulatorInfo;
 use crate::node::InternalNode;
 use crate::node_index::NodeIndex;
 use crate::MAX_ACCUMULATOR_PROOF_DEPTH;
@@ -82,4 +83,55 @@ impl AccumulatorProof {
         );
         Ok(())
     }
+
+    /// Rebuilds the info of the accumulator whose last leaf is `leaf_hash` at `leaf_index`
+    /// from the proof of the last leaf, the proof is verified against `expected_root_hash`.
+    /// The frozen subtree roots are either the siblings on the left of the path, or the
+    /// ancestor of the last leaf at the lowest frozen subtree level.
+    pub fn last_leaf_accumulator_info(
+        &self,
+        expected_root_hash: H256,
+        leaf_hash: H256,
+        leaf_index: u64,
+    ) -> Result<AccumulatorInfo> {
+        self.verify(expected_root_hash, leaf_hash, leaf_index)?;
+        let num_leaves = leaf_index + 1;
+        let lowest_level = num_leaves.trailing_zeros() as usize;
+        let highest_level = (u64::BITS - 1 - num_leaves.leading_zeros()) as usize;
+        ensure!(
+            self.siblings.len() >= lowest_level,
+            "Accumulator proof has less than {} ({}) siblings.",
+            lowest_level,
+            self.siblings.len()
+        );
+        // The last leaf is the rightmost leaf of the lowest frozen subtree
+        let (lowest_root_hash, _) = self.siblings[..lowest_level].iter().fold(
+            (leaf_hash, leaf_index),
+            |(hash, index), sibling_hash| {
+                (
+                    InternalNode::new(NodeIndex::from_inorder_index(index), *sibling_hash, hash)
+                        .hash(),
+                    index / 2,
+                )
+            },
+        );
+        let mut frozen_subtree_roots = vec![lowest_root_hash];
+        for level in (lowest_level + 1)..=highest_level {
+            if num_leaves & (1 << level) != 0 {
+                let sibling_hash = self.siblings.get(level).ok_or_else(|| {
+                    anyhow::anyhow!("Accumulator proof has no sibling at level {}", level)
+                })?;
+                frozen_subtree_roots.push(*sibling_hash);
+            }
+        }
+        // The frozen subtree roots are ordered from the highest level to the lowest level
+        frozen_subtree_roots.reverse();
+        let num_nodes = 2 * num_leaves - num_leaves.count_ones() as u64;
+        Ok(AccumulatorInfo::new(
+            expected_root_hash,
+            frozen_subtree_roots,
+            num_leaves,
+            num_nodes,
+        ))
+    }
 }
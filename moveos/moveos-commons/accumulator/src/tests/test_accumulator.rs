@@ -180,6 +180,25 @@ fn test_proof() {
     proof_verify(&accumulator, root_hash1, &batch1, 0);
 }
 
+#[test]
+fn test_last_leaf_accumulator_info() {
+    let accumulator = MerkleAccumulator::new_empty(Arc::new(MockAccumulatorStore::new()));
+    for leaf in create_leaves(0..70) {
+        let root_hash = accumulator.append(&[leaf]).unwrap();
+        accumulator.flush().unwrap();
+        let info = accumulator.get_info();
+        let leaf_index = info.num_leaves - 1;
+        let proof = accumulator.get_proof(leaf_index).unwrap().unwrap();
+        let rebuilt_info = proof
+            .last_leaf_accumulator_info(root_hash, leaf, leaf_index)
+            .unwrap();
+        assert_eq!(rebuilt_info, info);
+        assert!(proof
+            .last_leaf_accumulator_info(root_hash, H256::random(), leaf_index)
+            .is_err());
+    }
+}
+
 #[test]
 fn test_multiple_leaves() {
     let mut batch1 = create_leaves(600..608);
@@ -10,6 +10,7 @@ use std::string::ToString;
 
 pub const STARTUP_INFO_KEY: &str = "startup_info";
 pub const GENESIS_KEY: &str = "genesis";
+pub const FORK_STARTUP_INFO_KEY: &str = "fork_startup_info";
 
 derive_store!(
     StartupInfoStore,
@@ -57,6 +58,16 @@ impl ConfigDBStore {
             .put_sync(STARTUP_INFO_KEY.to_string(), startup_info)
     }
 
+    /// Get the startup info of the remote node state which the store is forked from
+    pub fn get_fork_startup_info(&self) -> Result<Option<StartupInfo>> {
+        self.startup_store.kv_get(FORK_STARTUP_INFO_KEY.to_string())
+    }
+
+    pub fn save_fork_startup_info(&self, startup_info: StartupInfo) -> Result<()> {
+        self.startup_store
+            .put_sync(FORK_STARTUP_INFO_KEY.to_string(), startup_info)
+    }
+
     pub fn get_genesis(&self) -> Result<Option<GenesisInfo>> {
         self.genesis_store.kv_get(GENESIS_KEY.to_string())
     }
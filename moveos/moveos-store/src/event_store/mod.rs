@@ -6,7 +6,7 @@ use anyhow::{anyhow, Result};
 use move_core_types::language_storage::StructTag;
 use moveos_types::moveos_std::event::{Event, EventHandle, EventID, TransactionEvent};
 use moveos_types::moveos_std::object::ObjectID;
-use raw_store::{derive_store, CodecKVStore, StoreInstance};
+use raw_store::{derive_store, CodecKVStore, CodecWriteBatch, StoreInstance};
 use std::cmp::min;
 use std::collections::{HashMap, HashSet};
 
@@ -62,7 +62,7 @@ impl EventDBStore {
         }
     }
 
-    fn get_event_handle(&self, event_handle_id: ObjectID) -> Result<Option<EventHandle>> {
+    pub fn get_event_handle(&self, event_handle_id: ObjectID) -> Result<Option<EventHandle>> {
         self.event_handle_store.kv_get(event_handle_id)
     }
 
@@ -123,6 +123,25 @@ impl EventDBStore {
         Ok(event_ids)
     }
 
+    /// Remove the events of the event handle whose seq is not less than `event_seq`,
+    /// and reset the event handle count to `event_seq`, it is used to roll back the events.
+    /// Return the number of the removed events.
+    pub fn truncate_events(&self, event_handle_id: ObjectID, event_seq: u64) -> Result<u64> {
+        let mut event_handle = match self.get_event_handle(event_handle_id.clone())? {
+            Some(event_handle) if event_handle.count > event_seq => event_handle,
+            _ => return Ok(0),
+        };
+        let keys = (event_seq..event_handle.count)
+            .map(|seq| (event_handle_id.clone(), seq))
+            .collect::<Vec<_>>();
+        let removed = keys.len() as u64;
+        self.event_store
+            .write_batch(CodecWriteBatch::new_deletes(keys))?;
+        event_handle.count = event_seq;
+        self.save_event_handle(event_handle)?;
+        Ok(removed)
+    }
+
     pub fn get_event(&self, event_id: EventID) -> Result<Option<Event>> {
         let key = (event_id.event_handle_id, event_id.event_seq);
         self.event_store.kv_get(key)
@@ -6,6 +6,7 @@
 
 use crate::config_store::{ConfigDBStore, ConfigStore};
 use crate::event_store::{EventDBStore, EventStore};
+use crate::state_store::pruner::StatePruneStore;
 use crate::state_store::statedb::StateDBStore;
 use crate::state_store::NodeDBStore;
 use crate::transaction_store::{TransactionDBStore, TransactionStore};
@@ -20,6 +21,7 @@ use moveos_types::moveos_std::event::{Event, EventID, TransactionEvent};
 use moveos_types::moveos_std::object::ObjectID;
 use moveos_types::startup_info::StartupInfo;
 use moveos_types::state::{FieldKey, ObjectState};
+use moveos_types::state_proof::StateProofReader;
 use moveos_types::state_resolver::{StateKV, StatelessResolver};
 use moveos_types::transaction::{TransactionExecutionInfo, TransactionOutput};
 use once_cell::sync::Lazy;
@@ -27,7 +29,7 @@ use prometheus::Registry;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::RocksDB;
 use raw_store::{ColumnFamilyName, StoreInstance};
-use smt::NodeReader;
+use smt::{NodeReader, SparseMerkleProof};
 use std::fmt::{Debug, Display, Formatter};
 use std::path::Path;
 use std::sync::Arc;
@@ -41,6 +43,10 @@ pub mod transaction_store;
 
 // pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
 pub const STATE_NODE_COLUMN_FAMILY_NAME: ColumnFamilyName = "state_node";
+pub const STATE_NODE_REFCOUNT_COLUMN_FAMILY_NAME: ColumnFamilyName = "state_node_refcount";
+pub const STATE_STALE_NODE_INDEX_COLUMN_FAMILY_NAME: ColumnFamilyName = "state_stale_node_index";
+pub const STATE_PRUNE_INFO_COLUMN_FAMILY_NAME: ColumnFamilyName = "state_prune_info";
+pub const STATE_REMOTE_OBJECT_COLUMN_FAMILY_NAME: ColumnFamilyName = "state_remote_object";
 pub const TRANSACTION_EXECUTION_INFO_COLUMN_FAMILY_NAME: ColumnFamilyName =
     "transaction_execution_info";
 pub const EVENT_COLUMN_FAMILY_NAME: ColumnFamilyName = "event";
@@ -58,6 +64,10 @@ static VEC_COLUMN_FAMILY_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         EVENT_HANDLE_COLUMN_FAMILY_NAME,
         CONFIG_STARTUP_INFO_COLUMN_FAMILY_NAME,
         CONFIG_GENESIS_COLUMN_FAMILY_NAME,
+        STATE_NODE_REFCOUNT_COLUMN_FAMILY_NAME,
+        STATE_STALE_NODE_INDEX_COLUMN_FAMILY_NAME,
+        STATE_PRUNE_INFO_COLUMN_FAMILY_NAME,
+        STATE_REMOTE_OBJECT_COLUMN_FAMILY_NAME,
     ]
 });
 
@@ -95,7 +105,8 @@ impl MoveOSStore {
 
     pub fn new_with_instance(instance: StoreInstance, registry: &Registry) -> Result<Self> {
         let node_store = NodeDBStore::new(instance.clone());
-        let state_store = StateDBStore::new(node_store.clone(), registry);
+        let prune_store = StatePruneStore::new(node_store.clone(), instance.clone())?;
+        let state_store = StateDBStore::new(node_store.clone(), prune_store, registry);
 
         let store = Self {
             node_store,
@@ -260,6 +271,11 @@ impl TransactionStore for MoveOSStore {
         self.get_transaction_store()
             .multi_get_tx_execution_infos(tx_hashes)
     }
+
+    fn remove_tx_execution_infos(&self, tx_hashes: Vec<H256>) -> Result<()> {
+        self.get_transaction_store()
+            .remove_tx_execution_infos(tx_hashes)
+    }
 }
 
 impl ConfigStore for MoveOSStore {
@@ -310,6 +326,17 @@ impl<'a, T: 'a + NodeReader> IntoSuper<dyn NodeReader + 'a> for T {
 
 impl Store for MoveOSStore {}
 
+impl StateProofReader for MoveOSStore {
+    fn get_field_with_proof_at(
+        &self,
+        state_root: H256,
+        key: &FieldKey,
+    ) -> Result<(Option<ObjectState>, SparseMerkleProof)> {
+        self.get_state_store()
+            .get_field_with_proof_at(state_root, key)
+    }
+}
+
 impl StatelessResolver for MoveOSStore {
     fn get_field_at(&self, state_root: H256, key: &FieldKey) -> Result<Option<ObjectState>, Error> {
         self.get_state_store().get_field_at(state_root, key)
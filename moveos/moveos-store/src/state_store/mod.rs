@@ -2,20 +2,160 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod metrics;
+pub mod pruner;
 pub mod statedb;
 
-use crate::STATE_NODE_COLUMN_FAMILY_NAME;
-use anyhow::Result;
+use crate::{STATE_NODE_COLUMN_FAMILY_NAME, STATE_REMOTE_OBJECT_COLUMN_FAMILY_NAME};
+use anyhow::{ensure, Result};
 use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta, GENESIS_STATE_ROOT};
+use moveos_types::state::{FieldKey, ObjectState};
+use moveos_types::state_resolver::StateKV;
+use once_cell::sync::OnceCell;
+use prometheus::Registry;
 use raw_store::rocks::batch::WriteBatch;
-use raw_store::CodecKVStore;
-use raw_store::{derive_store, WriteOp};
-use smt::{NodeReader, NodeWriter};
+use raw_store::{
+    derive_store, CodecKVStore, ColumnFamily, ColumnFamilyName, InnerStore, SchemaStore,
+    StoreInstance, WriteOp,
+};
+use smt::{InMemoryNodeStore, NodeReader, NodeWriter, SMTree};
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
-derive_store!(NodeDBStore, H256, Vec<u8>, STATE_NODE_COLUMN_FAMILY_NAME);
+// The objects of a remote node whose trees are not loaded yet, indexed by the state roots of the trees
+derive_store!(
+    RemoteObjectStore,
+    H256,
+    ObjectMeta,
+    STATE_REMOTE_OBJECT_COLUMN_FAMILY_NAME
+);
+
+/// Read the states of a remote node at the forked state root
+pub trait RemoteStateReader: Send + Sync {
+    /// List all the fields of the object, fail if the object has more fields than `max_fields`
+    fn list_all_fields(&self, object_id: &ObjectID, max_fields: u64) -> Result<Vec<StateKV>>;
+}
+
+struct RemoteStateLoader {
+    reader: Arc<dyn RemoteStateReader>,
+    /// The max fields of a remote object, the whole object is loaded at once,
+    /// so the objects with more fields are not forked.
+    max_object_fields: u64,
+    /// Rebuild the trees of the remote objects from the empty tree, no node is read from the store
+    smt: SMTree<FieldKey, ObjectState, InMemoryNodeStore>,
+}
+
+/// The store of the state tree nodes.
+/// If the remote state reader is set, the tree of a remote object missing in the local store is rebuilt
+/// from all the fields of the object read from the remote node when its root node is read, and cached in the local store.
+/// So the states of a remote node can be lazily forked, the objects with too many fields are not loaded.
+#[derive(Clone)]
+pub struct NodeDBStore {
+    store: InnerStore<Self>,
+    remote_object_store: RemoteObjectStore,
+    remote_loader: Arc<OnceCell<RemoteStateLoader>>,
+}
+
+impl ColumnFamily for NodeDBStore {
+    type Key = H256;
+    type Value = Vec<u8>;
+
+    fn name() -> ColumnFamilyName {
+        STATE_NODE_COLUMN_FAMILY_NAME
+    }
+}
+
+impl SchemaStore for NodeDBStore {
+    fn get_store(&self) -> &InnerStore<Self> {
+        &self.store
+    }
+}
 
 impl NodeDBStore {
+    pub fn new(instance: StoreInstance) -> Self {
+        Self {
+            store: InnerStore::new(instance.clone()),
+            remote_object_store: RemoteObjectStore::new(instance),
+            remote_loader: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Set the remote state reader and the forked root object of the remote node,
+    /// the reader is shared by all the clones of the store and can only be set once
+    pub fn set_remote_reader(
+        &self,
+        remote_reader: Arc<dyn RemoteStateReader>,
+        remote_root: ObjectMeta,
+        max_object_fields: u64,
+    ) -> Result<()> {
+        let remote_loader = RemoteStateLoader {
+            reader: remote_reader,
+            max_object_fields,
+            smt: SMTree::new(InMemoryNodeStore::default(), &Registry::new()),
+        };
+        ensure!(
+            self.remote_loader.set(remote_loader).is_ok(),
+            "The remote state reader is already set"
+        );
+        self.remote_object_store
+            .kv_put(remote_root.state_root(), remote_root)
+    }
+
+    /// Get the node from the local store only, the remote state reader is not used
+    pub fn get_local(&self, hash: &H256) -> Result<Option<Vec<u8>>> {
+        self.get_raw(hash.as_bytes())
+    }
+
+    /// Rebuild the tree of the remote object and check its state root before caching the nodes
+    fn load_remote_object(
+        &self,
+        remote_loader: &RemoteStateLoader,
+        state_root: H256,
+        object: &ObjectMeta,
+    ) -> Result<()> {
+        let object_id = &object.id;
+        ensure!(
+            object.size <= remote_loader.max_object_fields,
+            "The remote object {} has {} fields, more than the max fields({}) of a forked object",
+            object_id,
+            object.size,
+            remote_loader.max_object_fields
+        );
+        let fields = remote_loader
+            .reader
+            .list_all_fields(object_id, remote_loader.max_object_fields)?;
+        ensure!(
+            fields.len() as u64 <= remote_loader.max_object_fields,
+            "The remote object {} has more than the max fields({}) of a forked object",
+            object_id,
+            remote_loader.max_object_fields
+        );
+        let mut child_objects = vec![];
+        for (_key, state) in &fields {
+            let child_state_root = state.metadata.state_root();
+            if child_state_root != *GENESIS_STATE_ROOT {
+                child_objects.push((child_state_root, state.metadata.clone()));
+            }
+        }
+        let tree_change_set = remote_loader.smt.puts(
+            *GENESIS_STATE_ROOT,
+            fields
+                .into_iter()
+                .map(|(key, state)| (key, Some(state)))
+                .collect::<Vec<_>>(),
+        )?;
+        ensure!(
+            tree_change_set.state_root == state_root,
+            "The state root({}) of the fields of the remote object {} is not equal to the expected state root({})",
+            tree_change_set.state_root,
+            object_id,
+            state_root
+        );
+        // The child objects are loaded when their trees are read
+        self.remote_object_store.put_all(child_objects)?;
+        self.write_nodes(tree_change_set.nodes)
+    }
+
     pub fn put(&self, key: H256, node: Vec<u8>) -> Result<()> {
         self.put_raw(key.as_bytes().to_vec(), node)
     }
@@ -29,11 +169,34 @@ impl NodeDBStore {
         );
         self.write_batch_raw(batch)
     }
+
+    pub fn remove_nodes(&self, keys: Vec<H256>) -> Result<()> {
+        let batch = WriteBatch::new_with_rows(
+            keys.into_iter()
+                .map(|k| (k.0.to_vec(), WriteOp::Deletion))
+                .collect(),
+        );
+        self.write_batch_raw(batch)
+    }
 }
 
 impl NodeReader for NodeDBStore {
     fn get(&self, hash: &H256) -> Result<Option<Vec<u8>>> {
-        self.get_raw(hash.as_bytes())
+        let node = self.get_local(hash)?;
+        if node.is_some() {
+            return Ok(node);
+        }
+        match self.remote_loader.get() {
+            // Only the root node of a remote object tree can be missing, the whole tree is loaded with it
+            Some(remote_loader) => match self.remote_object_store.kv_get(*hash)? {
+                Some(object) => {
+                    self.load_remote_object(remote_loader, *hash, &object)?;
+                    self.get_local(hash)
+                }
+                None => Ok(None),
+            },
+            None => Ok(None),
+        }
     }
 }
 
@@ -0,0 +1,306 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::state_store::NodeDBStore;
+use crate::{
+    STATE_NODE_REFCOUNT_COLUMN_FAMILY_NAME, STATE_PRUNE_INFO_COLUMN_FAMILY_NAME,
+    STATE_STALE_NODE_INDEX_COLUMN_FAMILY_NAME,
+};
+use anyhow::{bail, ensure, Result};
+use moveos_types::h256::H256;
+use moveos_types::state_prune::{StaleNodeIndex, StatePruneInfo};
+use raw_store::{derive_store, CodecKVStore, CodecWriteBatch, StoreInstance};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub const STATE_PRUNE_INFO_KEY: &str = "state_prune_info";
+
+/// The nodes written before the stale node index is enabled have unknown references,
+/// they are pinned and never pruned.
+const PINNED_REFCOUNT: u64 = u64::MAX;
+
+derive_store!(
+    NodeRefCountStore,
+    H256,
+    u64,
+    STATE_NODE_REFCOUNT_COLUMN_FAMILY_NAME
+);
+derive_store!(
+    StaleNodeIndexStore,
+    u64,
+    StaleNodeIndex,
+    STATE_STALE_NODE_INDEX_COLUMN_FAMILY_NAME
+);
+derive_store!(
+    StatePruneInfoStore,
+    String,
+    StatePruneInfo,
+    STATE_PRUNE_INFO_COLUMN_FAMILY_NAME
+);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PruneResult {
+    /// The number of the pruned versions
+    pub pruned_versions: u64,
+    /// The number of the removed state nodes
+    pub removed_nodes: u64,
+}
+
+/// StatePruneStore indexes the state nodes which become stale when a new state root is created,
+/// and removes them once the previous state root is no longer retained.
+/// The state tree is content addressed, a node may be shared by different objects or state roots,
+/// so the references of every node are counted and a stale node is removed only when
+/// it has no live reference.
+/// The nodes are indexed only after the store is enabled in prune mode,
+/// in archive mode the nodes are written directly.
+#[derive(Clone)]
+pub struct StatePruneStore {
+    node_store: NodeDBStore,
+    refcount_store: NodeRefCountStore,
+    stale_index_store: StaleNodeIndexStore,
+    info_store: StatePruneInfoStore,
+    // The lock serializes writing new nodes and pruning stale nodes
+    info: Arc<Mutex<StatePruneInfo>>,
+    enabled: Arc<AtomicBool>,
+}
+
+impl StatePruneStore {
+    pub fn new(node_store: NodeDBStore, instance: StoreInstance) -> Result<Self> {
+        let info_store = StatePruneInfoStore::new(instance.clone());
+        let info = info_store
+            .kv_get(STATE_PRUNE_INFO_KEY.to_string())?
+            .unwrap_or_default();
+        Ok(Self {
+            node_store,
+            refcount_store: NodeRefCountStore::new(instance.clone()),
+            stale_index_store: StaleNodeIndexStore::new(instance),
+            info_store,
+            info: Arc::new(Mutex::new(info)),
+            enabled: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Enable the stale node index in prune mode, it should be called before writing new nodes.
+    /// If the store has been written in archive mode after the index is started,
+    /// the references of the nodes are incomplete and the stale nodes can not be pruned safely.
+    pub fn enable(&self, latest_state_root: H256) -> Result<()> {
+        let info = self.lock_info();
+        if let Some(last_state_root) = info.last_state_root {
+            // The last indexed state root may be ahead of the latest state root
+            // if the node is killed before the state root is saved.
+            if last_state_root != latest_state_root && !self.is_indexed(&info, latest_state_root)? {
+                bail!(
+                    "The state root {} is not found in the stale node index, the store has been written in archive mode after the state prune is enabled, the stale nodes can not be pruned safely",
+                    latest_state_root
+                );
+            }
+        }
+        self.enabled.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    fn is_indexed(&self, info: &StatePruneInfo, state_root: H256) -> Result<bool> {
+        for version in (info.pruned_version..info.next_version).rev() {
+            if let Some(index) = self.stale_index_store.kv_get(version)? {
+                if index.state_root == state_root {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn lock_info(&self) -> MutexGuard<StatePruneInfo> {
+        self.info
+            .lock()
+            .expect("state prune info lock should not be poisoned")
+    }
+
+    pub fn get_prune_info(&self) -> StatePruneInfo {
+        self.lock_info().clone()
+    }
+
+    pub fn get_stale_node_index(&self, version: u64) -> Result<Option<StaleNodeIndex>> {
+        self.stale_index_store.kv_get(version)
+    }
+
+    pub fn get_node_refcount(&self, node: H256) -> Result<Option<u64>> {
+        self.refcount_store.kv_get(node)
+    }
+
+    /// Write the nodes of the new state root, count the references of the new nodes
+    /// and index the nodes which become stale.
+    /// `node_refs` is the number of the trees in the change set which reference each new node.
+    /// Only the nodes are written in archive mode.
+    pub fn write_nodes(
+        &self,
+        state_root: H256,
+        nodes: BTreeMap<H256, Vec<u8>>,
+        node_refs: BTreeMap<H256, u64>,
+        stale_nodes: Vec<H256>,
+    ) -> Result<()> {
+        if !self.is_enabled() {
+            return self.node_store.write_nodes(nodes);
+        }
+        let mut info = self.lock_info();
+        // The state root is not changed, or the same change set is applied again
+        // because the node is restarted before the state root is saved.
+        if info.last_state_root == Some(state_root) {
+            return self.node_store.write_nodes(nodes);
+        }
+
+        let refcounts = self
+            .refcount_store
+            .multiple_get(node_refs.keys().cloned().collect())?;
+        let mut refcount_batch = CodecWriteBatch::new();
+        for ((node, refs), refcount) in node_refs.into_iter().zip(refcounts) {
+            let refcount = match refcount {
+                Some(PINNED_REFCOUNT) => continue,
+                Some(refcount) => refcount + refs,
+                None if self.node_store.get_local(&node)?.is_some() => PINNED_REFCOUNT,
+                None => refs,
+            };
+            refcount_batch.put(node, refcount)?;
+        }
+        // The nodes are written before the references, if the node is killed in between,
+        // the nodes without references are pinned, it is safe but they are never pruned.
+        self.node_store.write_nodes(nodes)?;
+        self.refcount_store.write_batch(refcount_batch)?;
+
+        let version = info.next_version;
+        self.stale_index_store
+            .kv_put(version, StaleNodeIndex::new(state_root, stale_nodes))?;
+        let new_info = StatePruneInfo {
+            next_version: version + 1,
+            pruned_version: info.pruned_version,
+            last_state_root: Some(state_root),
+        };
+        self.info_store
+            .kv_put(STATE_PRUNE_INFO_KEY.to_string(), new_info.clone())?;
+        *info = new_info;
+        Ok(())
+    }
+
+    /// Remove the stale nodes of the state roots older than the latest `retain_roots` roots.
+    /// At most `max_versions` versions are pruned if it is set, so the writer is not blocked for long.
+    pub fn prune(&self, retain_roots: u64, max_versions: Option<u64>) -> Result<PruneResult> {
+        ensure!(
+            retain_roots > 0,
+            "At least one state root should be retained"
+        );
+        let mut result = PruneResult::default();
+        while max_versions.map_or(true, |max| result.pruned_versions < max) {
+            let mut info = self.lock_info();
+            // The stale nodes of a version belong to the previous state root,
+            // they can be removed when the previous state root is not retained.
+            if info.pruned_version + retain_roots > info.next_version {
+                break;
+            }
+            result.removed_nodes += self.prune_version(info.pruned_version)?;
+            let new_info = StatePruneInfo {
+                pruned_version: info.pruned_version + 1,
+                ..info.clone()
+            };
+            self.info_store
+                .kv_put(STATE_PRUNE_INFO_KEY.to_string(), new_info.clone())?;
+            *info = new_info;
+            result.pruned_versions += 1;
+        }
+        Ok(result)
+    }
+
+    /// Get the next version after the index is rolled back to the state root.
+    pub fn rollback_next_version(&self, state_root: H256) -> Result<u64> {
+        let info = self.lock_info();
+        self.find_rollback_next_version(&info, state_root)
+    }
+
+    /// Roll back the stale node index to the state root, the versions after the state root are removed,
+    /// so the nodes of the state root which become stale later are never pruned.
+    /// The references of the nodes written after the state root are not decreased,
+    /// it is safe but these nodes are never pruned.
+    /// Return the number of the removed versions.
+    pub fn rollback(&self, state_root: H256) -> Result<u64> {
+        let mut info = self.lock_info();
+        let next_version = self.find_rollback_next_version(&info, state_root)?;
+        let versions = (next_version..info.next_version).collect::<Vec<_>>();
+        let removed = versions.len() as u64;
+        self.stale_index_store
+            .write_batch(CodecWriteBatch::new_deletes(versions))?;
+        let new_info = StatePruneInfo {
+            next_version,
+            pruned_version: info.pruned_version,
+            last_state_root: Some(state_root),
+        };
+        self.info_store
+            .kv_put(STATE_PRUNE_INFO_KEY.to_string(), new_info.clone())?;
+        *info = new_info;
+        Ok(removed)
+    }
+
+    fn find_rollback_next_version(&self, info: &StatePruneInfo, state_root: H256) -> Result<u64> {
+        if info.last_state_root == Some(state_root) {
+            return Ok(info.next_version);
+        }
+        for version in (info.pruned_version..info.next_version).rev() {
+            if let Some(index) = self.stale_index_store.kv_get(version)? {
+                if index.state_root == state_root {
+                    return Ok(version + 1);
+                }
+            }
+        }
+        // The state root is written before the stale node index is enabled,
+        // all the versions are after it.
+        ensure!(
+            info.pruned_version == 0,
+            "The state root {} is not found in the stale node index, it may have been pruned",
+            state_root
+        );
+        Ok(0)
+    }
+
+    fn prune_version(&self, version: u64) -> Result<u64> {
+        let index = match self.stale_index_store.kv_get(version)? {
+            Some(index) => index,
+            None => return Ok(0),
+        };
+        let mut stale_refs = BTreeMap::<H256, u64>::new();
+        for node in index.stale_nodes {
+            *stale_refs.entry(node).or_default() += 1;
+        }
+        let refcounts = self
+            .refcount_store
+            .multiple_get(stale_refs.keys().cloned().collect())?;
+        let mut refcount_batch = CodecWriteBatch::new();
+        let mut removed_nodes = vec![];
+        for ((node, refs), refcount) in stale_refs.into_iter().zip(refcounts) {
+            match refcount {
+                // The node is written before the stale node index is enabled
+                None | Some(PINNED_REFCOUNT) => {}
+                Some(refcount) if refcount > refs => refcount_batch.put(node, refcount - refs)?,
+                Some(_) => {
+                    refcount_batch.delete(node)?;
+                    removed_nodes.push(node);
+                }
+            }
+        }
+        let removed = removed_nodes.len() as u64;
+        self.node_store.remove_nodes(removed_nodes)?;
+        self.refcount_store.write_batch(refcount_batch)?;
+        self.stale_index_store.remove(version)?;
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "prune state version: {}, state_root: {}, removed nodes: {}",
+                version,
+                index.state_root,
+                removed
+            );
+        }
+        Ok(removed)
+    }
+}
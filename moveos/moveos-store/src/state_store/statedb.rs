@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::state_store::metrics::StateDBMetrics;
+use crate::state_store::pruner::StatePruneStore;
 use crate::state_store::NodeDBStore;
 use anyhow::{Error, Ok, Result};
 use function_name::named;
@@ -13,35 +14,70 @@ use moveos_types::state::FieldKey;
 use moveos_types::state::ObjectChange;
 use moveos_types::state::ObjectState;
 use moveos_types::state::StateChangeSet;
+use moveos_types::state_proof::StateProofReader;
 use moveos_types::state_resolver::RootObjectResolver;
 use moveos_types::state_resolver::StateKV;
 use moveos_types::state_resolver::StateResolver;
 use moveos_types::state_resolver::StatelessResolver;
 use prometheus::Registry;
-use smt::{SMTIterator, TreeChangeSet};
+use smt::{SMTIterator, SparseMerkleProof, TreeChangeSet};
 use smt::{SMTree, UpdateSet};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
 pub const STATEDB_DUMP_BATCH_SIZE: usize = 5000;
 
+/// The nodes written and replaced by the tree updates of a change set
+#[derive(Default)]
+struct NodeChanges {
+    nodes: BTreeMap<H256, Vec<u8>>,
+    /// The number of the updated trees which reference the new node
+    node_refs: BTreeMap<H256, u64>,
+    stale_nodes: Vec<H256>,
+    /// Index the nodes of the deleted objects as stale, only in prune mode
+    index_deleted_objects: bool,
+}
+
+impl NodeChanges {
+    fn new(index_deleted_objects: bool) -> Self {
+        Self {
+            index_deleted_objects,
+            ..Default::default()
+        }
+    }
+
+    fn append(&mut self, tree_change_set: TreeChangeSet) {
+        for (node, bytes) in tree_change_set.nodes {
+            *self.node_refs.entry(node).or_default() += 1;
+            self.nodes.insert(node, bytes);
+        }
+        self.stale_nodes.extend(tree_change_set.stale_nodes);
+    }
+}
+
 /// StateDB provide state storage and state proof
 #[derive(Clone)]
 pub struct StateDBStore {
     pub node_store: NodeDBStore,
+    prune_store: StatePruneStore,
     smt: SMTree<FieldKey, ObjectState, NodeDBStore>,
     metrics: Arc<StateDBMetrics>,
 }
 
 impl StateDBStore {
-    pub fn new(node_store: NodeDBStore, registry: &Registry) -> Self {
+    pub fn new(node_store: NodeDBStore, prune_store: StatePruneStore, registry: &Registry) -> Self {
         Self {
             node_store: node_store.clone(),
+            prune_store,
             smt: SMTree::new(node_store, registry),
             metrics: Arc::new(StateDBMetrics::new(registry)),
         }
     }
 
+    pub fn get_prune_store(&self) -> &StatePruneStore {
+        &self.prune_store
+    }
+
     #[named]
     pub fn update_fields<I>(&self, pre_state_root: H256, update_set: I) -> Result<TreeChangeSet>
     where
@@ -84,7 +120,7 @@ impl StateDBStore {
     fn apply_object_change(
         &self,
         resolver: &dyn StateResolver,
-        nodes: &mut BTreeMap<H256, Vec<u8>>,
+        node_changes: &mut NodeChanges,
         update_set: &mut UpdateSet<FieldKey, ObjectState>,
         field_key: FieldKey,
         obj_change: &mut ObjectChange,
@@ -95,7 +131,12 @@ impl StateDBStore {
                     ObjectState::new(obj_change.metadata.clone(), state.clone())
                 }
                 Op::Delete => {
-                    //TODO clean up the removed object fields
+                    if node_changes.index_deleted_objects {
+                        self.append_deleted_object_nodes(
+                            obj_change.metadata.state_root(),
+                            node_changes,
+                        )?;
+                    }
                     update_set.remove(field_key);
                     return Ok(());
                 }
@@ -115,15 +156,15 @@ impl StateDBStore {
         for (child_field_key, child_change) in &mut obj_change.fields {
             self.apply_object_change(
                 resolver,
-                nodes,
+                node_changes,
                 &mut field_update_set,
                 *child_field_key,
                 child_change,
             )?;
         }
-        let mut tree_change_set = self.update_fields(obj.state_root(), field_update_set)?;
-        nodes.append(&mut tree_change_set.nodes);
+        let tree_change_set = self.update_fields(obj.state_root(), field_update_set)?;
         let new_state_root = tree_change_set.state_root;
+        node_changes.append(tree_change_set);
         obj.update_state_root(new_state_root);
         obj_change.update_state_root(new_state_root);
         update_set.put(field_key, obj);
@@ -131,6 +172,26 @@ impl StateDBStore {
         Ok(())
     }
 
+    /// The fields of the deleted object are not removed from its tree one by one,
+    /// so all the nodes of the object tree and the child object trees become stale.
+    fn append_deleted_object_nodes(
+        &self,
+        state_root: H256,
+        node_changes: &mut NodeChanges,
+    ) -> Result<()> {
+        if state_root == *GENESIS_STATE_ROOT {
+            return Ok(());
+        }
+        node_changes
+            .stale_nodes
+            .extend(self.smt.list_nodes(state_root)?);
+        for item in self.smt.iter(state_root, None)? {
+            let (_field_key, field) = item?;
+            self.append_deleted_object_nodes(field.state_root(), node_changes)?;
+        }
+        Ok(())
+    }
+
     #[named]
     pub fn apply_change_set(&self, state_change_set: &mut StateChangeSet) -> Result<()> {
         let fn_name = function_name!();
@@ -140,6 +201,41 @@ impl StateDBStore {
             .with_label_values(&[fn_name])
             .start_timer();
 
+        let (new_state_root, node_changes, size) =
+            self.update_change_set(state_change_set, self.prune_store.is_enabled())?;
+        self.prune_store.write_nodes(
+            new_state_root,
+            node_changes.nodes,
+            node_changes.node_refs,
+            node_changes.stale_nodes,
+        )?;
+        state_change_set.update_state_root(new_state_root);
+
+        self.metrics
+            .state_apply_change_set_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
+        Ok(())
+    }
+
+    /// Calculate the state roots of the change set in the same way as `apply_change_set`, but do not write the nodes.
+    /// It is used to re-derive the change set of an executed transaction.
+    pub fn calculate_change_set_state_root(
+        &self,
+        state_change_set: &mut StateChangeSet,
+    ) -> Result<H256> {
+        let (new_state_root, _node_changes, _size) =
+            self.update_change_set(state_change_set, false)?;
+        state_change_set.update_state_root(new_state_root);
+        Ok(new_state_root)
+    }
+
+    /// Update the state roots of the object changes, return the new state root, the node changes and the value bytes
+    fn update_change_set(
+        &self,
+        state_change_set: &mut StateChangeSet,
+        index_deleted_objects: bool,
+    ) -> Result<(H256, NodeChanges, usize)> {
         let root = state_change_set.root_metadata();
         let pre_state_root = root.state_root();
         let global_size = root.size;
@@ -147,11 +243,11 @@ impl StateDBStore {
         let resolver = RootObjectResolver::new(root, self);
 
         let mut update_set = UpdateSet::new();
-        let mut nodes = BTreeMap::new();
+        let mut node_changes = NodeChanges::new(index_deleted_objects);
         for (field_key, obj_change) in &mut state_change_set.changes {
             self.apply_object_change(
                 &resolver,
-                &mut nodes,
+                &mut node_changes,
                 &mut update_set,
                 *field_key,
                 obj_change,
@@ -167,25 +263,29 @@ impl StateDBStore {
                 k_len + v_len
             })
             .sum::<usize>();
-        let mut tree_change_set = self.update_fields(pre_state_root, update_set)?;
+        let tree_change_set = self.update_fields(pre_state_root, update_set)?;
         let new_state_root = tree_change_set.state_root;
-        nodes.append(&mut tree_change_set.nodes);
+        node_changes.append(tree_change_set);
         if log::log_enabled!(log::Level::Debug) {
             log::debug!(
-                "apply_change_set new_state_root: {:?}, smt nodes: {}, new_global_size: {}",
+                "apply_change_set new_state_root: {:?}, smt nodes: {}, stale nodes: {}, new_global_size: {}",
                 new_state_root,
-                nodes.len(),
+                node_changes.nodes.len(),
+                node_changes.stale_nodes.len(),
                 global_size
             );
         }
-        self.node_store.write_nodes(nodes)?;
-        state_change_set.update_state_root(new_state_root);
+        Ok((new_state_root, node_changes, size))
+    }
 
-        self.metrics
-            .state_apply_change_set_bytes
-            .with_label_values(&[fn_name])
-            .observe(size as f64);
-        Ok(())
+    /// Compare the fields of the two state roots, return the (key, old state, new state) of the changed fields.
+    /// The child object trees are not compared.
+    pub fn diff(
+        &self,
+        old_state_root: H256,
+        new_state_root: H256,
+    ) -> Result<Vec<(FieldKey, Option<ObjectState>, Option<ObjectState>)>> {
+        self.smt.diff(old_state_root, new_state_root)
     }
 
     #[named]
@@ -204,6 +304,19 @@ impl StateDBStore {
     }
 }
 
+impl StateProofReader for StateDBStore {
+    fn get_field_with_proof_at(
+        &self,
+        state_root: H256,
+        key: &FieldKey,
+    ) -> Result<(Option<ObjectState>, SparseMerkleProof)> {
+        if state_root == *GENESIS_STATE_ROOT {
+            return Ok((None, SparseMerkleProof::default()));
+        }
+        self.smt.get_with_proof(state_root, *key)
+    }
+}
+
 impl StatelessResolver for StateDBStore {
     #[named]
     fn get_field_at(&self, state_root: H256, key: &FieldKey) -> Result<Option<ObjectState>, Error> {
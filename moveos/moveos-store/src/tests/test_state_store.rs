@@ -1,15 +1,23 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::state_store::pruner::PruneResult;
+use crate::state_store::RemoteStateReader;
 use crate::{MoveOSStore, StoreMeta};
 use anyhow::Result;
+use move_core_types::effects::Op;
 use moveos_config::store_config::RocksdbConfig;
 use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta, GENESIS_STATE_ROOT};
+use moveos_types::state::{ObjectChange, StateChangeSet};
+use moveos_types::state_proof::StateProofReader;
+use moveos_types::state_resolver::{RootObjectResolver, StateKV, StateResolver, StatelessResolver};
 use moveos_types::test_utils::random_state_change_set;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::RocksDB;
 use raw_store::{StoreInstance, CF_METRICS_REPORT_PERIOD_MILLIS};
 use smt::NodeReader;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::test]
@@ -71,6 +79,330 @@ async fn test_statedb_state_root() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_calculate_change_set_state_root() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    let mut change_set = random_state_change_set();
+    let mut calculated_change_set = change_set.clone();
+    let state_root = state_store.calculate_change_set_state_root(&mut calculated_change_set)?;
+    // The nodes are not written
+    assert!(moveos_store
+        .get_state_node_store()
+        .get(&state_root)?
+        .is_none());
+
+    state_store.apply_change_set(&mut change_set)?;
+    assert_eq!(change_set.state_root, state_root);
+    for (object_key, object_change) in &change_set.changes {
+        let calculated_object_change = calculated_change_set.changes.get(object_key).unwrap();
+        assert_eq!(
+            object_change.metadata.state_root,
+            calculated_object_change.metadata.state_root
+        );
+    }
+    Ok(())
+}
+
+/// Read the states from the store of the remote node at the state root
+struct MockRemoteStateReader {
+    store: MoveOSStore,
+    root: ObjectMeta,
+}
+
+impl RemoteStateReader for MockRemoteStateReader {
+    fn list_all_fields(&self, object_id: &ObjectID, max_fields: u64) -> Result<Vec<StateKV>> {
+        let fields = RootObjectResolver::new(self.root.clone(), &self.store).list_fields(
+            object_id,
+            None,
+            usize::MAX,
+        )?;
+        anyhow::ensure!(fields.len() as u64 <= max_fields, "Too many fields");
+        Ok(fields)
+    }
+}
+
+#[tokio::test]
+async fn test_remote_state_reader() -> Result<()> {
+    let (remote_store, _remote_dir) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let mut change_set = random_state_change_set();
+    remote_store
+        .get_state_store()
+        .apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+    let (object_key, object_change) = change_set.changes.iter().next().unwrap();
+    let remote_reader = Arc::new(MockRemoteStateReader {
+        store: remote_store.clone(),
+        root: change_set.root_metadata(),
+    });
+
+    let (local_store, _local_dir) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    assert!(local_store.get_field_at(state_root, object_key).is_err());
+    local_store.get_state_node_store().set_remote_reader(
+        remote_reader.clone(),
+        change_set.root_metadata(),
+        u64::MAX,
+    )?;
+
+    // The tree of the root object is loaded from the remote node and cached in the local store
+    let object = local_store.get_field_at(state_root, object_key)?.unwrap();
+    assert_eq!(object.metadata, object_change.metadata);
+    assert!(local_store
+        .get_state_node_store()
+        .get_local(&state_root)?
+        .is_some());
+
+    // The tree of the child object is loaded when it is read
+    let object_state_root = object.state_root();
+    assert!(local_store
+        .get_state_node_store()
+        .get_local(&object_state_root)?
+        .is_none());
+    assert_eq!(
+        local_store.list_fields_at(object_state_root, None, usize::MAX)?,
+        remote_store.list_fields_at(object_state_root, None, usize::MAX)?
+    );
+
+    // The new changes are applied on top of the remote states
+    let mut new_change_set = random_state_change_set();
+    new_change_set.state_root = state_root;
+    new_change_set.global_size = change_set.global_size + new_change_set.global_size;
+    let mut remote_change_set = new_change_set.clone();
+    local_store
+        .get_state_store()
+        .apply_change_set(&mut new_change_set)?;
+    remote_store
+        .get_state_store()
+        .apply_change_set(&mut remote_change_set)?;
+    assert_eq!(new_change_set.state_root, remote_change_set.state_root);
+
+    // The remote tree is not cached if its state root does not match
+    let (other_store, _other_dir) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let wrong_state_root = H256::random();
+    other_store.get_state_node_store().set_remote_reader(
+        remote_reader.clone(),
+        ObjectMeta::root_metadata(wrong_state_root, change_set.global_size),
+        u64::MAX,
+    )?;
+    assert!(other_store
+        .get_field_at(wrong_state_root, object_key)
+        .is_err());
+    assert!(other_store
+        .get_state_node_store()
+        .get_local(&state_root)?
+        .is_none());
+
+    // The remote object with more fields than the limit is not loaded
+    let (limited_store, _limited_dir) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    limited_store.get_state_node_store().set_remote_reader(
+        remote_reader,
+        change_set.root_metadata(),
+        0,
+    )?;
+    assert!(limited_store.get_field_at(state_root, object_key).is_err());
+    assert!(limited_store
+        .get_state_node_store()
+        .get_local(&state_root)?
+        .is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_field_with_proof() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let mut change_set = random_state_change_set();
+    moveos_store
+        .get_state_store()
+        .apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+    let (object_key, object_change) = change_set.changes.iter().next().unwrap();
+    let object_id = object_change.metadata.id.clone();
+    let field_key = *object_change.fields.keys().next().unwrap();
+
+    // an object in the root object
+    let object_proof =
+        moveos_store.get_field_with_proof(state_root, &ObjectID::root(), *object_key)?;
+    assert!(object_proof.ancestors.is_empty());
+    assert_eq!(object_proof.state.as_ref().unwrap().metadata.id, object_id);
+    object_proof.verify(state_root)?;
+
+    // a field of the object is proved through the object
+    let field_proof = moveos_store.get_field_with_proof(state_root, &object_id, field_key)?;
+    assert_eq!(field_proof.ancestors.len(), 1);
+    assert!(field_proof.state.is_some());
+    field_proof.verify(state_root)?;
+    assert!(field_proof.verify(H256::random()).is_err());
+
+    let mut tampered_proof = field_proof.clone();
+    tampered_proof.state.as_mut().unwrap().value.push(0);
+    assert!(tampered_proof.verify(state_root).is_err());
+
+    // a field of a missing object is proved by the non-inclusion proof of the object
+    let missing_proof =
+        moveos_store.get_field_with_proof(state_root, &ObjectID::random(), field_key)?;
+    assert!(missing_proof.ancestors.is_empty());
+    assert!(missing_proof.state.is_none());
+    missing_proof.verify(state_root)?;
+
+    let mut forged_proof = missing_proof.clone();
+    forged_proof.state = field_proof.state.clone();
+    assert!(forged_proof.verify(state_root).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_stale_nodes() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    state_store.get_prune_store().enable(*GENESIS_STATE_ROOT)?;
+    let mut change_set = random_state_change_set();
+    state_store.apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+
+    let mut new_change_set = random_state_change_set();
+    new_change_set.state_root = state_root;
+    new_change_set.global_size += change_set.global_size;
+    state_store.apply_change_set(&mut new_change_set)?;
+    let new_state_root = new_change_set.state_root;
+
+    let prune_store = state_store.get_prune_store();
+    assert_eq!(prune_store.get_prune_info().next_version, 2);
+    let stale_node_index = prune_store.get_stale_node_index(1)?.unwrap();
+    assert_eq!(stale_node_index.state_root, new_state_root);
+    // The root node of the previous state tree is replaced
+    assert!(stale_node_index.stale_nodes.contains(&state_root));
+
+    // Both the state roots are retained
+    assert_eq!(prune_store.prune(2, None)?.pruned_versions, 1);
+    assert!(moveos_store
+        .get_state_node_store()
+        .get(&state_root)?
+        .is_some());
+
+    let result = prune_store.prune(1, None)?;
+    assert_eq!(result.pruned_versions, 1);
+    assert!(result.removed_nodes > 0);
+    assert!(moveos_store
+        .get_state_node_store()
+        .get(&state_root)?
+        .is_none());
+    assert_eq!(prune_store.prune(1, None)?, PruneResult::default());
+
+    // The states of the latest state root are not affected
+    for change_set in [&change_set, &new_change_set] {
+        for (object_key, object_change) in &change_set.changes {
+            let object = state_store
+                .get_field_at(new_state_root, object_key)?
+                .unwrap();
+            for field_key in object_change.fields.keys() {
+                assert!(state_store
+                    .get_field_at(object.state_root(), field_key)?
+                    .is_some());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_deleted_object() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    state_store.get_prune_store().enable(*GENESIS_STATE_ROOT)?;
+    let mut change_set = random_state_change_set();
+    state_store.apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+
+    let object_key = *change_set.changes.keys().next().unwrap();
+    let object = state_store.get_field_at(state_root, &object_key)?.unwrap();
+    let mut delete_change_set = StateChangeSet::default();
+    delete_change_set.state_root = state_root;
+    delete_change_set.global_size = change_set.global_size - 1;
+    delete_change_set.changes.insert(
+        object_key,
+        ObjectChange::new(object.metadata.clone(), Op::Delete),
+    );
+    state_store.apply_change_set(&mut delete_change_set)?;
+
+    // All the nodes of the deleted object tree are stale
+    let prune_store = state_store.get_prune_store();
+    let stale_node_index = prune_store.get_stale_node_index(1)?.unwrap();
+    assert!(stale_node_index.stale_nodes.contains(&object.state_root()));
+    prune_store.prune(1, None)?;
+    assert!(moveos_store
+        .get_state_node_store()
+        .get(&object.state_root())?
+        .is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_mode_without_stale_node_index() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    let mut change_set = random_state_change_set();
+    state_store.apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+
+    let prune_store = state_store.get_prune_store();
+    assert_eq!(prune_store.get_prune_info().next_version, 0);
+    assert!(prune_store.get_node_refcount(state_root)?.is_none());
+
+    // The nodes written in archive mode are pinned after the prune mode is enabled
+    prune_store.enable(state_root)?;
+    let mut new_change_set = random_state_change_set();
+    new_change_set.state_root = state_root;
+    new_change_set.global_size += change_set.global_size;
+    state_store.apply_change_set(&mut new_change_set)?;
+    prune_store.prune(1, None)?;
+    assert!(moveos_store
+        .get_state_node_store()
+        .get(&state_root)?
+        .is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rollback_stale_node_index() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let state_store = moveos_store.get_state_store();
+    state_store.get_prune_store().enable(*GENESIS_STATE_ROOT)?;
+    let mut change_set = random_state_change_set();
+    state_store.apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+
+    let mut new_change_set = random_state_change_set();
+    new_change_set.state_root = state_root;
+    new_change_set.global_size += change_set.global_size;
+    state_store.apply_change_set(&mut new_change_set)?;
+
+    let prune_store = state_store.get_prune_store();
+    assert_eq!(prune_store.rollback_next_version(state_root)?, 1);
+    assert_eq!(prune_store.rollback(state_root)?, 1);
+    let info = prune_store.get_prune_info();
+    assert_eq!(info.next_version, 1);
+    assert_eq!(info.last_state_root, Some(state_root));
+    assert!(prune_store.get_stale_node_index(1)?.is_none());
+
+    // The nodes of the rolled back state root are not pruned
+    prune_store.prune(1, None)?;
+    assert!(moveos_store
+        .get_state_node_store()
+        .get(&state_root)?
+        .is_some());
+    Ok(())
+}
+
 // #[tokio::test]
 // async fn test_child_state_db_dump_and_apply() -> Result<()> {
 //     let mut moveos_store = MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
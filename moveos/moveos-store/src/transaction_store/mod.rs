@@ -4,7 +4,7 @@
 use anyhow::Result;
 use moveos_types::h256::H256;
 use moveos_types::transaction::TransactionExecutionInfo;
-use raw_store::CodecKVStore;
+use raw_store::{CodecKVStore, CodecWriteBatch};
 
 use crate::TRANSACTION_EXECUTION_INFO_COLUMN_FAMILY_NAME;
 use raw_store::derive_store;
@@ -23,6 +23,7 @@ pub trait TransactionStore {
         &self,
         tx_hashes: Vec<H256>,
     ) -> Result<Vec<Option<TransactionExecutionInfo>>>;
+    fn remove_tx_execution_infos(&self, tx_hashes: Vec<H256>) -> Result<()>;
 }
 
 impl TransactionStore for TransactionDBStore {
@@ -40,4 +41,8 @@ impl TransactionStore for TransactionDBStore {
     ) -> Result<Vec<Option<TransactionExecutionInfo>>> {
         self.multiple_get(tx_hashes)
     }
+
+    fn remove_tx_execution_infos(&self, tx_hashes: Vec<H256>) -> Result<()> {
+        self.write_batch(CodecWriteBatch::new_deletes(tx_hashes))
+    }
 }
@@ -0,0 +1,140 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::moveos_std::object::ObjectID;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::resolver::MoveResolver;
+use move_resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator};
+use serde::{Deserialize, Serialize};
+
+/// A value captured by the call tracer, the value is the BCS bytes of the value,
+/// if the value is a reference, it is the value behind the reference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TracedValue {
+    pub type_tag: TypeTag,
+    pub value: Vec<u8>,
+}
+
+impl TracedValue {
+    pub fn new(type_tag: TypeTag, value: Vec<u8>) -> Self {
+        Self { type_tag, value }
+    }
+
+    /// The value is not decoded if the type can not be resolved by the annotator,
+    /// such as the type is defined in a module published by the traced transaction.
+    pub fn into_annotated<T: MoveResolver + ?Sized>(
+        self,
+        annotator: &MoveValueAnnotator<T>,
+    ) -> AnnotatedTracedValue {
+        let decoded_value = annotator.view_value(&self.type_tag, &self.value).ok();
+        AnnotatedTracedValue {
+            value: self,
+            decoded_value,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnotatedTracedValue {
+    pub value: TracedValue,
+    pub decoded_value: Option<AnnotatedMoveValue>,
+}
+
+/// The object operations performed by the object natives of MoveosStd
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum ObjectOperation {
+    /// A new object or a new field is added to the parent object
+    Add,
+    Remove,
+    BorrowMut,
+    Take,
+    Transfer {
+        new_owner: AccountAddress,
+    },
+    ToShared,
+    ToFrozen,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TracedObjectChange {
+    pub object_id: ObjectID,
+    pub operation: ObjectOperation,
+}
+
+impl TracedObjectChange {
+    pub fn new(object_id: ObjectID, operation: ObjectOperation) -> Self {
+        Self {
+            object_id,
+            operation,
+        }
+    }
+}
+
+/// The trace of a Move function call and its sub calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallTrace {
+    /// The module of the function, None if the function is the main function of a script
+    pub module_id: Option<ModuleId>,
+    pub function_name: Identifier,
+    pub ty_args: Vec<TypeTag>,
+    /// The arguments of the call, it is empty if the parameter types can not be resolved
+    pub args: Vec<TracedValue>,
+    /// The gas used by the call, including the gas used by its sub calls
+    pub gas_used: u64,
+    pub is_native: bool,
+    /// False if the call does not return, such as it aborts or runs out of gas
+    pub success: bool,
+    /// The events emitted by the call, the events emitted by its sub calls are not included
+    pub events: Vec<TracedValue>,
+    /// The object changes made by the call, the changes made by its sub calls are not included
+    pub object_changes: Vec<TracedObjectChange>,
+    pub sub_calls: Vec<CallTrace>,
+}
+
+impl CallTrace {
+    pub fn into_annotated<T: MoveResolver + ?Sized>(
+        self,
+        annotator: &MoveValueAnnotator<T>,
+    ) -> AnnotatedCallTrace {
+        AnnotatedCallTrace {
+            module_id: self.module_id,
+            function_name: self.function_name,
+            ty_args: self.ty_args,
+            args: self
+                .args
+                .into_iter()
+                .map(|arg| arg.into_annotated(annotator))
+                .collect(),
+            gas_used: self.gas_used,
+            is_native: self.is_native,
+            success: self.success,
+            events: self
+                .events
+                .into_iter()
+                .map(|event| event.into_annotated(annotator))
+                .collect(),
+            object_changes: self.object_changes,
+            sub_calls: self
+                .sub_calls
+                .into_iter()
+                .map(|call| call.into_annotated(annotator))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnotatedCallTrace {
+    pub module_id: Option<ModuleId>,
+    pub function_name: Identifier,
+    pub ty_args: Vec<TypeTag>,
+    pub args: Vec<AnnotatedTracedValue>,
+    pub gas_used: u64,
+    pub is_native: bool,
+    pub success: bool,
+    pub events: Vec<AnnotatedTracedValue>,
+    pub object_changes: Vec<TracedObjectChange>,
+    pub sub_calls: Vec<AnnotatedCallTrace>,
+}
@@ -0,0 +1,111 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// The category of the gas charged by the gas meter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub enum GasCategory {
+    /// The gas charged by the bytecode instructions
+    Instruction,
+    /// The gas charged by the native functions
+    Native,
+    /// The gas charged by the native functions which read or write the object storage
+    Storage,
+    /// The gas charged for the transaction bytes
+    Io,
+}
+
+impl GasCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GasCategory::Instruction => "instruction",
+            GasCategory::Native => "native",
+            GasCategory::Storage => "storage",
+            GasCategory::Io => "io",
+        }
+    }
+}
+
+impl std::fmt::Display for GasCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The gas used by a category in a call stack, the gas used by the sub calls is not included
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct GasProfileEntry {
+    /// The function frames from the root to the current function
+    pub stack: Vec<String>,
+    pub category: GasCategory,
+    pub gas_used: u64,
+}
+
+/// The gas used by the transaction broken down by the call stacks and the gas categories
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct GasProfile {
+    pub entries: Vec<GasProfileEntry>,
+}
+
+impl GasProfile {
+    pub fn new(entries: Vec<GasProfileEntry>) -> Self {
+        Self { entries }
+    }
+
+    pub fn total_gas_used(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.gas_used).sum()
+    }
+
+    pub fn gas_used_of(&self, category: GasCategory) -> u64 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.category == category)
+            .map(|entry| entry.gas_used)
+            .sum()
+    }
+
+    /// Export the profile as the folded stacks text, which can be rendered by flamegraph tools.
+    /// The instruction gas is the self cost of the frame,
+    /// the other categories are appended to the stack as a `[category]` leaf frame.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut output = String::new();
+        for entry in &self.entries {
+            let mut stack = entry.stack.join(";");
+            if entry.category != GasCategory::Instruction {
+                write!(stack, ";[{}]", entry.category).expect("write to string should success");
+            }
+            writeln!(output, "{} {}", stack, entry.gas_used)
+                .expect("write to string should success");
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folded_stacks() {
+        let profile = GasProfile::new(vec![
+            GasProfileEntry {
+                stack: vec!["transaction".to_owned()],
+                category: GasCategory::Io,
+                gas_used: 10,
+            },
+            GasProfileEntry {
+                stack: vec!["transaction".to_owned(), "0x3::empty::empty".to_owned()],
+                category: GasCategory::Instruction,
+                gas_used: 5,
+            },
+        ]);
+        assert_eq!(profile.total_gas_used(), 15);
+        assert_eq!(profile.gas_used_of(GasCategory::Io), 10);
+        assert_eq!(
+            profile.to_folded_stacks(),
+            "transaction;[io] 10\ntransaction;0x3::empty::empty 5\n"
+        );
+    }
+}
@@ -3,7 +3,9 @@
 
 pub mod access_path;
 pub mod addresses;
+pub mod call_trace;
 pub mod function_return_value;
+pub mod gas_profile;
 pub mod genesis_info;
 pub mod h256;
 pub mod module_binding;
@@ -13,6 +15,8 @@ pub mod moveos_std;
 pub mod serde;
 pub mod startup_info;
 pub mod state;
+pub mod state_proof;
+pub mod state_prune;
 pub mod state_resolver;
 pub mod test_utils;
 pub mod transaction;
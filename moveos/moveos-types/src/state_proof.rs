@@ -0,0 +1,143 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::access_path::AccessPath;
+use crate::h256::H256;
+use crate::moveos_std::object::{ObjectID, GENESIS_STATE_ROOT};
+use crate::state::{FieldKey, ObjectState};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use smt::SparseMerkleProof;
+
+/// A field state with the merkle proofs chained from the state root of the root object.
+/// The field is proved in the state tree of its object, the object is proved in the state tree of its parent,
+/// and so on, up to the root object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateWithProof {
+    /// The object which the field belongs to
+    pub object_id: ObjectID,
+    pub field_key: FieldKey,
+    /// The ancestor objects of the field, from the child of the root object to the object itself.
+    /// If an ancestor does not exist, the ancestors stop before it, and the last proof is its non-inclusion proof.
+    pub ancestors: Vec<ObjectState>,
+    pub state: Option<ObjectState>,
+    /// The proofs of the ancestors and the state, each one is proved in the state tree of the previous one
+    pub proofs: Vec<SparseMerkleProof>,
+}
+
+impl StateWithProof {
+    /// The field keys from the root object to the field
+    fn path(object_id: &ObjectID, field_key: FieldKey) -> Vec<FieldKey> {
+        let mut path = vec![field_key];
+        let mut id = object_id.clone();
+        while let Some(parent) = id.parent() {
+            path.push(id.field_key());
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Verify the state against the trusted state root of the root object
+    pub fn verify(&self, state_root: H256) -> Result<()> {
+        let path = Self::path(&self.object_id, self.field_key);
+        ensure!(
+            self.proofs.len() == self.ancestors.len() + 1 && self.proofs.len() <= path.len(),
+            "Invalid proof length, ancestors: {}, proofs: {}, path: {}",
+            self.ancestors.len(),
+            self.proofs.len(),
+            path.len()
+        );
+        if self.proofs.len() < path.len() {
+            ensure!(
+                self.state.is_none(),
+                "The state should not exist if its ancestor does not exist"
+            );
+        }
+
+        let mut current_root = state_root;
+        for ((ancestor, proof), key) in self.ancestors.iter().zip(&self.proofs).zip(&path) {
+            ensure!(
+                ancestor.metadata.id.field_key() == *key,
+                "Ancestor {} is not on the path of object {}",
+                ancestor.metadata.id,
+                self.object_id
+            );
+            proof.verify(current_root, *key, Some(ancestor.clone()))?;
+            current_root = ancestor.state_root();
+        }
+        let last_proof = self.proofs.last().expect("proofs should not be empty");
+        let last_key = path[self.proofs.len() - 1];
+        // the last proof is the proof of the state, or the non-inclusion proof of the missing ancestor
+        let last_state = if self.proofs.len() == path.len() {
+            self.state.clone()
+        } else {
+            None
+        };
+        last_proof.verify(current_root, last_key, last_state)
+    }
+}
+
+/// Read the states with the merkle proofs from the state storage
+pub trait StateProofReader {
+    /// Get an object field and its merkle proof at the given state root
+    fn get_field_with_proof_at(
+        &self,
+        state_root: H256,
+        key: &FieldKey,
+    ) -> Result<(Option<ObjectState>, SparseMerkleProof)>;
+
+    /// Get an object field with the proofs chained from the state root of the root object
+    fn get_field_with_proof(
+        &self,
+        state_root: H256,
+        object_id: &ObjectID,
+        field_key: FieldKey,
+    ) -> Result<StateWithProof> {
+        let path = StateWithProof::path(object_id, field_key);
+        let mut ancestors = vec![];
+        let mut proofs = vec![];
+        let mut current_root = state_root;
+        let mut state = None;
+        for (index, key) in path.iter().enumerate() {
+            let (field, proof) = if current_root == *GENESIS_STATE_ROOT {
+                // the state tree is empty
+                (None, SparseMerkleProof::default())
+            } else {
+                self.get_field_with_proof_at(current_root, key)?
+            };
+            proofs.push(proof);
+            if index == path.len() - 1 {
+                state = field;
+                break;
+            }
+            match field {
+                Some(ancestor) => {
+                    current_root = ancestor.state_root();
+                    ancestors.push(ancestor);
+                }
+                None => break,
+            }
+        }
+        Ok(StateWithProof {
+            object_id: object_id.clone(),
+            field_key,
+            ancestors,
+            state,
+            proofs,
+        })
+    }
+
+    /// Get the states of the access path with the proofs chained from the state root of the root object
+    fn get_states_with_proof(
+        &self,
+        state_root: H256,
+        path: AccessPath,
+    ) -> Result<Vec<StateWithProof>> {
+        let query = path.into_state_query().into_fields_query()?;
+        query
+            .into_iter()
+            .map(|(object_id, key)| self.get_field_with_proof(state_root, &object_id, key))
+            .collect()
+    }
+}
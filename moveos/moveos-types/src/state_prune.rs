@@ -0,0 +1,36 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use primitive_types::H256;
+use serde::{Deserialize, Serialize};
+
+/// The state tree nodes which become stale when the state root is created.
+/// The nodes can be removed once the previous state root is not retained.
+#[derive(Eq, PartialEq, Deserialize, Serialize, Clone, Debug)]
+pub struct StaleNodeIndex {
+    /// The new state root
+    pub state_root: H256,
+    /// The nodes of the previous state tree which are replaced by the new state root
+    pub stale_nodes: Vec<H256>,
+}
+
+impl StaleNodeIndex {
+    pub fn new(state_root: H256, stale_nodes: Vec<H256>) -> Self {
+        Self {
+            state_root,
+            stale_nodes,
+        }
+    }
+}
+
+/// The progress of the state pruner.
+/// Every new state root gets an increasing version, and the stale nodes are indexed by the version.
+#[derive(Eq, PartialEq, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct StatePruneInfo {
+    /// The version of the next state root
+    pub next_version: u64,
+    /// The versions before this are pruned
+    pub pruned_version: u64,
+    /// The latest indexed state root
+    pub last_state_root: Option<H256>,
+}
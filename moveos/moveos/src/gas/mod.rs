@@ -7,6 +7,7 @@ mod r#abstract;
 mod gas_member;
 mod native;
 mod parameters;
+pub mod profiler;
 pub mod table;
 
 pub trait SwitchableGasMeter: GasMeter {
@@ -0,0 +1,162 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::ModuleId;
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::gas_profile::{GasCategory, GasProfile, GasProfileEntry};
+use moveos_types::moveos_std::object;
+use std::collections::BTreeMap;
+
+/// The name of the root frame, the gas charged out of the Move calls is attributed to it.
+pub const ROOT_FRAME_NAME: &str = "transaction";
+
+#[derive(Debug, Clone)]
+struct ProfileNode {
+    name: String,
+    /// The native functions of the object module read or write the object storage
+    is_storage: bool,
+    children: BTreeMap<String, usize>,
+    gas_used: BTreeMap<GasCategory, u64>,
+}
+
+impl ProfileNode {
+    fn new(name: String, is_storage: bool) -> Self {
+        Self {
+            name,
+            is_storage,
+            children: BTreeMap::new(),
+            gas_used: BTreeMap::new(),
+        }
+    }
+}
+
+/// GasProfiler attributes the gas charged by the gas meter to the function frames.
+/// The frames are entered and exited via the same gas meter hooks as the CallTracer,
+/// and the frames with the same call stack are merged.
+#[derive(Debug, Clone)]
+pub struct GasProfiler {
+    nodes: Vec<ProfileNode>,
+    stack: Vec<usize>,
+}
+
+impl Default for GasProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GasProfiler {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![ProfileNode::new(ROOT_FRAME_NAME.to_owned(), false)],
+            stack: vec![0],
+        }
+    }
+
+    pub fn enter(&mut self, module_id: Option<&ModuleId>, function_name: &str) {
+        let (name, is_storage) = match module_id {
+            Some(module_id) => (
+                format!("{}::{}", module_id.short_str_lossless(), function_name),
+                module_id.address() == &MOVEOS_STD_ADDRESS
+                    && module_id.name() == object::MODULE_NAME,
+            ),
+            None => (format!("script::{}", function_name), false),
+        };
+        let parent = self.current();
+        let index = match self.nodes[parent].children.get(&name) {
+            Some(index) => *index,
+            None => {
+                let index = self.nodes.len();
+                self.nodes[parent].children.insert(name.clone(), index);
+                self.nodes.push(ProfileNode::new(name, is_storage));
+                index
+            }
+        };
+        self.stack.push(index);
+    }
+
+    /// Exit the current frame, the root frame is never exited
+    pub fn exit(&mut self) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+    }
+
+    /// The category of the gas charged by the current native function
+    pub fn native_category(&self) -> GasCategory {
+        if self.nodes[self.current()].is_storage {
+            GasCategory::Storage
+        } else {
+            GasCategory::Native
+        }
+    }
+
+    pub fn record(&mut self, category: GasCategory, gas_used: u64) {
+        if gas_used == 0 {
+            return;
+        }
+        let current = self.current();
+        *self.nodes[current].gas_used.entry(category).or_default() += gas_used;
+    }
+
+    pub fn finish(self) -> GasProfile {
+        let mut entries = vec![];
+        let mut stack = vec![];
+        self.collect(0, &mut stack, &mut entries);
+        GasProfile::new(entries)
+    }
+
+    fn current(&self) -> usize {
+        *self
+            .stack
+            .last()
+            .expect("the root frame should always exist")
+    }
+
+    fn collect(&self, index: usize, stack: &mut Vec<String>, entries: &mut Vec<GasProfileEntry>) {
+        let node = &self.nodes[index];
+        stack.push(node.name.clone());
+        for (category, gas_used) in &node.gas_used {
+            entries.push(GasProfileEntry {
+                stack: stack.clone(),
+                category: *category,
+                gas_used: *gas_used,
+            });
+        }
+        for child in node.children.values() {
+            self.collect(*child, stack, entries);
+        }
+        stack.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_profiler() {
+        let module_id = ModuleId::new(MOVEOS_STD_ADDRESS, object::MODULE_NAME.to_owned());
+        let mut profiler = GasProfiler::new();
+        profiler.record(GasCategory::Io, 10);
+        for _ in 0..2 {
+            profiler.enter(None, "main");
+            profiler.record(GasCategory::Instruction, 1);
+            profiler.enter(Some(&module_id), "native_add_field");
+            profiler.record(profiler.native_category(), 2);
+            profiler.exit();
+            profiler.exit();
+        }
+        // The root frame is never exited
+        profiler.exit();
+        profiler.record(GasCategory::Instruction, 3);
+
+        let profile = profiler.finish();
+        assert_eq!(profile.total_gas_used(), 19);
+        assert_eq!(profile.gas_used_of(GasCategory::Storage), 4);
+        assert_eq!(
+            profile.to_folded_stacks(),
+            "transaction 3\ntransaction;[io] 10\ntransaction;script::main 2\ntransaction;script::main;0x2::object::native_add_field;[storage] 4\n"
+        );
+    }
+}
@@ -3,9 +3,11 @@
 
 use super::SwitchableGasMeter;
 use crate::gas::gas_member::{FromOnChainGasSchedule, InitialGasSchedule, ToOnChainGasSchedule};
+use crate::gas::profiler::GasProfiler;
 use crate::gas::r#abstract::{
     AbstractValueSize, AbstractValueSizePerArg, InternalGasPerAbstractValueUnit,
 };
+use crate::vm::call_tracer::CallTracer;
 use anyhow::Result;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_binary_format::file_format::CodeOffset;
@@ -14,10 +16,11 @@ use move_core_types::gas_algebra::{
     AbstractMemorySize, GasQuantity, InternalGas, InternalGasPerArg, InternalGasPerByte, NumArgs,
     NumBytes,
 };
-use move_core_types::language_storage::ModuleId;
+use move_core_types::language_storage::{ModuleId, TypeTag};
 use move_core_types::vm_status::StatusCode;
 use move_vm_types::gas::{GasMeter, SimpleInstruction, UnmeteredGasMeter};
 use move_vm_types::views::{TypeView, ValueView};
+use moveos_types::gas_profile::GasCategory;
 use moveos_types::moveos_std::gas_schedule::GasSchedule;
 use moveos_types::state_resolver::StateResolver;
 use moveos_types::transaction::GasStatement;
@@ -553,6 +556,11 @@ pub struct MoveOSGasMeter {
     instructions_executed: u64,
     instructions_next_tier_start: Option<u64>,
     instructions_current_tier_mult: u64,
+
+    // The call tracer, the calls are traced only if it is enabled.
+    call_tracer: Option<CallTracer>,
+    // The gas profiler, the gas is profiled only if it is enabled.
+    gas_profiler: Option<GasProfiler>,
 }
 
 impl MoveOSGasMeter {
@@ -587,6 +595,8 @@ impl MoveOSGasMeter {
             stack_height_next_tier_start,
             stack_size_next_tier_start,
             instructions_next_tier_start,
+            call_tracer: None,
+            gas_profiler: None,
         }
     }
 
@@ -612,6 +622,8 @@ impl MoveOSGasMeter {
             instructions_executed: 0,
             instructions_next_tier_start: None,
             instructions_current_tier_mult: 0,
+            call_tracer: None,
+            gas_profiler: None,
         }
     }
 
@@ -691,11 +703,20 @@ impl MoveOSGasMeter {
     }
 
     pub fn deduct_gas(&mut self, cost: InternalGas) -> PartialVMResult<()> {
+        self.deduct_gas_with_category(cost, GasCategory::Instruction)
+    }
+
+    fn deduct_gas_with_category(
+        &mut self,
+        cost: InternalGas,
+        category: GasCategory,
+    ) -> PartialVMResult<()> {
         if !self.charge {
             return Ok(());
         }
 
-        match self.gas_left.checked_sub(cost) {
+        let gas_left_before: u64 = self.gas_left.into();
+        let result = match self.gas_left.checked_sub(cost) {
             None => {
                 self.gas_left = InternalGas::from(0);
                 Err(PartialVMError::new(StatusCode::OUT_OF_GAS))
@@ -704,12 +725,64 @@ impl MoveOSGasMeter {
                 self.gas_left = gas_left;
                 Ok(())
             }
+        };
+        if let Some(profiler) = self.gas_profiler.as_mut() {
+            let gas_left: u64 = self.gas_left.into();
+            profiler.record(category, gas_left_before - gas_left);
         }
+        result
     }
 
     pub fn set_metering(&mut self, enabled: bool) {
         self.charge = enabled;
     }
+
+    /// Start to trace the calls, the calls before it are not traced.
+    pub fn start_tracing(&mut self) {
+        self.call_tracer = Some(CallTracer::new());
+    }
+
+    /// Stop tracing the calls and return the tracer.
+    pub fn take_call_tracer(&mut self) -> Option<CallTracer> {
+        self.call_tracer.take()
+    }
+
+    /// Start to profile the gas, the gas charged before it is not profiled.
+    pub fn start_profiling(&mut self) {
+        self.gas_profiler = Some(GasProfiler::new());
+    }
+
+    /// Stop profiling the gas and return the profiler.
+    pub fn take_gas_profiler(&mut self) -> Option<GasProfiler> {
+        self.gas_profiler.take()
+    }
+
+    fn enter_frame(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: Vec<TypeTag>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        if let Some(profiler) = self.gas_profiler.as_mut() {
+            profiler.enter(Some(module_id), func_name);
+        }
+        let gas_left = self.gas_left.into();
+        if let Some(tracer) = self.call_tracer.as_mut() {
+            tracer.enter_call(module_id, func_name, ty_args, args, gas_left)?;
+        }
+        Ok(())
+    }
+
+    fn exit_frame(&mut self, is_native: bool, success: bool) {
+        if let Some(profiler) = self.gas_profiler.as_mut() {
+            profiler.exit();
+        }
+        let gas_left = self.gas_left.into();
+        if let Some(tracer) = self.call_tracer.as_mut() {
+            tracer.exit(is_native, success, gas_left);
+        }
+    }
 }
 
 pub trait ClassifiedGasMeter {
@@ -720,6 +793,10 @@ pub trait ClassifiedGasMeter {
     //fn charge_change_set(&mut self, change_set: &StateChangeSet) -> PartialVMResult<()>;
     fn check_constrains(&self, max_gas_amount: u64) -> PartialVMResult<()>;
     fn gas_statement(&self) -> GasStatement;
+    /// The call tracer, it is None if the tracing is not enabled.
+    fn call_tracer(&mut self) -> Option<&mut CallTracer>;
+    /// The gas profiler, it is None if the profiling is not enabled.
+    fn gas_profiler(&mut self) -> Option<&mut GasProfiler>;
 }
 
 impl ClassifiedGasMeter for MoveOSGasMeter {
@@ -754,7 +831,9 @@ impl ClassifiedGasMeter for MoveOSGasMeter {
                 self.gas_left = InternalGas::from(0);
                 Err(PartialVMError::new(StatusCode::OUT_OF_GAS))
             }
-            Some(final_gas) => self.charge_v1(InternalGas::from(final_gas)),
+            Some(final_gas) => {
+                self.deduct_gas_with_category(InternalGas::from(final_gas), GasCategory::Io)
+            }
         }
     }
     //TODO cleanup
@@ -841,6 +920,14 @@ impl ClassifiedGasMeter for MoveOSGasMeter {
             storage_gas_used: *self.storage_gas_used.borrow(),
         }
     }
+
+    fn call_tracer(&mut self) -> Option<&mut CallTracer> {
+        self.call_tracer.as_mut()
+    }
+
+    fn gas_profiler(&mut self) -> Option<&mut GasProfiler> {
+        self.gas_profiler.as_mut()
+    }
 }
 
 impl GasMeter for MoveOSGasMeter {
@@ -858,8 +945,9 @@ impl GasMeter for MoveOSGasMeter {
         }
 
         let instruction_gas_parameter = self.cost_table.instruction_gas_parameter.clone();
+        let is_ret = matches!(instr, SimpleInstruction::Ret);
 
-        dispatch! {
+        let result = dispatch! {
             Nop => instruction_gas_parameter.nop,
 
             Abort => instruction_gas_parameter.abort,
@@ -909,7 +997,11 @@ impl GasMeter for MoveOSGasMeter {
             Gt => instruction_gas_parameter.gt,
             Le => instruction_gas_parameter.le,
             Ge => instruction_gas_parameter.ge,
+        };
+        if is_ret && result.is_ok() {
+            self.exit_frame(false, true);
         }
+        result
     }
 
     fn charge_br_true(&mut self, _target_offset: Option<CodeOffset>) -> PartialVMResult<()> {
@@ -930,8 +1022,8 @@ impl GasMeter for MoveOSGasMeter {
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         num_locals: NumArgs,
     ) -> PartialVMResult<()> {
@@ -939,13 +1031,14 @@ impl GasMeter for MoveOSGasMeter {
         let call_per_arg = self.cost_table.instruction_gas_parameter.call_per_arg;
         let cost = call_base + call_per_arg * NumArgs::new(args.len() as u64);
         let call_per_local = self.cost_table.instruction_gas_parameter.call_per_local;
-        self.charge_v1(cost + call_per_local * num_locals)
+        self.charge_v1(cost + call_per_local * num_locals)?;
+        self.enter_frame(module_id, func_name, vec![], args)
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         num_locals: NumArgs,
@@ -969,7 +1062,13 @@ impl GasMeter for MoveOSGasMeter {
             .instruction_gas_parameter
             .call_generic_per_local;
 
-        self.charge_v1(cost + call_generic_per_local * num_locals)
+        self.charge_v1(cost + call_generic_per_local * num_locals)?;
+        let ty_args = if self.call_tracer.is_some() {
+            ty_args.map(|ty| ty.to_type_tag()).collect()
+        } else {
+            vec![]
+        };
+        self.enter_frame(module_id, func_name, ty_args, args)
     }
 
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
@@ -1302,9 +1401,16 @@ impl GasMeter for MoveOSGasMeter {
     fn charge_native_function(
         &mut self,
         amount: InternalGas,
-        _ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
     ) -> PartialVMResult<()> {
-        self.charge_v1(amount)
+        let category = self
+            .gas_profiler
+            .as_ref()
+            .map_or(GasCategory::Native, |profiler| profiler.native_category());
+        let result = self.deduct_gas_with_category(amount, category);
+        // The return values are None if the native function aborts
+        self.exit_frame(true, result.is_ok() && ret_vals.is_some());
+        result
     }
 
     fn charge_native_function_before_execution(
@@ -1425,4 +1531,12 @@ impl ClassifiedGasMeter for UnmeteredGasMeter {
             storage_gas_used: InternalGas::from(0),
         }
     }
+
+    fn call_tracer(&mut self) -> Option<&mut CallTracer> {
+        None
+    }
+
+    fn gas_profiler(&mut self) -> Option<&mut GasProfiler> {
+        None
+    }
 }
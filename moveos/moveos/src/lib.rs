@@ -5,4 +5,5 @@ pub mod gas;
 pub mod moveos;
 pub mod moveos_test_model_builder;
 pub mod moveos_test_runner;
+pub mod parallel;
 pub mod vm;
@@ -23,13 +23,16 @@ use move_core_types::{
 use move_vm_runtime::config::VMConfig;
 use move_vm_runtime::data_cache::TransactionCache;
 use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::gas::GasMeter;
 use moveos_store::config_store::ConfigDBStore;
 use moveos_store::event_store::EventDBStore;
 use moveos_store::state_store::statedb::StateDBStore;
 use moveos_store::transaction_store::TransactionDBStore;
 use moveos_store::MoveOSStore;
 use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::call_trace::CallTrace;
 use moveos_types::function_return_value::FunctionResult;
+use moveos_types::gas_profile::GasProfile;
 use moveos_types::moveos_std::event::Event;
 use moveos_types::moveos_std::gas_schedule::{GasScheduleConfig, GasScheduleUpdated};
 use moveos_types::moveos_std::object::ObjectMeta;
@@ -37,7 +40,7 @@ use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::moveos_std::tx_result::TxResult;
 use moveos_types::startup_info::StartupInfo;
 use moveos_types::state::{MoveStructState, MoveStructType, ObjectState};
-use moveos_types::state_resolver::RootObjectResolver;
+use moveos_types::state_resolver::{MoveOSResolver, RootObjectResolver, StatelessResolver};
 use moveos_types::transaction::{FunctionCall, VMErrorInfo};
 use moveos_types::transaction::{
     MoveOSTransaction, RawTransactionOutput, TransactionOutput, VerifiedMoveAction,
@@ -104,6 +107,22 @@ impl Clone for MoveOSConfig {
     }
 }
 
+/// The options to collect the diagnostics when executing a transaction
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecuteOptions {
+    /// Trace the Move call tree of the transaction action
+    pub trace: bool,
+    /// Profile the gas used by the transaction action
+    pub profile_gas: bool,
+}
+
+/// The diagnostics of a transaction, only the ones enabled by the ExecuteOptions are returned
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionDiagnostics {
+    pub call_traces: Option<Vec<CallTrace>>,
+    pub gas_profile: Option<GasProfile>,
+}
+
 pub struct MoveOS {
     vm: MoveOSVM,
     pub db: MoveOSStore,
@@ -254,6 +273,53 @@ impl MoveOS {
         &self,
         tx: VerifiedMoveOSTransaction,
     ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
+        self.execute_with_resolver(tx, &self.db, false)
+    }
+
+    /// Execute the transaction and collect the diagnostics enabled by the options,
+    /// the system functions executed before and after the action are not traced or profiled.
+    pub fn execute_with_options(
+        &self,
+        tx: VerifiedMoveOSTransaction,
+        options: ExecuteOptions,
+    ) -> Result<(
+        RawTransactionOutput,
+        Option<VMErrorInfo>,
+        ExecutionDiagnostics,
+    )> {
+        self.execute_with_diagnostics(tx, &self.db, false, options)
+    }
+
+    /// Execute the transaction and read the states via the given resolver,
+    /// the parallel executor uses it to record the states read by the transaction.
+    /// A speculative execution may read stale states, so the failures of the system functions
+    /// are returned as errors instead of panics, and the caller should re-execute the transaction.
+    pub(crate) fn execute_with_resolver<R: StatelessResolver>(
+        &self,
+        tx: VerifiedMoveOSTransaction,
+        state_resolver: &R,
+        speculative: bool,
+    ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
+        let (raw_output, vm_error_info, _) = self.execute_with_diagnostics(
+            tx,
+            state_resolver,
+            speculative,
+            ExecuteOptions::default(),
+        )?;
+        Ok((raw_output, vm_error_info))
+    }
+
+    fn execute_with_diagnostics<R: StatelessResolver>(
+        &self,
+        tx: VerifiedMoveOSTransaction,
+        state_resolver: &R,
+        speculative: bool,
+        options: ExecuteOptions,
+    ) -> Result<(
+        RawTransactionOutput,
+        Option<VMErrorInfo>,
+        ExecutionDiagnostics,
+    )> {
         let VerifiedMoveOSTransaction { root, ctx, action } = tx;
         let tx_hash = ctx.tx_hash();
         if log::log_enabled!(log::Level::Debug) {
@@ -273,21 +339,44 @@ impl MoveOS {
 
         let cost_table = self.load_cost_table(&root)?;
         let mut gas_meter = MoveOSGasMeter::new(cost_table, ctx.max_gas_amount);
+        // The gas for the transaction bytes is profiled, it is a part of the gas used.
+        if options.profile_gas {
+            gas_meter.start_profiling();
+        }
         gas_meter.charge_io_write(ctx.tx_size)?;
 
-        let resolver = RootObjectResolver::new(root, &self.db);
+        let resolver = RootObjectResolver::new(root, state_resolver);
         let mut session = self.vm.new_session(&resolver, ctx, gas_meter);
 
         //We do not execute pre_execute and post_execute functions for system call
         if !is_system_call {
             // system pre_execute
             // we do not charge gas for system_pre_execute function
-            session
-                .execute_function_call(self.system_pre_execute_functions.clone(), false)
-                .expect("system_pre_execute should not fail.");
+            check_system_function_result(
+                session.execute_function_call(self.system_pre_execute_functions.clone(), false),
+                speculative,
+                "system_pre_execute should not fail.",
+            )?;
         }
 
-        match self.execute_action(&mut session, action.clone()) {
+        if options.trace {
+            session.gas_meter.start_tracing();
+        }
+        let action_result = self.execute_action(&mut session, action.clone());
+        let call_traces = session.gas_meter.take_call_tracer().map(|tracer| {
+            let gas_left = session.gas_meter.balance_internal().into();
+            session.resolve_call_traces(tracer.finish(gas_left))
+        });
+        let gas_profile = session
+            .gas_meter
+            .take_gas_profiler()
+            .map(|profiler| profiler.finish());
+        let diagnostics = ExecutionDiagnostics {
+            call_traces,
+            gas_profile,
+        };
+
+        let (raw_output, vm_error_info) = match action_result {
             Ok(status) => {
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!(
@@ -296,7 +385,7 @@ impl MoveOS {
                         status
                     );
                 }
-                self.execution_cleanup(is_system_call, session, status, None)
+                self.execution_cleanup(is_system_call, speculative, session, status, None)
             }
             Err(vm_err) => {
                 if log::log_enabled!(log::Level::Warn) {
@@ -318,10 +407,14 @@ impl MoveOS {
                 if !is_system_call {
                     let mut s = session.respawn(system_env);
                     //Because the session is respawned, the pre_execute function should be called again.
-                    s.execute_function_call(self.system_pre_execute_functions.clone(), false)
-                        .expect("system_pre_execute should not fail.");
+                    check_system_function_result(
+                        s.execute_function_call(self.system_pre_execute_functions.clone(), false),
+                        speculative,
+                        "system_pre_execute should not fail.",
+                    )?;
                     self.execution_cleanup(
                         is_system_call,
+                        speculative,
                         s,
                         vm_err.into_vm_status(),
                         Some(vm_error_info),
@@ -329,13 +422,15 @@ impl MoveOS {
                 } else {
                     self.execution_cleanup(
                         is_system_call,
+                        speculative,
                         session,
                         vm_err.into_vm_status(),
                         Some(vm_error_info),
                     )
                 }
             }
-        }
+        }?;
+        Ok((raw_output, vm_error_info, diagnostics))
     }
 
     pub fn execute_only(
@@ -454,9 +549,9 @@ impl MoveOS {
     // Execute action with pre_execute and post_execute.
     // Return the action execution status if success,
     // else return VMError and a bool which indicate if we should respawn the session.
-    fn execute_action(
+    fn execute_action<S: MoveOSResolver>(
         &self,
-        session: &mut MoveOSSession<'_, '_, RootObjectResolver<MoveOSStore>, MoveOSGasMeter>,
+        session: &mut MoveOSSession<'_, '_, S, MoveOSGasMeter>,
         action: VerifiedMoveAction,
     ) -> Result<VMStatus, VMError> {
         // execute main tx
@@ -485,16 +580,20 @@ impl MoveOS {
         Ok(status)
     }
 
-    fn execution_cleanup(
+    fn execution_cleanup<S: MoveOSResolver>(
         &self,
         is_system_call: bool,
-        mut session: MoveOSSession<'_, '_, RootObjectResolver<MoveOSStore>, MoveOSGasMeter>,
+        speculative: bool,
+        mut session: MoveOSSession<'_, '_, S, MoveOSGasMeter>,
         status: VMStatus,
         vm_error_info: Option<VMErrorInfo>,
     ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
         let kept_status = match status.keep_or_discard() {
             Ok(kept_status) => {
                 if is_system_call && kept_status != KeptVMStatus::Executed {
+                    if speculative {
+                        bail!("Speculative system call failed: {:?}", kept_status);
+                    }
                     // system call should always success
                     let backtrace = Backtrace::new();
                     panic!("System call failed: {:?}\n{:?}", kept_status, backtrace);
@@ -528,9 +627,11 @@ impl MoveOS {
         if !is_system_call {
             // system post_execute
             // we do not charge gas for system_post_execute function
-            session
-                .execute_function_call(self.system_post_execute_functions.clone(), false)
-                .expect("system_post_execute should not fail.");
+            check_system_function_result(
+                session.execute_function_call(self.system_post_execute_functions.clone(), false),
+                speculative,
+                "system_post_execute should not fail.",
+            )?;
         }
 
         let gas_schedule_updated = session.tx_context().get::<GasScheduleUpdated>()?;
@@ -551,9 +652,17 @@ impl MoveOS {
     }
 }
 
-fn extract_execution_state(
+fn check_system_function_result(result: VMResult<()>, speculative: bool, msg: &str) -> Result<()> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) if speculative => bail!("{} {:?}", msg, e),
+        Err(e) => panic!("{}: {:?}", msg, e),
+    }
+}
+
+fn extract_execution_state<S: MoveOSResolver>(
     vm_err: VMError,
-    data_cache: &MoveosDataCache<RootObjectResolver<MoveOSStore>>,
+    data_cache: &MoveosDataCache<S>,
 ) -> Result<Vec<String>> {
     let mut execution_stack_trace = Vec::new();
     if let Some(exec_state) = vm_err.exec_state() {
@@ -578,10 +687,10 @@ fn extract_execution_state(
     Ok(execution_stack_trace)
 }
 
-fn func_name_from_db(
+fn func_name_from_db<S: MoveOSResolver>(
     module_id: &ModuleId,
     func_idx: &FunctionDefinitionIndex,
-    data_cache: &MoveosDataCache<RootObjectResolver<MoveOSStore>>,
+    data_cache: &MoveosDataCache<S>,
 ) -> Result<String> {
     let module_bytes = data_cache.load_module(module_id)?;
     let compiled_module = CompiledModule::deserialize(module_bytes.as_slice())?;
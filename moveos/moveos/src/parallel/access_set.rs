@@ -0,0 +1,164 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta, GENESIS_STATE_ROOT};
+use moveos_types::state::{FieldKey, ObjectChange, ObjectState, StateChangeSet};
+use moveos_types::state_resolver::{StateKV, StatelessResolver};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The states read by a transaction, at the object field level.
+#[derive(Clone, Debug, Default)]
+pub struct ReadSet {
+    /// The fields read by the transaction, with the object which the field belongs to
+    fields: HashSet<(ObjectID, FieldKey)>,
+    /// The objects whose fields are listed by the transaction
+    listed_objects: HashSet<ObjectID>,
+    /// The transaction read a state tree which can not be mapped to an object,
+    /// so the read set is incomplete and can not be validated.
+    unresolved: bool,
+}
+
+/// The states written by a transaction, at the object field level.
+#[derive(Clone, Debug, Default)]
+pub struct WriteSet {
+    /// The fields changed by the transaction, with the object which the field belongs to
+    fields: HashSet<(ObjectID, FieldKey)>,
+    /// The objects whose fields are changed by the transaction
+    objects: HashSet<ObjectID>,
+}
+
+impl WriteSet {
+    pub fn from_change_set(change_set: &StateChangeSet) -> Self {
+        let mut write_set = Self::default();
+        write_set.add_changes(&ObjectID::root(), &change_set.changes);
+        write_set
+    }
+
+    fn add_changes(&mut self, object_id: &ObjectID, changes: &BTreeMap<FieldKey, ObjectChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        self.objects.insert(object_id.clone());
+        for (key, change) in changes {
+            self.fields.insert((object_id.clone(), *key));
+            self.add_changes(&change.metadata.id, &change.fields);
+        }
+    }
+
+    pub fn contains_field(&self, object_id: &ObjectID, key: &FieldKey) -> bool {
+        self.fields.contains(&(object_id.clone(), *key))
+    }
+
+    pub fn merge(&mut self, other: WriteSet) {
+        self.fields.extend(other.fields);
+        self.objects.extend(other.objects);
+    }
+
+    /// Check whether the states read by a transaction are changed by this write set
+    pub fn is_conflict_with(&self, read_set: &ReadSet) -> bool {
+        read_set.unresolved
+            || read_set
+                .fields
+                .iter()
+                .any(|field| self.fields.contains(field))
+            || read_set
+                .listed_objects
+                .iter()
+                .any(|object_id| self.objects.contains(object_id))
+    }
+}
+
+/// A resolver which records the fields read by a transaction.
+/// The fields are read by the state root of the object, so the recorder maps the state roots
+/// of the loaded objects back to the objects. Different objects may have the same state root,
+/// in this case the read is recorded for all of them.
+pub struct ReadSetRecorder<'a, R> {
+    resolver: &'a R,
+    objects: RefCell<HashMap<H256, HashSet<ObjectID>>>,
+    read_set: RefCell<ReadSet>,
+}
+
+impl<'a, R> ReadSetRecorder<'a, R>
+where
+    R: StatelessResolver,
+{
+    pub fn new(root: &ObjectMeta, resolver: &'a R) -> Self {
+        let recorder = Self {
+            resolver,
+            objects: RefCell::new(HashMap::new()),
+            read_set: RefCell::new(ReadSet::default()),
+        };
+        recorder.record_object(root.state_root(), ObjectID::root());
+        recorder
+    }
+
+    pub fn into_read_set(self) -> ReadSet {
+        self.read_set.into_inner()
+    }
+
+    fn record_object(&self, state_root: H256, object_id: ObjectID) {
+        self.objects
+            .borrow_mut()
+            .entry(state_root)
+            .or_default()
+            .insert(object_id);
+    }
+
+    fn record_loaded_object(&self, state: &ObjectState) {
+        self.record_object(state.state_root(), state.metadata.id.clone());
+    }
+
+    fn record_read(&self, state_root: H256, key: Option<&FieldKey>) {
+        let objects = self.objects.borrow();
+        let mut read_set = self.read_set.borrow_mut();
+        match objects.get(&state_root) {
+            Some(object_ids) => {
+                for object_id in object_ids {
+                    match key {
+                        Some(key) => {
+                            read_set.fields.insert((object_id.clone(), *key));
+                        }
+                        None => {
+                            read_set.listed_objects.insert(object_id.clone());
+                        }
+                    }
+                }
+            }
+            // The empty state tree belongs to the objects created by the transaction itself,
+            // the creation is recorded by reading the object from its parent.
+            None if state_root == *GENESIS_STATE_ROOT => {}
+            None => read_set.unresolved = true,
+        }
+    }
+}
+
+impl<R> StatelessResolver for ReadSetRecorder<'_, R>
+where
+    R: StatelessResolver,
+{
+    fn get_field_at(&self, state_root: H256, key: &FieldKey) -> Result<Option<ObjectState>> {
+        self.record_read(state_root, Some(key));
+        let state = self.resolver.get_field_at(state_root, key)?;
+        if let Some(state) = &state {
+            self.record_loaded_object(state);
+        }
+        Ok(state)
+    }
+
+    fn list_fields_at(
+        &self,
+        state_root: H256,
+        cursor: Option<FieldKey>,
+        limit: usize,
+    ) -> Result<Vec<StateKV>> {
+        self.record_read(state_root, None);
+        let states = self.resolver.list_fields_at(state_root, cursor, limit)?;
+        for (_, state) in &states {
+            self.record_loaded_object(state);
+        }
+        Ok(states)
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::access_set::{ReadSet, ReadSetRecorder, WriteSet};
+use crate::moveos::MoveOS;
+use anyhow::Result;
+use moveos_types::moveos_std::gas_schedule::GasSchedule;
+use moveos_types::moveos_std::module_store::ModuleStore;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::transaction::{
+    RawTransactionOutput, TransactionOutput, VMErrorInfo, VerifiedMoveAction,
+    VerifiedMoveOSTransaction,
+};
+use rayon::prelude::*;
+
+/// The output of a speculative execution, with the states it read and wrote.
+struct SpeculativeOutput {
+    raw_output: RawTransactionOutput,
+    vm_error_info: Option<VMErrorInfo>,
+    read_set: ReadSet,
+    write_set: WriteSet,
+}
+
+#[derive(Debug)]
+pub struct ParallelExecutionOutput {
+    /// The outputs of the executed transactions, in the order of the batch
+    pub outputs: Vec<(TransactionOutput, Option<VMErrorInfo>)>,
+    /// The number of transactions re-executed because their speculative execution is invalid
+    pub reexecuted: usize,
+    /// The execution stops after the transaction which updates the gas schedule,
+    /// the caller should reload the gas parameters and execute the remaining transactions.
+    pub gas_schedule_updated: bool,
+}
+
+/// ParallelExecutor executes a batch of sequenced transactions in the Block-STM style.
+/// All the transactions are executed speculatively in parallel on the same root,
+/// and the fields they read are recorded. Then the outputs are committed in order,
+/// a transaction which read a field written by a previous transaction in the batch
+/// is re-executed on the latest state, so the final change sets are the same as serial execution.
+pub struct ParallelExecutor<'a> {
+    moveos: &'a MoveOS,
+}
+
+impl<'a> ParallelExecutor<'a> {
+    pub fn new(moveos: &'a MoveOS) -> Self {
+        Self { moveos }
+    }
+
+    /// Execute the transactions on the root and apply the outputs to the store in order.
+    /// The root of the transactions is ignored, every transaction is executed on the root
+    /// left by the previous one.
+    pub fn execute_block(
+        &self,
+        root: ObjectMeta,
+        txs: Vec<VerifiedMoveOSTransaction>,
+    ) -> Result<ParallelExecutionOutput> {
+        let publish_modules = txs
+            .iter()
+            .any(|tx| matches!(tx.action, VerifiedMoveAction::ModuleBundle { .. }));
+        let mut speculative_outputs = if publish_modules {
+            vec![]
+        } else {
+            txs.par_iter()
+                .map(|tx| self.execute_speculatively(&root, tx.clone()))
+                .collect::<Vec<_>>()
+        };
+        // The module cache of the VM is shared by all the speculative executions,
+        // a module published by a transaction may be loaded by others without reading the store,
+        // so we can not validate the speculative outputs and fall back to serial execution.
+        if publish_modules
+            || speculative_outputs
+                .iter()
+                .flatten()
+                .any(|output| Self::is_publish_modules(&output.raw_output, &output.write_set))
+        {
+            self.moveos.flush_module_cache(true)?;
+            speculative_outputs.clear();
+        }
+        speculative_outputs.resize_with(txs.len(), || None);
+
+        let base_size = root.size;
+        let mut current_root = root;
+        let mut committed_write_set = WriteSet::default();
+        let mut serial = false;
+        let mut outputs = Vec::with_capacity(txs.len());
+        let mut reexecuted = 0;
+        let mut gas_schedule_updated = false;
+        for (mut tx, speculative_output) in txs.into_iter().zip(speculative_outputs) {
+            let (raw_output, vm_error_info, write_set) = match speculative_output {
+                Some(output)
+                    if !serial && !committed_write_set.is_conflict_with(&output.read_set) =>
+                {
+                    let mut raw_output = output.raw_output;
+                    // The speculative execution is based on the batch root,
+                    // move the root changes onto the latest root.
+                    raw_output.changeset.state_root = current_root.state_root();
+                    raw_output.changeset.global_size =
+                        current_root.size + raw_output.changeset.global_size - base_size;
+                    (raw_output, output.vm_error_info, output.write_set)
+                }
+                _ => {
+                    reexecuted += 1;
+                    tx.root = current_root.clone();
+                    let (raw_output, vm_error_info) = self.moveos.execute(tx)?;
+                    let write_set = WriteSet::from_change_set(&raw_output.changeset);
+                    (raw_output, vm_error_info, write_set)
+                }
+            };
+
+            if Self::is_publish_modules(&raw_output, &write_set) {
+                // The speculative outputs of the following transactions may use the old modules
+                self.moveos.flush_module_cache(true)?;
+                serial = true;
+            }
+            gas_schedule_updated = write_set.contains_field(
+                &ObjectID::root(),
+                &GasSchedule::gas_schedule_object_id().field_key(),
+            );
+            committed_write_set.merge(write_set);
+
+            let output = MoveOS::apply_transaction_output(&self.moveos.db, raw_output)?;
+            current_root = output.changeset.root_metadata();
+            outputs.push((output, vm_error_info));
+            if gas_schedule_updated {
+                self.moveos.cost_table.write().take();
+                break;
+            }
+        }
+
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "parallel execute {} txs, re-executed {} txs",
+                outputs.len(),
+                reexecuted
+            );
+        }
+        Ok(ParallelExecutionOutput {
+            outputs,
+            reexecuted,
+            gas_schedule_updated,
+        })
+    }
+
+    fn execute_speculatively(
+        &self,
+        root: &ObjectMeta,
+        mut tx: VerifiedMoveOSTransaction,
+    ) -> Option<SpeculativeOutput> {
+        tx.root = root.clone();
+        let recorder = ReadSetRecorder::new(root, &self.moveos.db);
+        match self.moveos.execute_with_resolver(tx, &recorder, true) {
+            Ok((raw_output, vm_error_info)) => {
+                let write_set = WriteSet::from_change_set(&raw_output.changeset);
+                Some(SpeculativeOutput {
+                    raw_output,
+                    vm_error_info,
+                    read_set: recorder.into_read_set(),
+                    write_set,
+                })
+            }
+            Err(e) => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("speculative execution failed, re-execute it later: {:?}", e);
+                }
+                None
+            }
+        }
+    }
+
+    fn is_publish_modules(raw_output: &RawTransactionOutput, write_set: &WriteSet) -> bool {
+        raw_output.is_upgrade
+            || write_set.contains_field(&ObjectID::root(), &ModuleStore::object_id().field_key())
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod access_set;
+pub mod executor;
+
+pub use executor::{ParallelExecutionOutput, ParallelExecutor};
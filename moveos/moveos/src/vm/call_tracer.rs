@@ -0,0 +1,399 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::moveos_vm::MoveOSSession;
+use crate::gas::table::ClassifiedGasMeter;
+use crate::gas::SwitchableGasMeter;
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::{IdentStr, Identifier};
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::u256::U256;
+use move_core_types::vm_status::StatusCode;
+use move_vm_runtime::session::LoadedFunctionInstantiation;
+use move_vm_types::loaded_data::runtime_types::Type;
+use move_vm_types::views::{ValueView, ValueVisitor};
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::call_trace::{CallTrace, ObjectOperation, TracedObjectChange, TracedValue};
+use moveos_types::module_binding::ModuleBinding;
+use moveos_types::moveos_std::event::EventModule;
+use moveos_types::moveos_std::object::{self, ObjectID};
+use moveos_types::state::{FieldKey, MoveState};
+use moveos_types::state_resolver::MoveOSResolver;
+
+/// A call frame recorded by the CallTracer.
+/// The arguments are captured as BCS bytes when the function is called,
+/// and the types of them are resolved after the execution.
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    pub module_id: Option<ModuleId>,
+    pub function_name: Identifier,
+    pub ty_args: Vec<TypeTag>,
+    /// The parameter types, only known for the top-level calls
+    pub arg_types: Option<Vec<TypeTag>>,
+    pub args: Vec<Vec<u8>>,
+    pub gas_used: u64,
+    pub is_native: bool,
+    pub success: bool,
+    pub sub_calls: Vec<CallFrame>,
+    gas_left_at_enter: u64,
+}
+
+/// CallTracer records the Move call tree via the gas meter hooks.
+/// A frame is entered when the function is called, and exited when the function returns,
+/// or when the native function is charged.
+#[derive(Debug, Clone, Default)]
+pub struct CallTracer {
+    stack: Vec<CallFrame>,
+    calls: Vec<CallFrame>,
+}
+
+impl CallTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enter(
+        &mut self,
+        module_id: Option<ModuleId>,
+        function_name: Identifier,
+        ty_args: Vec<TypeTag>,
+        arg_types: Option<Vec<TypeTag>>,
+        args: Vec<Vec<u8>>,
+        gas_left: u64,
+    ) {
+        self.stack.push(CallFrame {
+            module_id,
+            function_name,
+            ty_args,
+            arg_types,
+            args,
+            gas_used: 0,
+            is_native: false,
+            success: false,
+            sub_calls: vec![],
+            gas_left_at_enter: gas_left,
+        });
+    }
+
+    /// Enter a frame from the gas meter hooks of the call instructions
+    pub fn enter_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: Vec<TypeTag>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        gas_left: u64,
+    ) -> PartialVMResult<()> {
+        let function_name = Identifier::new(func_name).map_err(|e| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message(e.to_string())
+        })?;
+        let args = args.map(|arg| serialize_value(&arg)).collect();
+        self.enter(
+            Some(module_id.clone()),
+            function_name,
+            ty_args,
+            None,
+            args,
+            gas_left,
+        );
+        Ok(())
+    }
+
+    pub fn exit(&mut self, is_native: bool, success: bool, gas_left: u64) {
+        if let Some(mut frame) = self.stack.pop() {
+            frame.is_native = is_native;
+            frame.success = success;
+            frame.gas_used = frame.gas_left_at_enter.saturating_sub(gas_left);
+            match self.stack.last_mut() {
+                Some(parent) => parent.sub_calls.push(frame),
+                None => self.calls.push(frame),
+            }
+        }
+    }
+
+    /// Exit all the frames which are not returned, and return the top-level calls
+    pub fn finish(mut self, gas_left: u64) -> Vec<CallFrame> {
+        while !self.stack.is_empty() {
+            self.exit(false, false, gas_left);
+        }
+        self.calls
+    }
+}
+
+impl<'r, 'l, S, G> MoveOSSession<'r, 'l, S, G>
+where
+    S: MoveOSResolver,
+    G: SwitchableGasMeter + ClassifiedGasMeter,
+{
+    /// Enter the frame of a top-level call if the tracing or the gas profiling is enabled,
+    /// the top-level calls do not go through the call hooks of the gas meter.
+    pub(crate) fn trace_entry_call(
+        &mut self,
+        module_id: Option<ModuleId>,
+        function_name: &IdentStr,
+        loaded_function: &LoadedFunctionInstantiation,
+        ty_args: Vec<TypeTag>,
+        serialized_args: &[Vec<u8>],
+    ) {
+        if let Some(profiler) = self.gas_meter.gas_profiler() {
+            profiler.enter(module_id.as_ref(), function_name.as_str());
+        }
+        if self.gas_meter.call_tracer().is_none() {
+            return;
+        }
+        let arg_types = self.parameter_type_tags(loaded_function);
+        let gas_left = self.gas_meter.balance_internal().into();
+        if let Some(tracer) = self.gas_meter.call_tracer() {
+            tracer.enter(
+                module_id,
+                function_name.to_owned(),
+                ty_args,
+                arg_types,
+                serialized_args.to_vec(),
+                gas_left,
+            );
+        }
+    }
+
+    /// Resolve the argument types of the recorded frames,
+    /// and attribute the events and object changes of the natives to their callers.
+    pub(crate) fn resolve_call_traces(&self, frames: Vec<CallFrame>) -> Vec<CallTrace> {
+        frames
+            .into_iter()
+            .map(|frame| self.resolve_call_trace(frame))
+            .collect()
+    }
+
+    fn resolve_call_trace(&self, frame: CallFrame) -> CallTrace {
+        let mut events = vec![];
+        let mut object_changes = vec![];
+        for sub_call in frame.sub_calls.iter().filter(|call| call.is_native) {
+            events.extend(native_event(sub_call));
+            object_changes.extend(native_object_change(sub_call));
+        }
+        let arg_types = frame.arg_types.or_else(|| {
+            let module_id = frame.module_id.as_ref()?;
+            let loaded_function = self
+                .session
+                .load_function(module_id, &frame.function_name, &frame.ty_args)
+                .ok()?;
+            self.parameter_type_tags(&loaded_function)
+        });
+        let args = match arg_types {
+            Some(arg_types) if arg_types.len() == frame.args.len() => arg_types
+                .into_iter()
+                .zip(frame.args)
+                .map(|(type_tag, value)| TracedValue::new(type_tag, value))
+                .collect(),
+            _ => vec![],
+        };
+        CallTrace {
+            module_id: frame.module_id,
+            function_name: frame.function_name,
+            ty_args: frame.ty_args,
+            args,
+            gas_used: frame.gas_used,
+            is_native: frame.is_native,
+            success: frame.success,
+            events,
+            object_changes,
+            sub_calls: self.resolve_call_traces(frame.sub_calls),
+        }
+    }
+
+    /// The type tags of the parameters, the references are dereferenced
+    fn parameter_type_tags(
+        &self,
+        loaded_function: &LoadedFunctionInstantiation,
+    ) -> Option<Vec<TypeTag>> {
+        loaded_function
+            .parameters
+            .iter()
+            .map(|ty| {
+                let ty = ty.subst(&loaded_function.type_arguments).ok()?;
+                let ty = match ty {
+                    Type::Reference(ty) | Type::MutableReference(ty) => *ty,
+                    ty => ty,
+                };
+                self.session.get_type_tag(&ty).ok()
+            })
+            .collect()
+    }
+}
+
+fn is_moveos_std_call(frame: &CallFrame, module_name: &IdentStr) -> bool {
+    frame.module_id.as_ref().map_or(false, |module_id| {
+        module_id.address() == &MOVEOS_STD_ADDRESS && module_id.name() == module_name
+    })
+}
+
+/// The event emitted by the `event::native_emit` native function
+fn native_event(frame: &CallFrame) -> Option<TracedValue> {
+    if !is_moveos_std_call(frame, EventModule::MODULE_NAME)
+        || frame.function_name.as_str() != "native_emit"
+    {
+        return None;
+    }
+    Some(TracedValue::new(
+        frame.ty_args.first()?.clone(),
+        frame.args.first()?.clone(),
+    ))
+}
+
+/// The object change made by the object native functions
+fn native_object_change(frame: &CallFrame) -> Option<TracedObjectChange> {
+    if !is_moveos_std_call(frame, object::MODULE_NAME) {
+        return None;
+    }
+    // The first argument is the ObjectID or the Object<T>, both of them are serialized as the ObjectID
+    let object_id = ObjectID::from_bytes(frame.args.first()?).ok()?;
+    let field_id = || -> Option<ObjectID> {
+        let key = AccountAddress::from_bytes(frame.args.get(1)?).ok()?;
+        Some(object_id.child_id(FieldKey::from(key)))
+    };
+    let change = match frame.function_name.as_str() {
+        "native_add_field" => TracedObjectChange::new(field_id()?, ObjectOperation::Add),
+        "native_remove_field" => TracedObjectChange::new(field_id()?, ObjectOperation::Remove),
+        "native_borrow_mut_field" => {
+            TracedObjectChange::new(field_id()?, ObjectOperation::BorrowMut)
+        }
+        "native_borrow_mut_object" => {
+            TracedObjectChange::new(object_id, ObjectOperation::BorrowMut)
+        }
+        "native_take_object" => TracedObjectChange::new(object_id, ObjectOperation::Take),
+        "native_transfer_object" => {
+            let new_owner = AccountAddress::from_bytes(frame.args.get(1)?).ok()?;
+            TracedObjectChange::new(object_id, ObjectOperation::Transfer { new_owner })
+        }
+        "native_to_shared_object" => TracedObjectChange::new(object_id, ObjectOperation::ToShared),
+        "native_to_frozen_object" => TracedObjectChange::new(object_id, ObjectOperation::ToFrozen),
+        _ => return None,
+    };
+    Some(change)
+}
+
+/// Serialize the value to BCS bytes, if the value is a reference, the value behind it is serialized
+fn serialize_value(value: &impl ValueView) -> Vec<u8> {
+    let mut visitor = BcsVisitor::default();
+    value.visit(&mut visitor);
+    visitor.bytes
+}
+
+#[derive(Default)]
+struct BcsVisitor {
+    bytes: Vec<u8>,
+}
+
+impl BcsVisitor {
+    fn write_len(&mut self, len: usize) {
+        // ULEB128 encoding of the length
+        let mut len = len as u64;
+        while len >= 0x80 {
+            self.bytes.push(((len & 0x7f) as u8) | 0x80);
+            len >>= 7;
+        }
+        self.bytes.push(len as u8);
+    }
+}
+
+impl ValueVisitor for BcsVisitor {
+    fn visit_u8(&mut self, _depth: usize, val: u8) {
+        self.bytes.push(val);
+    }
+
+    fn visit_u16(&mut self, _depth: usize, val: u16) {
+        self.bytes.extend(val.to_le_bytes());
+    }
+
+    fn visit_u32(&mut self, _depth: usize, val: u32) {
+        self.bytes.extend(val.to_le_bytes());
+    }
+
+    fn visit_u64(&mut self, _depth: usize, val: u64) {
+        self.bytes.extend(val.to_le_bytes());
+    }
+
+    fn visit_u128(&mut self, _depth: usize, val: u128) {
+        self.bytes.extend(val.to_le_bytes());
+    }
+
+    fn visit_u256(&mut self, _depth: usize, val: U256) {
+        self.bytes.extend(val.to_le_bytes());
+    }
+
+    fn visit_bool(&mut self, _depth: usize, val: bool) {
+        self.bytes.push(val as u8);
+    }
+
+    fn visit_address(&mut self, _depth: usize, val: AccountAddress) {
+        self.bytes.extend(val.into_bytes());
+    }
+
+    fn visit_struct(&mut self, _depth: usize, _len: usize) -> bool {
+        true
+    }
+
+    fn visit_vec(&mut self, _depth: usize, len: usize) -> bool {
+        self.write_len(len);
+        true
+    }
+
+    fn visit_vec_u8(&mut self, _depth: usize, vals: &[u8]) {
+        self.write_len(vals.len());
+        self.bytes.extend(vals);
+    }
+
+    fn visit_vec_u16(&mut self, _depth: usize, vals: &[u16]) {
+        self.write_len(vals.len());
+        for val in vals {
+            self.bytes.extend(val.to_le_bytes());
+        }
+    }
+
+    fn visit_vec_u32(&mut self, _depth: usize, vals: &[u32]) {
+        self.write_len(vals.len());
+        for val in vals {
+            self.bytes.extend(val.to_le_bytes());
+        }
+    }
+
+    fn visit_vec_u64(&mut self, _depth: usize, vals: &[u64]) {
+        self.write_len(vals.len());
+        for val in vals {
+            self.bytes.extend(val.to_le_bytes());
+        }
+    }
+
+    fn visit_vec_u128(&mut self, _depth: usize, vals: &[u128]) {
+        self.write_len(vals.len());
+        for val in vals {
+            self.bytes.extend(val.to_le_bytes());
+        }
+    }
+
+    fn visit_vec_u256(&mut self, _depth: usize, vals: &[U256]) {
+        self.write_len(vals.len());
+        for val in vals {
+            self.bytes.extend(val.to_le_bytes());
+        }
+    }
+
+    fn visit_vec_bool(&mut self, _depth: usize, vals: &[bool]) {
+        self.write_len(vals.len());
+        self.bytes.extend(vals.iter().map(|val| *val as u8));
+    }
+
+    fn visit_vec_address(&mut self, _depth: usize, vals: &[AccountAddress]) {
+        self.write_len(vals.len());
+        for val in vals {
+            self.bytes.extend(val.into_bytes());
+        }
+    }
+
+    fn visit_ref(&mut self, _depth: usize, _is_global: bool) -> bool {
+        // Visit the value behind the reference
+        true
+    }
+}
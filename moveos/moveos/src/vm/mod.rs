@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod call_tracer;
 #[allow(dead_code)]
 pub mod data_cache;
 pub mod moveos_vm;
@@ -15,6 +15,7 @@ use move_binary_format::{
 };
 use move_core_types::{
     account_address::AccountAddress,
+    ident_str,
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
     value::MoveTypeLayout,
@@ -309,6 +310,13 @@ where
                 let location: Location = Location::Script;
                 let resolved_args = self.resolve_argument(&loaded_function, call.args, location)?;
                 let serialized_args = self.load_arguments(resolved_args)?;
+                self.trace_entry_call(
+                    None,
+                    ident_str!("main"),
+                    &loaded_function,
+                    call.ty_args.clone(),
+                    &serialized_args,
+                );
                 self.session
                     .execute_script(
                         call.code,
@@ -335,6 +343,13 @@ where
                 let location = Location::Module(call.function_id.module_id.clone());
                 let resolved_args = self.resolve_argument(&loaded_function, call.args, location)?;
                 let serialized_args = self.load_arguments(resolved_args)?;
+                self.trace_entry_call(
+                    Some(call.function_id.module_id.clone()),
+                    &call.function_id.function_name,
+                    &loaded_function,
+                    call.ty_args.clone(),
+                    &serialized_args,
+                );
                 if bypass_visibility {
                     // bypass visibility call is system call, such as execute L1 block transaction
                     self.session
@@ -507,6 +522,13 @@ where
         let location = Location::Module(call.function_id.module_id.clone());
         let resolved_args = self.resolve_argument(&loaded_function, call.args, location)?;
         let serialized_args = self.load_arguments(resolved_args)?;
+        self.trace_entry_call(
+            Some(call.function_id.module_id.clone()),
+            &call.function_id.function_name,
+            &loaded_function,
+            call.ty_args.clone(),
+            &serialized_args,
+        );
         let return_values = self.session.execute_function_bypass_visibility(
             &call.function_id.module_id,
             &call.function_id.function_name,
@@ -11,6 +11,7 @@ use std::marker::PhantomData;
 use std::path::Path;
 
 use anyhow::{ensure, format_err, Error, Result};
+use rocksdb::checkpoint::Checkpoint;
 use rocksdb::{
     statistics, AsColumnFamilyRef, BlockBasedIndexType, BlockBasedOptions, CStrLike, Cache,
     ColumnFamily, ColumnFamilyDescriptor, DBCompressionType, DBRawIterator, DBRecoveryMode,
@@ -214,6 +215,19 @@ impl RocksDB {
         Ok(())
     }
 
+    /// Create a consistent checkpoint of all column families at `path`.
+    /// The path should not exist, the sst files are hard linked if the path is on the same filesystem.
+    pub fn create_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        ensure!(
+            !path.as_ref().exists(),
+            "Checkpoint path {:?} already exists",
+            path.as_ref()
+        );
+        let checkpoint = Checkpoint::new(&self.db)?;
+        checkpoint.create_checkpoint(path)?;
+        Ok(())
+    }
+
     /// List cf
     pub fn list_cf(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
         Ok(DB::list_cf(&Options::default(), path)?)
@@ -6,7 +6,8 @@ use function_name::named;
 use jellyfish_merkle::hash::SPARSE_MERKLE_PLACEHOLDER_HASH_VALUE;
 use jellyfish_merkle::{
     iterator::JellyfishMerkleIterator,
-    node_type::{Node, NodeKey},
+    nibble::Nibble,
+    node_type::{Children, LeafNode, Node, NodeKey},
     JellyfishMerkleTree, TreeReader,
 };
 use parking_lot::RwLock;
@@ -100,11 +101,17 @@ impl NodeWriter for InMemoryNodeStore {
 pub struct TreeChangeSet {
     pub state_root: H256,
     pub nodes: BTreeMap<H256, Vec<u8>>,
+    /// The nodes of the previous tree which are replaced by this change set
+    pub stale_nodes: Vec<H256>,
 }
 
 impl TreeChangeSet {
-    pub fn new(state_root: H256, nodes: BTreeMap<H256, Vec<u8>>) -> Self {
-        Self { state_root, nodes }
+    pub fn new(state_root: H256, nodes: BTreeMap<H256, Vec<u8>>, stale_nodes: Vec<H256>) -> Self {
+        Self {
+            state_root,
+            nodes,
+            stale_nodes,
+        }
     }
 }
 
@@ -293,6 +300,7 @@ where
             return Ok(TreeChangeSet {
                 state_root,
                 nodes: BTreeMap::default(),
+                stale_nodes: vec![],
             });
         }
 
@@ -305,15 +313,137 @@ where
         for (nk, n) in change_set.node_batch.into_iter() {
             node_map.insert(nk.into(), n.encode()?);
         }
+        let stale_nodes = change_set
+            .stale_node_index_batch
+            .into_iter()
+            .map(|index| index.node_key.into())
+            .collect();
 
         let new_state_root: H256 = new_state_root.into();
 
         Ok(TreeChangeSet {
             state_root: new_state_root,
             nodes: node_map,
+            stale_nodes,
         })
     }
 
+    /// List the hashes of all the nodes of the tree
+    pub fn list_nodes(&self, state_root: H256) -> Result<Vec<H256>> {
+        let mut nodes = vec![];
+        let mut pending = vec![state_root];
+        while let Some(node_hash) = pending.pop() {
+            if node_hash == *SPARSE_MERKLE_PLACEHOLDER_HASH {
+                continue;
+            }
+            let node = self.get_node(node_hash)?;
+            if let Node::Internal(internal_node) = node {
+                pending.extend(
+                    Children::from(internal_node)
+                        .into_values()
+                        .map(|child| H256::from(child.hash)),
+                );
+            }
+            nodes.push(node_hash);
+        }
+        Ok(nodes)
+    }
+
+    /// Compare the trees of the two state roots, return the (key, old value, new value) of the changed keys.
+    /// The subtrees with the same hash are skipped, so the cost is proportional to the changes.
+    pub fn diff(&self, old_root: H256, new_root: H256) -> Result<Vec<(K, Option<V>, Option<V>)>> {
+        let mut changes = vec![];
+        self.diff_nodes(old_root, new_root, &mut changes)?;
+        Ok(changes)
+    }
+
+    fn diff_nodes(
+        &self,
+        old_hash: H256,
+        new_hash: H256,
+        changes: &mut Vec<(K, Option<V>, Option<V>)>,
+    ) -> Result<()> {
+        if old_hash == new_hash {
+            return Ok(());
+        }
+        let old_node = self.get_node(old_hash)?;
+        let new_node = self.get_node(new_hash)?;
+        match (old_node, new_node) {
+            // The internal nodes at the same position cover the same key range,
+            // so the children of the same nibble are compared
+            (Node::Internal(old_internal), Node::Internal(new_internal)) => {
+                let old_children = Children::from(old_internal);
+                let new_children = Children::from(new_internal);
+                for nibble in 0..16u8 {
+                    let nibble = Nibble::from(nibble);
+                    let child_hash = |children: &Children| {
+                        children
+                            .get(&nibble)
+                            .map(|child| H256::from(child.hash))
+                            .unwrap_or(*SPARSE_MERKLE_PLACEHOLDER_HASH)
+                    };
+                    self.diff_nodes(
+                        child_hash(&old_children),
+                        child_hash(&new_children),
+                        changes,
+                    )?;
+                }
+            }
+            // A leaf may be placed at a higher level than the leaves of the other subtree,
+            // compare the leaves of the subtrees directly
+            (old_node, new_node) => {
+                let mut leaves =
+                    BTreeMap::<K, (Option<LeafNode<K, V>>, Option<LeafNode<K, V>>)>::new();
+                for leaf in self.list_leaves(old_node)? {
+                    leaves.entry(*leaf.key()).or_default().0 = Some(leaf);
+                }
+                for leaf in self.list_leaves(new_node)? {
+                    leaves.entry(*leaf.key()).or_default().1 = Some(leaf);
+                }
+                for (key, (old_leaf, new_leaf)) in leaves {
+                    let changed = match (&old_leaf, &new_leaf) {
+                        (Some(old_leaf), Some(new_leaf)) => {
+                            old_leaf.value_hash() != new_leaf.value_hash()
+                        }
+                        _ => true,
+                    };
+                    if changed {
+                        changes.push((
+                            key,
+                            old_leaf.map(|leaf| leaf.value().origin.clone()),
+                            new_leaf.map(|leaf| leaf.value().origin.clone()),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_node(&self, node_hash: H256) -> Result<Node<K, V>> {
+        if node_hash == *SPARSE_MERKLE_PLACEHOLDER_HASH {
+            return Ok(Node::new_null());
+        }
+        self.node_reader.get_node(&node_hash.into())
+    }
+
+    fn list_leaves(&self, node: Node<K, V>) -> Result<Vec<LeafNode<K, V>>> {
+        let mut leaves = vec![];
+        let mut pending = vec![node];
+        while let Some(node) = pending.pop() {
+            match node {
+                Node::Null => {}
+                Node::Leaf(leaf) => leaves.push(leaf),
+                Node::Internal(internal_node) => {
+                    for child in Children::from(internal_node).into_values() {
+                        pending.push(self.get_node(child.hash.into())?);
+                    }
+                }
+            }
+        }
+        Ok(leaves)
+    }
+
     /// Dump all (key, value) from the tree
     pub fn dump(&self, state_root: H256) -> Result<Vec<(K, V)>> {
         let iter = self.iter(state_root, None)?;
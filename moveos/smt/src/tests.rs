@@ -42,6 +42,8 @@ fn test_smt() {
         )
         .unwrap();
 
+    // The root node of the previous tree is replaced
+    assert!(changeset2.stale_nodes.contains(&changeset.state_root));
     node_store.write_nodes(changeset2.nodes).unwrap();
     let (result, proof) = smt.get_with_proof(changeset2.state_root, key2).unwrap();
     assert_eq!(result, Some(value2.clone()));
@@ -57,3 +59,69 @@ fn test_smt() {
     let iter = smt.iter(changeset3.state_root, None).unwrap();
     assert_eq!(iter.count(), 2);
 }
+
+#[test]
+fn test_list_nodes() {
+    let node_store = InMemoryNodeStore::default();
+    let registry = prometheus::Registry::new();
+    let smt = SMTree::new(node_store.clone(), &registry);
+    let genesis_root = *SPARSE_MERKLE_PLACEHOLDER_HASH;
+    assert!(smt.list_nodes(genesis_root).unwrap().is_empty());
+
+    let updates = (0..100)
+        .map(|i| (H256::random(), Some(format!("value{}", i))))
+        .collect::<Vec<_>>();
+    let changeset = smt.puts(genesis_root, updates).unwrap();
+    node_store.write_nodes(changeset.nodes.clone()).unwrap();
+    // All the nodes of a new tree are written by the change set
+    let mut nodes = smt.list_nodes(changeset.state_root).unwrap();
+    nodes.sort();
+    assert_eq!(nodes, changeset.nodes.keys().cloned().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_diff() {
+    let node_store = InMemoryNodeStore::default();
+    let registry = prometheus::Registry::new();
+    let smt = SMTree::new(node_store.clone(), &registry);
+    let genesis_root = *SPARSE_MERKLE_PLACEHOLDER_HASH;
+
+    let keys = (0..100).map(|_| H256::random()).collect::<Vec<_>>();
+    let updates = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (*key, Some(format!("value{}", i))))
+        .collect::<Vec<_>>();
+    let changeset = smt.puts(genesis_root, updates).unwrap();
+    node_store.write_nodes(changeset.nodes).unwrap();
+    let old_root = changeset.state_root;
+    assert!(smt.diff(old_root, old_root).unwrap().is_empty());
+
+    let new_key = H256::random();
+    let changeset = smt
+        .puts(
+            old_root,
+            vec![
+                (keys[0], None),
+                (keys[1], Some("new_value".to_owned())),
+                (new_key, Some("value".to_owned())),
+            ],
+        )
+        .unwrap();
+    node_store.write_nodes(changeset.nodes).unwrap();
+    let new_root = changeset.state_root;
+
+    let mut expected = vec![
+        (keys[0], Some("value0".to_owned()), None),
+        (
+            keys[1],
+            Some("value1".to_owned()),
+            Some("new_value".to_owned()),
+        ),
+        (new_key, None, Some("value".to_owned())),
+    ];
+    expected.sort();
+    let mut changes = smt.diff(old_root, new_root).unwrap();
+    changes.sort();
+    assert_eq!(changes, expected);
+}